@@ -1,19 +1,37 @@
 use crate::commands;
-use crate::config::Config;
-use crate::data::{BotData, PersistentJson};
-use crate::services::servitor::HttpServitorController;
-use anyhow::Result;
+use crate::config::{Config, ServitorConfig, ServitorKind};
+use crate::controllers::servitor::health::ServitorHealth;
+use crate::data::{BotData, Data, PersistentJson};
+use crate::interactions;
+use crate::views;
+use crate::services::query::NetworkGameQuery;
+use crate::services::servitor::{
+	HttpServitorController, HttpServitorOptions, LocalServitorController, ServitorBackend, ServitorTlsOptions,
+};
+use crate::services::wake_on_lan::TcpMachineProbe;
+use crate::tasks;
+use anyhow::{anyhow, Result};
+use chrono::TimeDelta;
 use log::{debug, error};
 use poise::{serenity_prelude as serenity, Framework, FrameworkOptions};
 use secrecy::ExposeSecret;
 use serenity::Client;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
+pub type ServitorHandlers = Arc<BTreeMap<String, ServitorBackend>>;
+
 pub struct BotState {
 	pub data: BotData,
-	pub servitor: Arc<BTreeMap<String, HttpServitorController>>,
+	pub servitor: ServitorHandlers,
+	pub servitor_health: ServitorHealth,
+	pub bulk_concurrency: usize,
+	pub game_query: NetworkGameQuery,
+	pub machine_probe: TcpMachineProbe,
+	pub access_channel: Option<serenity::ChannelId>,
+	pub access_duration: TimeDelta,
 }
 
 pub type BotError = Box<dyn std::error::Error + Send + Sync>;
@@ -23,7 +41,7 @@ pub async fn client(config: &Config) -> Result<Client> {
 	let intents = serenity::GatewayIntents::non_privileged();
 
 	let client = serenity::ClientBuilder::new(config.bot.token.expose_secret(), intents)
-		.framework(build_framework(&config).await?)
+		.framework(build_framework(config).await?)
 		.await?;
 	Ok(client)
 }
@@ -32,39 +50,152 @@ async fn build_framework(config: &Config) -> Result<Framework<BotState, BotError
 	let servitor_controllers = config
 		.servitor
 		.iter()
-		.map(|(name, info)| {
-			HttpServitorController::new(&info.url, info.token.as_ref())
-				.map(|controller| (name.to_owned(), controller))
-		})
-		.collect::<Result<BTreeMap<_, _>, _>>()?;
+		.map(|(name, info)| servitor_backend(name, info).map(|backend| (name.to_owned(), backend)))
+		.collect::<Result<BTreeMap<_, _>>>()?;
 
 	let servitor = Arc::new(servitor_controllers);
-	let data = Arc::new(RwLock::new(PersistentJson::new("data.json")?));
+	let servitor_health = ServitorHealth::default();
+	let mut data = PersistentJson::<Data>::new("data.json")?;
+	if data.needs_migration() {
+		data.write().migrate(config.migration.guild);
+	}
+	let data = Arc::new(RwLock::new(data));
+
+	let health_interval = Duration::from_secs(config.health.interval);
+	let health_channel = config.health.channel;
+	let watch_interval = Duration::from_secs(config.watch.interval);
+	let dashboard_interval = Duration::from_secs(config.dashboard.interval);
+	let bulk_concurrency = config.bulk.concurrency;
+	let schedule_interval = Duration::from_secs(config.schedule.interval);
+	let idle_interval = Duration::from_secs(config.idle.interval);
+	let query_timeout = Duration::from_millis(config.query.timeout);
+	let game_query = NetworkGameQuery::new(query_timeout);
+	let machine_probe = TcpMachineProbe::new(query_timeout);
+	let grants_interval = Duration::from_secs(config.grants.interval);
+	let grants_notify = config.grants.notify;
+	let access_channel = config.access.channel;
+	let access_duration = TimeDelta::hours(config.access.hours.try_into()?);
 
 	Ok(Framework::builder()
 		.options(framework_options())
-		.setup(|ctx, _, framework| {
+		.setup(move |ctx, _, framework| {
 			Box::pin(async move {
 				poise::builtins::register_globally(ctx, &framework.options().commands).await?;
+
+				tokio::spawn(tasks::grants::prune_expired_grants(
+					ctx.http.clone(),
+					data.clone(),
+					grants_interval,
+					grants_notify,
+				));
+
+				if !servitor.is_empty() {
+					tokio::spawn(tasks::servitor::health::monitor_health(
+						ctx.http.clone(),
+						servitor.clone(),
+						servitor_health.clone(),
+						health_interval,
+						health_channel,
+					));
+					tokio::spawn(tasks::servitor::watch::notify_state_changes(
+						ctx.http.clone(),
+						data.clone(),
+						servitor.clone(),
+						watch_interval,
+					));
+					tokio::spawn(tasks::servitor::dashboard::refresh_dashboards(
+						ctx.http.clone(),
+						data.clone(),
+						servitor.clone(),
+						machine_probe,
+						dashboard_interval,
+					));
+					tokio::spawn(tasks::servitor::schedule::run_scheduled_actions(
+						ctx.http.clone(),
+						data.clone(),
+						servitor.clone(),
+						schedule_interval,
+					));
+					tokio::spawn(tasks::servitor::idle::stop_idle_servers(
+						ctx.http.clone(),
+						data.clone(),
+						servitor.clone(),
+						idle_interval,
+						query_timeout,
+					));
+				}
+
 				Ok(BotState {
 					data,
 					servitor,
+					servitor_health,
+					bulk_concurrency,
+					game_query,
+					machine_probe,
+					access_channel,
+					access_duration,
 				})
 			})
 		})
 		.build())
 }
 
+fn servitor_backend(name: &str, info: &ServitorConfig) -> Result<ServitorBackend> {
+	if info.kind == ServitorKind::Local {
+		return Ok(ServitorBackend::Local(LocalServitorController::new(Duration::from_millis(info.timeout))));
+	}
+
+	let url = info
+		.url
+		.as_ref()
+		.ok_or_else(|| anyhow!("servitor {name} is missing its url"))?;
+	let options = HttpServitorOptions {
+		timeout: Duration::from_millis(info.timeout),
+		connect_timeout: Duration::from_millis(info.connect_timeout),
+		retries: info.retries,
+		backoff: Duration::from_millis(info.backoff),
+		failures: info.failures,
+		cooldown: Duration::from_secs(info.cooldown),
+		tls: ServitorTlsOptions {
+			ca: info.ca.clone(),
+			cert: info.cert.clone(),
+			key: info.key.clone(),
+			fingerprint: info.fingerprint.clone(),
+		},
+	};
+
+	Ok(ServitorBackend::Http(HttpServitorController::new(url, info.token.as_ref(), options)?))
+}
+
 fn framework_options() -> FrameworkOptions<BotState, BotError> {
 	FrameworkOptions {
 		commands: commands::commands(),
 		on_error: |error| Box::pin(on_error(error)),
+		event_handler: |ctx, event, framework, state| {
+			Box::pin(event_handler(ctx, event, &framework.options().owners, state))
+		},
 		initialize_owners: true,
 		reply_callback: Some(log_replies),
 		..Default::default()
 	}
 }
 
+async fn event_handler(
+	ctx: &serenity::Context,
+	event: &serenity::FullEvent,
+	owners: &HashSet<serenity::UserId>,
+	state: &BotState,
+) -> Result<(), BotError> {
+	if let serenity::FullEvent::InteractionCreate {
+		interaction: serenity::Interaction::Component(component),
+	} = event
+	{
+		interactions::handle_component(ctx, component, state, owners).await?;
+	}
+
+	Ok(())
+}
+
 fn log_replies(_: Context, reply: poise::CreateReply) -> poise::CreateReply {
 	debug!("Replied with embeds {:?}", reply.embeds);
 	reply
@@ -76,6 +207,12 @@ async fn on_error(error: poise::FrameworkError<'_, BotState, BotError>) {
 		poise::FrameworkError::Command { error, ctx, .. } => {
 			error!("Error in command `{}`: {:?}", ctx.command().name, error,);
 		}
+		poise::FrameworkError::CommandCheckFailed { error: None, ctx, .. } => {
+			let reply = poise::CreateReply::default().embed(views::admin::missing_permission_embed()).ephemeral(true);
+			if let Err(e) = ctx.send(reply).await {
+				error!("Error while replying to failed check: {}", e)
+			}
+		}
 		error => {
 			if let Err(e) = poise::builtins::on_error(error).await {
 				error!("Error while handling error: {}", e)
@@ -1,15 +1,29 @@
 mod wake_on_lan;
+mod admin;
+mod access;
+mod authorization;
+mod checks;
+mod group;
 #[cfg(debug_assertions)]
 mod register;
 mod servitor;
 
 use poise::{Command, CreateReply, ReplyHandle};
-use serenity::all::{CreateAllowedMentions, CreateEmbed};
+use serenity::all::{ComponentInteractionCollector, Role, User, CreateActionRow, CreateAllowedMentions, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage};
 use crate::bot::{BotError, BotState, Context};
+use crate::controllers::{self, DiscordEntity};
+use chrono::{DateTime, Utc};
+use crate::interactions::CONFIRMATION_TIMEOUT;
+use crate::views::confirmation as view_confirmation;
+use crate::views::pagination as view_pagination;
+use std::time::Duration;
 pub fn commands() -> Vec<Command<BotState, BotError>> {
 	let commands = vec![
 		wake_on_lan::wake_on_lan(),
 		servitor::servitor(),
+		admin::admin(),
+		group::group(),
+		access::access(),
 		#[cfg(debug_assertions)] register::register(),
 	];
 
@@ -18,10 +32,179 @@ pub fn commands() -> Vec<Command<BotState, BotError>> {
 
 const DISCORD_MAX_AUTOCOMPLETE_CHOICES: usize = 25;
 
-async fn reply_no_mentions<'a>(ctx: Context<'a>, embed: CreateEmbed) -> Result<ReplyHandle<'a>, BotError> {
+async fn reply_no_mentions(ctx: Context<'_>, embed: CreateEmbed) -> Result<ReplyHandle<'_>, BotError> {
 	Ok(ctx.send(
 		CreateReply::default()
 			.embed(embed)
 			.allowed_mentions(CreateAllowedMentions::default().empty_users().empty_roles())
 	).await?)
 }
+
+async fn reply_with_components(ctx: Context<'_>, embed: CreateEmbed, components: Vec<CreateActionRow>) -> Result<ReplyHandle<'_>, BotError> {
+	Ok(ctx.send(
+		CreateReply::default()
+			.embed(embed)
+			.components(components)
+			.allowed_mentions(CreateAllowedMentions::default().empty_users().empty_roles())
+	).await?)
+}
+
+/// The one user or role picked out of two optional command options.
+fn discord_entity(user: Option<User>, role: Option<Role>) -> Option<DiscordEntity> {
+	match (user, role) {
+		(Some(user), None) => Some(DiscordEntity::User(user.id)),
+		(None, Some(role)) => Some(DiscordEntity::Role(role.id)),
+		_ => None,
+	}
+}
+
+/// When a grant given now for `duration` ends, failing with the duration when it does not parse or ends too late to be
+/// represented.
+fn grant_expiry(duration: Option<&str>) -> Result<Option<DateTime<Utc>>, &str> {
+	duration
+		.map(|duration| {
+			controllers::parse_duration(duration)
+				.and_then(|delta| Utc::now().checked_add_signed(delta))
+				.ok_or(duration)
+		})
+		.transpose()
+}
+
+/// Asks the author to confirm through an ephemeral prompt, anything other than pressing confirm in time is a no.
+async fn confirm(ctx: Context<'_>, prompt: CreateEmbed) -> Result<bool, BotError> {
+	let confirm_id = format!("{}-confirm", ctx.id());
+	let cancel_id = format!("{}-cancel", ctx.id());
+
+	let handle = ctx.send(
+		CreateReply::default()
+			.embed(prompt)
+			.components(view_confirmation::confirmation_buttons(&confirm_id, &cancel_id))
+			.ephemeral(true)
+			.allowed_mentions(CreateAllowedMentions::default().empty_users().empty_roles())
+	).await?;
+
+	let ids = [confirm_id.clone(), cancel_id];
+	let interaction = ComponentInteractionCollector::new(ctx)
+		.author_id(ctx.author().id)
+		.channel_id(ctx.channel_id())
+		.timeout(CONFIRMATION_TIMEOUT)
+		.filter(move |i| ids.contains(&i.data.custom_id))
+		.await;
+
+	let Some(interaction) = interaction else {
+		handle.edit(ctx, CreateReply::default().embed(view_confirmation::timed_out_embed()).components(vec![])).await?;
+		return Ok(false);
+	};
+
+	let confirmed = interaction.data.custom_id == confirm_id;
+	let embed = if confirmed { view_confirmation::confirmed_embed() } else { view_confirmation::cancelled_embed() };
+
+	interaction.create_response(
+		ctx,
+		CreateInteractionResponse::UpdateMessage(CreateInteractionResponseMessage::new().embed(embed).components(vec![]))
+	).await?;
+
+	Ok(confirmed)
+}
+
+/// How long a paginated reply keeps its buttons after the last page change.
+const PAGINATION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Replies ephemerally with the first page, letting the author flip through the rest until they stop for a while.
+async fn paginate(ctx: Context<'_>, pages: Vec<CreateEmbed>) -> Result<(), BotError> {
+	let previous_id = format!("{}-previous", ctx.id());
+	let next_id = format!("{}-next", ctx.id());
+	let mut page = 0;
+
+	let reply = |page: usize| {
+		let reply = CreateReply::default()
+			.embed(pages[page].clone())
+			.ephemeral(true)
+			.allowed_mentions(CreateAllowedMentions::default().empty_users().empty_roles());
+		match pages.len() {
+			1 => reply,
+			len => reply.components(view_pagination::pagination_buttons(&previous_id, &next_id, page, len)),
+		}
+	};
+
+	let handle = ctx.send(reply(page)).await?;
+	if pages.len() == 1 {
+		return Ok(());
+	}
+
+	let ids = [previous_id.clone(), next_id.clone()];
+	while let Some(interaction) = ComponentInteractionCollector::new(ctx)
+		.author_id(ctx.author().id)
+		.channel_id(ctx.channel_id())
+		.timeout(PAGINATION_TIMEOUT)
+		.filter({
+			let ids = ids.clone();
+			move |i| ids.contains(&i.data.custom_id)
+		})
+		.await
+	{
+		page = if interaction.data.custom_id == previous_id {
+			page.saturating_sub(1)
+		} else {
+			(page + 1).min(pages.len() - 1)
+		};
+
+		interaction.create_response(
+			ctx,
+			CreateInteractionResponse::UpdateMessage(
+				CreateInteractionResponseMessage::new()
+					.embed(pages[page].clone())
+					.components(view_pagination::pagination_buttons(&previous_id, &next_id, page, pages.len()))
+			)
+		).await?;
+	}
+
+	handle.edit(ctx, CreateReply::default().embed(pages[page].clone()).components(vec![])).await?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	const DISCORD_MAX_SUBCOMMANDS: usize = 25;
+
+	fn assert_subcommands_fit(command: &Command<BotState, BotError>) {
+		assert!(
+			command.subcommands.len() <= DISCORD_MAX_SUBCOMMANDS,
+			"/{} has {} subcommands",
+			command.name,
+			command.subcommands.len()
+		);
+		command.subcommands.iter().for_each(assert_subcommands_fit);
+	}
+
+	#[test]
+	fn given_no_duration_then_grant_expiry_is_permanent() {
+		assert_eq!(grant_expiry(None), Ok(None));
+	}
+
+	#[test]
+	fn given_duration_then_grant_expiry_ends_after_it() {
+		let before = Utc::now();
+
+		let expiry = grant_expiry(Some("2h")).unwrap().unwrap();
+
+		assert!(expiry >= before + chrono::TimeDelta::hours(2));
+		assert!(expiry <= Utc::now() + chrono::TimeDelta::hours(2));
+	}
+
+	#[rstest]
+	#[case("2w")]
+	#[case("100000000d")]
+	fn given_invalid_or_overflowing_duration_then_grant_expiry_fails_with_it(#[case] duration: &str) {
+		assert_eq!(grant_expiry(Some(duration)), Err(duration));
+	}
+
+	#[test]
+	fn given_commands_then_none_has_more_subcommands_than_discord_allows() {
+		commands().iter().for_each(assert_subcommands_fit);
+	}
+}
@@ -0,0 +1,53 @@
+use super::checks::{is_admin, is_global_admin};
+use super::{confirm, paginate, reply_no_mentions};
+use crate::bot::{BotError, Context};
+use crate::controllers::audit as ctrl_audit;
+use crate::controllers::DiscordEntity;
+use crate::views::audit as view_audit;
+use poise::serenity_prelude::{Role, User};
+
+#[poise::command(slash_command, subcommands("user", "role", "revoke_all"), subcommand_required)]
+pub async fn access(_: Context<'_>) -> Result<(), BotError> {
+	unreachable!("Can't call parent commands");
+}
+
+#[poise::command(slash_command, check = "is_admin")]
+pub async fn user(
+	ctx: Context<'_>,
+	#[description = "User whose access to report, including through their roles in this guild"] user: User,
+) -> Result<(), BotError> {
+	let roles = match ctx.guild_id() {
+		Some(guild) => guild.member(ctx, user.id).await.map(|member| member.roles).unwrap_or_default(),
+		None => Vec::new(),
+	};
+
+	let report = ctrl_audit::audit_user(&ctx.data().data, ctx.guild_id(), user.id, &roles).await;
+
+	paginate(ctx, view_audit::audit_pages(DiscordEntity::User(user.id), &report)).await
+}
+
+#[poise::command(slash_command, check = "is_admin")]
+pub async fn role(
+	ctx: Context<'_>,
+	#[description = "Role whose access to report"] role: Role,
+) -> Result<(), BotError> {
+	let report = ctrl_audit::audit_role(&ctx.data().data, ctx.guild_id(), role.id).await;
+
+	paginate(ctx, view_audit::audit_pages(DiscordEntity::Role(role.id), &report)).await
+}
+
+#[poise::command(slash_command, rename = "revoke-all", check = "is_global_admin")]
+pub async fn revoke_all(
+	ctx: Context<'_>,
+	#[description = "User that will lose everything granted to them directly"] user: User,
+) -> Result<(), BotError> {
+	if !confirm(ctx, view_audit::confirm_revoke_all_embed(user.id)).await? {
+		return Ok(());
+	}
+
+	let revoked = ctrl_audit::revoke_all(&ctx.data().data, user.id).await;
+
+	reply_no_mentions(ctx, view_audit::revoke_all_embed(&revoked, user.id)).await?;
+
+	Ok(())
+}
@@ -0,0 +1,54 @@
+use super::{discord_entity, reply_no_mentions};
+use crate::bot::{BotError, Context};
+use crate::controllers::admin as ctrl_admin;
+use crate::data::scope::Scope;
+use crate::views::admin as view_admin;
+use poise::serenity_prelude::{Role, User};
+
+#[poise::command(slash_command, subcommands("add_admin", "remove_admin"), subcommand_required)]
+pub async fn admin(_: Context<'_>) -> Result<(), BotError> {
+	unreachable!("Can't call parent commands");
+}
+
+#[poise::command(slash_command, owners_only, rename = "add-admin")]
+pub async fn add_admin(
+	ctx: Context<'_>,
+	#[description = "User that will be allowed to run management commands"] user: Option<User>,
+	#[description = "Role that will be allowed to run management commands"] role: Option<Role>,
+	#[description = "Let the admin manage every guild and DMs, defaults to only this guild"] global: Option<bool>,
+) -> Result<(), BotError> {
+	let Some(entity) = discord_entity(user, role) else {
+		reply_no_mentions(ctx, view_admin::missing_entity_embed()).await?;
+		return Ok(());
+	};
+	let scope = match global.unwrap_or(false) {
+		true => Scope::Global,
+		false => Scope::of(ctx.guild_id()),
+	};
+
+	let result = ctrl_admin::add_admin(&ctx.data().data, entity, scope).await;
+	let embed = view_admin::add_admin_embed(result, entity, scope);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+#[poise::command(slash_command, owners_only, rename = "remove-admin")]
+pub async fn remove_admin(
+	ctx: Context<'_>,
+	#[description = "User that will no longer be allowed to run management commands"] user: Option<User>,
+	#[description = "Role that will no longer be allowed to run management commands"] role: Option<Role>,
+) -> Result<(), BotError> {
+	let Some(entity) = discord_entity(user, role) else {
+		reply_no_mentions(ctx, view_admin::missing_entity_embed()).await?;
+		return Ok(());
+	};
+
+	let result = ctrl_admin::remove_admin(&ctx.data().data, entity).await;
+	let embed = view_admin::remove_admin_embed(result, entity);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
@@ -0,0 +1,293 @@
+use super::{discord_entity, grant_expiry, reply_no_mentions};
+use crate::bot::{BotError, Context};
+use crate::controllers::authorization as ctrl_auth;
+use crate::controllers::DiscordEntity;
+use crate::embeds;
+use crate::views::authorization::{self as view_auth, AuthorizationView};
+use serenity::all::{CreateEmbed, Role, User};
+
+pub(super) async fn list<R: AuthorizationView>(ctx: Context<'_>) -> Result<(), BotError> {
+	let embed = ctrl_auth::list::<R, _, _>(&ctx.data().data, ctx.guild_id(), async |resources| {
+		view_auth::list_embed(resources)
+	})
+	.await;
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+pub(super) async fn permit<R: AuthorizationView>(
+	ctx: Context<'_>,
+	name: &str,
+	entity: DiscordEntity,
+	grant: Result<R::Grant, CreateEmbed>,
+	duration: Option<&str>,
+) -> Result<(), BotError> {
+	let parsed = grant.and_then(|grant| Ok((grant, grant_expiry(duration).map_err(embeds::invalid_duration)?)));
+	let (grant, expires) = match parsed {
+		Ok(parsed) => parsed,
+		Err(embed) => {
+			reply_no_mentions(ctx, embed).await?;
+			return Ok(());
+		}
+	};
+	let result = ctrl_auth::permit::<R>(&ctx.data().data, name, entity, grant.clone(), expires).await;
+	let embed = view_auth::permit_embed::<R>(result, name, entity, &grant, expires);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+pub(super) async fn revoke<R: AuthorizationView>(
+	ctx: Context<'_>,
+	name: &str,
+	entity: DiscordEntity,
+	grant: Result<R::Grant, CreateEmbed>,
+) -> Result<(), BotError> {
+	let grant = match grant {
+		Ok(grant) => grant,
+		Err(embed) => {
+			reply_no_mentions(ctx, embed).await?;
+			return Ok(());
+		}
+	};
+	let result = ctrl_auth::revoke::<R>(&ctx.data().data, name, entity, grant.clone()).await;
+	let embed = view_auth::revoke_embed::<R>(result, name, entity, &grant);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+pub(super) async fn permit_group<R: AuthorizationView>(
+	ctx: Context<'_>,
+	name: &str,
+	group: &str,
+	grant: Result<R::Grant, CreateEmbed>,
+) -> Result<(), BotError> {
+	let grant = match grant {
+		Ok(grant) => grant,
+		Err(embed) => {
+			reply_no_mentions(ctx, embed).await?;
+			return Ok(());
+		}
+	};
+	let result = ctrl_auth::permit_group::<R>(&ctx.data().data, name, group, grant.clone()).await;
+	let embed = view_auth::permit_group_embed::<R>(result, name, group, &grant);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+pub(super) async fn revoke_group<R: AuthorizationView>(
+	ctx: Context<'_>,
+	name: &str,
+	group: &str,
+	grant: Result<R::Grant, CreateEmbed>,
+) -> Result<(), BotError> {
+	let grant = match grant {
+		Ok(grant) => grant,
+		Err(embed) => {
+			reply_no_mentions(ctx, embed).await?;
+			return Ok(());
+		}
+	};
+	let result = ctrl_auth::revoke_group::<R>(&ctx.data().data, name, group, grant.clone()).await;
+	let embed = view_auth::revoke_group_embed::<R>(result, name, group, &grant);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+pub(super) async fn deny<R: AuthorizationView>(
+	ctx: Context<'_>,
+	name: &str,
+	user: Option<User>,
+	role: Option<Role>,
+	reason: Option<String>,
+) -> Result<(), BotError> {
+	let Some(entity) = discord_entity(user, role) else {
+		reply_no_mentions(ctx, embeds::missing_entity("denied")).await?;
+		return Ok(());
+	};
+
+	let result = ctrl_auth::deny::<R>(&ctx.data().data, name, entity, reason.clone()).await;
+	let embed = view_auth::deny_embed::<R>(result, name, entity, reason.as_deref());
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+pub(super) async fn lift_denial<R: AuthorizationView>(
+	ctx: Context<'_>,
+	name: &str,
+	user: Option<User>,
+	role: Option<Role>,
+) -> Result<(), BotError> {
+	let Some(entity) = discord_entity(user, role) else {
+		reply_no_mentions(ctx, embeds::missing_entity("denied")).await?;
+		return Ok(());
+	};
+
+	let result = ctrl_auth::lift_denial::<R>(&ctx.data().data, name, entity).await;
+	let embed = view_auth::lift_denial_embed::<R>(result, name, entity);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+/// Generates the list subcommand of a resource with an [`AuthorizationView`](crate::views::authorization::AuthorizationView)
+/// and its `access` group, made of `add-user`, `remove-user`, `add-role`, `remove-role`, `add-group`, `remove-group`,
+/// `deny` and `lift-deny`.
+///
+/// The grant parameters are taken by every subcommand but the list and denial ones, the grant expression turns them into
+/// the resource's grant or the embed to reply with when they are invalid.
+macro_rules! authorization_commands {
+	(
+		resource: $resource:ty,
+		check: $check:tt,
+		list: $list:tt,
+		name: $name:ident ($name_description:tt, $autocomplete:tt),
+		grant: ($($grant_param:tt)*) => $grant:expr $(,)?
+	) => {
+		#[poise::command(slash_command, rename = $list)]
+		pub async fn list(ctx: $crate::bot::Context<'_>) -> Result<(), $crate::bot::BotError> {
+			$crate::commands::authorization::list::<$resource>(ctx).await
+		}
+
+		#[poise::command(
+			slash_command,
+			subcommands(
+				"add_user",
+				"remove_user",
+				"add_role",
+				"remove_role",
+				"add_group",
+				"remove_group",
+				"deny",
+				"lift_deny",
+			),
+			subcommand_required
+		)]
+		pub async fn access(_: $crate::bot::Context<'_>) -> Result<(), $crate::bot::BotError> {
+			unreachable!("Can't call parent commands");
+		}
+
+		#[poise::command(slash_command, rename = "add-user", check = $check)]
+		pub async fn add_user(
+			ctx: $crate::bot::Context<'_>,
+			#[description = $name_description]
+			#[autocomplete = $autocomplete]
+			$name: String,
+			#[description = "User that will be permitted"] user: poise::serenity_prelude::User,
+			$($grant_param)*
+			#[description = "How long the grant lasts, like 90m or 2d, defaults to forever"] duration: Option<String>,
+		) -> Result<(), $crate::bot::BotError> {
+			let entity = $crate::controllers::DiscordEntity::User(user.id);
+			$crate::commands::authorization::permit::<$resource>(ctx, &$name, entity, $grant, duration.as_deref()).await
+		}
+
+		#[poise::command(slash_command, rename = "remove-user", check = $check)]
+		pub async fn remove_user(
+			ctx: $crate::bot::Context<'_>,
+			#[description = $name_description]
+			#[autocomplete = $autocomplete]
+			$name: String,
+			#[description = "User that will no longer be permitted"] user: poise::serenity_prelude::User,
+			$($grant_param)*
+		) -> Result<(), $crate::bot::BotError> {
+			let entity = $crate::controllers::DiscordEntity::User(user.id);
+			$crate::commands::authorization::revoke::<$resource>(ctx, &$name, entity, $grant).await
+		}
+
+		#[poise::command(slash_command, rename = "add-role", check = $check)]
+		pub async fn add_role(
+			ctx: $crate::bot::Context<'_>,
+			#[description = $name_description]
+			#[autocomplete = $autocomplete]
+			$name: String,
+			#[description = "Role whose members will be permitted"] role: poise::serenity_prelude::Role,
+			$($grant_param)*
+			#[description = "How long the grant lasts, like 90m or 2d, defaults to forever"] duration: Option<String>,
+		) -> Result<(), $crate::bot::BotError> {
+			let entity = $crate::controllers::DiscordEntity::Role(role.id);
+			$crate::commands::authorization::permit::<$resource>(ctx, &$name, entity, $grant, duration.as_deref()).await
+		}
+
+		#[poise::command(slash_command, rename = "remove-role", check = $check)]
+		pub async fn remove_role(
+			ctx: $crate::bot::Context<'_>,
+			#[description = $name_description]
+			#[autocomplete = $autocomplete]
+			$name: String,
+			#[description = "Role whose members will no longer be permitted"] role: poise::serenity_prelude::Role,
+			$($grant_param)*
+		) -> Result<(), $crate::bot::BotError> {
+			let entity = $crate::controllers::DiscordEntity::Role(role.id);
+			$crate::commands::authorization::revoke::<$resource>(ctx, &$name, entity, $grant).await
+		}
+
+		#[poise::command(slash_command, rename = "add-group", check = $check)]
+		pub async fn add_group(
+			ctx: $crate::bot::Context<'_>,
+			#[description = $name_description]
+			#[autocomplete = $autocomplete]
+			$name: String,
+			#[description = "Access group whose members will be permitted"]
+			#[autocomplete = "crate::commands::group::autocomplete_group_name"]
+			group: String,
+			$($grant_param)*
+		) -> Result<(), $crate::bot::BotError> {
+			$crate::commands::authorization::permit_group::<$resource>(ctx, &$name, &group, $grant).await
+		}
+
+		#[poise::command(slash_command, rename = "remove-group", check = $check)]
+		pub async fn remove_group(
+			ctx: $crate::bot::Context<'_>,
+			#[description = $name_description]
+			#[autocomplete = $autocomplete]
+			$name: String,
+			#[description = "Access group whose members will no longer be permitted"]
+			#[autocomplete = "crate::commands::group::autocomplete_group_name"]
+			group: String,
+			$($grant_param)*
+		) -> Result<(), $crate::bot::BotError> {
+			$crate::commands::authorization::revoke_group::<$resource>(ctx, &$name, &group, $grant).await
+		}
+
+		#[poise::command(slash_command, check = $check)]
+		pub async fn deny(
+			ctx: $crate::bot::Context<'_>,
+			#[description = $name_description]
+			#[autocomplete = $autocomplete]
+			$name: String,
+			#[description = "User that will be denied, whatever they are permitted"]
+			user: Option<poise::serenity_prelude::User>,
+			#[description = "Role whose members will be denied, whatever they are permitted"]
+			role: Option<poise::serenity_prelude::Role>,
+			#[description = "Why they are denied, shown when describing it"] reason: Option<String>,
+		) -> Result<(), $crate::bot::BotError> {
+			$crate::commands::authorization::deny::<$resource>(ctx, &$name, user, role, reason).await
+		}
+
+		#[poise::command(slash_command, rename = "lift-deny", check = $check)]
+		pub async fn lift_deny(
+			ctx: $crate::bot::Context<'_>,
+			#[description = $name_description]
+			#[autocomplete = $autocomplete]
+			$name: String,
+			#[description = "User that will no longer be denied"] user: Option<poise::serenity_prelude::User>,
+			#[description = "Role that will no longer be denied"] role: Option<poise::serenity_prelude::Role>,
+		) -> Result<(), $crate::bot::BotError> {
+			$crate::commands::authorization::lift_denial::<$resource>(ctx, &$name, user, role).await
+		}
+	};
+}
+
+pub(super) use authorization_commands;
@@ -0,0 +1,64 @@
+use crate::bot::{BotError, Context};
+use crate::controllers::admin::{self as ctrl_admin, ManagedResource};
+use crate::data::scope::Scope;
+use poise::serenity_prelude::ResolvedValue;
+
+/// Option names that hold the machine or server a management command works on.
+const RESOURCE_OPTIONS: [&str; 3] = ["name", "machine_name", "server"];
+
+fn option<'a>(ctx: Context<'a>, name: &str) -> Option<&'a ResolvedValue<'a>> {
+	let poise::Context::Application(ctx) = ctx else {
+		return None;
+	};
+	ctx.args.iter().find(|option| option.name == name).map(|option| &option.value)
+}
+
+fn resource_name(ctx: Context<'_>) -> &str {
+	RESOURCE_OPTIONS
+		.iter()
+		.find_map(|name| match option(ctx, name) {
+			Some(ResolvedValue::String(value)) => Some(*value),
+			_ => None,
+		})
+		.unwrap_or_default()
+}
+
+async fn check(ctx: Context<'_>, resource: ManagedResource<'_>, allow_managers: bool) -> Result<bool, BotError> {
+	if ctx.framework().options().owners.contains(&ctx.author().id) {
+		return Ok(true);
+	}
+
+	let member = ctx.author_member().await;
+	Ok(ctrl_admin::can_manage(&ctx.data().data, ctx.author(), member.as_deref(), resource, allow_managers).await)
+}
+
+/// Admins of this guild, or global admins when the command asks for something global.
+pub async fn is_admin(ctx: Context<'_>) -> Result<bool, BotError> {
+	let scope = match option(ctx, "global") {
+		Some(ResolvedValue::Boolean(true)) => Scope::Global,
+		_ => Scope::of(ctx.guild_id()),
+	};
+
+	check(ctx, ManagedResource::New(scope), false).await
+}
+
+/// Admins that manage every guild, for things shared across guilds.
+pub async fn is_global_admin(ctx: Context<'_>) -> Result<bool, BotError> {
+	check(ctx, ManagedResource::New(Scope::Global), false).await
+}
+
+pub async fn is_machine_admin(ctx: Context<'_>) -> Result<bool, BotError> {
+	check(ctx, ManagedResource::Machine(resource_name(ctx)), false).await
+}
+
+pub async fn can_manage_machine(ctx: Context<'_>) -> Result<bool, BotError> {
+	check(ctx, ManagedResource::Machine(resource_name(ctx)), true).await
+}
+
+pub async fn is_server_admin(ctx: Context<'_>) -> Result<bool, BotError> {
+	check(ctx, ManagedResource::Server(resource_name(ctx)), false).await
+}
+
+pub async fn can_manage_server(ctx: Context<'_>) -> Result<bool, BotError> {
+	check(ctx, ManagedResource::Server(resource_name(ctx)), true).await
+}
@@ -0,0 +1,109 @@
+use super::checks::is_global_admin;
+use super::{discord_entity, reply_no_mentions, DISCORD_MAX_AUTOCOMPLETE_CHOICES};
+use crate::bot::{BotError, Context};
+use crate::controllers::group as ctrl_group;
+use crate::views::group as view_group;
+use poise::serenity_prelude::{Role, User};
+
+#[poise::command(
+	slash_command,
+	subcommands("create_group", "delete_group", "add_member", "remove_member", "list_groups"),
+	subcommand_required
+)]
+pub async fn group(_: Context<'_>) -> Result<(), BotError> {
+	unreachable!("Can't call parent commands");
+}
+
+pub(super) async fn autocomplete_group_name(ctx: Context<'_>, partial: &str) -> Vec<String> {
+	ctx.data()
+		.data
+		.read()
+		.await
+		.access_groups
+		.keys()
+		.filter(|name| name.starts_with(partial))
+		.take(DISCORD_MAX_AUTOCOMPLETE_CHOICES)
+		.cloned()
+		.collect()
+}
+
+#[poise::command(slash_command, rename = "create", check = "is_global_admin")]
+pub async fn create_group(
+	ctx: Context<'_>,
+	#[description = "Group name"] name: String,
+) -> Result<(), BotError> {
+	let result = ctrl_group::create_group(&ctx.data().data, &name).await;
+	let embed = view_group::create_group_embed(result, &name);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+#[poise::command(slash_command, rename = "delete", check = "is_global_admin")]
+pub async fn delete_group(
+	ctx: Context<'_>,
+	#[description = "Group name"]
+	#[autocomplete = "autocomplete_group_name"]
+	name: String,
+) -> Result<(), BotError> {
+	let result = ctrl_group::delete_group(&ctx.data().data, &name).await;
+	let embed = view_group::delete_group_embed(result, &name);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+#[poise::command(slash_command, rename = "add-member", check = "is_global_admin")]
+pub async fn add_member(
+	ctx: Context<'_>,
+	#[description = "Group name"]
+	#[autocomplete = "autocomplete_group_name"]
+	name: String,
+	#[description = "User that will get everything the group is permitted"] user: Option<User>,
+	#[description = "Role whose members will get everything the group is permitted"] role: Option<Role>,
+) -> Result<(), BotError> {
+	let Some(entity) = discord_entity(user, role) else {
+		reply_no_mentions(ctx, view_group::missing_member_embed()).await?;
+		return Ok(());
+	};
+
+	let result = ctrl_group::add_member(&ctx.data().data, &name, entity).await;
+	let embed = view_group::add_member_embed(result, &name, entity);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+#[poise::command(slash_command, rename = "remove-member", check = "is_global_admin")]
+pub async fn remove_member(
+	ctx: Context<'_>,
+	#[description = "Group name"]
+	#[autocomplete = "autocomplete_group_name"]
+	name: String,
+	#[description = "User that will no longer be in the group"] user: Option<User>,
+	#[description = "Role that will no longer be in the group"] role: Option<Role>,
+) -> Result<(), BotError> {
+	let Some(entity) = discord_entity(user, role) else {
+		reply_no_mentions(ctx, view_group::missing_member_embed()).await?;
+		return Ok(());
+	};
+
+	let result = ctrl_group::remove_member(&ctx.data().data, &name, entity).await;
+	let embed = view_group::remove_member_embed(result, &name, entity);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+#[poise::command(slash_command, rename = "list")]
+pub async fn list_groups(ctx: Context<'_>) -> Result<(), BotError> {
+	let embed = ctrl_group::list_groups(&ctx.data().data, async |groups| view_group::list_groups_embed(groups)).await;
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
@@ -1,6 +1,14 @@
 mod action;
 mod authorization;
+mod bulk;
+mod dashboard;
+mod health;
+mod idle;
+mod manager;
+mod query;
+mod schedule;
 mod server;
+mod watch;
 
 use crate::bot::{BotError, Context};
 use crate::commands::DISCORD_MAX_AUTOCOMPLETE_CHOICES;
@@ -13,14 +21,23 @@ use crate::commands::DISCORD_MAX_AUTOCOMPLETE_CHOICES;
 		"action::restart",
 		"action::reload",
 		"action::status",
+		"action::logs",
+		"bulk::bulk",
+		"schedule::schedule",
+		"idle::idle",
+		"query::query",
 		"server::add_server",
 		"server::remove_server",
-		"server::list_servers",
+		"authorization::list",
 		"server::describe_server",
-		"authorization::add_user",
-		"authorization::remove_user",
-		"authorization::add_role",
-		"authorization::remove_role",
+		"server::set_confirmation",
+		"authorization::access",
+		"manager::manager",
+		"health::instances",
+		"watch::watch",
+		"watch::unwatch",
+		"dashboard::dashboard",
+		"dashboard::remove_dashboard",
 	),
 	subcommand_required
 )]
@@ -29,15 +46,16 @@ pub async fn servitor(_: Context<'_>) -> Result<(), BotError> {
 }
 
 async fn autocomplete_server_name(ctx: Context<'_>, partial: &str) -> Vec<String> {
+	let guild = ctx.guild_id();
 	ctx.data()
 		.data
 		.read()
 		.await
 		.servitor
-		.keys()
-		.filter(|name| name.starts_with(partial))
+		.iter()
+		.filter(|(name, info)| name.starts_with(partial) && info.scope.is_visible_in(guild))
 		.take(DISCORD_MAX_AUTOCOMPLETE_CHOICES)
-		.cloned()
+		.map(|(name, _)| name.clone())
 		.collect()
 }
 
@@ -1,9 +1,13 @@
-use super::super::reply_no_mentions;
+use super::super::{confirm, reply_with_components};
 use super::autocomplete_server_name;
 use crate::bot::{BotError, Context};
 
 use crate::controllers::servitor::action as ctrl_serv_act;
+use crate::controllers::servitor::query as ctrl_serv_qry;
+use crate::data::servitor::ServitorAction;
 use crate::views::servitor::action as view_serv_act;
+use poise::CreateReply;
+use serenity::all::CreateAllowedMentions;
 
 #[poise::command(slash_command)]
 pub async fn start(
@@ -15,9 +19,10 @@ pub async fn start(
 	let result =
 		ctrl_serv_act::start(&ctx.data().data, &ctx.data().servitor, &name, ctx.author(), ctx.author_member().await.as_deref()).await;
 
+	let buttons = view_serv_act::action_buttons(&result);
 	let embed = view_serv_act::start_embed(result, &name);
 
-	reply_no_mentions(ctx, embed).await?;
+	reply_with_components(ctx, embed, buttons).await?;
 
 	Ok(())
 }
@@ -29,12 +34,19 @@ pub async fn stop(
 	#[autocomplete = "autocomplete_server_name"]
 	name: String,
 ) -> Result<(), BotError> {
-	let result =
-		ctrl_serv_act::stop(&ctx.data().data, &ctx.data().servitor, &name, ctx.author(), ctx.author_member().await.as_deref()).await;
+	let member = ctx.author_member().await;
+	if ctrl_serv_act::requires_confirmation(&ctx.data().data, &name, ServitorAction::Stop, ctx.author(), member.as_deref()).await
+		&& !confirm(ctx, view_serv_act::confirm_action_embed(&name, ServitorAction::Stop)).await?
+	{
+		return Ok(());
+	}
+
+	let result = ctrl_serv_act::stop(&ctx.data().data, &ctx.data().servitor, &name, ctx.author(), member.as_deref()).await;
 
+	let buttons = view_serv_act::action_buttons(&result);
 	let embed = view_serv_act::stop_embed(result, &name);
 
-	reply_no_mentions(ctx, embed).await?;
+	reply_with_components(ctx, embed, buttons).await?;
 
 	Ok(())
 }
@@ -46,12 +58,19 @@ pub async fn restart(
 	#[autocomplete = "autocomplete_server_name"]
 	name: String,
 ) -> Result<(), BotError> {
-	let result =
-		ctrl_serv_act::restart(&ctx.data().data, &ctx.data().servitor, &name, ctx.author(), ctx.author_member().await.as_deref()).await;
+	let member = ctx.author_member().await;
+	if ctrl_serv_act::requires_confirmation(&ctx.data().data, &name, ServitorAction::Restart, ctx.author(), member.as_deref()).await
+		&& !confirm(ctx, view_serv_act::confirm_action_embed(&name, ServitorAction::Restart)).await?
+	{
+		return Ok(());
+	}
+
+	let result = ctrl_serv_act::restart(&ctx.data().data, &ctx.data().servitor, &name, ctx.author(), member.as_deref()).await;
 
+	let buttons = view_serv_act::action_buttons(&result);
 	let embed = view_serv_act::restart_embed(result, &name);
 
-	reply_no_mentions(ctx, embed).await?;
+	reply_with_components(ctx, embed, buttons).await?;
 
 	Ok(())
 }
@@ -66,9 +85,10 @@ pub async fn reload(
 	let result =
 		ctrl_serv_act::reload(&ctx.data().data, &ctx.data().servitor, &name, ctx.author(), ctx.author_member().await.as_deref()).await;
 
+	let buttons = view_serv_act::action_buttons(&result);
 	let embed = view_serv_act::reload_embed(result, &name);
 
-	reply_no_mentions(ctx, embed).await?;
+	reply_with_components(ctx, embed, buttons).await?;
 
 	Ok(())
 }
@@ -83,9 +103,53 @@ pub async fn status(
 	let result =
 		ctrl_serv_act::status(&ctx.data().data, &ctx.data().servitor, &name, ctx.author(), ctx.author_member().await.as_deref()).await;
 
-	let embed = view_serv_act::status_embed(result, &name);
+	let (buttons, game) = match result {
+		Ok(_) => (
+			view_serv_act::status_buttons(&name),
+			ctrl_serv_qry::query_game(&ctx.data().data, &name, &ctx.data().game_query).await,
+		),
+		Err(_) => (view_serv_act::action_buttons(&result), None),
+	};
+	let embed = view_serv_act::game_info_fields(view_serv_act::status_embed(result, &name), game);
+
+	reply_with_components(ctx, embed, buttons).await?;
+
+	Ok(())
+}
 
-	reply_no_mentions(ctx, embed).await?;
+#[poise::command(slash_command)]
+pub async fn logs(
+	ctx: Context<'_>,
+	#[description = "Server name"]
+	#[autocomplete = "autocomplete_server_name"]
+	name: String,
+	#[description = "Number of log lines to fetch, defaults to 20"]
+	#[min = 1]
+	#[max = 1000]
+	lines: Option<u32>,
+) -> Result<(), BotError> {
+	let result = ctrl_serv_act::logs(
+		&ctx.data().data,
+		&ctx.data().servitor,
+		&name,
+		lines.unwrap_or(20),
+		ctx.author(),
+		ctx.author_member().await.as_deref(),
+	)
+	.await;
+
+	let buttons = view_serv_act::action_buttons(&result);
+	let (embed, attachment) = view_serv_act::logs_embed(result, &name);
+
+	let mut reply = CreateReply::default()
+		.embed(embed)
+		.components(buttons)
+		.allowed_mentions(CreateAllowedMentions::default().empty_users().empty_roles());
+	if let Some(attachment) = attachment {
+		reply = reply.attachment(attachment);
+	}
+
+	ctx.send(reply).await?;
 
 	Ok(())
 }
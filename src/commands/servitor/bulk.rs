@@ -0,0 +1,42 @@
+use super::super::{confirm, reply_no_mentions};
+use crate::bot::{BotError, Context};
+use crate::controllers::servitor::bulk as ctrl_serv_blk;
+use crate::data::servitor::ServitorAction;
+use crate::views::servitor::bulk as view_serv_blk;
+
+#[poise::command(slash_command)]
+pub async fn bulk(
+	ctx: Context<'_>,
+	#[description = "Action to run"] action: ctrl_serv_blk::BulkAction,
+	#[description = "Comma separated server names"] servers: String,
+) -> Result<(), BotError> {
+	let servers = ctrl_serv_blk::parse_servers(&servers);
+	let servitor_action = ServitorAction::from(action);
+
+	let member = ctx.author_member().await;
+
+	if ctrl_serv_blk::requires_confirmation(&ctx.data().data, &servers, ctx.author(), member.as_deref(), action).await
+		&& !confirm(ctx, view_serv_blk::confirm_bulk_embed(&servers, servitor_action)).await?
+	{
+		return Ok(());
+	}
+
+	ctx.defer().await?;
+
+	let results = ctrl_serv_blk::bulk(
+		&ctx.data().data,
+		&ctx.data().servitor,
+		&servers,
+		ctx.author(),
+		member.as_deref(),
+		action,
+		ctx.data().bulk_concurrency,
+	)
+	.await;
+
+	let embed = view_serv_blk::bulk_embed(results, servitor_action);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
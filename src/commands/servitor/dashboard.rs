@@ -0,0 +1,63 @@
+use super::super::checks::is_admin;
+use super::super::reply_no_mentions;
+use crate::bot::{BotError, Context};
+use crate::controllers::servitor::dashboard as ctrl_serv_dsh;
+use crate::views::servitor::dashboard as view_serv_dsh;
+use chrono::Utc;
+use log::warn;
+use serenity::all::CreateMessage;
+
+#[poise::command(slash_command, check = "is_admin")]
+pub async fn dashboard(ctx: Context<'_>) -> Result<(), BotError> {
+	let channel = ctx.channel_id();
+	let guild = ctx.guild_id();
+
+	// Offline machines only answer once the probe times out
+	ctx.defer().await?;
+
+	let snapshot = ctrl_serv_dsh::snapshot(
+		&ctx.data().data,
+		guild,
+		&ctx.data().servitor,
+		&ctx.data().machine_probe,
+	)
+	.await;
+
+	let message = channel
+		.send_message(
+			ctx,
+			CreateMessage::new().embed(view_serv_dsh::dashboard_embed(&snapshot, Utc::now())),
+		)
+		.await?;
+
+	if let Some(previous) = ctrl_serv_dsh::set_dashboard(&ctx.data().data, channel, guild, message.id).await
+		&& let Err(e) = channel.delete_message(ctx, previous).await
+	{
+		warn!("Failed to delete previous dashboard message {previous} in channel {channel}: {e}");
+	}
+
+	let embed = view_serv_dsh::create_dashboard_embed(channel);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+#[poise::command(slash_command, rename = "remove-dashboard", check = "is_admin")]
+pub async fn remove_dashboard(ctx: Context<'_>) -> Result<(), BotError> {
+	let channel = ctx.channel_id();
+
+	let result = ctrl_serv_dsh::remove_dashboard(&ctx.data().data, channel).await;
+
+	if let Ok(message) = result
+		&& let Err(e) = channel.delete_message(ctx, message).await
+	{
+		warn!("Failed to delete dashboard message {message} in channel {channel}: {e}");
+	}
+
+	let embed = view_serv_dsh::remove_dashboard_embed(result.map(|_| ()), channel);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
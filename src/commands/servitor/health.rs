@@ -0,0 +1,19 @@
+use super::super::reply_no_mentions;
+use crate::bot::{BotError, Context};
+use crate::controllers::servitor::health as ctrl_serv_hlt;
+use crate::views::servitor::health as view_serv_hlt;
+
+#[poise::command(slash_command)]
+pub async fn instances(ctx: Context<'_>) -> Result<(), BotError> {
+	let embed = ctrl_serv_hlt::list_instances(
+		&ctx.data().data,
+		&ctx.data().servitor_health,
+		&ctx.data().servitor,
+		async |instances| view_serv_hlt::list_instances_embed(instances),
+	)
+	.await;
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
@@ -0,0 +1,87 @@
+use super::super::checks::can_manage_server;
+use super::super::reply_no_mentions;
+use super::autocomplete_server_name;
+use crate::bot::{BotError, Context};
+use crate::{controllers, embeds};
+use crate::controllers::servitor::idle as ctrl_serv_idl;
+use crate::data::servitor::IdleProbe;
+use crate::views::servitor::idle as view_serv_idl;
+use chrono::Utc;
+
+#[poise::command(
+	slash_command,
+	subcommands("set_idle_policy", "remove_idle_policy", "keep_alive"),
+	subcommand_required
+)]
+pub async fn idle(_: Context<'_>) -> Result<(), BotError> {
+	unreachable!("Can't call parent commands");
+}
+
+#[poise::command(slash_command, rename = "set", check = "can_manage_server")]
+pub async fn set_idle_policy(
+	ctx: Context<'_>,
+	#[description = "Server name"]
+	#[autocomplete = "autocomplete_server_name"]
+	name: String,
+	#[description = "Minutes without activity before the server is stopped"]
+	#[min = 1]
+	minutes: u32,
+	#[description = "Port on this host whose connections count as activity, defaults to players in the game query"]
+	port: Option<u16>,
+) -> Result<(), BotError> {
+	let probe = port.map_or(IdleProbe::Query, |port| IdleProbe::Connections { port });
+	let result =
+		ctrl_serv_idl::set_idle_policy(&ctx.data().data, &name, probe.clone(), minutes, ctx.channel_id()).await;
+
+	let embed = view_serv_idl::set_idle_policy_embed(result, &name, &probe, minutes);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+#[poise::command(slash_command, rename = "remove", check = "can_manage_server")]
+pub async fn remove_idle_policy(
+	ctx: Context<'_>,
+	#[description = "Server name"]
+	#[autocomplete = "autocomplete_server_name"]
+	name: String,
+) -> Result<(), BotError> {
+	let result = ctrl_serv_idl::remove_idle_policy(&ctx.data().data, &name).await;
+
+	let embed = view_serv_idl::remove_idle_policy_embed(result, &name);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+#[poise::command(slash_command, rename = "keep-alive")]
+pub async fn keep_alive(
+	ctx: Context<'_>,
+	#[description = "Server name"]
+	#[autocomplete = "autocomplete_server_name"]
+	name: String,
+	#[description = "How long to keep the server alive, like 90m or 2h"] duration: String,
+) -> Result<(), BotError> {
+	let Some(delta) = controllers::parse_duration(&duration) else {
+		reply_no_mentions(ctx, embeds::invalid_duration(&duration)).await?;
+		return Ok(());
+	};
+
+	let result = ctrl_serv_idl::keep_alive(
+		&ctx.data().data,
+		&name,
+		delta,
+		ctx.author(),
+		ctx.author_member().await.as_deref(),
+		Utc::now(),
+	)
+	.await;
+
+	let embed = view_serv_idl::keep_alive_embed(result, &name, &duration);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
@@ -0,0 +1,44 @@
+use super::super::checks::is_server_admin;
+use super::super::reply_no_mentions;
+use super::autocomplete_server_name;
+use crate::bot::{BotError, Context};
+use crate::controllers::servitor::manager as ctrl_serv_mgr;
+use crate::views::servitor::manager as view_serv_mgr;
+use poise::serenity_prelude::User;
+
+#[poise::command(slash_command, subcommands("add_manager", "remove_manager"), subcommand_required)]
+pub async fn manager(_: Context<'_>) -> Result<(), BotError> {
+	unreachable!("Can't call parent commands");
+}
+
+#[poise::command(slash_command, rename = "add", check = "is_server_admin")]
+pub async fn add_manager(
+	ctx: Context<'_>,
+	#[description = "Server name"]
+	#[autocomplete = "autocomplete_server_name"]
+	server: String,
+	#[description = "User that will be allowed to manage this server"] user: User,
+) -> Result<(), BotError> {
+	let result = ctrl_serv_mgr::add_manager(&ctx.data().data, &server, user.id).await;
+	let embed = view_serv_mgr::add_manager_embed(result, &server, user.id);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+#[poise::command(slash_command, rename = "remove", check = "is_server_admin")]
+pub async fn remove_manager(
+	ctx: Context<'_>,
+	#[description = "Server name"]
+	#[autocomplete = "autocomplete_server_name"]
+	server: String,
+	#[description = "User that will no longer be allowed to manage this server"] user: User,
+) -> Result<(), BotError> {
+	let result = ctrl_serv_mgr::remove_manager(&ctx.data().data, &server, user.id).await;
+	let embed = view_serv_mgr::remove_manager_embed(result, &server, user.id);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
@@ -0,0 +1,46 @@
+use super::super::checks::can_manage_server;
+use super::super::reply_no_mentions;
+use super::autocomplete_server_name;
+use crate::bot::{BotError, Context};
+use crate::controllers::servitor::query as ctrl_serv_qry;
+use crate::views::servitor::query as view_serv_qry;
+
+#[poise::command(slash_command, subcommands("set_query", "remove_query"), subcommand_required)]
+pub async fn query(_: Context<'_>) -> Result<(), BotError> {
+	unreachable!("Can't call parent commands");
+}
+
+#[poise::command(slash_command, rename = "set", check = "can_manage_server")]
+pub async fn set_query(
+	ctx: Context<'_>,
+	#[description = "Server name"]
+	#[autocomplete = "autocomplete_server_name"]
+	name: String,
+	#[description = "Query protocol spoken by the game server"] protocol: ctrl_serv_qry::QueryProtocol,
+	#[description = "Address of the game server, like host:port, defaults to the protocol's port"] address: String,
+) -> Result<(), BotError> {
+	let query = protocol.config(address);
+	let result = ctrl_serv_qry::set_query(&ctx.data().data, &name, query.clone()).await;
+
+	let embed = view_serv_qry::set_query_embed(result, &name, &query);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+#[poise::command(slash_command, rename = "remove", check = "can_manage_server")]
+pub async fn remove_query(
+	ctx: Context<'_>,
+	#[description = "Server name"]
+	#[autocomplete = "autocomplete_server_name"]
+	name: String,
+) -> Result<(), BotError> {
+	let result = ctrl_serv_qry::remove_query(&ctx.data().data, &name).await;
+
+	let embed = view_serv_qry::remove_query_embed(result, &name);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
@@ -0,0 +1,77 @@
+use super::super::checks::is_admin;
+use super::super::{paginate, reply_no_mentions};
+use super::autocomplete_server_name;
+use crate::bot::{BotError, Context};
+use crate::controllers::servitor::schedule as ctrl_serv_sch;
+use crate::data::servitor::SchedulableAction;
+use crate::views::servitor::schedule as view_serv_sch;
+use chrono::Utc;
+
+#[poise::command(
+	slash_command,
+	subcommands("add_schedule", "list_schedules", "cancel_schedule"),
+	subcommand_required
+)]
+pub async fn schedule(_: Context<'_>) -> Result<(), BotError> {
+	unreachable!("Can't call parent commands");
+}
+
+#[poise::command(slash_command, rename = "add")]
+pub async fn add_schedule(
+	ctx: Context<'_>,
+	#[description = "Server name"]
+	#[autocomplete = "autocomplete_server_name"]
+	name: String,
+	#[description = "Action to run"] action: SchedulableAction,
+	#[description = "A date like 2025-03-07 04:00, or a cron expression like 0 4 * * *"] when: String,
+	#[description = "Timezone of the date or cron expression, defaults to UTC"] timezone: Option<String>,
+) -> Result<(), BotError> {
+	let result = match ctrl_serv_sch::parse_when(&when, timezone.as_deref(), Utc::now()) {
+		Ok(when) => {
+			ctrl_serv_sch::schedule(
+				&ctx.data().data,
+				&name,
+				action,
+				when,
+				ctx.author(),
+				ctx.author_member().await.as_deref(),
+				ctx.channel_id(),
+			)
+			.await
+		}
+		Err(e) => Err(e),
+	};
+
+	let embed = view_serv_sch::schedule_embed(result, &name);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+#[poise::command(slash_command, rename = "list", check = "is_admin")]
+pub async fn list_schedules(ctx: Context<'_>) -> Result<(), BotError> {
+	let schedules = ctrl_serv_sch::list_schedules(&ctx.data().data, ctx.guild_id()).await;
+
+	paginate(ctx, view_serv_sch::list_schedules_pages(&schedules)).await
+}
+
+#[poise::command(slash_command, rename = "cancel")]
+pub async fn cancel_schedule(
+	ctx: Context<'_>,
+	#[description = "Scheduled action number"] id: u64,
+) -> Result<(), BotError> {
+	let result = ctrl_serv_sch::cancel_schedule(
+		&ctx.data().data,
+		id,
+		ctx.author(),
+		ctx.author_member().await.as_deref(),
+	)
+	.await;
+
+	let embed = view_serv_sch::cancel_schedule_embed(result, id);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
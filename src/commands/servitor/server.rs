@@ -1,10 +1,12 @@
-use super::super::reply_no_mentions;
+use super::super::checks::{can_manage_server, is_admin, is_server_admin};
+use super::super::{confirm, reply_no_mentions};
 use super::{autocomplete_server_name, autocomplete_servitor_name};
 use crate::bot::{BotError, Context};
 use crate::controllers::servitor::server as ctrl_serv_srv;
+use crate::data::scope::Scope;
 use crate::views::servitor::server as view_serv_srv;
 
-#[poise::command(slash_command, owners_only, rename = "add-server")]
+#[poise::command(slash_command, rename = "add-server", check = "is_admin")]
 pub async fn add_server(
 	ctx: Context<'_>,
 	#[description = "Server name"] name: String,
@@ -12,13 +14,19 @@ pub async fn add_server(
 	#[autocomplete = "autocomplete_servitor_name"]
 	servitor: String,
 	#[description = "Unit name"] unit_name: String,
+	#[description = "Make the server usable from every guild and DMs, defaults to only this guild"] global: Option<bool>,
 ) -> Result<(), BotError> {
+	let scope = match global.unwrap_or(false) {
+		true => Scope::Global,
+		false => Scope::of(ctx.guild_id()),
+	};
 	let result = ctrl_serv_srv::add_server(
 		&ctx.data().data,
 		&ctx.data().servitor,
 		&name,
 		&servitor,
 		&unit_name,
+		scope,
 	)
 	.await;
 
@@ -29,11 +37,17 @@ pub async fn add_server(
 	Ok(())
 }
 
-#[poise::command(slash_command, owners_only, rename = "remove-server")]
+#[poise::command(slash_command, rename = "remove-server", check = "is_server_admin")]
 pub async fn remove_server(
 	ctx: Context<'_>,
 	#[description = "Server name"] name: String,
 ) -> Result<(), BotError> {
+	if ctrl_serv_srv::requires_confirmation(&ctx.data().data, &name).await
+		&& !confirm(ctx, view_serv_srv::confirm_remove_server_embed(&name)).await?
+	{
+		return Ok(());
+	}
+
 	let result = ctrl_serv_srv::remove_server(&ctx.data().data, &name).await;
 
 	let embed = view_serv_srv::remove_server_embed(result, &name);
@@ -43,12 +57,17 @@ pub async fn remove_server(
 	Ok(())
 }
 
-#[poise::command(slash_command, rename = "list-servers")]
-pub async fn list_servers(ctx: Context<'_>) -> Result<(), BotError> {
-	let embed = ctrl_serv_srv::list_servers(&ctx.data().data, async |info| {
-		view_serv_srv::list_servers_embed(info)
-	})
-	.await;
+#[poise::command(slash_command, rename = "set-confirmation", check = "can_manage_server")]
+pub async fn set_confirmation(
+	ctx: Context<'_>,
+	#[description = "Server name"]
+	#[autocomplete = "autocomplete_server_name"]
+	name: String,
+	#[description = "Whether stop, restart and remove-server ask for confirmation"] enabled: bool,
+) -> Result<(), BotError> {
+	let result = ctrl_serv_srv::set_confirmation(&ctx.data().data, &name, enabled).await;
+
+	let embed = view_serv_srv::set_confirmation_embed(result, &name, enabled);
 
 	reply_no_mentions(ctx, embed).await?;
 
@@ -62,7 +81,7 @@ pub async fn describe_server(
 	#[autocomplete = "autocomplete_server_name"]
 	name: String,
 ) -> Result<(), BotError> {
-	let embed = ctrl_serv_srv::describe_server(&ctx.data().data, &name, async |info, name| {
+	let embed = ctrl_serv_srv::describe_server(&ctx.data().data, ctx.guild_id(), &name, async |info, name| {
 		view_serv_srv::describe_server_embed(info, name)
 	})
 	.await;
@@ -0,0 +1,51 @@
+use super::super::reply_no_mentions;
+use super::autocomplete_server_name;
+use crate::bot::{BotError, Context};
+use crate::controllers::servitor::watch as ctrl_serv_wtc;
+use crate::views::servitor::watch as view_serv_wtc;
+
+#[poise::command(slash_command)]
+pub async fn watch(
+	ctx: Context<'_>,
+	#[description = "Server name"]
+	#[autocomplete = "autocomplete_server_name"]
+	name: String,
+) -> Result<(), BotError> {
+	let result = ctrl_serv_wtc::watch(
+		&ctx.data().data,
+		&name,
+		ctx.channel_id(),
+		ctx.author(),
+		ctx.author_member().await.as_deref(),
+	)
+	.await;
+
+	let embed = view_serv_wtc::watch_embed(result, &name, ctx.channel_id());
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+#[poise::command(slash_command)]
+pub async fn unwatch(
+	ctx: Context<'_>,
+	#[description = "Server name"]
+	#[autocomplete = "autocomplete_server_name"]
+	name: String,
+) -> Result<(), BotError> {
+	let result = ctrl_serv_wtc::unwatch(
+		&ctx.data().data,
+		&name,
+		ctx.channel_id(),
+		ctx.author(),
+		ctx.author_member().await.as_deref(),
+	)
+	.await;
+
+	let embed = view_serv_wtc::unwatch_embed(result, &name, ctx.channel_id());
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
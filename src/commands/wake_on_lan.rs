@@ -1,5 +1,6 @@
 mod authorization;
 mod machine;
+mod manager;
 mod wake;
 
 use super::DISCORD_MAX_AUTOCOMPLETE_CHOICES;
@@ -12,12 +13,11 @@ use crate::bot::{BotError, Context};
 		"wake::wake",
 		"machine::add_machine",
 		"machine::remove_machine",
-		"machine::list_machines",
+		"machine::set_address",
+		"authorization::list",
 		"machine::describe_machine",
-		"authorization::add_user",
-		"authorization::remove_user",
-		"authorization::add_role",
-		"authorization::remove_role",
+		"authorization::access",
+		"manager::manager",
 	),
 	subcommand_required
 )]
@@ -26,14 +26,15 @@ pub async fn wake_on_lan(_: Context<'_>) -> Result<(), BotError> {
 }
 
 async fn autocomplete_machine_name(ctx: Context<'_>, partial: &str) -> Vec<String> {
+	let guild = ctx.guild_id();
 	ctx.data()
 		.data
 		.read()
 		.await
 		.wake_on_lan
-		.keys()
-		.filter(|name| name.starts_with(partial))
+		.iter()
+		.filter(|(name, info)| name.starts_with(partial) && info.scope.is_visible_in(guild))
 		.take(DISCORD_MAX_AUTOCOMPLETE_CHOICES)
-		.cloned()
+		.map(|(name, _)| name.clone())
 		.collect()
 }
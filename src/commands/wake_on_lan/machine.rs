@@ -1,17 +1,24 @@
+use super::super::checks::{is_admin, is_machine_admin};
 use super::autocomplete_machine_name;
-use crate::commands::reply_no_mentions;
+use crate::commands::{confirm, reply_no_mentions, reply_with_components};
 use crate::{controllers, views};
 use controllers::wake_on_lan::machine as ctrl_wol_mch;
 use views::wake_on_lan::machine as view_wol_mch;
 use crate::bot::{BotError, Context};
+use crate::data::scope::Scope;
 
-#[poise::command(slash_command, owners_only, rename = "add-machine")]
+#[poise::command(slash_command, rename = "add-machine", check = "is_admin")]
 pub async fn add_machine(
 	ctx: Context<'_>,
 	#[description = "Machine name"] name: String,
 	#[description = "Machine MAC Address as hex digits separated by :"] mac: String,
+	#[description = "Make the machine usable from every guild and DMs, defaults to only this guild"] global: Option<bool>,
 ) -> Result<(), BotError> {
-	let result = ctrl_wol_mch::add_machine(&ctx.data().data, &name, &mac).await;
+	let scope = match global.unwrap_or(false) {
+		true => Scope::Global,
+		false => Scope::of(ctx.guild_id()),
+	};
+	let result = ctrl_wol_mch::add_machine(&ctx.data().data, &name, &mac, scope).await;
 	let embed = view_wol_mch::add_machine_embed(result, &name, &mac);
 
 	reply_no_mentions(ctx, embed).await?;
@@ -19,13 +26,17 @@ pub async fn add_machine(
 	Ok(())
 }
 
-#[poise::command(slash_command, owners_only, rename = "remove-machine")]
+#[poise::command(slash_command, rename = "remove-machine", check = "is_machine_admin")]
 pub async fn remove_machine(
 	ctx: Context<'_>,
 	#[description = "Machine name"]
 	#[autocomplete = "autocomplete_machine_name"]
 	name: String,
 ) -> Result<(), BotError> {
+	if !confirm(ctx, view_wol_mch::confirm_remove_machine_embed(&name)).await? {
+		return Ok(());
+	}
+
 	let result = ctrl_wol_mch::remove_machine(&ctx.data().data, &name).await;
 	let embed = view_wol_mch::remove_machine_embed(result, &name);
 
@@ -34,12 +45,17 @@ pub async fn remove_machine(
 	Ok(())
 }
 
-#[poise::command(slash_command, rename = "list-machines")]
-pub async fn list_machines(ctx: Context<'_>) -> Result<(), BotError> {
-	let embed = ctrl_wol_mch::list_machines(&ctx.data().data, async |info| {
-		view_wol_mch::list_machines_embed(info)
-	})
-	.await;
+#[poise::command(slash_command, rename = "set-address", check = "is_machine_admin")]
+pub async fn set_address(
+	ctx: Context<'_>,
+	#[description = "Machine name"]
+	#[autocomplete = "autocomplete_machine_name"]
+	name: String,
+	#[description = "Address like host:port that answers while the machine is online, omit to stop checking"]
+	address: Option<String>,
+) -> Result<(), BotError> {
+	let result = ctrl_wol_mch::set_address(&ctx.data().data, &name, address.clone()).await;
+	let embed = view_wol_mch::set_address_embed(result, &name, address.as_deref());
 
 	reply_no_mentions(ctx, embed).await?;
 
@@ -53,12 +69,16 @@ pub async fn describe_machine(
 	#[autocomplete = "autocomplete_machine_name"]
 	name: String,
 ) -> Result<(), BotError> {
-	let embed = ctrl_wol_mch::describe_machine(&ctx.data().data, &name, async |result, name| {
-		view_wol_mch::describe_machine_embed(result, &name)
+	let (embed, buttons) = ctrl_wol_mch::describe_machine(&ctx.data().data, ctx.guild_id(), &name, async |result, name| {
+		let buttons = match result {
+			Ok(_) => view_wol_mch::describe_machine_buttons(name),
+			Err(_) => vec![],
+		};
+		(view_wol_mch::describe_machine_embed(result, name), buttons)
 	})
 	.await;
 
-	reply_no_mentions(ctx, embed).await?;
+	reply_with_components(ctx, embed, buttons).await?;
 
 	Ok(())
 }
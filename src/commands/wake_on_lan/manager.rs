@@ -0,0 +1,44 @@
+use super::super::checks::is_machine_admin;
+use super::super::reply_no_mentions;
+use super::autocomplete_machine_name;
+use crate::bot::{BotError, Context};
+use crate::controllers::wake_on_lan::manager as ctrl_wol_mgr;
+use crate::views::wake_on_lan::manager as view_wol_mgr;
+use poise::serenity_prelude::User;
+
+#[poise::command(slash_command, subcommands("add_manager", "remove_manager"), subcommand_required)]
+pub async fn manager(_: Context<'_>) -> Result<(), BotError> {
+	unreachable!("Can't call parent commands");
+}
+
+#[poise::command(slash_command, rename = "add", check = "is_machine_admin")]
+pub async fn add_manager(
+	ctx: Context<'_>,
+	#[description = "Machine name"]
+	#[autocomplete = "autocomplete_machine_name"]
+	machine_name: String,
+	#[description = "User that will be allowed to manage this machine"] user: User,
+) -> Result<(), BotError> {
+	let result = ctrl_wol_mgr::add_manager(&ctx.data().data, &machine_name, user.id).await;
+	let embed = view_wol_mgr::add_manager_embed(result, &machine_name, user.id);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
+
+#[poise::command(slash_command, rename = "remove", check = "is_machine_admin")]
+pub async fn remove_manager(
+	ctx: Context<'_>,
+	#[description = "Machine name"]
+	#[autocomplete = "autocomplete_machine_name"]
+	machine_name: String,
+	#[description = "User that will no longer be allowed to manage this machine"] user: User,
+) -> Result<(), BotError> {
+	let result = ctrl_wol_mgr::remove_manager(&ctx.data().data, &machine_name, user.id).await;
+	let embed = view_wol_mgr::remove_manager_embed(result, &machine_name, user.id);
+
+	reply_no_mentions(ctx, embed).await?;
+
+	Ok(())
+}
@@ -1,5 +1,5 @@
 use crate::bot::{BotError, Context};
-use crate::commands::reply_no_mentions;
+use crate::commands::reply_with_components;
 use crate::commands::wake_on_lan::autocomplete_machine_name;
 use crate::services::wake_on_lan::UdpMagicPacketSender;
 use crate::{controllers, views};
@@ -22,9 +22,10 @@ pub async fn wake(
 	)
 	.await;
 
+	let buttons = views::wake_on_lan::wake::wake_buttons(&result);
 	let embed = views::wake_on_lan::wake::wake_embed(result, &name);
 
-	reply_no_mentions(ctx, embed).await?;
+	reply_with_components(ctx, embed, buttons).await?;
 
 	Ok(())
 }
@@ -0,0 +1,265 @@
+use crate::controllers::access::{AccessDecision, RequestKey};
+use crate::data::access::AccessResource;
+use crate::data::servitor::ServitorAction;
+use poise::serenity_prelude::UserId;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Longest machine or server name, so that every custom ID embedding one stays within Discord's 100 characters.
+pub const MAX_NAME_LENGTH: usize = 24;
+
+/// Whether `name` fits in the custom IDs of components.
+pub fn is_valid_name(name: &str) -> bool {
+	name.chars().count() <= MAX_NAME_LENGTH
+}
+
+/// Custom IDs of message components that stay usable after the command that sent them has finished.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComponentId {
+	Servitor {
+		action: ServitorAction,
+		server_name: String,
+	},
+	Wake {
+		machine_name: String,
+	},
+	RequestAccess {
+		resource: AccessResource,
+	},
+	ReviewAccess {
+		request: RequestKey,
+		decision: AccessDecision,
+	},
+	/// Asks for a single use of the resource instead of a standing grant.
+	AskApproval {
+		resource: AccessResource,
+	},
+	ReviewApproval {
+		requester: UserId,
+		approve: bool,
+		resource: AccessResource,
+	},
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("unknown component id {0}")]
+pub struct UnknownComponentId(pub String);
+
+impl fmt::Display for ComponentId {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ComponentId::Servitor {
+				action,
+				server_name,
+			} => write!(f, "servitor:{action}:{server_name}"),
+			ComponentId::Wake { machine_name } => write!(f, "wol:wake:{machine_name}"),
+			ComponentId::RequestAccess { resource } => {
+				write!(f, "access:request:{}", format_access_resource(resource))
+			}
+			ComponentId::ReviewAccess { request, decision } => {
+				let decision = match decision {
+					AccessDecision::Approve => "approve",
+					AccessDecision::ApproveTemporarily => "approve-temporarily",
+					AccessDecision::Deny => "deny",
+				};
+				write!(
+					f,
+					"access:{decision}:{}:{}:{}",
+					request.id,
+					request.user,
+					format_access_resource(&request.resource)
+				)
+			}
+			ComponentId::AskApproval { resource } => write!(f, "once:ask:{}", format_access_resource(resource)),
+			ComponentId::ReviewApproval {
+				requester,
+				approve,
+				resource,
+			} => {
+				let decision = if *approve { "approve" } else { "deny" };
+				write!(f, "once:{decision}:{requester}:{}", format_access_resource(resource))
+			}
+		}
+	}
+}
+
+fn format_access_resource(resource: &AccessResource) -> String {
+	match resource {
+		AccessResource::Machine { name } => format!("wol:{name}"),
+		AccessResource::Server { name, action } => format!("servitor:{action}:{name}"),
+	}
+}
+
+fn parse_access_resource(s: &str) -> Option<AccessResource> {
+	match s.split_once(':')? {
+		("wol", name) if !name.is_empty() => Some(AccessResource::Machine { name: name.to_string() }),
+		("servitor", rest) => match rest.split_once(':')? {
+			(action, name) if !name.is_empty() => Some(AccessResource::Server {
+				name: name.to_string(),
+				action: action.parse().ok()?,
+			}),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+impl FromStr for ComponentId {
+	type Err = UnknownComponentId;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let unknown = || UnknownComponentId(s.to_string());
+
+		match s.splitn(3, ':').collect::<Vec<_>>()[..] {
+			["servitor", action, server_name] if !server_name.is_empty() => Ok(ComponentId::Servitor {
+				action: action.parse().map_err(|_| unknown())?,
+				server_name: server_name.to_string(),
+			}),
+			["wol", "wake", machine_name] if !machine_name.is_empty() => Ok(ComponentId::Wake {
+				machine_name: machine_name.to_string(),
+			}),
+			["access", "request", resource] => Ok(ComponentId::RequestAccess {
+				resource: parse_access_resource(resource).ok_or_else(unknown)?,
+			}),
+			["access", decision, rest] => Ok(ComponentId::ReviewAccess {
+				request: match rest.splitn(3, ':').collect::<Vec<_>>()[..] {
+					[id, user, resource] => RequestKey {
+						id: id.parse().map_err(|_| unknown())?,
+						user: user.parse().map_err(|_| unknown())?,
+						resource: parse_access_resource(resource).ok_or_else(unknown)?,
+					},
+					_ => return Err(unknown()),
+				},
+				decision: match decision {
+					"approve" => AccessDecision::Approve,
+					"approve-temporarily" => AccessDecision::ApproveTemporarily,
+					"deny" => AccessDecision::Deny,
+					_ => return Err(unknown()),
+				},
+			}),
+			["once", "ask", resource] => Ok(ComponentId::AskApproval {
+				resource: parse_access_resource(resource).ok_or_else(unknown)?,
+			}),
+			["once", decision @ ("approve" | "deny"), rest] => {
+				let (requester, resource) = rest.split_once(':').ok_or_else(unknown)?;
+				Ok(ComponentId::ReviewApproval {
+					requester: requester.parse().map_err(|_| unknown())?,
+					approve: decision == "approve",
+					resource: parse_access_resource(resource).ok_or_else(unknown)?,
+				})
+			}
+			_ => Err(unknown()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	#[rstest]
+	#[case(
+		ComponentId::Servitor { action: ServitorAction::Restart, server_name: "Some:Server".to_string() },
+		"servitor:restart:Some:Server"
+	)]
+	#[case(
+		ComponentId::Wake { machine_name: "SomeMachine".to_string() },
+		"wol:wake:SomeMachine"
+	)]
+	#[case(
+		ComponentId::RequestAccess { resource: AccessResource::Machine { name: "Some:Machine".to_string() } },
+		"access:request:wol:Some:Machine"
+	)]
+	#[case(
+		ComponentId::RequestAccess {
+			resource: AccessResource::Server { name: "SomeServer".to_string(), action: ServitorAction::Start }
+		},
+		"access:request:servitor:start:SomeServer"
+	)]
+	#[case(
+		ComponentId::ReviewAccess {
+			request: RequestKey {
+				id: 12,
+				user: UserId::new(5),
+				resource: AccessResource::Server { name: "Some:Server".to_string(), action: ServitorAction::Start }
+			},
+			decision: AccessDecision::ApproveTemporarily
+		},
+		"access:approve-temporarily:12:5:servitor:start:Some:Server"
+	)]
+	#[case(
+		ComponentId::ReviewAccess {
+			request: RequestKey { id: 3, user: UserId::new(5), resource: AccessResource::Machine { name: "SomeMachine".to_string() } },
+			decision: AccessDecision::Deny
+		},
+		"access:deny:3:5:wol:SomeMachine"
+	)]
+	#[case(
+		ComponentId::AskApproval { resource: AccessResource::Machine { name: "SomeMachine".to_string() } },
+		"once:ask:wol:SomeMachine"
+	)]
+	#[case(
+		ComponentId::ReviewApproval {
+			requester: UserId::new(5),
+			approve: true,
+			resource: AccessResource::Server { name: "Some:Server".to_string(), action: ServitorAction::Stop }
+		},
+		"once:approve:5:servitor:stop:Some:Server"
+	)]
+	#[case(
+		ComponentId::ReviewApproval {
+			requester: UserId::new(5),
+			approve: false,
+			resource: AccessResource::Machine { name: "SomeMachine".to_string() }
+		},
+		"once:deny:5:wol:SomeMachine"
+	)]
+	#[test]
+	fn given_component_id_then_roundtrips_through_custom_id(#[case] id: ComponentId, #[case] custom_id: &str) {
+		assert_eq!(id.to_string(), custom_id);
+		assert_eq!(custom_id.parse(), Ok(id));
+	}
+
+	#[test]
+	fn given_longest_name_then_longest_custom_id_fits_in_discord_limit() {
+		let name = "n".repeat(MAX_NAME_LENGTH);
+		let id = ComponentId::ReviewAccess {
+			request: RequestKey {
+				id: u32::MAX.into(),
+				user: UserId::new(u64::MAX),
+				resource: AccessResource::Server {
+					name,
+					action: ServitorAction::Restart,
+				},
+			},
+			decision: AccessDecision::ApproveTemporarily,
+		};
+
+		assert_eq!(id.to_string().chars().count(), 100);
+	}
+
+	#[rstest]
+	#[case("1234-confirm")]
+	#[case("servitor:explode:SomeServer")]
+	#[case("servitor:start:")]
+	#[case("wol:sleep:SomeMachine")]
+	#[case("access:request:wol:")]
+	#[case("access:request:servitor:explode:SomeServer")]
+	#[case("access:approve:3")]
+	#[case("access:approve:abc:5:wol:SomeMachine")]
+	#[case("access:approve:3:abc:wol:SomeMachine")]
+	#[case("access:approve:3:5:wol:")]
+	#[case("access:ignore:1:5:wol:SomeMachine")]
+	#[case("once:approve:abc:wol:SomeMachine")]
+	#[case("once:deny:5")]
+	#[case("once:maybe:5:wol:SomeMachine")]
+	#[test]
+	fn given_unknown_custom_id_then_parse_returns_error(#[case] custom_id: &str) {
+		assert_eq!(
+			custom_id.parse::<ComponentId>(),
+			Err(UnknownComponentId(custom_id.to_string()))
+		);
+	}
+}
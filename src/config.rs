@@ -1,12 +1,15 @@
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use figment::providers::{Env, Format, Toml};
 use figment::Figment;
 use secrecy::SecretString;
-use serde::Deserialize;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use serenity::all::{ChannelId, GuildId};
 
-static ENV_PREFIX: &'static str = "GJ_";
-static ENV_CONFIG_FILE: &'static str = "GJ_CONFIG_FILE";
-static DEFAULT_CONFIG_FILE: &'static str = "gjallarbot.toml";
+static ENV_PREFIX: &str = "GJ_";
+static ENV_CONFIG_FILE: &str = "GJ_CONFIG_FILE";
+static DEFAULT_CONFIG_FILE: &str = "gjallarbot.toml";
 
 impl Config {
 	pub(crate) fn load() -> figment::error::Result<Config> {
@@ -25,6 +28,26 @@ pub struct Config {
 	pub log: LogConfig,
 	#[serde(default)]
 	pub servitor: BTreeMap<String, ServitorConfig>,
+	#[serde(default)]
+	pub health: HealthConfig,
+	#[serde(default)]
+	pub watch: WatchConfig,
+	#[serde(default)]
+	pub dashboard: DashboardConfig,
+	#[serde(default)]
+	pub bulk: BulkConfig,
+	#[serde(default)]
+	pub schedule: ScheduleConfig,
+	#[serde(default)]
+	pub idle: IdleConfig,
+	#[serde(default)]
+	pub query: GameQueryConfig,
+	#[serde(default)]
+	pub migration: MigrationConfig,
+	#[serde(default)]
+	pub grants: GrantsConfig,
+	#[serde(default)]
+	pub access: AccessConfig,
 }
 
 #[derive(Deserialize)]
@@ -45,9 +68,340 @@ impl Default for LogConfig {
 	}
 }
 
+#[derive(Deserialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ServitorKind {
+	/// A remote Servitor instance reached over HTTP.
+	#[default]
+	Http,
+	/// The system manager of the host the bot runs on.
+	Local,
+}
+
 #[derive(Deserialize)]
 pub struct ServitorConfig {
-	pub url: String,
+	#[serde(default)]
+	pub kind: ServitorKind,
+	#[serde(default)]
+	pub url: Option<String>,
 	#[serde(default)]
 	pub token: Option<SecretString>,
+	#[serde(default = "ServitorConfig::default_timeout")]
+	pub timeout: u64,
+	#[serde(default = "ServitorConfig::default_timeout")]
+	pub connect_timeout: u64,
+	#[serde(default = "ServitorConfig::default_retries")]
+	pub retries: u32,
+	#[serde(default = "ServitorConfig::default_backoff")]
+	pub backoff: u64,
+	#[serde(default = "ServitorConfig::default_failures")]
+	pub failures: u32,
+	#[serde(default = "ServitorConfig::default_cooldown")]
+	pub cooldown: u64,
+	#[serde(default)]
+	pub ca: Option<PathBuf>,
+	#[serde(default)]
+	pub cert: Option<PathBuf>,
+	#[serde(default)]
+	pub key: Option<PathBuf>,
+	#[serde(default)]
+	pub fingerprint: Option<String>,
+}
+
+impl ServitorConfig {
+	fn default_timeout() -> u64 {
+		2000
+	}
+
+	fn default_retries() -> u32 {
+		2
+	}
+
+	fn default_backoff() -> u64 {
+		200
+	}
+
+	fn default_failures() -> u32 {
+		5
+	}
+
+	fn default_cooldown() -> u64 {
+		30
+	}
+}
+
+/// Rejects intervals of 0 seconds, periodic tasks can't tick that often.
+fn deserialize_interval<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+	let interval = u64::deserialize(deserializer)?;
+	if interval == 0 {
+		return Err(D::Error::custom("interval must be at least 1 second"));
+	}
+
+	Ok(interval)
+}
+
+#[derive(Deserialize)]
+pub struct HealthConfig {
+	#[serde(default = "HealthConfig::default_interval", deserialize_with = "deserialize_interval")]
+	pub interval: u64,
+	#[serde(default)]
+	pub channel: Option<ChannelId>,
+}
+
+impl HealthConfig {
+	fn default_interval() -> u64 {
+		60
+	}
+}
+
+impl Default for HealthConfig {
+	fn default() -> Self {
+		HealthConfig {
+			interval: Self::default_interval(),
+			channel: None,
+		}
+	}
+}
+
+#[derive(Deserialize)]
+pub struct WatchConfig {
+	#[serde(default = "WatchConfig::default_interval", deserialize_with = "deserialize_interval")]
+	pub interval: u64,
+}
+
+impl WatchConfig {
+	fn default_interval() -> u64 {
+		30
+	}
+}
+
+impl Default for WatchConfig {
+	fn default() -> Self {
+		WatchConfig {
+			interval: Self::default_interval(),
+		}
+	}
+}
+
+#[derive(Deserialize)]
+pub struct DashboardConfig {
+	#[serde(default = "DashboardConfig::default_interval", deserialize_with = "deserialize_interval")]
+	pub interval: u64,
+}
+
+impl DashboardConfig {
+	fn default_interval() -> u64 {
+		60
+	}
+}
+
+impl Default for DashboardConfig {
+	fn default() -> Self {
+		DashboardConfig {
+			interval: Self::default_interval(),
+		}
+	}
+}
+
+#[derive(Deserialize)]
+pub struct BulkConfig {
+	#[serde(default = "BulkConfig::default_concurrency")]
+	pub concurrency: usize,
+}
+
+impl BulkConfig {
+	fn default_concurrency() -> usize {
+		2
+	}
+}
+
+impl Default for BulkConfig {
+	fn default() -> Self {
+		BulkConfig {
+			concurrency: Self::default_concurrency(),
+		}
+	}
+}
+
+#[derive(Deserialize)]
+pub struct ScheduleConfig {
+	#[serde(default = "ScheduleConfig::default_interval", deserialize_with = "deserialize_interval")]
+	pub interval: u64,
+}
+
+impl ScheduleConfig {
+	fn default_interval() -> u64 {
+		15
+	}
+}
+
+impl Default for ScheduleConfig {
+	fn default() -> Self {
+		ScheduleConfig {
+			interval: Self::default_interval(),
+		}
+	}
+}
+
+#[derive(Deserialize)]
+pub struct IdleConfig {
+	#[serde(default = "IdleConfig::default_interval", deserialize_with = "deserialize_interval")]
+	pub interval: u64,
+}
+
+impl IdleConfig {
+	fn default_interval() -> u64 {
+		60
+	}
+}
+
+impl Default for IdleConfig {
+	fn default() -> Self {
+		IdleConfig {
+			interval: Self::default_interval(),
+		}
+	}
+}
+
+#[derive(Deserialize)]
+pub struct GameQueryConfig {
+	#[serde(default = "GameQueryConfig::default_timeout")]
+	pub timeout: u64,
+}
+
+impl GameQueryConfig {
+	fn default_timeout() -> u64 {
+		2000
+	}
+}
+
+impl Default for GameQueryConfig {
+	fn default() -> Self {
+		GameQueryConfig {
+			timeout: Self::default_timeout(),
+		}
+	}
+}
+
+#[derive(Deserialize, Default)]
+pub struct MigrationConfig {
+	#[serde(default)]
+	pub guild: Option<GuildId>,
+}
+
+#[derive(Deserialize)]
+pub struct GrantsConfig {
+	#[serde(default = "GrantsConfig::default_interval", deserialize_with = "deserialize_interval")]
+	pub interval: u64,
+	#[serde(default = "GrantsConfig::default_notify")]
+	pub notify: bool,
+}
+
+impl GrantsConfig {
+	fn default_interval() -> u64 {
+		60
+	}
+
+	fn default_notify() -> bool {
+		true
+	}
+}
+
+impl Default for GrantsConfig {
+	fn default() -> Self {
+		GrantsConfig {
+			interval: Self::default_interval(),
+			notify: Self::default_notify(),
+		}
+	}
+}
+
+#[derive(Deserialize)]
+pub struct AccessConfig {
+	#[serde(default)]
+	pub channel: Option<ChannelId>,
+	#[serde(default = "AccessConfig::default_hours", deserialize_with = "AccessConfig::deserialize_hours")]
+	pub hours: u64,
+}
+
+impl AccessConfig {
+	fn default_hours() -> u64 {
+		24
+	}
+
+	/// Ten years, long enough for any temporary approval while keeping its expiry well within range.
+	const MAX_HOURS: u64 = 24 * 365 * 10;
+
+	fn deserialize_hours<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+		let hours = u64::deserialize(deserializer)?;
+		if !(1..=Self::MAX_HOURS).contains(&hours) {
+			return Err(D::Error::custom(format!("hours must be between 1 and {}", Self::MAX_HOURS)));
+		}
+
+		Ok(hours)
+	}
+}
+
+impl Default for AccessConfig {
+	fn default() -> Self {
+		AccessConfig {
+			channel: None,
+			hours: Self::default_hours(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	fn parse(toml: &str) -> figment::error::Result<Config> {
+		Figment::from(Toml::string(&format!("[bot]\ntoken = \"secret\"\n{toml}"))).extract()
+	}
+
+	#[test]
+	fn given_no_intervals_then_defaults_are_used() {
+		let config = parse("").unwrap();
+
+		assert_eq!(config.health.interval, 60);
+		assert_eq!(config.schedule.interval, 15);
+	}
+
+	#[rstest]
+	#[case("health")]
+	#[case("watch")]
+	#[case("dashboard")]
+	#[case("schedule")]
+	#[case("idle")]
+	#[case("grants")]
+	fn given_zero_interval_then_config_is_rejected(#[case] section: &str) {
+		let result = parse(&format!("[{section}]\ninterval = 0"));
+
+		assert!(result.is_err());
+	}
+
+	#[rstest]
+	#[case(0)]
+	#[case(87601)]
+	#[case(u64::MAX)]
+	fn given_access_hours_out_of_range_then_config_is_rejected(#[case] hours: u64) {
+		let result = parse(&format!("[access]\nhours = {hours}"));
+
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn given_access_hours_in_range_then_they_are_used() {
+		let config = parse("[access]\nhours = 87600").unwrap();
+
+		assert_eq!(config.access.hours, 87600);
+	}
+
+	#[test]
+	fn given_positive_interval_then_it_is_used() {
+		let config = parse("[health]\ninterval = 5").unwrap();
+
+		assert_eq!(config.health.interval, 5);
+	}
 }
@@ -1,30 +1,96 @@
+use chrono::{TimeDelta, Utc};
 use serenity::all::{Member, RoleId, User, UserId};
-use crate::data::authorization::AuthorizationInfo;
+use crate::data::authorization::{AuthorizationInfo, Denials};
+use crate::data::group::AccessGroups;
 
 pub mod wake_on_lan;
 pub mod servitor;
+pub mod admin;
+pub mod grants;
+pub mod access;
+pub mod group;
+pub mod audit;
+pub mod authorization;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DiscordEntity {
 	User(UserId),
 	Role(RoleId),
 }
 
+/// Resources scoped to a guild are only usable by members invoking from that guild, expired grants are ignored.
+///
+/// Users are also authorized through any granted group they are in, directly or through their roles. Denied users and
+/// members of denied roles are never authorized, whatever they were granted.
 fn is_user_authorized<T: AuthorizationInfo>(
 	author: &User,
 	member: Option<&Member>,
 	info: &T,
+	groups: &AccessGroups,
 ) -> bool {
-	info.authorized_users().contains(&author.id)
-		|| member.map_or(false, |m| {
-		m.roles.iter().any(|&role| info.authorized_roles().contains(&role))
-	})
+	if !info.scope().is_visible_in(member.map(|m| m.guild_id)) {
+		return false;
+	}
+
+	let now = Utc::now();
+	let roles = member.map_or(&[][..], |m| &m.roles[..]);
+	if info.denials().denies(Some(author.id), roles) {
+		return false;
+	}
+
+	info.allows_user(author.id, now)
+		|| roles.iter().any(|&role| info.allows_role(role, now))
+		|| groups
+			.iter()
+			.any(|(name, group)| info.allows_group(name) && group.contains(author.id, roles))
+}
+
+/// Denies `entity`, or changes the reason it is denied, returning whether that changed anything.
+fn set_denial(denials: &mut Denials, entity: DiscordEntity, reason: Option<String>) -> bool {
+	let previous = match entity {
+		DiscordEntity::User(id) => denials.users.insert(id, reason.clone()),
+		DiscordEntity::Role(id) => denials.roles.insert(id, reason.clone()),
+	};
+	previous != Some(reason)
+}
+
+/// Lifts the denial of `entity`, returning whether it was denied.
+fn clear_denial(denials: &mut Denials, entity: DiscordEntity) -> bool {
+	match entity {
+		DiscordEntity::User(id) => denials.users.remove(&id).is_some(),
+		DiscordEntity::Role(id) => denials.roles.remove(&id).is_some(),
+	}
 }
 
+/// Parses durations like `90m`, `2h` or `1d12h`.
+pub fn parse_duration(duration: &str) -> Option<TimeDelta> {
+	let mut total = TimeDelta::zero();
+	let mut number = String::new();
+
+	for c in duration.trim().chars() {
+		if c.is_ascii_digit() {
+			number.push(c);
+			continue;
+		}
+
+		let value: i64 = number.parse().ok()?;
+		number.clear();
+		let delta = match c.to_ascii_lowercase() {
+			'd' => TimeDelta::try_days(value)?,
+			'h' => TimeDelta::try_hours(value)?,
+			'm' => TimeDelta::try_minutes(value)?,
+			_ => return None,
+		};
+		total = total.checked_add(&delta)?;
+	}
+
+	(number.is_empty() && total > TimeDelta::zero()).then_some(total)
+}
 
 #[cfg(test)]
 pub mod tests {
-	use serenity::all::{Member, RoleId, User, UserId};
+	use super::*;
+	use rstest::rstest;
 
 	pub fn mock_author_dms(id: UserId) -> (User, Option<Member>) {
 		let mut user = User::default();
@@ -43,4 +109,18 @@ pub mod tests {
 
 		(user, Some(member))
 	}
+
+	#[rstest]
+	#[case("90m", Some(TimeDelta::minutes(90)))]
+	#[case("1d12h", Some(TimeDelta::hours(36)))]
+	#[case(" 2H ", Some(TimeDelta::hours(2)))]
+	#[case("2", None)]
+	#[case("0m", None)]
+	#[case("2w", None)]
+	#[case("h", None)]
+	#[case("100000000d", Some(TimeDelta::days(100_000_000)))]
+	#[case("106751991167d106751991167d", None)]
+	fn given_duration_then_parse_duration_returns_delta(#[case] duration: &str, #[case] expected: Option<TimeDelta>) {
+		assert_eq!(parse_duration(duration), expected);
+	}
 }
@@ -0,0 +1,589 @@
+use super::admin::{can_manage, ManagedResource};
+use super::is_user_authorized;
+use super::authorization::{permit, AddPermissionError};
+use super::servitor::ServerError;
+use super::wake_on_lan::MachineError;
+use super::DiscordEntity;
+use crate::data::access::{AccessRequest, AccessResource};
+use crate::data::scope::Scope;
+use crate::data::servitor::ServerInfo;
+use crate::data::wake_on_lan::WakeOnLanMachineInfo;
+use crate::data::{next_id, BotData, Data};
+use chrono::{DateTime, TimeDelta, Utc};
+use log::info;
+use serenity::all::{GuildId, Member, MessageId, User, UserId};
+use std::collections::{BTreeSet, HashSet};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccessDecision {
+	Approve,
+	ApproveTemporarily,
+	Deny,
+}
+
+/// The request review buttons were sent for, reviews only apply while that same request is pending.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestKey {
+	pub id: u64,
+	pub user: UserId,
+	pub resource: AccessResource,
+}
+
+impl RequestKey {
+	pub fn new(id: u64, request: &AccessRequest) -> Self {
+		RequestKey {
+			id,
+			user: request.user,
+			resource: request.resource.clone(),
+		}
+	}
+
+	fn matches(&self, request: &AccessRequest) -> bool {
+		request.user == self.user && request.resource == self.resource
+	}
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum RequestAccessError {
+	#[error(transparent)]
+	Machine(#[from] MachineError),
+
+	#[error(transparent)]
+	Server(#[from] ServerError),
+
+	#[error("user {user} already has the pending access request {request}")]
+	AlreadyRequested { user: UserId, request: u64 },
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ReviewAccessError {
+	#[error("access request {request} does not exist")]
+	DoesNotExist { request: u64 },
+
+	#[error("user {user} may not review access request {request}")]
+	Unauthorized { user: UserId, request: u64 },
+
+	#[error(transparent)]
+	Machine(#[from] MachineError),
+
+	#[error(transparent)]
+	Server(#[from] ServerError),
+}
+
+/// Scope and managers of the requested resource, if it exists.
+fn resource_owners(data: &Data, resource: &AccessResource) -> Option<(Scope, BTreeSet<UserId>)> {
+	match resource {
+		AccessResource::Machine { name } => data.wake_on_lan.get(name).map(|info| (info.scope, info.managers.clone())),
+		AccessResource::Server { name, .. } => data.servitor.get(name).map(|info| (info.scope, info.managers.clone())),
+	}
+}
+
+fn missing_resource(resource: &AccessResource) -> RequestAccessError {
+	match resource {
+		AccessResource::Machine { name } => MachineError::DoesNotExist {
+			machine_name: name.clone(),
+		}
+		.into(),
+		AccessResource::Server { name, .. } => ServerError::DoesNotExist {
+			server_name: name.clone(),
+		}
+		.into(),
+	}
+}
+
+pub async fn request_access(
+	data: &BotData,
+	user: UserId,
+	guild: Option<GuildId>,
+	resource: AccessResource,
+	now: DateTime<Utc>,
+) -> Result<u64, RequestAccessError> {
+	let mut lock = data.write().await;
+	match resource_owners(&lock, &resource) {
+		Some((scope, _)) if scope.is_visible_in(guild) => {}
+		_ => return Err(missing_resource(&resource)),
+	}
+
+	let pending = lock
+		.access_requests
+		.iter()
+		.find(|(_, request)| request.user == user && request.resource == resource);
+	if let Some((&request, _)) = pending {
+		return Err(RequestAccessError::AlreadyRequested { user, request });
+	}
+
+	let mut data_write = lock.write();
+	let data_write = &mut *data_write;
+	let id = next_id(&mut data_write.last_access_request, &data_write.access_requests);
+	info!("User {user} requested access to {resource:?} as request {id}");
+	data_write.access_requests.insert(
+		id,
+		AccessRequest {
+			user,
+			guild,
+			resource,
+			requested_at: now,
+		},
+	);
+
+	Ok(id)
+}
+
+pub async fn pending_request(data: &BotData, request: u64) -> Option<AccessRequest> {
+	data.read().await.access_requests.get(&request).cloned()
+}
+
+/// Users to DM about a request: the owners, admins whose scope covers the resource and its managers.
+///
+/// Admin roles can't be resolved to users here, they are only reached through an approvals channel.
+pub async fn approvers(data: &BotData, owners: &HashSet<UserId>, resource: &AccessResource) -> BTreeSet<UserId> {
+	let read = data.read().await;
+	let Some((scope, managers)) = resource_owners(&read, resource) else {
+		return owners.iter().copied().collect();
+	};
+
+	let admins = read
+		.admins
+		.users
+		.iter()
+		.filter(|(_, admin)| admin.is_global() || **admin == scope)
+		.map(|(user, _)| *user);
+
+	owners.iter().copied().chain(admins).chain(managers).collect()
+}
+
+/// Approves or denies a pending request, granting the requester access on approval.
+///
+/// Reviewers must be owners, admins or managers of the requested resource.
+pub async fn review_access(
+	data: &BotData,
+	key: &RequestKey,
+	reviewer: &User,
+	member: Option<&Member>,
+	is_owner: bool,
+	decision: AccessDecision,
+	expires: Option<DateTime<Utc>>,
+) -> Result<AccessRequest, ReviewAccessError> {
+	let request = key.id;
+	let pending = pending_request(data, request)
+		.await
+		.filter(|pending| key.matches(pending))
+		.ok_or(ReviewAccessError::DoesNotExist { request })?;
+
+	let resource = match &pending.resource {
+		AccessResource::Machine { name } => ManagedResource::Machine(name),
+		AccessResource::Server { name, .. } => ManagedResource::Server(name),
+	};
+	if !is_owner && !can_manage(data, reviewer, member, resource, true).await {
+		return Err(ReviewAccessError::Unauthorized {
+			user: reviewer.id,
+			request,
+		});
+	}
+
+	// Taken out before granting so that concurrent reviews of the same request only apply once
+	let mut lock = data.write().await;
+	let removed = match lock.access_requests.get(&request) {
+		Some(pending) if key.matches(pending) => lock.write().access_requests.remove(&request),
+		_ => None,
+	};
+	drop(lock);
+	let Some(pending) = removed else {
+		return Err(ReviewAccessError::DoesNotExist { request });
+	};
+	info!("User {} reviewed access request {request} with {decision:?}", reviewer.id);
+
+	if decision == AccessDecision::Deny {
+		return Ok(pending);
+	}
+
+	match &pending.resource {
+		AccessResource::Machine { name } => {
+			match permit::<WakeOnLanMachineInfo>(data, name, DiscordEntity::User(pending.user), (), expires).await {
+				Ok(()) | Err(AddPermissionError::AlreadyAuthorized { .. }) => {}
+				Err(AddPermissionError::Resource(e)) => return Err(e.into()),
+			}
+		}
+		AccessResource::Server { name, action } => {
+			let actions = BTreeSet::from([*action]);
+			match permit::<ServerInfo>(data, name, DiscordEntity::User(pending.user), actions, expires).await {
+				Ok(()) | Err(AddPermissionError::AlreadyAuthorized { .. }) => {}
+				Err(AddPermissionError::Resource(e)) => return Err(e.into()),
+			}
+		}
+	}
+
+	Ok(pending)
+}
+
+/// Whether `user` may use the resource themselves, which lets them approve or deny a single use by someone else.
+pub async fn can_approve(data: &BotData, resource: &AccessResource, user: &User, member: Option<&Member>) -> bool {
+	let read = data.read().await;
+	match resource {
+		AccessResource::Machine { name } => read
+			.wake_on_lan
+			.get(name)
+			.is_some_and(|info| is_user_authorized(user, member, info, &read.access_groups)),
+		AccessResource::Server { name, action } => read
+			.servitor
+			.get(name)
+			.is_some_and(|info| is_user_authorized(user, member, &info.for_action(*action), &read.access_groups)),
+	}
+}
+
+/// How long decided approvals are remembered, long after their buttons were removed from the message.
+const APPROVAL_CLAIM_RETENTION: TimeDelta = TimeDelta::days(1);
+
+/// Claims the single use approval posted in `message` for one reviewer, false when someone already decided on it.
+pub async fn claim_approval(data: &BotData, message: MessageId, now: DateTime<Utc>) -> bool {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+
+	data_write.approval_claims.retain(|claimed| {
+		*claimed == message
+			|| DateTime::from_timestamp(claimed.created_at().unix_timestamp(), 0)
+				.is_some_and(|posted| now - posted < APPROVAL_CLAIM_RETENTION)
+	});
+	data_write.approval_claims.insert(message)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::super::tests::{mock_author_dms, mock_author_guild};
+	use super::*;
+	use crate::data::access::ApprovalClaims;
+	use crate::data::servitor::ServitorAction;
+	use crate::data::tests::mock_data;
+	use serde_json::json;
+	use serenity::all::RoleId;
+	use std::collections::BTreeMap;
+
+	const REQUESTER: u64 = 12345678901234567;
+
+	fn now() -> DateTime<Utc> {
+		DateTime::from_timestamp(1741291185, 0).unwrap()
+	}
+
+	fn machine(name: &str) -> AccessResource {
+		AccessResource::Machine { name: name.to_string() }
+	}
+
+	fn server(name: &str, action: ServitorAction) -> AccessResource {
+		AccessResource::Server {
+			name: name.to_string(),
+			action,
+		}
+	}
+
+	/// Key of a request by the requester, as sent with its review buttons.
+	fn key(id: u64, resource: AccessResource) -> RequestKey {
+		RequestKey {
+			id,
+			user: UserId::new(REQUESTER),
+			resource,
+		}
+	}
+
+	fn access_data() -> BotData {
+		mock_data(Some(json!({
+			"admins": {
+				"users": { "1": "global", "2": { "guild": "100" }, "3": { "guild": "200" } }
+			},
+			"wake_on_lan": {
+				"SomeMachine": { "mac": [1, 2, 3, 4, 5, 6], "managers": [4] },
+				"GuildMachine": { "mac": [1, 2, 3, 4, 5, 7], "scope": { "guild": "100" } }
+			},
+			"servitor": {
+				"SomeServer": { "servitor": "foo", "unit_name": "bar", "scope": { "guild": "100" } }
+			},
+			"access_requests": {
+				"1": {
+					"user": "12345678901234567",
+					"resource": { "kind": "machine", "name": "SomeMachine" },
+					"requested_at": "2025-03-06T19:59:45Z"
+				},
+				"2": {
+					"user": "12345678901234567",
+					"guild": "100",
+					"resource": { "kind": "server", "name": "SomeServer", "action": "start" },
+					"requested_at": "2025-03-06T19:59:45Z"
+				}
+			},
+			"last_access_request": 2
+		})))
+	}
+
+	#[tokio::test]
+	async fn given_new_request_then_request_access_stores_it_with_next_id() {
+		let data = access_data();
+
+		let result = request_access(&data, UserId::new(5), None, machine("SomeMachine"), now()).await;
+
+		assert_eq!(result, Ok(3));
+		assert_eq!(
+			data.read().await.access_requests[&3],
+			AccessRequest {
+				user: UserId::new(5),
+				guild: None,
+				resource: machine("SomeMachine"),
+				requested_at: now(),
+			}
+		);
+	}
+
+	#[tokio::test]
+	async fn given_last_request_id_then_request_access_continues_after_it() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": { "SomeMachine": { "mac": [1, 2, 3, 4, 5, 6] } },
+			"last_access_request": 7
+		})));
+
+		let result = request_access(&data, UserId::new(5), None, machine("SomeMachine"), now()).await;
+
+		assert_eq!(result, Ok(8));
+		assert_eq!(data.read().await.last_access_request, 8);
+	}
+
+	#[tokio::test]
+	async fn given_newest_request_removed_then_its_review_buttons_do_not_apply_to_the_next_one() {
+		let data = access_data();
+		let (owner, member) = mock_author_dms(UserId::new(9));
+		let stale = key(2, server("SomeServer", ServitorAction::Start));
+		let denied = review_access(&data, &stale, &owner, member.as_ref(), true, AccessDecision::Deny, None).await;
+
+		let result = request_access(&data, UserId::new(5), None, machine("SomeMachine"), now()).await;
+		let review = review_access(&data, &stale, &owner, member.as_ref(), true, AccessDecision::Approve, None).await;
+
+		assert!(denied.is_ok());
+		assert_eq!(result, Ok(3));
+		assert_eq!(review, Err(ReviewAccessError::DoesNotExist { request: 2 }));
+		let read = data.read().await;
+		assert!(read.access_requests.contains_key(&3));
+		assert!(read.wake_on_lan["SomeMachine"].authorized_users.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_key_of_other_request_then_review_access_returns_error() {
+		let data = access_data();
+		let (owner, member) = mock_author_dms(UserId::new(9));
+		let other = RequestKey {
+			id: 1,
+			user: UserId::new(5),
+			resource: machine("SomeMachine"),
+		};
+
+		let result = review_access(&data, &other, &owner, member.as_ref(), true, AccessDecision::Approve, None).await;
+
+		assert_eq!(result, Err(ReviewAccessError::DoesNotExist { request: 1 }));
+		let read = data.read().await;
+		assert!(read.access_requests.contains_key(&1));
+		assert!(read.wake_on_lan["SomeMachine"].authorized_users.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_same_pending_request_then_request_access_returns_error() {
+		let data = access_data();
+
+		let result = request_access(&data, UserId::new(REQUESTER), None, machine("SomeMachine"), now()).await;
+
+		assert_eq!(
+			result,
+			Err(RequestAccessError::AlreadyRequested {
+				user: UserId::new(REQUESTER),
+				request: 1
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_request_for_other_action_then_request_access_stores_it() {
+		let data = access_data();
+
+		let resource = server("SomeServer", ServitorAction::Stop);
+		let result = request_access(&data, UserId::new(REQUESTER), Some(GuildId::new(100)), resource, now()).await;
+
+		assert_eq!(result, Ok(3));
+	}
+
+	#[tokio::test]
+	async fn given_resource_of_other_guild_then_request_access_returns_error() {
+		let data = access_data();
+
+		let resource = machine("GuildMachine");
+		let result = request_access(&data, UserId::new(5), Some(GuildId::new(200)), resource, now()).await;
+
+		assert_eq!(
+			result,
+			Err(RequestAccessError::Machine(MachineError::DoesNotExist {
+				machine_name: "GuildMachine".to_string()
+			}))
+		);
+		assert_eq!(data.read().await.access_requests.len(), 2);
+	}
+
+	#[tokio::test]
+	async fn given_nonexistent_server_then_request_access_returns_error() {
+		let data = access_data();
+
+		let resource = server("UnknownServer", ServitorAction::Start);
+		let result = request_access(&data, UserId::new(5), None, resource, now()).await;
+
+		assert_eq!(
+			result,
+			Err(RequestAccessError::Server(ServerError::DoesNotExist {
+				server_name: "UnknownServer".to_string()
+			}))
+		);
+	}
+
+	#[tokio::test]
+	async fn given_resource_then_approvers_are_owners_covering_admins_and_managers() {
+		let data = access_data();
+		let owners = HashSet::from([UserId::new(9)]);
+
+		let global = approvers(&data, &owners, &machine("SomeMachine")).await;
+		let guild = approvers(&data, &owners, &server("SomeServer", ServitorAction::Start)).await;
+
+		assert_eq!(global, BTreeSet::from([UserId::new(1), UserId::new(4), UserId::new(9)]));
+		assert_eq!(guild, BTreeSet::from([UserId::new(1), UserId::new(2), UserId::new(9)]));
+	}
+
+	#[tokio::test]
+	async fn given_manager_approval_then_review_access_permits_requester() {
+		let data = access_data();
+		let (reviewer, member) = mock_author_dms(UserId::new(4));
+
+		let result = review_access(&data, &key(1, machine("SomeMachine")), &reviewer, member.as_ref(), false, AccessDecision::Approve, None).await;
+
+		assert_eq!(result.map(|request| request.user), Ok(UserId::new(REQUESTER)));
+		let read = data.read().await;
+		assert!(read.wake_on_lan["SomeMachine"].authorized_users.contains(&UserId::new(REQUESTER)));
+		assert!(!read.access_requests.contains_key(&1));
+	}
+
+	#[tokio::test]
+	async fn given_temporary_approval_then_review_access_permits_only_the_action_until_expiry() {
+		let data = access_data();
+		{
+			let mut lock = data.write().await;
+			let mut data_write = lock.write();
+			let server = data_write.servitor.get_mut("SomeServer").unwrap();
+			server.authorized_users.insert(UserId::new(REQUESTER), BTreeSet::from([ServitorAction::Status]));
+		}
+		let (reviewer, mut member) = mock_author_guild(UserId::new(2), vec![]);
+		member.as_mut().unwrap().guild_id = GuildId::new(100);
+		let expires = now() + chrono::TimeDelta::hours(24);
+
+		let decision = AccessDecision::ApproveTemporarily;
+		let result = review_access(&data, &key(2, server("SomeServer", ServitorAction::Start)), &reviewer, member.as_ref(), false, decision, Some(expires)).await;
+
+		assert!(result.is_ok());
+		let read = data.read().await;
+		let server = &read.servitor["SomeServer"];
+		assert_eq!(
+			server.authorized_users[&UserId::new(REQUESTER)],
+			BTreeSet::from([ServitorAction::Status, ServitorAction::Start])
+		);
+		assert_eq!(
+			server.user_expiries[&UserId::new(REQUESTER)],
+			BTreeMap::from([(ServitorAction::Start, expires)])
+		);
+	}
+
+	#[tokio::test]
+	async fn given_denial_then_review_access_only_removes_request() {
+		let data = access_data();
+		let (reviewer, member) = mock_author_dms(UserId::new(9));
+
+		let result = review_access(&data, &key(1, machine("SomeMachine")), &reviewer, member.as_ref(), true, AccessDecision::Deny, None).await;
+
+		assert!(result.is_ok());
+		let read = data.read().await;
+		assert!(read.wake_on_lan["SomeMachine"].authorized_users.is_empty());
+		assert!(!read.access_requests.contains_key(&1));
+	}
+
+	#[tokio::test]
+	async fn given_admin_of_other_guild_then_review_access_returns_error() {
+		let data = access_data();
+		let (reviewer, mut member) = mock_author_guild(UserId::new(3), vec![RoleId::new(1)]);
+		member.as_mut().unwrap().guild_id = GuildId::new(200);
+
+		let result = review_access(&data, &key(2, server("SomeServer", ServitorAction::Start)), &reviewer, member.as_ref(), false, AccessDecision::Approve, None).await;
+
+		assert_eq!(
+			result,
+			Err(ReviewAccessError::Unauthorized {
+				user: UserId::new(3),
+				request: 2
+			})
+		);
+		assert!(data.read().await.access_requests.contains_key(&2));
+	}
+
+	#[tokio::test]
+	async fn given_nonexistent_request_then_review_access_returns_error() {
+		let data = access_data();
+		let (reviewer, member) = mock_author_dms(UserId::new(1));
+
+		let result = review_access(&data, &key(7, machine("SomeMachine")), &reviewer, member.as_ref(), false, AccessDecision::Approve, None).await;
+
+		assert_eq!(result, Err(ReviewAccessError::DoesNotExist { request: 7 }));
+	}
+
+	#[tokio::test]
+	async fn given_authorized_user_then_can_approve_only_their_actions() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"SomeMachine": { "mac": [1, 2, 3, 4, 5, 6], "authorized_users": [1] }
+			},
+			"servitor": {
+				"SomeServer": { "servitor": "foo", "unit_name": "bar", "authorized_users": { "1": ["start"] } }
+			}
+		})));
+		let (user, member) = mock_author_dms(UserId::new(1));
+		let (other, other_member) = mock_author_dms(UserId::new(2));
+
+		assert!(can_approve(&data, &machine("SomeMachine"), &user, member.as_ref()).await);
+		assert!(can_approve(&data, &server("SomeServer", ServitorAction::Start), &user, member.as_ref()).await);
+		assert!(!can_approve(&data, &server("SomeServer", ServitorAction::Stop), &user, member.as_ref()).await);
+		assert!(!can_approve(&data, &machine("SomeMachine"), &other, other_member.as_ref()).await);
+		assert!(!can_approve(&data, &machine("UnknownMachine"), &user, member.as_ref()).await);
+	}
+
+	fn message_posted_at(posted: DateTime<Utc>) -> MessageId {
+		const DISCORD_EPOCH: i64 = 1420070400000;
+		MessageId::new(((posted.timestamp_millis() - DISCORD_EPOCH) as u64) << 22)
+	}
+
+	#[tokio::test]
+	async fn given_concurrent_claims_then_claim_approval_succeeds_only_once() {
+		let data = mock_data(None);
+		let message = message_posted_at(now());
+
+		let claims = tokio::join!(
+			claim_approval(&data, message, now()),
+			claim_approval(&data, message, now()),
+		);
+
+		assert!(matches!(claims, (true, false) | (false, true)));
+		assert!(claim_approval(&data, message_posted_at(now() + TimeDelta::seconds(1)), now()).await);
+	}
+
+	#[tokio::test]
+	async fn given_claim_older_than_retention_then_claim_approval_forgets_it() {
+		let old = message_posted_at(now() - TimeDelta::days(2));
+		let recent = message_posted_at(now() - TimeDelta::hours(1));
+		let data = mock_data(Some(json!({
+			"approval_claims": [old, recent]
+		})));
+
+		assert!(!claim_approval(&data, old, now()).await);
+		assert!(!claim_approval(&data, recent, now()).await);
+
+		let claimed = message_posted_at(now());
+		assert!(claim_approval(&data, claimed, now()).await);
+		assert_eq!(data.read().await.approval_claims, ApprovalClaims::from([recent, claimed]));
+	}
+}
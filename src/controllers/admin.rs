@@ -0,0 +1,293 @@
+use super::DiscordEntity;
+use crate::data::scope::Scope;
+use crate::data::BotData;
+use log::info;
+use serenity::all::{Member, User};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AdminError {
+	#[error("{entity:?} is already an admin")]
+	AlreadyAdmin { entity: DiscordEntity },
+
+	#[error("{entity:?} is not an admin")]
+	NotAdmin { entity: DiscordEntity },
+}
+
+/// What a management command is about to touch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ManagedResource<'a> {
+	/// Something that does not exist yet and will be created with the given scope.
+	New(Scope),
+	Machine(&'a str),
+	Server(&'a str),
+}
+
+pub async fn add_admin(data: &BotData, entity: DiscordEntity, scope: Scope) -> Result<(), AdminError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+
+	let previous = match entity {
+		DiscordEntity::User(user) => data_write.admins.users.insert(user, scope),
+		DiscordEntity::Role(role) => data_write.admins.roles.insert(role, scope),
+	};
+
+	match previous {
+		Some(previous) if previous == scope => Err(AdminError::AlreadyAdmin { entity }),
+		_ => {
+			info!("Made {entity:?} an admin with scope {scope:?}");
+			Ok(())
+		}
+	}
+}
+
+pub async fn remove_admin(data: &BotData, entity: DiscordEntity) -> Result<(), AdminError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+
+	let removed = match entity {
+		DiscordEntity::User(user) => data_write.admins.users.remove(&user),
+		DiscordEntity::Role(role) => data_write.admins.roles.remove(&role),
+	};
+
+	match removed {
+		Some(_) => {
+			info!("Removed {entity:?} from the admins");
+			Ok(())
+		}
+		None => Err(AdminError::NotAdmin { entity }),
+	}
+}
+
+/// Whether the author may manage `resource` from where they invoked the command.
+///
+/// Admins of a guild only manage what belongs to that guild, global admins manage everything.
+/// Managers of an existing resource may manage it too when `allow_managers` is set.
+pub async fn can_manage(
+	data: &BotData,
+	author: &User,
+	member: Option<&Member>,
+	resource: ManagedResource<'_>,
+	allow_managers: bool,
+) -> bool {
+	let guild = member.map(|m| m.guild_id);
+	let roles = member.map_or(&[][..], |m| &m.roles[..]);
+	let read = data.read().await;
+
+	let target = match resource {
+		ManagedResource::New(scope) => Some((scope, false)),
+		ManagedResource::Machine(name) => read
+			.wake_on_lan
+			.get(name)
+			.map(|info| (info.scope, info.managers.contains(&author.id))),
+		ManagedResource::Server(name) => read
+			.servitor
+			.get(name)
+			.map(|info| (info.scope, info.managers.contains(&author.id))),
+	};
+
+	let mut admin_scopes = read
+		.admins
+		.scopes_of(author.id, roles)
+		.filter(|admin| admin.is_visible_in(guild));
+
+	// Unknown resources are left for the command to report
+	let Some((scope, is_manager)) = target else {
+		return admin_scopes.next().is_some();
+	};
+
+	if allow_managers && is_manager && scope.is_visible_in(guild) {
+		return true;
+	}
+
+	admin_scopes.any(|admin| admin.is_global() || admin == scope)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::super::tests::{mock_author_dms, mock_author_guild};
+	use super::*;
+	use crate::data::tests::mock_data;
+	use rstest::rstest;
+	use serde_json::json;
+	use serenity::all::{GuildId, RoleId, UserId};
+
+	const AUTHOR: u64 = 12345678901234567;
+	const ROLE: u64 = 98765432109876543;
+
+	fn admin_data() -> BotData {
+		mock_data(Some(json!({
+			"admins": {
+				"users": { "1": "global", "2": { "guild": "100" } },
+				"roles": { "98765432109876543": { "guild": "100" } }
+			},
+			"wake_on_lan": {
+				"GlobalMachine": { "mac": [1, 2, 3, 4, 5, 6] },
+				"GuildMachine": { "mac": [1, 2, 3, 4, 5, 7], "scope": { "guild": "100" } },
+				"ManagedMachine": {
+					"mac": [1, 2, 3, 4, 5, 8],
+					"scope": { "guild": "100" },
+					"managers": [12345678901234567i64]
+				}
+			},
+			"servitor": {
+				"GuildServer": { "servitor": "foo", "unit_name": "bar", "scope": { "guild": "100" } },
+				"ManagedServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"managers": [12345678901234567i64]
+				}
+			}
+		})))
+	}
+
+	fn in_guild(user: u64, roles: Vec<RoleId>, guild: u64) -> (User, Option<Member>) {
+		let (author, mut member) = mock_author_guild(UserId::new(user), roles);
+		member.as_mut().unwrap().guild_id = GuildId::new(guild);
+		(author, member)
+	}
+
+	#[rstest]
+	#[case(ManagedResource::New(Scope::Global), true)]
+	#[case(ManagedResource::Machine("GlobalMachine"), true)]
+	#[case(ManagedResource::Server("GuildServer"), true)]
+	#[case(ManagedResource::Machine("UnknownMachine"), true)]
+	#[tokio::test]
+	async fn given_global_admin_then_can_manage_anything_even_from_dms(
+		#[case] resource: ManagedResource<'static>,
+		#[case] expected: bool,
+	) {
+		let data = admin_data();
+		let (author, member) = mock_author_dms(UserId::new(1));
+
+		assert_eq!(can_manage(&data, &author, member.as_ref(), resource, false).await, expected);
+	}
+
+	#[rstest]
+	#[case(ManagedResource::New(Scope::Guild(GuildId::new(100))), true)]
+	#[case(ManagedResource::New(Scope::Global), false)]
+	#[case(ManagedResource::Machine("GuildMachine"), true)]
+	#[case(ManagedResource::Machine("GlobalMachine"), false)]
+	#[case(ManagedResource::Server("GuildServer"), true)]
+	#[case(ManagedResource::Server("ManagedServer"), false)]
+	#[tokio::test]
+	async fn given_guild_admin_then_can_manage_only_resources_of_that_guild(
+		#[case] resource: ManagedResource<'static>,
+		#[case] expected: bool,
+	) {
+		let data = admin_data();
+		let (author, member) = in_guild(2, vec![], 100);
+
+		assert_eq!(can_manage(&data, &author, member.as_ref(), resource, false).await, expected);
+	}
+
+	#[tokio::test]
+	async fn given_guild_admin_outside_their_guild_then_can_not_manage() {
+		let data = admin_data();
+		let (author, member) = in_guild(2, vec![], 200);
+		let (_, dms) = mock_author_dms(UserId::new(2));
+
+		let resource = ManagedResource::New(Scope::Guild(GuildId::new(200)));
+		assert!(!can_manage(&data, &author, member.as_ref(), resource, false).await);
+		assert!(!can_manage(&data, &author, dms.as_ref(), ManagedResource::New(Scope::Global), false).await);
+	}
+
+	#[tokio::test]
+	async fn given_admin_role_then_member_can_manage_resources_of_that_guild() {
+		let data = admin_data();
+		let (author, member) = in_guild(AUTHOR, vec![RoleId::new(ROLE)], 100);
+
+		let resource = ManagedResource::Machine("GuildMachine");
+		assert!(can_manage(&data, &author, member.as_ref(), resource, false).await);
+	}
+
+	#[rstest]
+	#[case(ManagedResource::Machine("ManagedMachine"), true, true)]
+	#[case(ManagedResource::Machine("ManagedMachine"), false, false)]
+	#[case(ManagedResource::Machine("GuildMachine"), true, false)]
+	#[case(ManagedResource::Server("ManagedServer"), true, true)]
+	#[case(ManagedResource::Server("UnknownServer"), true, false)]
+	#[case(ManagedResource::New(Scope::Guild(GuildId::new(100))), true, false)]
+	#[tokio::test]
+	async fn given_manager_then_can_manage_only_their_resources_when_allowed(
+		#[case] resource: ManagedResource<'static>,
+		#[case] allow_managers: bool,
+		#[case] expected: bool,
+	) {
+		let data = admin_data();
+		let (author, member) = in_guild(AUTHOR, vec![], 100);
+
+		assert_eq!(can_manage(&data, &author, member.as_ref(), resource, allow_managers).await, expected);
+	}
+
+	#[tokio::test]
+	async fn given_manager_of_guild_resource_outside_that_guild_then_can_not_manage() {
+		let data = admin_data();
+		let (author, member) = in_guild(AUTHOR, vec![], 200);
+
+		let resource = ManagedResource::Machine("ManagedMachine");
+		assert!(!can_manage(&data, &author, member.as_ref(), resource, true).await);
+	}
+
+	#[tokio::test]
+	async fn given_new_admin_then_add_admin_stores_it() {
+		let data = mock_data(None);
+
+		let user = add_admin(&data, DiscordEntity::User(UserId::new(1)), Scope::Global).await;
+		let role = add_admin(&data, DiscordEntity::Role(RoleId::new(2)), Scope::Guild(GuildId::new(3))).await;
+
+		assert_eq!(user, Ok(()));
+		assert_eq!(role, Ok(()));
+		let read = data.read().await;
+		assert_eq!(read.admins.users[&UserId::new(1)], Scope::Global);
+		assert_eq!(read.admins.roles[&RoleId::new(2)], Scope::Guild(GuildId::new(3)));
+	}
+
+	#[tokio::test]
+	async fn given_existing_admin_with_same_scope_then_add_admin_returns_error() {
+		let data = admin_data();
+
+		let result = add_admin(&data, DiscordEntity::User(UserId::new(1)), Scope::Global).await;
+
+		assert_eq!(
+			result,
+			Err(AdminError::AlreadyAdmin {
+				entity: DiscordEntity::User(UserId::new(1))
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_existing_admin_with_other_scope_then_add_admin_changes_scope() {
+		let data = admin_data();
+
+		let result = add_admin(&data, DiscordEntity::User(UserId::new(2)), Scope::Global).await;
+
+		assert_eq!(result, Ok(()));
+		assert_eq!(data.read().await.admins.users[&UserId::new(2)], Scope::Global);
+	}
+
+	#[tokio::test]
+	async fn given_admin_then_remove_admin_removes_it() {
+		let data = admin_data();
+
+		let result = remove_admin(&data, DiscordEntity::Role(RoleId::new(ROLE))).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.admins.roles.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_non_admin_then_remove_admin_returns_error() {
+		let data = admin_data();
+
+		let result = remove_admin(&data, DiscordEntity::User(UserId::new(3))).await;
+
+		assert_eq!(
+			result,
+			Err(AdminError::NotAdmin {
+				entity: DiscordEntity::User(UserId::new(3))
+			})
+		);
+	}
+}
@@ -0,0 +1,321 @@
+use crate::data::authorization::AuthorizationInfo;
+use crate::data::servitor::ServitorAction;
+use crate::data::{BotData, Data};
+use chrono::{DateTime, Utc};
+use log::info;
+use serenity::all::{GuildId, RoleId, UserId};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// What gives access to a resource.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AccessSource {
+	Direct,
+	Role(RoleId),
+	Group(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditedResource {
+	Machine(String),
+	Server(String),
+}
+
+/// What a user or role may do on one resource, and what grants it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceAccess {
+	pub resource: AuditedResource,
+	/// Every grant that applies, with the Servitor actions it allows, which are always empty for machines.
+	pub sources: BTreeMap<AccessSource, BTreeSet<ServitorAction>>,
+	pub denied: bool,
+}
+
+/// Everything taken away from a user by [`revoke_all`].
+#[derive(Debug, Default, PartialEq)]
+pub struct RevokedAccess {
+	pub machines: BTreeSet<String>,
+	pub servers: BTreeSet<String>,
+	pub groups: BTreeSet<String>,
+	pub requests: usize,
+}
+
+impl RevokedAccess {
+	pub fn is_empty(&self) -> bool {
+		self.machines.is_empty() && self.servers.is_empty() && self.groups.is_empty() && self.requests == 0
+	}
+}
+
+fn sources<T: AuthorizationInfo>(
+	info: &T,
+	user: Option<UserId>,
+	roles: &[RoleId],
+	groups: &[&str],
+	now: DateTime<Utc>,
+) -> Vec<AccessSource> {
+	let direct = user.filter(|&user| info.allows_user(user, now)).map(|_| AccessSource::Direct);
+	let roles = roles
+		.iter()
+		.filter(|&&role| info.allows_role(role, now))
+		.map(|&role| AccessSource::Role(role));
+	let groups = groups
+		.iter()
+		.filter(|group| info.allows_group(group))
+		.map(|group| AccessSource::Group(group.to_string()));
+
+	direct.into_iter().chain(roles).chain(groups).collect()
+}
+
+/// Resources visible in `guild` that grant or deny anything to the user, roles or groups.
+fn audit(
+	data: &Data,
+	guild: Option<GuildId>,
+	user: Option<UserId>,
+	roles: &[RoleId],
+	groups: &[&str],
+) -> Vec<ResourceAccess> {
+	let now = Utc::now();
+
+	let machines = data
+		.wake_on_lan
+		.iter()
+		.filter(|(_, info)| info.scope.is_visible_in(guild))
+		.map(|(name, info)| ResourceAccess {
+			resource: AuditedResource::Machine(name.clone()),
+			sources: sources(info, user, roles, groups, now)
+				.into_iter()
+				.map(|source| (source, BTreeSet::new()))
+				.collect(),
+			denied: info.denials().denies(user, roles),
+		});
+
+	let servers = data
+		.servitor
+		.iter()
+		.filter(|(_, info)| info.scope.is_visible_in(guild))
+		.map(|(name, info)| {
+			let mut granted: BTreeMap<AccessSource, BTreeSet<ServitorAction>> = BTreeMap::new();
+			for action in ServitorAction::ALL {
+				for source in sources(&info.for_action(action), user, roles, groups, now) {
+					granted.entry(source).or_default().insert(action);
+				}
+			}
+
+			ResourceAccess {
+				resource: AuditedResource::Server(name.clone()),
+				sources: granted,
+				denied: info.denied.denies(user, roles),
+			}
+		});
+
+	machines
+		.chain(servers)
+		.filter(|access| access.denied || !access.sources.is_empty())
+		.collect()
+}
+
+/// What the user may do in `guild`, directly, through the given roles they hold there or through their groups.
+pub async fn audit_user(data: &BotData, guild: Option<GuildId>, user: UserId, roles: &[RoleId]) -> Vec<ResourceAccess> {
+	let read = data.read().await;
+	let groups = read
+		.access_groups
+		.iter()
+		.filter(|(_, group)| group.contains(user, roles))
+		.map(|(name, _)| name.as_str())
+		.collect::<Vec<_>>();
+
+	audit(&read, guild, Some(user), roles, &groups)
+}
+
+/// What members of the role may do in `guild`, through the role itself or the groups it is in.
+pub async fn audit_role(data: &BotData, guild: Option<GuildId>, role: RoleId) -> Vec<ResourceAccess> {
+	let read = data.read().await;
+	let groups = read
+		.access_groups
+		.iter()
+		.filter(|(_, group)| group.roles.contains(&role))
+		.map(|(name, _)| name.as_str())
+		.collect::<Vec<_>>();
+
+	audit(&read, guild, None, &[role], &groups)
+}
+
+/// Takes away everything granted to the user themselves in every guild, for when they leave.
+///
+/// Their grants, managed resources, group memberships and pending access requests are removed. Whatever they get through
+/// their roles is left alone, and so are their denials.
+pub async fn revoke_all(data: &BotData, user: UserId) -> RevokedAccess {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let mut revoked = RevokedAccess::default();
+
+	for (name, info) in data_write.wake_on_lan.iter_mut() {
+		info.user_expiries.remove(&user);
+		let granted = info.authorized_users.remove(&user);
+		let managed = info.managers.remove(&user);
+		if granted || managed {
+			revoked.machines.insert(name.clone());
+		}
+	}
+
+	for (name, info) in data_write.servitor.iter_mut() {
+		info.user_expiries.remove(&user);
+		let granted = info.authorized_users.remove(&user).is_some();
+		let managed = info.managers.remove(&user);
+		if granted || managed {
+			revoked.servers.insert(name.clone());
+		}
+	}
+
+	for (name, group) in data_write.access_groups.iter_mut() {
+		if group.users.remove(&user) {
+			revoked.groups.insert(name.clone());
+		}
+	}
+
+	let requests = data_write.access_requests.len();
+	data_write.access_requests.retain(|_, request| request.user != user);
+	revoked.requests = requests - data_write.access_requests.len();
+
+	info!("Revoked all access of user {user}: {revoked:?}");
+
+	revoked
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::data::tests::mock_data;
+	use serde_json::json;
+
+	fn audit_data() -> BotData {
+		mock_data(Some(json!({
+			"access_groups": {
+				"Friends": { "users": ["1"], "roles": ["20"] }
+			},
+			"access_requests": {
+				"1": { "user": "1", "resource": { "kind": "machine", "name": "Other" }, "requested_at": "2025-03-06T19:59:45Z" },
+				"2": { "user": "2", "resource": { "kind": "machine", "name": "Other" }, "requested_at": "2025-03-06T19:59:45Z" }
+			},
+			"wake_on_lan": {
+				"Desktop": { "mac": [1, 2, 3, 4, 5, 6], "authorized_users": ["1"], "authorized_roles": ["10"] },
+				"Laptop": { "mac": [1, 2, 3, 4, 5, 6], "authorized_groups": ["Friends"], "denied": { "users": { "1": null } } },
+				"Other": { "mac": [1, 2, 3, 4, 5, 6], "managers": ["1"], "scope": { "guild": "99" } },
+				"Nobody": { "mac": [1, 2, 3, 4, 5, 6], "authorized_users": ["2"] }
+			},
+			"servitor": {
+				"Minecraft": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": { "1": ["start", "stop"] },
+					"authorized_roles": { "10": ["status"] },
+					"authorized_groups": { "Friends": ["status", "logs"] }
+				}
+			}
+		})))
+	}
+
+	#[tokio::test]
+	async fn given_user_with_roles_then_audit_user_reports_every_source() {
+		let data = audit_data();
+
+		let report = audit_user(&data, None, UserId::new(1), &[RoleId::new(10)]).await;
+
+		assert_eq!(
+			report,
+			vec![
+				ResourceAccess {
+					resource: AuditedResource::Machine("Desktop".to_string()),
+					sources: BTreeMap::from([
+						(AccessSource::Direct, BTreeSet::new()),
+						(AccessSource::Role(RoleId::new(10)), BTreeSet::new()),
+					]),
+					denied: false,
+				},
+				ResourceAccess {
+					resource: AuditedResource::Machine("Laptop".to_string()),
+					sources: BTreeMap::from([(AccessSource::Group("Friends".to_string()), BTreeSet::new())]),
+					denied: true,
+				},
+				ResourceAccess {
+					resource: AuditedResource::Server("Minecraft".to_string()),
+					sources: BTreeMap::from([
+						(AccessSource::Direct, BTreeSet::from([ServitorAction::Start, ServitorAction::Stop])),
+						(AccessSource::Role(RoleId::new(10)), BTreeSet::from([ServitorAction::Status])),
+						(
+							AccessSource::Group("Friends".to_string()),
+							BTreeSet::from([ServitorAction::Status, ServitorAction::Logs])
+						),
+					]),
+					denied: false,
+				},
+			]
+		);
+	}
+
+	#[tokio::test]
+	async fn given_role_then_audit_role_reports_role_and_its_groups() {
+		let data = audit_data();
+
+		let report = audit_role(&data, None, RoleId::new(20)).await;
+
+		assert_eq!(
+			report,
+			vec![
+				ResourceAccess {
+					resource: AuditedResource::Machine("Laptop".to_string()),
+					sources: BTreeMap::from([(AccessSource::Group("Friends".to_string()), BTreeSet::new())]),
+					denied: false,
+				},
+				ResourceAccess {
+					resource: AuditedResource::Server("Minecraft".to_string()),
+					sources: BTreeMap::from([(
+						AccessSource::Group("Friends".to_string()),
+						BTreeSet::from([ServitorAction::Status, ServitorAction::Logs])
+					)]),
+					denied: false,
+				},
+			]
+		);
+	}
+
+	#[tokio::test]
+	async fn given_user_without_access_then_audit_user_is_empty() {
+		let data = audit_data();
+
+		let report = audit_user(&data, None, UserId::new(3), &[]).await;
+
+		assert!(report.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_user_then_revoke_all_removes_their_own_grants_everywhere() {
+		let data = audit_data();
+
+		let revoked = revoke_all(&data, UserId::new(1)).await;
+
+		assert_eq!(
+			revoked,
+			RevokedAccess {
+				machines: BTreeSet::from(["Desktop".to_string(), "Other".to_string()]),
+				servers: BTreeSet::from(["Minecraft".to_string()]),
+				groups: BTreeSet::from(["Friends".to_string()]),
+				requests: 1,
+			}
+		);
+		let read = data.read().await;
+		assert!(read.wake_on_lan["Desktop"].authorized_users.is_empty());
+		assert_eq!(read.wake_on_lan["Desktop"].authorized_roles, BTreeSet::from([RoleId::new(10)]));
+		assert!(read.wake_on_lan["Laptop"].denied.users.contains_key(&UserId::new(1)));
+		assert!(read.servitor["Minecraft"].authorized_users.is_empty());
+		assert!(read.access_groups["Friends"].users.is_empty());
+		assert_eq!(read.access_requests.keys().collect::<Vec<_>>(), vec![&2]);
+	}
+
+	#[tokio::test]
+	async fn given_user_without_grants_then_revoke_all_revokes_nothing() {
+		let data = audit_data();
+
+		let revoked = revoke_all(&data, UserId::new(3)).await;
+
+		assert!(revoked.is_empty());
+	}
+}
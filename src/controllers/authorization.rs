@@ -0,0 +1,900 @@
+use super::group::GroupError;
+use super::{clear_denial, set_denial, DiscordEntity};
+use crate::data::authorization::{AuthorizationInfo, Denials};
+use crate::data::{BotData, Data};
+use chrono::{DateTime, Utc};
+use log::info;
+use serenity::all::{GuildId, RoleId, UserId};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::ops::AsyncFnOnce;
+use thiserror::Error;
+
+/// Who a grant is given to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Grantee<'a> {
+	User(UserId),
+	Role(RoleId),
+	Group(&'a str),
+}
+
+impl From<DiscordEntity> for Grantee<'_> {
+	fn from(entity: DiscordEntity) -> Self {
+		match entity {
+			DiscordEntity::User(id) => Grantee::User(id),
+			DiscordEntity::Role(id) => Grantee::Role(id),
+		}
+	}
+}
+
+/// A kind of resource users, roles and access groups are permitted to use, and users and roles are denied from.
+///
+/// This is the mutable side of [`AuthorizationInfo`], which checks the grants changed through it.
+pub trait AuthorizedResource: AuthorizationInfo + Sized {
+	/// What a grant permits, `()` when there is only one thing to do with the resource.
+	type Grant: Debug + Clone + PartialEq;
+	/// Error for resources that do not exist.
+	type Error: std::error::Error + PartialEq;
+
+	/// What the resource is called, like `machine`.
+	const NOUN: &'static str;
+
+	/// Every resource of this kind, by name.
+	fn all(data: &Data) -> &BTreeMap<String, Self>;
+
+	fn get_mut<'a>(data: &'a mut Data, name: &str) -> Result<&'a mut Self, Self::Error>;
+
+	/// Returns whether anything in the grant was not granted before.
+	fn grant(&mut self, grantee: Grantee, grant: &Self::Grant) -> bool;
+
+	/// Returns whether anything in the grant was granted before, forgetting the grantee once it has nothing left.
+	fn revoke(&mut self, grantee: Grantee, grant: &Self::Grant) -> bool;
+
+	/// Sets when the grant given to the entity expires, or makes it permanent when `None`, returning whether that changed
+	/// anything. Whatever else the entity is granted keeps its own expiry.
+	fn set_expiry(&mut self, entity: DiscordEntity, grant: &Self::Grant, expires: Option<DateTime<Utc>>) -> bool;
+
+	/// Forgets when the revoked grant would have expired.
+	fn forget_expiry(&mut self, entity: DiscordEntity, grant: &Self::Grant);
+
+	fn denials_mut(&mut self) -> &mut Denials;
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AddPermissionError<E, G> {
+	#[error(transparent)]
+	Resource(E),
+
+	#[error("{entity:?} is already permitted {grant:?} on {name}")]
+	AlreadyAuthorized { name: String, entity: DiscordEntity, grant: G },
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum RemovePermissionError<E, G> {
+	#[error(transparent)]
+	Resource(E),
+
+	#[error("{entity:?} is already not permitted {grant:?} on {name}")]
+	AlreadyNotAuthorized { name: String, entity: DiscordEntity, grant: G },
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AddGroupPermissionError<E, G> {
+	#[error(transparent)]
+	Resource(E),
+
+	#[error(transparent)]
+	Group(#[from] GroupError),
+
+	#[error("access group {group} is already permitted {grant:?} on {name}")]
+	AlreadyAuthorized { name: String, group: String, grant: G },
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum RemoveGroupPermissionError<E, G> {
+	#[error(transparent)]
+	Resource(E),
+
+	#[error("access group {group} is already not permitted {grant:?} on {name}")]
+	AlreadyNotAuthorized { name: String, group: String, grant: G },
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AddDenialError<E> {
+	#[error(transparent)]
+	Resource(E),
+
+	#[error("{entity:?} is already denied from {name}")]
+	AlreadyDenied { name: String, entity: DiscordEntity },
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum RemoveDenialError<E> {
+	#[error(transparent)]
+	Resource(E),
+
+	#[error("{entity:?} is not denied from {name}")]
+	NotDenied { name: String, entity: DiscordEntity },
+}
+
+pub type AddPermissionErrorOf<R> =
+	AddPermissionError<<R as AuthorizedResource>::Error, <R as AuthorizedResource>::Grant>;
+pub type RemovePermissionErrorOf<R> =
+	RemovePermissionError<<R as AuthorizedResource>::Error, <R as AuthorizedResource>::Grant>;
+pub type AddGroupPermissionErrorOf<R> =
+	AddGroupPermissionError<<R as AuthorizedResource>::Error, <R as AuthorizedResource>::Grant>;
+pub type RemoveGroupPermissionErrorOf<R> =
+	RemoveGroupPermissionError<<R as AuthorizedResource>::Error, <R as AuthorizedResource>::Grant>;
+pub type AddDenialErrorOf<R> = AddDenialError<<R as AuthorizedResource>::Error>;
+pub type RemoveDenialErrorOf<R> = RemoveDenialError<<R as AuthorizedResource>::Error>;
+
+pub trait ListResourcesCallback<R, T> = AsyncFnOnce(Vec<(&String, &R)>) -> T;
+pub async fn list<R: AuthorizedResource, T, F: ListResourcesCallback<R, T>>(
+	data: &BotData,
+	guild: Option<GuildId>,
+	func: F,
+) -> T {
+	let read = data.read().await;
+
+	let resources = R::all(&read)
+		.iter()
+		.filter(|(_, info)| info.scope().is_visible_in(guild))
+		.collect();
+
+	func.async_call_once((resources,)).await
+}
+
+pub async fn permit<R: AuthorizedResource>(
+	data: &BotData,
+	name: &str,
+	entity: DiscordEntity,
+	grant: R::Grant,
+	expires: Option<DateTime<Utc>>,
+) -> Result<(), AddPermissionErrorOf<R>> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let info = R::get_mut(&mut data_write, name).map_err(AddPermissionError::Resource)?;
+
+	let newly_permitted = info.grant(entity.into(), &grant);
+	let expiry_changed = info.set_expiry(entity, &grant, expires);
+
+	if newly_permitted || expiry_changed {
+		info!("Permitted {entity:?} {grant:?} on {} {name}, expiring {expires:?}", R::NOUN);
+		Ok(())
+	} else {
+		Err(AddPermissionError::AlreadyAuthorized {
+			name: name.into(),
+			entity,
+			grant,
+		})
+	}
+}
+
+pub async fn revoke<R: AuthorizedResource>(
+	data: &BotData,
+	name: &str,
+	entity: DiscordEntity,
+	grant: R::Grant,
+) -> Result<(), RemovePermissionErrorOf<R>> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let info = R::get_mut(&mut data_write, name).map_err(RemovePermissionError::Resource)?;
+
+	let revoked = info.revoke(entity.into(), &grant);
+	info.forget_expiry(entity, &grant);
+
+	if revoked {
+		info!("Revoked {entity:?}'s permission {grant:?} on {} {name}", R::NOUN);
+		Ok(())
+	} else {
+		Err(RemovePermissionError::AlreadyNotAuthorized {
+			name: name.into(),
+			entity,
+			grant,
+		})
+	}
+}
+
+pub async fn permit_group<R: AuthorizedResource>(
+	data: &BotData,
+	name: &str,
+	group: &str,
+	grant: R::Grant,
+) -> Result<(), AddGroupPermissionErrorOf<R>> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+
+	let group_exists = data_write.access_groups.contains_key(group);
+	let info = R::get_mut(&mut data_write, name).map_err(AddGroupPermissionError::Resource)?;
+	if !group_exists {
+		return Err(GroupError::DoesNotExist { group: group.into() })?;
+	}
+
+	if info.grant(Grantee::Group(group), &grant) {
+		info!("Permitted access group {group} {grant:?} on {} {name}", R::NOUN);
+		Ok(())
+	} else {
+		Err(AddGroupPermissionError::AlreadyAuthorized {
+			name: name.into(),
+			group: group.into(),
+			grant,
+		})
+	}
+}
+
+pub async fn revoke_group<R: AuthorizedResource>(
+	data: &BotData,
+	name: &str,
+	group: &str,
+	grant: R::Grant,
+) -> Result<(), RemoveGroupPermissionErrorOf<R>> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let info = R::get_mut(&mut data_write, name).map_err(RemoveGroupPermissionError::Resource)?;
+
+	if info.revoke(Grantee::Group(group), &grant) {
+		info!("Revoked access group {group}'s permission {grant:?} on {} {name}", R::NOUN);
+		Ok(())
+	} else {
+		Err(RemoveGroupPermissionError::AlreadyNotAuthorized {
+			name: name.into(),
+			group: group.into(),
+			grant,
+		})
+	}
+}
+
+/// Denies the user or role from the resource whatever they are permitted, or changes the reason they are denied.
+pub async fn deny<R: AuthorizedResource>(
+	data: &BotData,
+	name: &str,
+	entity: DiscordEntity,
+	reason: Option<String>,
+) -> Result<(), AddDenialErrorOf<R>> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let info = R::get_mut(&mut data_write, name).map_err(AddDenialError::Resource)?;
+
+	if set_denial(info.denials_mut(), entity, reason.clone()) {
+		info!("Denied {entity:?} from {} {name}, because {reason:?}", R::NOUN);
+		Ok(())
+	} else {
+		Err(AddDenialError::AlreadyDenied {
+			name: name.into(),
+			entity,
+		})
+	}
+}
+
+pub async fn lift_denial<R: AuthorizedResource>(
+	data: &BotData,
+	name: &str,
+	entity: DiscordEntity,
+) -> Result<(), RemoveDenialErrorOf<R>> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let info = R::get_mut(&mut data_write, name).map_err(RemoveDenialError::Resource)?;
+
+	if clear_denial(info.denials_mut(), entity) {
+		info!("Lifted denial of {entity:?} from {} {name}", R::NOUN);
+		Ok(())
+	} else {
+		Err(RemoveDenialError::NotDenied {
+			name: name.into(),
+			entity,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::controllers::wake_on_lan::MachineError;
+	use crate::data::tests::mock_data;
+	use crate::data::wake_on_lan::WakeOnLanMachineInfo;
+	use chrono::TimeZone;
+	use serde_json::json;
+
+	#[tokio::test]
+	async fn given_scoped_resources_then_list_only_provides_visible_ones() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"GlobalMachine": { "mac": [1, 2, 3, 4, 5, 6] },
+				"GuildMachine": { "mac": [1, 2, 3, 4, 5, 7], "scope": { "guild": "1" } },
+				"OtherGuildMachine": { "mac": [1, 2, 3, 4, 5, 8], "scope": { "guild": "2" } }
+			}
+		})));
+
+		let in_guild = list::<WakeOnLanMachineInfo, _, _>(&data, Some(GuildId::new(1)), async |machines| {
+			machines.into_iter().map(|(name, _)| name.clone()).collect::<Vec<_>>()
+		})
+		.await;
+		let in_dms = list::<WakeOnLanMachineInfo, _, _>(&data, None, async |machines| {
+			machines.into_iter().map(|(name, _)| name.clone()).collect::<Vec<_>>()
+		})
+		.await;
+
+		assert_eq!(in_guild, vec!["GlobalMachine", "GuildMachine"]);
+		assert_eq!(in_dms, vec!["GlobalMachine"]);
+	}
+
+	#[tokio::test]
+	async fn given_nonexistent_machine_then_permit_user_returns_error_and_does_not_modify_data() {
+		let data = mock_data(None);
+		let result = permit::<WakeOnLanMachineInfo>(
+			&data,
+			"NonExistentMachine",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			(),
+			None,
+		).await;
+
+		assert_eq!(
+			result,
+			Err(AddPermissionError::Resource(MachineError::DoesNotExist {
+				machine_name: "NonExistentMachine".to_string()
+			}))
+		);
+		assert!(data.read().await.wake_on_lan.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_already_authorized_user_then_permit_user_returns_error_and_does_not_modify_data()
+	{
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [12345678901234567u64],
+					"authorized_roles": []
+				}
+			}
+		})));
+
+		let result = permit::<WakeOnLanMachineInfo>(
+			&data,
+			"ExistingMachine",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			(),
+			None,
+		).await;
+
+		assert_eq!(
+			result,
+			Err(AddPermissionError::AlreadyAuthorized {
+				name: "ExistingMachine".to_string(),
+				entity: DiscordEntity::User(UserId::new(12345678901234567)),
+				grant: ()
+			})
+		);
+		assert_eq!(
+			data.read().await.wake_on_lan["ExistingMachine"]
+				.authorized_users
+				.len(),
+			1
+		);
+	}
+
+	#[tokio::test]
+	async fn given_new_user_then_permit_user_returns_success_and_adds_user() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [],
+					"authorized_roles": []
+				}
+			}
+		})));
+
+		let result = permit::<WakeOnLanMachineInfo>(
+			&data,
+			"ExistingMachine",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			(),
+			None,
+		).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.wake_on_lan["ExistingMachine"]
+			.authorized_users
+			.contains(&UserId::new(12345678901234567)));
+	}
+
+	#[tokio::test]
+	async fn given_nonexistent_machine_then_revoke_user_returns_error_and_does_not_modify_data() {
+		let data = mock_data(None);
+
+		let result = revoke::<WakeOnLanMachineInfo>(
+			&data,
+			"NonExistentMachine",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			(),
+		).await;
+
+		assert_eq!(
+			result,
+			Err(RemovePermissionError::Resource(MachineError::DoesNotExist {
+				machine_name: "NonExistentMachine".to_string()
+			}))
+		);
+		assert!(data.read().await.wake_on_lan.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_non_authorized_user_then_revoke_user_returns_error_and_does_not_modify_data() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [12345678901234567u64],
+					"authorized_roles": []
+				}
+			}
+		})));
+
+		let result = revoke::<WakeOnLanMachineInfo>(
+			&data,
+			"ExistingMachine",
+			DiscordEntity::User(UserId::new(76543210987654321)),
+			(),
+		).await;
+
+		assert_eq!(
+			result,
+			Err(RemovePermissionError::AlreadyNotAuthorized {
+				name: "ExistingMachine".to_string(),
+				entity: DiscordEntity::User(UserId::new(76543210987654321)),
+				grant: ()
+			})
+		);
+		assert_eq!(
+			data.read().await.wake_on_lan["ExistingMachine"]
+				.authorized_users
+				.len(),
+			1
+		);
+	}
+
+	#[tokio::test]
+	async fn given_authorized_user_then_revoke_user_returns_success_and_removes_user() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [12345678901234567u64],
+					"authorized_roles": []
+				}
+			}
+		})));
+
+		let result = revoke::<WakeOnLanMachineInfo>(
+			&data,
+			"ExistingMachine",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			(),
+		).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(!data.read().await.wake_on_lan["ExistingMachine"]
+			.authorized_users
+			.contains(&UserId::new(12345678901234567)));
+	}
+
+	#[tokio::test]
+	async fn given_nonexistent_machine_then_permit_role_returns_error_and_does_not_modify_data() {
+		let data = mock_data(None);
+
+		let result = permit::<WakeOnLanMachineInfo>(
+			&data,
+			"NonExistentMachine",
+			DiscordEntity::Role(RoleId::new(98765432109876543)),
+			(),
+			None,
+		).await;
+
+		assert_eq!(
+			result,
+			Err(AddPermissionError::Resource(MachineError::DoesNotExist {
+				machine_name: "NonExistentMachine".to_string()
+			}))
+		);
+		assert!(data.read().await.wake_on_lan.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_already_authorized_role_then_permit_role_returns_error_and_does_not_modify_data()
+	{
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [],
+					"authorized_roles": [98765432109876543u64]
+				}
+			}
+		})));
+
+		let result = permit::<WakeOnLanMachineInfo>(
+			&data,
+			"ExistingMachine",
+			DiscordEntity::Role(RoleId::new(98765432109876543)),
+			(),
+			None,
+		).await;
+
+		assert_eq!(
+			result,
+			Err(AddPermissionError::AlreadyAuthorized {
+				name: "ExistingMachine".to_string(),
+				entity: DiscordEntity::Role(RoleId::new(98765432109876543)),
+				grant: ()
+			})
+		);
+		assert_eq!(
+			data.read().await.wake_on_lan["ExistingMachine"]
+				.authorized_roles
+				.len(),
+			1
+		);
+	}
+
+	#[tokio::test]
+	async fn given_new_role_then_permit_role_returns_success_and_adds_role() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [],
+					"authorized_roles": []
+				}
+			}
+		})));
+
+		let result = permit::<WakeOnLanMachineInfo>(
+			&data,
+			"ExistingMachine",
+			DiscordEntity::Role(RoleId::new(98765432109876543)),
+			(),
+			None,
+		).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.wake_on_lan["ExistingMachine"]
+			.authorized_roles
+			.contains(&RoleId::new(98765432109876543)));
+	}
+
+	#[tokio::test]
+	async fn given_nonexistent_machine_then_revoke_role_returns_error_and_does_not_modify_data() {
+		let data = mock_data(None);
+
+		let result = revoke::<WakeOnLanMachineInfo>(
+			&data,
+			"NonExistentMachine",
+			DiscordEntity::Role(RoleId::new(98765432109876543)),
+			(),
+		).await;
+
+		assert_eq!(
+			result,
+			Err(RemovePermissionError::Resource(MachineError::DoesNotExist {
+				machine_name: "NonExistentMachine".to_string()
+			}))
+		);
+		assert!(data.read().await.wake_on_lan.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_non_authorized_role_then_revoke_role_returns_error_and_does_not_modify_data() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [],
+					"authorized_roles": [12345678901234567u64]
+				}
+			}
+		})));
+
+		let result = revoke::<WakeOnLanMachineInfo>(
+			&data,
+			"ExistingMachine",
+			DiscordEntity::Role(RoleId::new(98765432109876543)),
+			(),
+		).await;
+
+		assert_eq!(
+			result,
+			Err(RemovePermissionError::AlreadyNotAuthorized {
+				name: "ExistingMachine".to_string(),
+				entity: DiscordEntity::Role(RoleId::new(98765432109876543)),
+				grant: ()
+			})
+		);
+		assert_eq!(
+			data.read().await.wake_on_lan["ExistingMachine"]
+				.authorized_roles
+				.len(),
+			1
+		);
+	}
+
+	#[tokio::test]
+	async fn given_authorized_role_then_revoke_role_returns_success_and_removes_role() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [],
+					"authorized_roles": [98765432109876543u64]
+				}
+			}
+		})));
+
+		let result = revoke::<WakeOnLanMachineInfo>(
+			&data,
+			"ExistingMachine",
+			DiscordEntity::Role(RoleId::new(98765432109876543)),
+			(),
+		).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(!data.read().await.wake_on_lan["ExistingMachine"]
+			.authorized_roles
+			.contains(&RoleId::new(98765432109876543)));
+	}
+
+	#[tokio::test]
+	async fn given_permitted_user_then_permit_user_with_expiry_makes_grant_temporary() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [12345678901234567i64]
+				}
+			}
+		})));
+		let expires = Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap();
+
+		let result = permit::<WakeOnLanMachineInfo>(
+			&data,
+			"ExistingMachine",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			(),
+			Some(expires),
+		).await;
+		let again = permit::<WakeOnLanMachineInfo>(
+			&data,
+			"ExistingMachine",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			(),
+			Some(expires),
+		).await;
+
+		assert_eq!(result, Ok(()));
+		assert_eq!(
+			again,
+			Err(AddPermissionError::AlreadyAuthorized {
+				name: "ExistingMachine".into(),
+				entity: DiscordEntity::User(UserId::new(12345678901234567)),
+				grant: ()
+			})
+		);
+		assert_eq!(
+			data.read().await.wake_on_lan["ExistingMachine"].user_expiries[&UserId::new(12345678901234567)],
+			expires
+		);
+	}
+
+	#[tokio::test]
+	async fn given_temporary_role_then_permit_role_without_expiry_makes_it_permanent() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_roles": [98765432109876543i64],
+					"role_expiries": { "98765432109876543": "2025-03-06T19:59:45Z" }
+				}
+			}
+		})));
+
+		let result = permit::<WakeOnLanMachineInfo>(
+			&data,
+			"ExistingMachine",
+			DiscordEntity::Role(RoleId::new(98765432109876543)),
+			(),
+			None,
+		).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.wake_on_lan["ExistingMachine"].role_expiries.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_temporary_user_then_revoke_user_also_drops_expiry() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [12345678901234567i64],
+					"user_expiries": { "12345678901234567": "2025-03-06T19:59:45Z" }
+				}
+			}
+		})));
+
+		let result = revoke::<WakeOnLanMachineInfo>(
+			&data,
+			"ExistingMachine",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			(),
+		).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.wake_on_lan["ExistingMachine"].user_expiries.is_empty());
+	}
+
+	fn group_data() -> BotData {
+		mock_data(Some(json!({
+			"access_groups": { "Friends": {} },
+			"wake_on_lan": {
+				"SomeMachine": { "mac": [1, 2, 3, 4, 5, 6], "authorized_groups": ["Others"] }
+			}
+		})))
+	}
+
+	#[tokio::test]
+	async fn given_existing_group_then_permit_group_grants_it() {
+		let data = group_data();
+
+		let result = permit_group::<WakeOnLanMachineInfo>(
+			&data,
+			"SomeMachine",
+			"Friends",
+			(),
+		).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.wake_on_lan["SomeMachine"].authorized_groups.contains("Friends"));
+	}
+
+	#[tokio::test]
+	async fn given_nonexistent_group_then_permit_group_returns_error() {
+		let data = group_data();
+
+		let result = permit_group::<WakeOnLanMachineInfo>(&data, "SomeMachine", "Family", ()).await;
+
+		assert_eq!(
+			result,
+			Err(AddGroupPermissionError::Group(GroupError::DoesNotExist {
+				group: "Family".to_string()
+			}))
+		);
+	}
+
+	#[tokio::test]
+	async fn given_granted_group_then_revoke_group_removes_it() {
+		let data = group_data();
+
+		let result = revoke_group::<WakeOnLanMachineInfo>(&data, "SomeMachine", "Others", ()).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.wake_on_lan["SomeMachine"].authorized_groups.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_not_granted_group_then_revoke_group_returns_error() {
+		let data = group_data();
+
+		let result = revoke_group::<WakeOnLanMachineInfo>(
+			&data,
+			"SomeMachine",
+			"Friends",
+			(),
+		).await;
+
+		assert_eq!(
+			result,
+			Err(RemoveGroupPermissionError::AlreadyNotAuthorized {
+				name: "SomeMachine".to_string(),
+				group: "Friends".to_string(),
+				grant: ()
+			})
+		);
+	}
+
+	fn denial_data() -> BotData {
+		mock_data(Some(json!({
+			"wake_on_lan": {
+				"SomeMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"denied": { "users": { "1": "Spamming wakes" } }
+				}
+			}
+		})))
+	}
+
+	#[tokio::test]
+	async fn given_new_denial_then_deny_stores_it_with_reason() {
+		let data = denial_data();
+
+		let result = deny::<WakeOnLanMachineInfo>(
+			&data,
+			"SomeMachine",
+			DiscordEntity::Role(RoleId::new(10)),
+			Some("Raiders".to_string()),
+		).await;
+
+		assert_eq!(result, Ok(()));
+		assert_eq!(
+			data.read().await.wake_on_lan["SomeMachine"].denied.roles[&RoleId::new(10)],
+			Some("Raiders".to_string())
+		);
+	}
+
+	#[tokio::test]
+	async fn given_denied_user_with_new_reason_then_deny_changes_reason() {
+		let data = denial_data();
+
+		let result = deny::<WakeOnLanMachineInfo>(
+			&data,
+			"SomeMachine",
+			DiscordEntity::User(UserId::new(1)),
+			None,
+		).await;
+
+		assert_eq!(result, Ok(()));
+		assert_eq!(data.read().await.wake_on_lan["SomeMachine"].denied.users[&UserId::new(1)], None);
+	}
+
+	#[tokio::test]
+	async fn given_denied_user_with_same_reason_then_deny_returns_error() {
+		let data = denial_data();
+
+		let entity = DiscordEntity::User(UserId::new(1));
+		let result = deny::<WakeOnLanMachineInfo>(
+			&data,
+			"SomeMachine",
+			entity,
+			Some("Spamming wakes".to_string()),
+		).await;
+
+		assert_eq!(
+			result,
+			Err(AddDenialError::AlreadyDenied {
+				name: "SomeMachine".to_string(),
+				entity
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_denied_user_then_lift_denial_removes_it() {
+		let data = denial_data();
+
+		let result = lift_denial::<WakeOnLanMachineInfo>(
+			&data,
+			"SomeMachine",
+			DiscordEntity::User(UserId::new(1)),
+		).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.wake_on_lan["SomeMachine"].denied.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_not_denied_role_then_lift_denial_returns_error() {
+		let data = denial_data();
+
+		let entity = DiscordEntity::Role(RoleId::new(10));
+		let result = lift_denial::<WakeOnLanMachineInfo>(&data, "SomeMachine", entity).await;
+
+		assert_eq!(
+			result,
+			Err(RemoveDenialError::NotDenied {
+				name: "SomeMachine".to_string(),
+				entity
+			})
+		);
+	}
+}
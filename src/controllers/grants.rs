@@ -0,0 +1,217 @@
+use super::authorization::AuthorizedResource;
+use super::DiscordEntity;
+use crate::data::authorization::GrantExpiries;
+use crate::data::servitor::{ActionExpiries, ServitorAction};
+use crate::data::{BotData, Data};
+use chrono::{DateTime, Utc};
+use log::info;
+use std::collections::BTreeSet;
+
+/// What an expired grant used to give access to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GrantResource {
+	Machine(String),
+	/// The actions that expired, others granted on the server are kept.
+	Server(String, BTreeSet<ServitorAction>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiredGrant {
+	pub resource: GrantResource,
+	pub entity: DiscordEntity,
+}
+
+fn has_expired<T>(expiries: &GrantExpiries<T>, now: DateTime<Utc>) -> bool {
+	expiries.values().any(|expiry| *expiry <= now)
+}
+
+fn take_expired<T: Ord + Copy>(expiries: &mut GrantExpiries<T>, now: DateTime<Utc>) -> Vec<T> {
+	let expired = expiries
+		.iter()
+		.filter(|(_, expiry)| **expiry <= now)
+		.map(|(id, _)| *id)
+		.collect::<Vec<_>>();
+	expired.iter().for_each(|id| {
+		expiries.remove(id);
+	});
+	expired
+}
+
+fn has_expired_action<T>(expiries: &ActionExpiries<T>, now: DateTime<Utc>) -> bool {
+	expiries.values().any(|actions| has_expired(actions, now))
+}
+
+/// Takes out the actions that expired, grouped by entity.
+fn take_expired_actions<T: Ord + Copy>(
+	expiries: &mut ActionExpiries<T>,
+	now: DateTime<Utc>,
+) -> Vec<(T, BTreeSet<ServitorAction>)> {
+	let expired = expiries
+		.iter_mut()
+		.map(|(id, actions)| (*id, take_expired(actions, now).into_iter().collect::<BTreeSet<_>>()))
+		.filter(|(_, actions)| !actions.is_empty())
+		.collect();
+	expiries.retain(|_, actions| !actions.is_empty());
+	expired
+}
+
+fn any_expired(data: &Data, now: DateTime<Utc>) -> bool {
+	data.wake_on_lan
+		.values()
+		.any(|info| has_expired(&info.user_expiries, now) || has_expired(&info.role_expiries, now))
+		|| data
+			.servitor
+			.values()
+			.any(|info| has_expired_action(&info.user_expiries, now) || has_expired_action(&info.role_expiries, now))
+}
+
+/// Removes every grant that expired by `now`, returning what was removed.
+pub async fn prune_expired(data: &BotData, now: DateTime<Utc>) -> Vec<ExpiredGrant> {
+	// Checked first so that nothing is written back when nothing expired
+	let read = data.read().await;
+	if !any_expired(&read, now) {
+		return vec![];
+	}
+	drop(read);
+
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let mut expired = Vec::new();
+
+	for (name, info) in data_write.wake_on_lan.iter_mut() {
+		for user in take_expired(&mut info.user_expiries, now) {
+			info.authorized_users.remove(&user);
+			expired.push(ExpiredGrant {
+				resource: GrantResource::Machine(name.clone()),
+				entity: DiscordEntity::User(user),
+			});
+		}
+		for role in take_expired(&mut info.role_expiries, now) {
+			info.authorized_roles.remove(&role);
+			expired.push(ExpiredGrant {
+				resource: GrantResource::Machine(name.clone()),
+				entity: DiscordEntity::Role(role),
+			});
+		}
+	}
+
+	for (name, info) in data_write.servitor.iter_mut() {
+		for (user, actions) in take_expired_actions(&mut info.user_expiries, now) {
+			let entity = DiscordEntity::User(user);
+			info.revoke(entity.into(), &actions);
+			expired.push(ExpiredGrant {
+				resource: GrantResource::Server(name.clone(), actions),
+				entity,
+			});
+		}
+		for (role, actions) in take_expired_actions(&mut info.role_expiries, now) {
+			let entity = DiscordEntity::Role(role);
+			info.revoke(entity.into(), &actions);
+			expired.push(ExpiredGrant {
+				resource: GrantResource::Server(name.clone(), actions),
+				entity,
+			});
+		}
+	}
+
+	expired.iter().for_each(|grant| info!("Grant of {:?} to {:?} expired", grant.entity, grant.resource));
+
+	expired
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::data::tests::mock_data;
+	use chrono::TimeZone;
+	use serde_json::json;
+	use serenity::all::{RoleId, UserId};
+	use std::collections::BTreeMap;
+
+	fn now() -> DateTime<Utc> {
+		Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap()
+	}
+
+	fn expiring_data() -> BotData {
+		mock_data(Some(json!({
+			"wake_on_lan": {
+				"SomeMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [1, 2],
+					"authorized_roles": [10],
+					"user_expiries": { "1": "2025-03-06T19:59:45Z", "2": "2025-03-06T20:00:00Z" },
+					"role_expiries": { "10": "2025-03-06T19:00:00Z" }
+				}
+			},
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": { "1": ["start"], "3": ["status", "start", "logs"] },
+					"user_expiries": {
+						"1": { "start": "2025-03-06T19:00:00Z" },
+						"3": { "start": "2025-03-06T19:00:00Z", "logs": "2025-03-06T21:00:00Z" }
+					}
+				}
+			}
+		})))
+	}
+
+	#[tokio::test]
+	async fn given_expired_grants_then_prune_expired_removes_and_returns_them() {
+		let data = expiring_data();
+
+		let expired = prune_expired(&data, now()).await;
+
+		assert_eq!(
+			expired,
+			vec![
+				ExpiredGrant {
+					resource: GrantResource::Machine("SomeMachine".to_string()),
+					entity: DiscordEntity::User(UserId::new(1)),
+				},
+				ExpiredGrant {
+					resource: GrantResource::Machine("SomeMachine".to_string()),
+					entity: DiscordEntity::Role(RoleId::new(10)),
+				},
+				ExpiredGrant {
+					resource: GrantResource::Server("SomeServer".to_string(), BTreeSet::from([ServitorAction::Start])),
+					entity: DiscordEntity::User(UserId::new(1)),
+				},
+				ExpiredGrant {
+					resource: GrantResource::Server("SomeServer".to_string(), BTreeSet::from([ServitorAction::Start])),
+					entity: DiscordEntity::User(UserId::new(3)),
+				},
+			]
+		);
+
+		let read = data.read().await;
+		let machine = &read.wake_on_lan["SomeMachine"];
+		assert_eq!(machine.authorized_users.iter().collect::<Vec<_>>(), vec![&UserId::new(2)]);
+		assert!(machine.authorized_roles.is_empty());
+		assert_eq!(machine.user_expiries.len(), 1);
+		assert!(machine.role_expiries.is_empty());
+		let server = &read.servitor["SomeServer"];
+		assert_eq!(
+			server.authorized_users,
+			BTreeMap::from([(UserId::new(3), BTreeSet::from([ServitorAction::Status, ServitorAction::Logs]))])
+		);
+		assert_eq!(
+			server.user_expiries,
+			BTreeMap::from([(
+				UserId::new(3),
+				BTreeMap::from([(ServitorAction::Logs, Utc.with_ymd_and_hms(2025, 3, 6, 21, 0, 0).unwrap())])
+			)])
+		);
+	}
+
+	#[tokio::test]
+	async fn given_no_expired_grants_then_prune_expired_returns_nothing() {
+		let data = expiring_data();
+
+		let expired = prune_expired(&data, Utc.with_ymd_and_hms(2025, 3, 6, 18, 0, 0).unwrap()).await;
+
+		assert_eq!(expired, vec![]);
+		assert_eq!(data.read().await.wake_on_lan["SomeMachine"].authorized_users.len(), 2);
+	}
+}
@@ -0,0 +1,265 @@
+use super::DiscordEntity;
+use crate::data::group::{AccessGroup, AccessGroups};
+use crate::data::BotData;
+use log::info;
+use std::ops::AsyncFnOnce;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum GroupError {
+	#[error("access group {group} does not exist")]
+	DoesNotExist { group: String },
+
+	#[error("access group {group} already exists")]
+	AlreadyExists { group: String },
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AddMemberError {
+	#[error(transparent)]
+	Group(#[from] GroupError),
+
+	#[error("{entity:?} is already in access group {group}")]
+	AlreadyMember { group: String, entity: DiscordEntity },
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum RemoveMemberError {
+	#[error(transparent)]
+	Group(#[from] GroupError),
+
+	#[error("{entity:?} is not in access group {group}")]
+	NotMember { group: String, entity: DiscordEntity },
+}
+
+pub async fn create_group(data: &BotData, name: &str) -> Result<(), GroupError> {
+	let mut lock = data.write().await;
+	if lock.access_groups.contains_key(name) {
+		return Err(GroupError::AlreadyExists { group: name.into() });
+	}
+
+	lock.write().access_groups.insert(name.into(), AccessGroup::default());
+	info!("Created access group {name}");
+
+	Ok(())
+}
+
+/// Removes the group along with every grant it had, its members lose whatever access came from it.
+pub async fn delete_group(data: &BotData, name: &str) -> Result<(), GroupError> {
+	let mut lock = data.write().await;
+	if !lock.access_groups.contains_key(name) {
+		return Err(GroupError::DoesNotExist { group: name.into() });
+	}
+
+	let mut data_write = lock.write();
+	data_write.access_groups.remove(name);
+	data_write.wake_on_lan.values_mut().for_each(|info| {
+		info.authorized_groups.remove(name);
+	});
+	data_write.servitor.values_mut().for_each(|info| {
+		info.authorized_groups.remove(name);
+	});
+	info!("Deleted access group {name}");
+
+	Ok(())
+}
+
+pub async fn add_member(data: &BotData, name: &str, entity: DiscordEntity) -> Result<(), AddMemberError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let group = data_write
+		.access_groups
+		.get_mut(name)
+		.ok_or_else(|| GroupError::DoesNotExist { group: name.into() })?;
+
+	let added = match entity {
+		DiscordEntity::User(user) => group.users.insert(user),
+		DiscordEntity::Role(role) => group.roles.insert(role),
+	};
+
+	if added {
+		info!("Added {entity:?} to access group {name}");
+		Ok(())
+	} else {
+		Err(AddMemberError::AlreadyMember {
+			group: name.into(),
+			entity,
+		})
+	}
+}
+
+pub async fn remove_member(data: &BotData, name: &str, entity: DiscordEntity) -> Result<(), RemoveMemberError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let group = data_write
+		.access_groups
+		.get_mut(name)
+		.ok_or_else(|| GroupError::DoesNotExist { group: name.into() })?;
+
+	let removed = match entity {
+		DiscordEntity::User(user) => group.users.remove(&user),
+		DiscordEntity::Role(role) => group.roles.remove(&role),
+	};
+
+	if removed {
+		info!("Removed {entity:?} from access group {name}");
+		Ok(())
+	} else {
+		Err(RemoveMemberError::NotMember {
+			group: name.into(),
+			entity,
+		})
+	}
+}
+
+pub trait ListGroupsCallback<T> = AsyncFnOnce(&AccessGroups) -> T;
+pub async fn list_groups<T, F: ListGroupsCallback<T>>(data: &BotData, func: F) -> T {
+	let read = data.read().await;
+
+	func.async_call_once((&read.access_groups,)).await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::data::tests::mock_data;
+	use serde_json::json;
+	use serenity::all::{RoleId, UserId};
+	use std::collections::BTreeSet;
+
+	fn group_data() -> BotData {
+		mock_data(Some(json!({
+			"access_groups": {
+				"Friends": { "users": ["1"], "roles": ["10"] }
+			},
+			"wake_on_lan": {
+				"SomeMachine": { "mac": [1, 2, 3, 4, 5, 6], "authorized_groups": ["Friends", "Others"] }
+			},
+			"servitor": {
+				"SomeServer": { "servitor": "foo", "unit_name": "bar", "authorized_groups": { "Friends": ["start"] } }
+			}
+		})))
+	}
+
+	#[tokio::test]
+	async fn given_new_group_then_create_group_stores_it_empty() {
+		let data = group_data();
+
+		let result = create_group(&data, "Family").await;
+
+		assert_eq!(result, Ok(()));
+		assert_eq!(data.read().await.access_groups["Family"], AccessGroup::default());
+	}
+
+	#[tokio::test]
+	async fn given_existing_group_then_create_group_returns_error() {
+		let data = group_data();
+
+		let result = create_group(&data, "Friends").await;
+
+		assert_eq!(
+			result,
+			Err(GroupError::AlreadyExists {
+				group: "Friends".to_string()
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_existing_group_then_delete_group_removes_its_grants() {
+		let data = group_data();
+
+		let result = delete_group(&data, "Friends").await;
+
+		assert_eq!(result, Ok(()));
+		let read = data.read().await;
+		assert!(read.access_groups.is_empty());
+		assert_eq!(
+			read.wake_on_lan["SomeMachine"].authorized_groups,
+			BTreeSet::from(["Others".to_string()])
+		);
+		assert!(read.servitor["SomeServer"].authorized_groups.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_nonexistent_group_then_delete_group_returns_error() {
+		let data = group_data();
+
+		let result = delete_group(&data, "Family").await;
+
+		assert_eq!(
+			result,
+			Err(GroupError::DoesNotExist {
+				group: "Family".to_string()
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_new_members_then_add_member_stores_them() {
+		let data = group_data();
+
+		let user = add_member(&data, "Friends", DiscordEntity::User(UserId::new(2))).await;
+		let role = add_member(&data, "Friends", DiscordEntity::Role(RoleId::new(11))).await;
+
+		assert_eq!(user, Ok(()));
+		assert_eq!(role, Ok(()));
+		let read = data.read().await;
+		assert_eq!(read.access_groups["Friends"].users, BTreeSet::from([UserId::new(1), UserId::new(2)]));
+		assert_eq!(read.access_groups["Friends"].roles, BTreeSet::from([RoleId::new(10), RoleId::new(11)]));
+	}
+
+	#[tokio::test]
+	async fn given_existing_member_then_add_member_returns_error() {
+		let data = group_data();
+
+		let result = add_member(&data, "Friends", DiscordEntity::Role(RoleId::new(10))).await;
+
+		assert_eq!(
+			result,
+			Err(AddMemberError::AlreadyMember {
+				group: "Friends".to_string(),
+				entity: DiscordEntity::Role(RoleId::new(10))
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_nonexistent_group_then_add_member_returns_error() {
+		let data = group_data();
+
+		let result = add_member(&data, "Family", DiscordEntity::User(UserId::new(1))).await;
+
+		assert_eq!(
+			result,
+			Err(AddMemberError::Group(GroupError::DoesNotExist {
+				group: "Family".to_string()
+			}))
+		);
+	}
+
+	#[tokio::test]
+	async fn given_member_then_remove_member_removes_it() {
+		let data = group_data();
+
+		let result = remove_member(&data, "Friends", DiscordEntity::User(UserId::new(1))).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.access_groups["Friends"].users.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_non_member_then_remove_member_returns_error() {
+		let data = group_data();
+
+		let result = remove_member(&data, "Friends", DiscordEntity::User(UserId::new(2))).await;
+
+		assert_eq!(
+			result,
+			Err(RemoveMemberError::NotMember {
+				group: "Friends".to_string(),
+				entity: DiscordEntity::User(UserId::new(2))
+			})
+		);
+	}
+}
@@ -6,6 +6,12 @@ use tokio::sync::RwLockReadGuard;
 pub mod authorization;
 pub mod server;
 pub mod action;
+pub mod health;
+pub mod watch;
+pub mod dashboard;
+pub mod bulk;
+pub mod schedule;
+pub mod idle;
 
 #[derive(Debug, Error, PartialEq)]
 pub enum ServerError {
@@ -39,3 +45,5 @@ async fn get_server_info<'a>(
 			server_name: server_name.into(),
 		})
 }
+pub mod query;
+pub mod manager;
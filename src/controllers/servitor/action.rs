@@ -1,7 +1,8 @@
 use super::super::is_user_authorized;
 use crate::controllers::servitor::{get_server_info, ServerError};
+use crate::data::servitor::ServitorAction;
 use crate::data::BotData;
-use crate::services::servitor::{ServitorController, ServitorError, UnitStatus};
+use crate::services::servitor::{ServitorController, ServitorError, UnitLogs, UnitStatus};
 use log::info;
 use serenity::all::{Member, User, UserId};
 use std::collections::BTreeMap;
@@ -19,8 +20,12 @@ pub enum ExecuteServitorActionError {
 		servitor_name: String,
 	},
 
-	#[error("User {user} is not authorized to operate Servitor server {server_name}")]
-	Unauthorized { user: UserId, server_name: String },
+	#[error("User {user} is not authorized to run {action} on Servitor server {server_name}")]
+	Unauthorized {
+		user: UserId,
+		server_name: String,
+		action: ServitorAction,
+	},
 
 	#[error(transparent)]
 	Servitor(#[from] ServitorError),
@@ -39,6 +44,7 @@ pub async fn start<S: ServitorController>(
 		server_name,
 		author,
 		member,
+		ServitorAction::Start,
 		async |h: &S, u: &str| {
 			info!("Running start for Servitor server {server_name}");
 			h.start(u).await
@@ -60,6 +66,7 @@ pub async fn stop<S: ServitorController>(
 		server_name,
 		author,
 		member,
+		ServitorAction::Stop,
 		async |h: &S, u: &str| {
 			info!("Running stop for Servitor server {server_name}");
 			h.stop(u).await
@@ -81,6 +88,7 @@ pub async fn restart<S: ServitorController>(
 		server_name,
 		author,
 		member,
+		ServitorAction::Restart,
 		async |h: &S, u: &str| {
 			info!("Running restart for Servitor server {server_name}");
 			h.restart(u).await
@@ -102,6 +110,7 @@ pub async fn reload<S: ServitorController>(
 		server_name,
 		author,
 		member,
+		ServitorAction::Reload,
 		async |h: &S, u: &str| {
 			info!("Running reload for Servitor server {server_name}");
 			h.reload(u).await
@@ -123,6 +132,7 @@ pub async fn status<S: ServitorController>(
 		server_name,
 		author,
 		member,
+		ServitorAction::Status,
 		async |h: &S, u: &str| {
 			info!("Running status for Servitor server {server_name}");
 			h.status(u).await
@@ -131,37 +141,80 @@ pub async fn status<S: ServitorController>(
 	.await
 }
 
+pub async fn logs<S: ServitorController>(
+	data: &BotData,
+	servitor_handlers: &BTreeMap<String, S>,
+	server_name: &str,
+	lines: u32,
+	author: &User,
+	member: Option<&Member>,
+) -> Result<UnitLogs, ExecuteServitorActionError> {
+	execute_action(
+		data,
+		servitor_handlers,
+		server_name,
+		author,
+		member,
+		ServitorAction::Logs,
+		async |h: &S, u: &str| {
+			info!("Fetching {lines} log lines for Servitor server {server_name}");
+			h.logs(u, lines).await
+		},
+	)
+	.await
+}
+
+/// Whether `author` should confirm `action` before it runs, only users allowed to run it on the server are asked.
+pub async fn requires_confirmation(
+	data: &BotData,
+	server_name: &str,
+	action: ServitorAction,
+	author: &User,
+	member: Option<&Member>,
+) -> bool {
+	let read = data.read().await;
+	read.servitor.get(server_name).is_some_and(|info| {
+		!info.skip_confirmation && is_user_authorized(author, member, &info.for_action(action), &read.access_groups)
+	})
+}
+
 async fn execute_action<S, F, T>(
 	data: &BotData,
 	servitor_handlers: &BTreeMap<String, S>,
 	server_name: &str,
 	author: &User,
 	member: Option<&Member>,
+	servitor_action: ServitorAction,
 	action: F,
 ) -> Result<T, ExecuteServitorActionError>
 where
 	S: ServitorController,
 	F: AsyncFnOnce(&S, &str) -> Result<T, ServitorError>,
 {
-	let read = data.read().await;
-
-	let server_info = get_server_info(&read, server_name).await?;
-
-	if !is_user_authorized(author, member, server_info) {
-		return Err(ExecuteServitorActionError::Unauthorized {
-			user: author.id,
-			server_name: server_name.to_string(),
-		});
-	}
-
-	let servitor_handler = servitor_handlers.get(&server_info.servitor).ok_or(
+	// Only hold the lock for the lookup, a slow Servitor must not block writes to the data
+	let (servitor_name, unit_name) = {
+		let read = data.read().await;
+		let server_info = get_server_info(&read, server_name).await?;
+
+		if !is_user_authorized(author, member, &server_info.for_action(servitor_action), &read.access_groups) {
+			return Err(ExecuteServitorActionError::Unauthorized {
+				user: author.id,
+				server_name: server_name.to_string(),
+				action: servitor_action,
+			});
+		}
+
+		(server_info.servitor.clone(), server_info.unit_name.clone())
+	};
+
+	let servitor_handler = servitor_handlers.get(&servitor_name).ok_or(
 		ExecuteServitorActionError::InvalidServitor {
 			server_name: server_name.to_string(),
-			servitor_name: server_info.servitor.to_string(),
+			servitor_name,
 		},
 	)?;
 
-	Ok(action(servitor_handler, &server_info.unit_name.clone()).await?)
+	Ok(action(servitor_handler, &unit_name).await?)
 }
 
 #[cfg(test)]
@@ -173,7 +226,11 @@ mod tests {
 	use rstest::rstest;
 	use serde_json::json;
 	use serenity::all::RoleId;
+	use crate::controllers::authorization::permit;
+	use crate::data::servitor::ServerInfo;
+	use crate::controllers::DiscordEntity;
 	use std::fmt::Debug;
+	use std::time::Duration;
 
 	#[rstest]
 	#[case(start)]
@@ -257,11 +314,11 @@ mod tests {
 	}
 
 	#[rstest]
-	#[case(start)]
-	#[case(stop)]
-	#[case(restart)]
-	#[case(reload)]
-	#[case(status)]
+	#[case(start, ServitorAction::Start)]
+	#[case(stop, ServitorAction::Stop)]
+	#[case(restart, ServitorAction::Restart)]
+	#[case(reload, ServitorAction::Reload)]
+	#[case(status, ServitorAction::Status)]
 	#[tokio::test]
 	async fn given_dm_call_but_user_not_in_allowed_list_then_action_returns_unauthorized_error<
 		T: Debug + PartialEq,
@@ -273,6 +330,7 @@ mod tests {
 			&User,
 			Option<&Member>,
 		) -> Result<T, ExecuteServitorActionError>,
+		#[case] expected_action: ServitorAction,
 	) {
 		let data = mock_data(Some(json!({
 			"servitor": {
@@ -294,17 +352,18 @@ mod tests {
 			Err(ExecuteServitorActionError::Unauthorized {
 				user: UserId::new(12345678901234567),
 				server_name: "SomeServer".to_string(),
+				action: expected_action,
 			})
 		);
 		serv.values().for_each(|s| s.assert_not_called());
 	}
 
 	#[rstest]
-	#[case(start)]
-	#[case(stop)]
-	#[case(restart)]
-	#[case(reload)]
-	#[case(status)]
+	#[case(start, ServitorAction::Start)]
+	#[case(stop, ServitorAction::Stop)]
+	#[case(restart, ServitorAction::Restart)]
+	#[case(reload, ServitorAction::Reload)]
+	#[case(status, ServitorAction::Status)]
 	#[tokio::test]
 	async fn given_guild_call_but_user_not_in_allowed_list_then_action_returns_unauthorized_error<
 		T: Debug + PartialEq,
@@ -316,6 +375,7 @@ mod tests {
 			&User,
 			Option<&Member>,
 		) -> Result<T, ExecuteServitorActionError>,
+		#[case] expected_action: ServitorAction,
 	) {
 		let data = mock_data(Some(json!({
 			"servitor": {
@@ -337,6 +397,7 @@ mod tests {
 			Err(ExecuteServitorActionError::Unauthorized {
 				user: UserId::new(12345678901234567),
 				server_name: "SomeServer".to_string(),
+				action: expected_action,
 			})
 		);
 		serv.values().for_each(|s| s.assert_not_called());
@@ -505,4 +566,240 @@ mod tests {
 		assert_eq!(result, Ok(expected_result));
 		serv["foo"].assert_called_times(calls.0, calls.1, calls.2, calls.3, calls.4);
 	}
+
+	#[tokio::test]
+	async fn given_user_not_allowed_to_view_logs_then_logs_returns_unauthorized_error() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": {
+						"12345678901234567": ["status", "start", "stop", "restart", "reload"]
+					},
+					"authorized_roles": {
+						"98765432109876543": ["status"]
+					}
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		let (author, member) = mock_author_guild(
+			UserId::new(12345678901234567u64),
+			vec![RoleId::new(98765432109876543u64)],
+		);
+
+		let result = logs(&data, &serv, "SomeServer", 10, &author, member.as_ref()).await;
+
+		assert_eq!(
+			result,
+			Err(ExecuteServitorActionError::Unauthorized {
+				user: UserId::new(12345678901234567),
+				server_name: "SomeServer".to_string(),
+				action: ServitorAction::Logs,
+			})
+		);
+		serv["foo"].assert_not_called();
+	}
+
+	#[tokio::test]
+	async fn given_user_only_allowed_status_then_stop_returns_unauthorized_error_and_status_succeeds() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": {
+						"12345678901234567": ["status"]
+					}
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567u64));
+
+		let stop_result = stop(&data, &serv, "SomeServer", &author, member.as_ref()).await;
+		let status_result = status(&data, &serv, "SomeServer", &author, member.as_ref()).await;
+
+		assert_eq!(
+			stop_result,
+			Err(ExecuteServitorActionError::Unauthorized {
+				user: UserId::new(12345678901234567),
+				server_name: "SomeServer".to_string(),
+				action: ServitorAction::Stop,
+			})
+		);
+		assert_eq!(status_result, Ok(MockServitorController::default_status("bar")));
+		serv["foo"].assert_called_times(0, 0, 0, 0, 1);
+	}
+
+	#[tokio::test]
+	async fn given_role_allowed_to_stop_through_one_role_and_start_through_another_then_both_succeed() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_roles": {
+						"98765432109876543": ["start"],
+						"98765432109876544": ["stop"]
+					}
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		let (author, member) = mock_author_guild(
+			UserId::new(12345678901234567u64),
+			vec![RoleId::new(98765432109876543u64), RoleId::new(98765432109876544u64)],
+		);
+
+		let start_result = start(&data, &serv, "SomeServer", &author, member.as_ref()).await;
+		let stop_result = stop(&data, &serv, "SomeServer", &author, member.as_ref()).await;
+
+		assert_eq!(start_result, Ok(()));
+		assert_eq!(stop_result, Ok(()));
+		serv["foo"].assert_called_times(1, 1, 0, 0, 0);
+	}
+
+	#[tokio::test]
+	async fn given_denied_user_with_allowed_role_then_start_returns_unauthorized_error() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_roles": {
+						"98765432109876543": ["start"]
+					},
+					"denied": { "users": { "12345678901234567": "Griefing" } }
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		let (author, member) =
+			mock_author_guild(UserId::new(12345678901234567u64), vec![RoleId::new(98765432109876543u64)]);
+
+		let result = start(&data, &serv, "SomeServer", &author, member.as_ref()).await;
+
+		assert_eq!(
+			result,
+			Err(ExecuteServitorActionError::Unauthorized {
+				user: UserId::new(12345678901234567),
+				server_name: "SomeServer".to_string(),
+				action: ServitorAction::Start,
+			})
+		);
+		serv["foo"].assert_not_called();
+	}
+
+	#[tokio::test]
+	async fn given_user_allowed_to_view_logs_then_logs_returns_unit_logs() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": {
+						"12345678901234567": ["logs"]
+					}
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567u64));
+
+		let result = logs(&data, &serv, "SomeServer", 10, &author, member.as_ref()).await;
+
+		assert_eq!(result, Ok(MockServitorController::default_logs("bar", 10)));
+		serv["foo"].assert_called_times(0, 0, 0, 0, 0);
+		serv["foo"].assert_logs_called_times(1);
+	}
+
+	#[tokio::test]
+	async fn given_user_with_legacy_grant_then_logs_returns_unit_logs() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_roles": [98765432109876543u64]
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		let (author, member) = mock_author_guild(
+			UserId::new(12345678901234567u64),
+			vec![RoleId::new(98765432109876543u64)],
+		);
+
+		let result = logs(&data, &serv, "SomeServer", 5, &author, member.as_ref()).await;
+
+		assert_eq!(result, Ok(MockServitorController::default_logs("bar", 5)));
+		serv["foo"].assert_logs_called_times(1);
+	}
+
+	#[tokio::test]
+	async fn given_slow_servitor_then_writes_proceed_while_action_is_in_flight() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": [12345678901234567u64]
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		serv["foo"].set_delay(Duration::from_millis(500)).await;
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567u64));
+
+		let write_during_action = async {
+			while serv["foo"].max_in_flight() == 0 {
+				tokio::task::yield_now().await;
+			}
+			let user = DiscordEntity::User(UserId::new(11111111111111111));
+			let write = permit::<ServerInfo>(&data, "SomeServer", user, ServitorAction::all(), None);
+			tokio::time::timeout(Duration::from_millis(100), write).await
+		};
+
+		let (result, write) = tokio::join!(
+			start(&data, &serv, "SomeServer", &author, member.as_ref()),
+			write_during_action
+		);
+
+		assert_eq!(result, Ok(()));
+		assert_eq!(write, Ok(Ok(())), "write was blocked by the in-flight action");
+	}
+
+	#[tokio::test]
+	async fn given_users_then_requires_confirmation_only_for_those_allowed_to_run_the_action() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": {
+						"1": ["stop"],
+						"2": ["status"]
+					}
+				},
+				"SkippingServer": {
+					"servitor": "foo",
+					"unit_name": "baz",
+					"skip_confirmation": true,
+					"authorized_users": {
+						"1": ["stop"]
+					}
+				}
+			}
+		})));
+		let (allowed, allowed_member) = mock_author_dms(UserId::new(1));
+		let (other, other_member) = mock_author_dms(UserId::new(2));
+
+		assert!(requires_confirmation(&data, "SomeServer", ServitorAction::Stop, &allowed, allowed_member.as_ref()).await);
+		assert!(!requires_confirmation(&data, "SomeServer", ServitorAction::Restart, &allowed, allowed_member.as_ref()).await);
+		assert!(!requires_confirmation(&data, "SomeServer", ServitorAction::Stop, &other, other_member.as_ref()).await);
+		assert!(!requires_confirmation(&data, "SkippingServer", ServitorAction::Stop, &allowed, allowed_member.as_ref()).await);
+		assert!(!requires_confirmation(&data, "NonExistingServer", ServitorAction::Stop, &allowed, allowed_member.as_ref()).await);
+	}
 }
@@ -1,138 +1,141 @@
-use super::super::DiscordEntity;
-use super::{get_server_info_mut, ServerError};
-use crate::data::BotData;
-use log::info;
-use serenity::all::{RoleId, UserId};
-use thiserror::Error;
-
-#[derive(Debug, Error, PartialEq)]
-pub enum AddPermissionError {
-	#[error(transparent)]
-	Server(#[from] ServerError),
-
-	#[error("{entity:?} is already permitted to operate server {server_name}")]
-	AlreadyAuthorized {
-		server_name: String,
-		entity: DiscordEntity,
-	},
-}
+use super::super::authorization::{AuthorizedResource, Grantee};
+use super::ServerError;
+use crate::controllers::DiscordEntity;
+use crate::data::authorization::{set_expiry, Denials};
+use crate::data::servitor::{ActionExpiries, ActionGrants, ServerInfo, ServitorAction};
+use crate::data::Data;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet};
+
+impl AuthorizedResource for ServerInfo {
+	type Grant = BTreeSet<ServitorAction>;
+	type Error = ServerError;
+
+	const NOUN: &'static str = "Servitor server";
+
+	fn all(data: &Data) -> &BTreeMap<String, Self> {
+		&data.servitor
+	}
+
+	fn get_mut<'a>(data: &'a mut Data, name: &str) -> Result<&'a mut Self, ServerError> {
+		data.servitor.get_mut(name).ok_or(ServerError::DoesNotExist {
+			server_name: name.into(),
+		})
+	}
+
+	fn grant(&mut self, grantee: Grantee, actions: &BTreeSet<ServitorAction>) -> bool {
+		match grantee {
+			Grantee::User(id) => grant(&mut self.authorized_users, id, actions),
+			Grantee::Role(id) => grant(&mut self.authorized_roles, id, actions),
+			Grantee::Group(group) => grant(&mut self.authorized_groups, group.into(), actions),
+		}
+	}
 
-#[derive(Debug, Error, PartialEq)]
-pub enum RemovePermissionError {
-	#[error(transparent)]
-	Server(#[from] ServerError),
+	fn revoke(&mut self, grantee: Grantee, actions: &BTreeSet<ServitorAction>) -> bool {
+		match grantee {
+			Grantee::User(id) => revoke(&mut self.authorized_users, id, actions),
+			Grantee::Role(id) => revoke(&mut self.authorized_roles, id, actions),
+			Grantee::Group(group) => revoke(&mut self.authorized_groups, group.into(), actions),
+		}
+	}
 
-	#[error("{entity:?} is already not permitted to operate server {server_name}")]
-	AlreadyNotAuthorized {
-		server_name: String,
+	fn set_expiry(
+		&mut self,
 		entity: DiscordEntity,
-	},
+		actions: &BTreeSet<ServitorAction>,
+		expires: Option<DateTime<Utc>>,
+	) -> bool {
+		match entity {
+			DiscordEntity::User(id) => set_actions_expiry(&mut self.user_expiries, id, actions, expires),
+			DiscordEntity::Role(id) => set_actions_expiry(&mut self.role_expiries, id, actions, expires),
+		}
+	}
+
+	fn forget_expiry(&mut self, entity: DiscordEntity, actions: &BTreeSet<ServitorAction>) {
+		match entity {
+			DiscordEntity::User(id) => forget_actions_expiry(&mut self.user_expiries, id, actions),
+			DiscordEntity::Role(id) => forget_actions_expiry(&mut self.role_expiries, id, actions),
+		}
+	}
+
+	fn denials_mut(&mut self) -> &mut Denials {
+		&mut self.denied
+	}
 }
 
-pub async fn permit_user(
-	data: &BotData,
-	server_name: &str,
-	user_id: UserId,
-) -> Result<(), AddPermissionError> {
-	let mut lock = data.write().await;
-	let mut data_write = lock.write();
-
-	let server_info = get_server_info_mut(&mut data_write, server_name).await?;
-
-	if server_info.authorized_users.insert(user_id) {
-		info!("Permitted user {user_id} to operate server {server_name}");
-		Ok(())
-	} else {
-		Err(AddPermissionError::AlreadyAuthorized {
-			server_name: server_name.into(),
-			entity: DiscordEntity::User(user_id),
-		})
+/// Returns whether the expiry of any of the actions changed, leaving the other actions of the entity alone.
+fn set_actions_expiry<T: Ord + Copy>(
+	expiries: &mut ActionExpiries<T>,
+	id: T,
+	actions: &BTreeSet<ServitorAction>,
+	expires: Option<DateTime<Utc>>,
+) -> bool {
+	let action_expiries = expiries.entry(id).or_default();
+	let changed = actions
+		.iter()
+		.fold(false, |changed, action| set_expiry(action_expiries, *action, expires) || changed);
+	if action_expiries.is_empty() {
+		expiries.remove(&id);
 	}
+
+	changed
 }
 
-pub async fn revoke_user(
-	data: &BotData,
-	server_name: &str,
-	user_id: UserId,
-) -> Result<(), RemovePermissionError> {
-	let mut lock = data.write().await;
-	let mut data_write = lock.write();
-
-	let server_info = get_server_info_mut(&mut data_write, server_name).await?;
-
-	if server_info.authorized_users.remove(&user_id) {
-		info!("Revoked user {user_id}'s permission to operate server {server_name}");
-		Ok(())
-	} else {
-		Err(RemovePermissionError::AlreadyNotAuthorized {
-			server_name: server_name.to_string(),
-			entity: DiscordEntity::User(user_id),
-		})
+fn forget_actions_expiry<T: Ord>(expiries: &mut ActionExpiries<T>, id: T, actions: &BTreeSet<ServitorAction>) {
+	let Some(action_expiries) = expiries.get_mut(&id) else {
+		return;
+	};
+
+	action_expiries.retain(|action, _| !actions.contains(action));
+	if action_expiries.is_empty() {
+		expiries.remove(&id);
 	}
 }
 
-pub async fn permit_role(
-	data: &BotData,
-	server_name: &str,
-	role_id: RoleId,
-) -> Result<(), AddPermissionError> {
-	let mut lock = data.write().await;
-	let mut data_write = lock.write();
-
-	let server_info = get_server_info_mut(&mut data_write, server_name).await?;
-
-	if server_info.authorized_roles.insert(role_id) {
-		info!("Permitted role {role_id} to operate server {server_name}");
-		Ok(())
-	} else {
-		Err(AddPermissionError::AlreadyAuthorized {
-			server_name: server_name.into(),
-			entity: DiscordEntity::Role(role_id),
-		})
+/// Returns whether any of the actions was not granted before.
+fn grant<T: Ord>(grants: &mut ActionGrants<T>, id: T, actions: &BTreeSet<ServitorAction>) -> bool {
+	if actions.is_empty() {
+		return false;
 	}
+
+	let granted = grants.entry(id).or_default();
+	let previous_len = granted.len();
+	granted.extend(actions);
+
+	granted.len() != previous_len
 }
 
-pub async fn revoke_role(
-	data: &BotData,
-	server_name: &str,
-	role_id: RoleId,
-) -> Result<(), RemovePermissionError> {
-	let mut lock = data.write().await;
-	let mut data_write = lock.write();
-
-	let server_info = get_server_info_mut(&mut data_write, server_name).await?;
-
-	if server_info.authorized_roles.remove(&role_id) {
-		info!("Revoked role {role_id}'s permission to operate server {server_name}");
-		Ok(())
-	} else {
-		Err(RemovePermissionError::AlreadyNotAuthorized {
-			server_name: server_name.to_string(),
-			entity: DiscordEntity::Role(role_id),
-		})
+/// Returns whether any of the actions was granted before, forgetting the entity once it has no actions left.
+fn revoke<T: Ord>(grants: &mut ActionGrants<T>, id: T, actions: &BTreeSet<ServitorAction>) -> bool {
+	let Some(granted) = grants.get_mut(&id) else {
+		return false;
+	};
+
+	let previous_len = granted.len();
+	granted.retain(|action| !actions.contains(action));
+	let changed = granted.len() != previous_len;
+
+	if granted.is_empty() {
+		grants.remove(&id);
 	}
+
+	changed
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::controllers::authorization::{
+		permit, permit_group, revoke, revoke_group, AddPermissionError, RemoveGroupPermissionError,
+		RemovePermissionError,
+	};
+	use crate::controllers::DiscordEntity;
 	use crate::data::tests::mock_data;
+	use crate::data::BotData;
+	use chrono::{TimeZone, Utc};
+	use std::collections::BTreeMap;
 	use serde_json::json;
-
-	#[tokio::test]
-	async fn given_non_existing_server_then_permit_user_returns_error_and_does_not_modify_data() {
-		let data = mock_data(None);
-
-		let result = permit_user(&data, "NonExistentServer", UserId::new(12345678901234567)).await;
-
-		assert_eq!(
-			result,
-			Err(AddPermissionError::Server(ServerError::DoesNotExist {
-				server_name: "NonExistentServer".to_string()
-			}))
-		);
-		assert!(data.read().await.servitor.is_empty())
-	}
+	use serenity::all::{RoleId, UserId};
 
 	#[tokio::test]
 	async fn given_already_authorized_user_then_permit_user_returns_error_and_does_not_modify_data()
@@ -148,13 +151,21 @@ mod tests {
 			}
 		})));
 
-		let result = permit_user(&data, "ExistingServer", UserId::new(12345678901234567)).await;
+		let result = permit::<ServerInfo>(
+			&data,
+			"ExistingServer",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			ServitorAction::all(),
+			None,
+		)
+		.await;
 
 		assert_eq!(
 			result,
 			Err(AddPermissionError::AlreadyAuthorized {
-				server_name: "ExistingServer".to_string(),
-				entity: DiscordEntity::User(UserId::new(12345678901234567u64))
+				name: "ExistingServer".to_string(),
+				entity: DiscordEntity::User(UserId::new(12345678901234567u64)),
+				grant: ServitorAction::all(),
 			})
 		);
 		assert_eq!(
@@ -166,224 +177,367 @@ mod tests {
 	}
 
 	#[tokio::test]
-	async fn given_new_user_then_permit_user_returns_success_and_adds_user() {
+	async fn given_user_with_some_actions_then_permit_user_adds_missing_actions() {
 		let data = mock_data(Some(json!({
 			"servitor": {
 				"ExistingServer": {
 					"servitor": "foo",
 					"unit_name": "bar",
-					"authorized_users": [],
-					"authorized_roles": []
+					"authorized_users": {
+						"12345678901234567": ["status"]
+					}
 				}
 			}
 		})));
 
-		let result = permit_user(&data, "ExistingServer", UserId::new(12345678901234567)).await;
+		let result = permit::<ServerInfo>(
+			&data,
+			"ExistingServer",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			BTreeSet::from([ServitorAction::Status, ServitorAction::Logs]),
+			None,
+		)
+		.await;
 
 		assert_eq!(result, Ok(()));
-
-		assert!(data.read().await.servitor["ExistingServer"]
-			.authorized_users
-			.contains(&UserId::new(12345678901234567)));
+		assert_eq!(
+			data.read().await.servitor["ExistingServer"].authorized_users,
+			BTreeMap::from([(
+				UserId::new(12345678901234567),
+				BTreeSet::from([ServitorAction::Status, ServitorAction::Logs])
+			)])
+		);
 	}
 
 	#[tokio::test]
-	async fn given_non_existing_server_then_revoke_user_returns_error_and_does_not_modify_data() {
-		let data = mock_data(None);
+	async fn given_legacy_user_grant_then_revoke_user_for_some_actions_keeps_the_others() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"ExistingServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": [12345678901234567u64]
+				}
+			}
+		})));
 
-		let result = revoke_user(&data, "NonExistentServer", UserId::new(12345678901234567)).await;
+		let result = revoke::<ServerInfo>(
+			&data,
+			"ExistingServer",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			BTreeSet::from([ServitorAction::Stop, ServitorAction::Restart]),
+		)
+		.await;
 
+		assert_eq!(result, Ok(()));
 		assert_eq!(
-			result,
-			Err(RemovePermissionError::Server(ServerError::DoesNotExist {
-				server_name: "NonExistentServer".to_string()
-			}))
+			data.read().await.servitor["ExistingServer"].authorized_users,
+			BTreeMap::from([(
+				UserId::new(12345678901234567),
+				BTreeSet::from([
+					ServitorAction::Status,
+					ServitorAction::Start,
+					ServitorAction::Reload,
+					ServitorAction::Logs
+				])
+			)])
 		);
-		assert!(data.read().await.servitor.is_empty());
 	}
 
 	#[tokio::test]
-	async fn given_not_authorized_user_then_revoke_user_returns_error_and_does_not_modify_data() {
+	async fn given_role_without_requested_actions_then_revoke_role_returns_error() {
 		let data = mock_data(Some(json!({
 			"servitor": {
 				"ExistingServer": {
 					"servitor": "foo",
 					"unit_name": "bar",
-					"authorized_users": [12345678901234567u64],
-					"authorized_roles": []
+					"authorized_roles": {
+						"98765432109876543": ["status"]
+					}
 				}
 			}
 		})));
 
-		let result = revoke_user(&data, "ExistingServer", UserId::new(76543210987654321)).await;
+		let result = revoke::<ServerInfo>(
+			&data,
+			"ExistingServer",
+			DiscordEntity::Role(RoleId::new(98765432109876543)),
+			BTreeSet::from([ServitorAction::Logs]),
+		)
+		.await;
 
 		assert_eq!(
 			result,
 			Err(RemovePermissionError::AlreadyNotAuthorized {
-				server_name: "ExistingServer".to_string(),
-				entity: DiscordEntity::User(UserId::new(76543210987654321))
+				name: "ExistingServer".to_string(),
+				entity: DiscordEntity::Role(RoleId::new(98765432109876543)),
+				grant: BTreeSet::from([ServitorAction::Logs]),
 			})
 		);
 		assert_eq!(
-			data.read().await.servitor["ExistingServer"]
-				.authorized_users
-				.len(),
-			1
+			data.read().await.servitor["ExistingServer"].authorized_roles,
+			BTreeMap::from([(
+				RoleId::new(98765432109876543),
+				BTreeSet::from([ServitorAction::Status])
+			)])
 		);
 	}
 
 	#[tokio::test]
-	async fn given_authorized_user_then_revoke_user_returns_success_and_removes_users() {
+	async fn given_role_losing_its_last_action_then_revoke_role_removes_role() {
 		let data = mock_data(Some(json!({
 			"servitor": {
 				"ExistingServer": {
 					"servitor": "foo",
 					"unit_name": "bar",
-					"authorized_users": [12345678901234567u64],
-					"authorized_roles": []
+					"authorized_roles": {
+						"98765432109876543": ["status", "logs"]
+					}
 				}
 			}
 		})));
 
-		let result = revoke_user(&data, "ExistingServer", UserId::new(12345678901234567)).await;
+		let result = revoke::<ServerInfo>(
+			&data,
+			"ExistingServer",
+			DiscordEntity::Role(RoleId::new(98765432109876543)),
+			BTreeSet::from([ServitorAction::Status, ServitorAction::Logs]),
+		)
+		.await;
 
 		assert_eq!(result, Ok(()));
-		assert!(!data.read().await.servitor["ExistingServer"]
-			.authorized_users
-			.contains(&UserId::new(12345678901234567)));
+		assert!(data.read().await.servitor["ExistingServer"]
+			.authorized_roles
+			.is_empty());
 	}
 
 	#[tokio::test]
-	async fn given_non_existing_server_then_permit_role_returns_error_and_does_not_modify_data() {
-		let data = mock_data(None);
+	async fn given_expiry_then_permit_user_records_it_for_the_granted_actions() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"ExistingServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": { "12345678901234567": ["status"] }
+				}
+			}
+		})));
+		let expires = Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap();
 
-		let result = permit_role(&data, "NonExistentServer", RoleId::new(98765432109876543)).await;
+		let result = permit::<ServerInfo>(
+			&data,
+			"ExistingServer",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			BTreeSet::from([ServitorAction::Status]),
+			Some(expires),
+		)
+		.await;
 
+		assert_eq!(result, Ok(()));
 		assert_eq!(
-			result,
-			Err(AddPermissionError::Server(ServerError::DoesNotExist {
-				server_name: "NonExistentServer".to_string()
-			}))
+			data.read().await.servitor["ExistingServer"].user_expiries,
+			BTreeMap::from([(UserId::new(12345678901234567), BTreeMap::from([(ServitorAction::Status, expires)]))])
 		);
-		assert!(data.read().await.servitor.is_empty());
 	}
 
 	#[tokio::test]
-	async fn given_already_authorized_role_then_permit_role_returns_error_and_does_not_modify_data()
-	{
+	async fn given_permanent_actions_then_temporary_permit_only_expires_the_new_ones() {
 		let data = mock_data(Some(json!({
 			"servitor": {
 				"ExistingServer": {
 					"servitor": "foo",
 					"unit_name": "bar",
-					"authorized_users": [],
-					"authorized_roles": [98765432109876543u64]
+					"authorized_users": { "12345678901234567": ["status", "logs"] }
 				}
 			}
 		})));
+		let expires = Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap();
 
-		let result = permit_role(&data, "ExistingServer", RoleId::new(98765432109876543)).await;
+		let result = permit::<ServerInfo>(
+			&data,
+			"ExistingServer",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			BTreeSet::from([ServitorAction::Start]),
+			Some(expires),
+		)
+		.await;
 
+		assert_eq!(result, Ok(()));
+		let read = data.read().await;
+		let server = &read.servitor["ExistingServer"];
 		assert_eq!(
-			result,
-			Err(AddPermissionError::AlreadyAuthorized {
-				server_name: "ExistingServer".to_string(),
-				entity: DiscordEntity::Role(RoleId::new(98765432109876543u64))
-			})
+			server.authorized_users[&UserId::new(12345678901234567)],
+			BTreeSet::from([ServitorAction::Status, ServitorAction::Start, ServitorAction::Logs])
 		);
 		assert_eq!(
-			data.read().await.servitor["ExistingServer"]
-				.authorized_roles
-				.len(),
-			1
+			server.user_expiries,
+			BTreeMap::from([(UserId::new(12345678901234567), BTreeMap::from([(ServitorAction::Start, expires)]))])
 		);
 	}
 
 	#[tokio::test]
-	async fn given_new_role_then_permit_role_returns_success_and_adds_role() {
+	async fn given_temporary_action_then_permanent_permit_of_another_keeps_its_expiry() {
 		let data = mock_data(Some(json!({
 			"servitor": {
 				"ExistingServer": {
 					"servitor": "foo",
 					"unit_name": "bar",
-					"authorized_users": [],
-					"authorized_roles": []
+					"authorized_roles": { "98765432109876543": ["start"] },
+					"role_expiries": { "98765432109876543": { "start": "2025-03-06T19:59:45Z" } }
 				}
 			}
 		})));
 
-		let result = permit_role(&data, "ExistingServer", RoleId::new(98765432109876543)).await;
+		let result = permit::<ServerInfo>(
+			&data,
+			"ExistingServer",
+			DiscordEntity::Role(RoleId::new(98765432109876543)),
+			BTreeSet::from([ServitorAction::Status]),
+			None,
+		)
+		.await;
 
 		assert_eq!(result, Ok(()));
-
-		assert!(data.read().await.servitor["ExistingServer"]
-			.authorized_roles
-			.contains(&RoleId::new(98765432109876543)));
-	}
-
-	#[tokio::test]
-	async fn given_non_existing_server_then_revoke_role_returns_error_and_does_not_modify_data() {
-		let data = mock_data(None);
-
-		let result = revoke_role(&data, "NonExistentServer", RoleId::new(98765432109876543)).await;
-
 		assert_eq!(
-			result,
-			Err(RemovePermissionError::Server(ServerError::DoesNotExist {
-				server_name: "NonExistentServer".to_string()
-			}))
+			data.read().await.servitor["ExistingServer"].role_expiries,
+			BTreeMap::from([(
+				RoleId::new(98765432109876543),
+				BTreeMap::from([(ServitorAction::Start, Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap())])
+			)])
 		);
-		assert!(data.read().await.servitor.is_empty());
 	}
 
 	#[tokio::test]
-	async fn given_not_authorized_role_then_revoke_role_returns_error_and_does_not_modify_data() {
+	async fn given_temporary_action_then_permanent_permit_of_it_clears_its_expiry() {
 		let data = mock_data(Some(json!({
 			"servitor": {
 				"ExistingServer": {
 					"servitor": "foo",
 					"unit_name": "bar",
-					"authorized_users": [],
-					"authorized_roles": [98765432109876543u64]
+					"authorized_users": { "12345678901234567": ["status", "start"] },
+					"user_expiries": {
+						"12345678901234567": { "status": "2025-03-06T19:59:45Z", "start": "2025-03-06T19:59:45Z" }
+					}
 				}
 			}
 		})));
 
-		let result = revoke_role(&data, "ExistingServer", RoleId::new(11223344556677889)).await;
+		let result = permit::<ServerInfo>(
+			&data,
+			"ExistingServer",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			BTreeSet::from([ServitorAction::Start]),
+			None,
+		)
+		.await;
 
+		assert_eq!(result, Ok(()));
 		assert_eq!(
-			result,
-			Err(RemovePermissionError::AlreadyNotAuthorized {
-				server_name: "ExistingServer".to_string(),
-				entity: DiscordEntity::Role(RoleId::new(11223344556677889))
-			})
-		);
-		assert_eq!(
-			data.read().await.servitor["ExistingServer"]
-				.authorized_roles
-				.len(),
-			1
+			data.read().await.servitor["ExistingServer"].user_expiries,
+			BTreeMap::from([(
+				UserId::new(12345678901234567),
+				BTreeMap::from([(ServitorAction::Status, Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap())])
+			)])
 		);
 	}
 
 	#[tokio::test]
-	async fn given_authorized_role_then_revoke_role_returns_success_and_removes_role() {
+	async fn given_partial_revoke_then_only_the_revoked_actions_lose_their_expiry() {
 		let data = mock_data(Some(json!({
 			"servitor": {
 				"ExistingServer": {
 					"servitor": "foo",
 					"unit_name": "bar",
-					"authorized_users": [],
-					"authorized_roles": [98765432109876543u64]
+					"authorized_roles": { "98765432109876543": ["status", "start"] },
+					"role_expiries": {
+						"98765432109876543": { "status": "2025-03-06T19:59:45Z", "start": "2025-03-06T19:59:45Z" }
+					}
 				}
 			}
 		})));
 
-		let result = revoke_role(&data, "ExistingServer", RoleId::new(98765432109876543)).await;
+		let partial = revoke::<ServerInfo>(
+			&data,
+			"ExistingServer",
+			DiscordEntity::Role(RoleId::new(98765432109876543)),
+			BTreeSet::from([ServitorAction::Start]),
+		)
+		.await;
+		let expiries_after_partial = data.read().await.servitor["ExistingServer"].role_expiries[&RoleId::new(98765432109876543)]
+			.keys()
+			.copied()
+			.collect::<Vec<_>>();
+		let full = revoke::<ServerInfo>(
+			&data,
+			"ExistingServer",
+			DiscordEntity::Role(RoleId::new(98765432109876543)),
+			BTreeSet::from([ServitorAction::Status]),
+		)
+		.await;
+
+		assert_eq!(partial, Ok(()));
+		assert_eq!(full, Ok(()));
+		assert_eq!(expiries_after_partial, vec![ServitorAction::Status]);
+		assert!(data.read().await.servitor["ExistingServer"].role_expiries.is_empty());
+	}
+
+	fn group_data() -> BotData {
+		mock_data(Some(json!({
+			"access_groups": { "Friends": {} },
+			"servitor": {
+				"SomeServer": { "servitor": "foo", "unit_name": "bar", "authorized_groups": { "Friends": ["status"] } }
+			}
+		})))
+	}
+
+	#[tokio::test]
+	async fn given_existing_group_then_permit_group_adds_actions() {
+		let data = group_data();
+
+		let actions = BTreeSet::from([ServitorAction::Start]);
+		let result = permit_group::<ServerInfo>(&data, "SomeServer", "Friends", actions).await;
 
 		assert_eq!(result, Ok(()));
-		assert!(!data.read().await.servitor["ExistingServer"]
-			.authorized_roles
-			.contains(&RoleId::new(98765432109876543)));
+		assert_eq!(
+			data.read().await.servitor["SomeServer"].authorized_groups["Friends"],
+			BTreeSet::from([ServitorAction::Status, ServitorAction::Start])
+		);
+	}
+
+	#[tokio::test]
+	async fn given_last_granted_action_then_revoke_group_forgets_group() {
+		let data = group_data();
+
+		let result = revoke_group::<ServerInfo>(
+			&data,
+			"SomeServer",
+			"Friends",
+			ServitorAction::all(),
+		).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.servitor["SomeServer"].authorized_groups.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_not_granted_actions_then_revoke_group_returns_error() {
+		let data = group_data();
+
+		let actions = BTreeSet::from([ServitorAction::Stop]);
+		let result = revoke_group::<ServerInfo>(
+			&data,
+			"SomeServer",
+			"Friends",
+			actions.clone(),
+		).await;
+
+		assert_eq!(
+			result,
+			Err(RemoveGroupPermissionError::AlreadyNotAuthorized {
+				name: "SomeServer".to_string(),
+				group: "Friends".to_string(),
+				grant: actions
+			})
+		);
 	}
 }
@@ -0,0 +1,290 @@
+use super::action::{self, ExecuteServitorActionError};
+use crate::data::servitor::ServitorAction;
+use crate::data::BotData;
+use crate::services::servitor::{ServitorController, UnitStatus};
+use futures::{stream, StreamExt};
+use serenity::all::{Member, User};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, poise::ChoiceParameter)]
+pub enum BulkAction {
+	#[name = "start"]
+	Start,
+	#[name = "stop"]
+	Stop,
+	#[name = "restart"]
+	Restart,
+	#[name = "status"]
+	Status,
+}
+
+impl From<BulkAction> for ServitorAction {
+	fn from(action: BulkAction) -> Self {
+		match action {
+			BulkAction::Start => ServitorAction::Start,
+			BulkAction::Stop => ServitorAction::Stop,
+			BulkAction::Restart => ServitorAction::Restart,
+			BulkAction::Status => ServitorAction::Status,
+		}
+	}
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BulkOutcome {
+	Done,
+	Status(UnitStatus),
+}
+
+pub type BulkResults = BTreeMap<String, Result<BulkOutcome, ExecuteServitorActionError>>;
+
+/// Parses a comma separated list of server names, ignoring duplicates and blanks.
+pub fn parse_servers(servers: &str) -> BTreeSet<String> {
+	servers
+		.split(',')
+		.map(str::trim)
+		.filter(|s| !s.is_empty())
+		.map(str::to_string)
+		.collect()
+}
+
+/// Whether `author` should confirm a destructive bulk action, only servers they may run it on count.
+pub async fn requires_confirmation(
+	data: &BotData,
+	servers: &BTreeSet<String>,
+	author: &User,
+	member: Option<&Member>,
+	bulk_action: BulkAction,
+) -> bool {
+	let servitor_action = ServitorAction::from(bulk_action);
+	if !matches!(servitor_action, ServitorAction::Stop | ServitorAction::Restart) {
+		return false;
+	}
+
+	for server_name in servers {
+		if action::requires_confirmation(data, server_name, servitor_action, author, member).await {
+			return true;
+		}
+	}
+	false
+}
+
+/// Runs the action on every server, with at most `concurrency` requests in flight to each Servitor instance.
+pub async fn bulk<S: ServitorController>(
+	data: &BotData,
+	servitor_handlers: &BTreeMap<String, S>,
+	servers: &BTreeSet<String>,
+	author: &User,
+	member: Option<&Member>,
+	bulk_action: BulkAction,
+	concurrency: usize,
+) -> BulkResults {
+	let mut by_instance: BTreeMap<Option<String>, Vec<&str>> = BTreeMap::new();
+	{
+		let read = data.read().await;
+		for server_name in servers {
+			let servitor = read.servitor.get(server_name).map(|info| info.servitor.clone());
+			by_instance.entry(servitor).or_default().push(server_name);
+		}
+	}
+
+	let run = async |server_name: &str| {
+		let result = match bulk_action {
+			BulkAction::Start => action::start(data, servitor_handlers, server_name, author, member)
+				.await
+				.map(|_| BulkOutcome::Done),
+			BulkAction::Stop => action::stop(data, servitor_handlers, server_name, author, member)
+				.await
+				.map(|_| BulkOutcome::Done),
+			BulkAction::Restart => action::restart(data, servitor_handlers, server_name, author, member)
+				.await
+				.map(|_| BulkOutcome::Done),
+			BulkAction::Status => action::status(data, servitor_handlers, server_name, author, member)
+				.await
+				.map(BulkOutcome::Status),
+		};
+		(server_name.to_string(), result)
+	};
+
+	let instances = by_instance.into_values().map(|server_names| {
+		stream::iter(server_names)
+			.map(run)
+			.buffer_unordered(concurrency.max(1))
+			.collect::<Vec<_>>()
+	});
+
+	futures::future::join_all(instances)
+		.await
+		.into_iter()
+		.flatten()
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::controllers::servitor::ServerError;
+	use crate::controllers::tests::mock_author_dms;
+	use crate::data::tests::mock_data;
+	use crate::services::servitor::tests::{controllers_from_bot_data, MockServitorController};
+	use serde_json::json;
+	use serenity::all::UserId;
+	use std::time::Duration;
+
+	fn servers(names: &[&str]) -> BTreeSet<String> {
+		names.iter().map(|s| s.to_string()).collect()
+	}
+
+	#[test]
+	fn given_server_list_then_parse_servers_trims_and_deduplicates() {
+		assert_eq!(parse_servers(" One, Two,,One "), servers(&["One", "Two"]));
+		assert_eq!(parse_servers(""), BTreeSet::new());
+	}
+
+	#[tokio::test]
+	async fn given_servers_then_requires_confirmation_only_counts_those_the_user_may_stop() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"Allowed": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": { "12345678901234567": ["start", "stop"] }
+				},
+				"Forbidden": {
+					"servitor": "foo",
+					"unit_name": "baz"
+				}
+			}
+		})));
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let confirm = async |names: &[&str], action: BulkAction| {
+			requires_confirmation(&data, &servers(names), &author, member.as_ref(), action).await
+		};
+
+		assert!(confirm(&["Allowed", "Forbidden"], BulkAction::Stop).await);
+		assert!(!confirm(&["Allowed"], BulkAction::Start).await);
+		assert!(!confirm(&["Allowed"], BulkAction::Restart).await);
+		assert!(!confirm(&["Forbidden", "Missing"], BulkAction::Stop).await);
+	}
+
+	#[tokio::test]
+	async fn given_mixed_servers_then_bulk_reports_each_result() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"Allowed": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": { "12345678901234567": ["start"] }
+				},
+				"Forbidden": {
+					"servitor": "foo",
+					"unit_name": "baz"
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let result = bulk(
+			&data,
+			&serv,
+			&servers(&["Allowed", "Forbidden", "Missing"]),
+			&author,
+			member.as_ref(),
+			BulkAction::Start,
+			2,
+		)
+		.await;
+
+		assert_eq!(
+			result,
+			BTreeMap::from([
+				("Allowed".to_string(), Ok(BulkOutcome::Done)),
+				(
+					"Forbidden".to_string(),
+					Err(ExecuteServitorActionError::Unauthorized {
+						user: UserId::new(12345678901234567),
+						server_name: "Forbidden".to_string(),
+						action: ServitorAction::Start,
+					})
+				),
+				(
+					"Missing".to_string(),
+					Err(ExecuteServitorActionError::Server(ServerError::DoesNotExist {
+						server_name: "Missing".to_string(),
+					}))
+				),
+			])
+		);
+		serv["foo"].assert_called_times(1, 0, 0, 0, 0);
+	}
+
+	#[tokio::test]
+	async fn given_status_then_bulk_returns_statuses() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"One": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": [12345678901234567u64]
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let result = bulk(&data, &serv, &servers(&["One"]), &author, member.as_ref(), BulkAction::Status, 2).await;
+
+		assert_eq!(
+			result,
+			BTreeMap::from([(
+				"One".to_string(),
+				Ok(BulkOutcome::Status(MockServitorController::default_status("bar")))
+			)])
+		);
+	}
+
+	#[tokio::test]
+	async fn given_many_servers_then_bulk_bounds_concurrency_per_instance() {
+		let server = |servitor: &str| {
+			json!({
+				"servitor": servitor,
+				"unit_name": "unit",
+				"authorized_users": [12345678901234567u64]
+			})
+		};
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"A1": server("foo"),
+				"A2": server("foo"),
+				"A3": server("foo"),
+				"A4": server("foo"),
+				"B1": server("other"),
+				"B2": server("other"),
+				"B3": server("other")
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		for handler in serv.values() {
+			handler.set_delay(Duration::from_millis(20)).await;
+		}
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let result = bulk(
+			&data,
+			&serv,
+			&servers(&["A1", "A2", "A3", "A4", "B1", "B2", "B3"]),
+			&author,
+			member.as_ref(),
+			BulkAction::Restart,
+			2,
+		)
+		.await;
+
+		assert!(result.values().all(|r| *r == Ok(BulkOutcome::Done)));
+		serv["foo"].assert_called_times(0, 0, 4, 0, 0);
+		serv["other"].assert_called_times(0, 0, 3, 0, 0);
+		assert_eq!(serv["foo"].max_in_flight(), 2);
+		assert_eq!(serv["other"].max_in_flight(), 2);
+	}
+}
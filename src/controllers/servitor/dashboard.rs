@@ -0,0 +1,306 @@
+use crate::data::servitor::{Dashboard, ServitorDashboards};
+use crate::data::BotData;
+use crate::services::servitor::{ServitorController, UnitStatus};
+use crate::services::wake_on_lan::MachineProbe;
+use futures::future::join_all;
+use log::{info, warn};
+use serenity::all::{ChannelId, GuildId, MessageId};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum RemoveDashboardError {
+	#[error("there is no dashboard in channel {channel}")]
+	DoesNotExist { channel: ChannelId },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DashboardSnapshot {
+	pub servers: BTreeMap<String, Option<UnitStatus>>,
+	/// Whether each machine is online, `None` for machines without an address to check.
+	pub machines: BTreeMap<String, Option<bool>>,
+}
+
+/// Collects the state of the servers and machines visible in `guild`, or in DMs when `None`.
+pub async fn snapshot<S: ServitorController, P: MachineProbe>(
+	data: &BotData,
+	guild: Option<GuildId>,
+	servitor_handlers: &BTreeMap<String, S>,
+	machine_probe: &P,
+) -> DashboardSnapshot {
+	let (units, addresses) = {
+		let read = data.read().await;
+		let units: Vec<(String, String, String)> = read
+			.servitor
+			.iter()
+			.filter(|(_, info)| info.scope.is_visible_in(guild))
+			.map(|(name, info)| (name.clone(), info.servitor.clone(), info.unit_name.clone()))
+			.collect();
+		let addresses: Vec<(String, Option<String>)> = read
+			.wake_on_lan
+			.iter()
+			.filter(|(_, info)| info.scope.is_visible_in(guild))
+			.map(|(name, info)| (name.clone(), info.address.clone()))
+			.collect();
+		(units, addresses)
+	};
+
+	let mut servers = BTreeMap::new();
+	for (server_name, servitor, unit_name) in units {
+		let status = match servitor_handlers.get(&servitor) {
+			Some(handler) => handler
+				.status(&unit_name)
+				.await
+				.inspect_err(|e| warn!("Failed to get status of server {server_name} for dashboard: {e}"))
+				.ok(),
+			None => None,
+		};
+		servers.insert(server_name, status);
+	}
+
+	// Offline machines take the whole timeout to answer, so check them all at once
+	let machines = join_all(addresses.into_iter().map(async |(name, address)| {
+		let online = match &address {
+			Some(address) => Some(machine_probe.is_online(address).await),
+			None => None,
+		};
+		(name, online)
+	}))
+	.await
+	.into_iter()
+	.collect();
+
+	DashboardSnapshot { servers, machines }
+}
+
+/// Registers the dashboard message for a channel of `guild`, returning the previous dashboard message if there was one.
+pub async fn set_dashboard(
+	data: &BotData,
+	channel: ChannelId,
+	guild: Option<GuildId>,
+	message: MessageId,
+) -> Option<MessageId> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+
+	info!("Set Servitor dashboard for channel {channel} to message {message}");
+
+	data_write
+		.servitor_dashboards
+		.insert(channel, Dashboard { message, guild })
+		.map(|previous| previous.message)
+}
+
+pub async fn remove_dashboard(data: &BotData, channel: ChannelId) -> Result<MessageId, RemoveDashboardError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+
+	let message = data_write
+		.servitor_dashboards
+		.remove(&channel)
+		.ok_or(RemoveDashboardError::DoesNotExist { channel })?
+		.message;
+
+	info!("Removed Servitor dashboard from channel {channel}");
+
+	Ok(message)
+}
+
+pub async fn list_dashboards(data: &BotData) -> ServitorDashboards {
+	data.read().await.servitor_dashboards.clone()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::data::tests::mock_data;
+	use crate::services::servitor::tests::{controllers_from_bot_data, MockServitorController};
+	use crate::services::servitor::ServitorError;
+	use serde_json::json;
+	use std::collections::BTreeSet;
+
+	struct MockMachineProbe {
+		online: BTreeSet<&'static str>,
+	}
+
+	impl MachineProbe for MockMachineProbe {
+		async fn is_online(&self, address: &str) -> bool {
+			self.online.contains(address)
+		}
+	}
+
+	fn probe(online: &[&'static str]) -> MockMachineProbe {
+		MockMachineProbe {
+			online: online.iter().copied().collect(),
+		}
+	}
+
+	#[tokio::test]
+	async fn given_servers_and_machines_then_snapshot_collects_statuses_and_machine_states() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar"
+				},
+				"OrphanServer": {
+					"servitor": "removed",
+					"unit_name": "baz"
+				}
+			},
+			"wake_on_lan": {
+				"OnlineMachine": { "mac": [1, 2, 3, 4, 5, 6], "address": "online:22" },
+				"OfflineMachine": { "mac": [1, 2, 3, 4, 5, 7], "address": "offline:22" },
+				"UncheckedMachine": { "mac": [1, 2, 3, 4, 5, 8] }
+			}
+		})));
+		let mut serv = controllers_from_bot_data(&data).await;
+		serv.remove("removed");
+
+		let result = snapshot(&data, None, &serv, &probe(&["online:22"])).await;
+
+		assert_eq!(
+			result,
+			DashboardSnapshot {
+				servers: BTreeMap::from([
+					("OrphanServer".to_string(), None),
+					(
+						"SomeServer".to_string(),
+						Some(MockServitorController::default_status("bar"))
+					),
+				]),
+				machines: BTreeMap::from([
+					("OfflineMachine".to_string(), Some(false)),
+					("OnlineMachine".to_string(), Some(true)),
+					("UncheckedMachine".to_string(), None),
+				]),
+			}
+		);
+		serv["foo"].assert_called_times(0, 0, 0, 0, 1);
+	}
+
+	#[tokio::test]
+	async fn given_status_error_then_snapshot_marks_server_as_unavailable() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar"
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		serv["foo"].set_error(ServitorError::InternalServerError).await;
+
+		let result = snapshot(&data, None, &serv, &probe(&[])).await;
+
+		assert_eq!(result.servers, BTreeMap::from([("SomeServer".to_string(), None)]));
+	}
+
+	#[tokio::test]
+	async fn given_resources_of_two_guilds_then_snapshot_only_collects_those_visible_in_the_guild() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"GlobalServer": { "servitor": "foo", "unit_name": "global" },
+				"GuildServer": { "servitor": "foo", "unit_name": "guild", "scope": { "guild": "1" } },
+				"OtherGuildServer": { "servitor": "foo", "unit_name": "other", "scope": { "guild": "2" } }
+			},
+			"wake_on_lan": {
+				"GuildMachine": { "mac": [1, 2, 3, 4, 5, 6], "scope": { "guild": "1" } },
+				"OtherGuildMachine": { "mac": [1, 2, 3, 4, 5, 7], "scope": { "guild": "2" } }
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+
+		let in_guild = snapshot(&data, Some(GuildId::new(1)), &serv, &probe(&[])).await;
+		let in_dms = snapshot(&data, None, &serv, &probe(&[])).await;
+
+		assert_eq!(
+			in_guild.servers.keys().collect::<Vec<_>>(),
+			vec!["GlobalServer", "GuildServer"]
+		);
+		assert_eq!(in_guild.machines, BTreeMap::from([("GuildMachine".to_string(), None)]));
+		assert_eq!(in_dms.servers.keys().collect::<Vec<_>>(), vec!["GlobalServer"]);
+		assert!(in_dms.machines.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_no_dashboard_then_set_dashboard_stores_it_and_returns_none() {
+		let data = mock_data(None);
+
+		let previous = set_dashboard(&data, ChannelId::new(1), Some(GuildId::new(5)), MessageId::new(2)).await;
+
+		assert_eq!(previous, None);
+		assert_eq!(
+			data.read().await.servitor_dashboards,
+			BTreeMap::from([(
+				ChannelId::new(1),
+				Dashboard {
+					message: MessageId::new(2),
+					guild: Some(GuildId::new(5)),
+				}
+			)])
+		);
+	}
+
+	#[tokio::test]
+	async fn given_existing_dashboard_then_set_dashboard_replaces_it_and_returns_previous() {
+		let data = mock_data(Some(json!({
+			"servitor_dashboards": {
+				"1": { "message": "2", "guild": "5" }
+			}
+		})));
+
+		let previous = set_dashboard(&data, ChannelId::new(1), Some(GuildId::new(5)), MessageId::new(3)).await;
+
+		assert_eq!(previous, Some(MessageId::new(2)));
+		assert_eq!(
+			data.read().await.servitor_dashboards,
+			BTreeMap::from([(
+				ChannelId::new(1),
+				Dashboard {
+					message: MessageId::new(3),
+					guild: Some(GuildId::new(5)),
+				}
+			)])
+		);
+	}
+
+	#[tokio::test]
+	async fn given_no_dashboard_then_remove_dashboard_returns_error() {
+		let data = mock_data(None);
+
+		let result = remove_dashboard(&data, ChannelId::new(1)).await;
+
+		assert_eq!(
+			result,
+			Err(RemoveDashboardError::DoesNotExist {
+				channel: ChannelId::new(1)
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_existing_dashboard_then_remove_dashboard_removes_it() {
+		let data = mock_data(Some(json!({
+			"servitor_dashboards": {
+				"1": { "message": "2" },
+				"3": { "message": "4" }
+			}
+		})));
+
+		let result = remove_dashboard(&data, ChannelId::new(1)).await;
+
+		assert_eq!(result, Ok(MessageId::new(2)));
+		assert_eq!(
+			list_dashboards(&data).await,
+			BTreeMap::from([(
+				ChannelId::new(3),
+				Dashboard {
+					message: MessageId::new(4),
+					guild: None,
+				}
+			)])
+		);
+	}
+}
@@ -0,0 +1,273 @@
+use crate::data::BotData;
+use crate::services::servitor::ServitorController;
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use std::collections::BTreeMap;
+use std::ops::AsyncFnOnce;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstanceHealth {
+	pub healthy: bool,
+	pub latency: Duration,
+	pub checked_at: DateTime<Utc>,
+	pub since: DateTime<Utc>,
+}
+
+pub type ServitorHealth = Arc<RwLock<BTreeMap<String, InstanceHealth>>>;
+
+#[derive(Debug, PartialEq)]
+pub struct HealthTransition {
+	pub servitor_name: String,
+	pub healthy: bool,
+	pub previous_since: DateTime<Utc>,
+	pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InstanceSummary {
+	pub health: Option<InstanceHealth>,
+	pub server_count: usize,
+}
+
+pub async fn check_instances<S: ServitorController>(
+	health: &ServitorHealth,
+	servitor_handlers: &BTreeMap<String, S>,
+	now: DateTime<Utc>,
+) -> Vec<HealthTransition> {
+	let mut transitions = Vec::new();
+
+	for (name, handler) in servitor_handlers {
+		let start = Instant::now();
+		let healthy = handler.health().await;
+		let latency = start.elapsed();
+
+		let mut write = health.write().await;
+
+		let since = match write.get(name) {
+			Some(previous) if previous.healthy != healthy => {
+				if healthy {
+					info!("Servitor instance {name} is healthy again");
+				} else {
+					warn!("Servitor instance {name} is no longer healthy");
+				}
+
+				transitions.push(HealthTransition {
+					servitor_name: name.to_string(),
+					healthy,
+					previous_since: previous.since,
+					at: now,
+				});
+				now
+			}
+			Some(previous) => previous.since,
+			None => now,
+		};
+
+		write.insert(
+			name.to_string(),
+			InstanceHealth {
+				healthy,
+				latency,
+				checked_at: now,
+				since,
+			},
+		);
+	}
+
+	transitions
+}
+
+pub trait ListInstancesCallback<T> = AsyncFnOnce(&BTreeMap<String, InstanceSummary>) -> T;
+pub async fn list_instances<S: ServitorController, T, F: ListInstancesCallback<T>>(
+	data: &BotData,
+	health: &ServitorHealth,
+	servitor_handlers: &BTreeMap<String, S>,
+	func: F,
+) -> T {
+	let health_read = health.read().await;
+
+	let mut instances: BTreeMap<String, InstanceSummary> = servitor_handlers
+		.keys()
+		.map(|name| {
+			(
+				name.to_string(),
+				InstanceSummary {
+					health: health_read.get(name).cloned(),
+					server_count: 0,
+				},
+			)
+		})
+		.collect();
+
+	drop(health_read);
+
+	for server_info in data.read().await.servitor.values() {
+		if let Some(instance) = instances.get_mut(&server_info.servitor) {
+			instance.server_count += 1;
+		}
+	}
+
+	func.async_call_once((&instances,)).await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::data::tests::mock_data;
+	use crate::services::servitor::tests::MockServitorController;
+	use chrono::TimeZone;
+	use serde_json::json;
+
+	fn mock_handlers(names: &[&str]) -> BTreeMap<String, MockServitorController> {
+		names
+			.iter()
+			.map(|name| (name.to_string(), MockServitorController::new()))
+			.collect()
+	}
+
+	fn mock_health(entries: &[(&str, bool, DateTime<Utc>)]) -> ServitorHealth {
+		Arc::new(RwLock::new(
+			entries
+				.iter()
+				.map(|(name, healthy, since)| {
+					(
+						name.to_string(),
+						InstanceHealth {
+							healthy: *healthy,
+							latency: Duration::ZERO,
+							checked_at: *since,
+							since: *since,
+						},
+					)
+				})
+				.collect(),
+		))
+	}
+
+	#[tokio::test]
+	async fn given_no_previous_state_then_check_instances_records_state_without_transitions() {
+		let now = Utc.with_ymd_and_hms(2025, 3, 6, 20, 0, 0).unwrap();
+		let health = mock_health(&[]);
+		let serv = mock_handlers(&["foo", "bar"]);
+		serv["bar"].set_healthy(false);
+
+		let transitions = check_instances(&health, &serv, now).await;
+
+		assert_eq!(transitions, vec![]);
+
+		let read = health.read().await;
+		assert!(read["foo"].healthy);
+		assert!(!read["bar"].healthy);
+		assert_eq!(read["foo"].since, now);
+		assert_eq!(read["bar"].checked_at, now);
+	}
+
+	#[tokio::test]
+	async fn given_unchanged_state_then_check_instances_keeps_since_and_updates_checked_at() {
+		let before = Utc.with_ymd_and_hms(2025, 3, 6, 19, 0, 0).unwrap();
+		let now = Utc.with_ymd_and_hms(2025, 3, 6, 20, 0, 0).unwrap();
+		let health = mock_health(&[("foo", true, before)]);
+		let serv = mock_handlers(&["foo"]);
+
+		let transitions = check_instances(&health, &serv, now).await;
+
+		assert_eq!(transitions, vec![]);
+
+		let read = health.read().await;
+		assert_eq!(read["foo"].since, before);
+		assert_eq!(read["foo"].checked_at, now);
+	}
+
+	#[tokio::test]
+	async fn given_healthy_instance_going_down_then_check_instances_returns_down_transition() {
+		let before = Utc.with_ymd_and_hms(2025, 3, 6, 19, 0, 0).unwrap();
+		let now = Utc.with_ymd_and_hms(2025, 3, 6, 20, 0, 0).unwrap();
+		let health = mock_health(&[("foo", true, before), ("bar", true, before)]);
+		let serv = mock_handlers(&["foo", "bar"]);
+		serv["foo"].set_healthy(false);
+
+		let transitions = check_instances(&health, &serv, now).await;
+
+		assert_eq!(
+			transitions,
+			vec![HealthTransition {
+				servitor_name: "foo".to_string(),
+				healthy: false,
+				previous_since: before,
+				at: now,
+			}]
+		);
+		assert_eq!(health.read().await["foo"].since, now);
+	}
+
+	#[tokio::test]
+	async fn given_unhealthy_instance_recovering_then_check_instances_returns_up_transition() {
+		let before = Utc.with_ymd_and_hms(2025, 3, 6, 19, 0, 0).unwrap();
+		let now = Utc.with_ymd_and_hms(2025, 3, 6, 20, 0, 0).unwrap();
+		let health = mock_health(&[("foo", false, before)]);
+		let serv = mock_handlers(&["foo"]);
+
+		let transitions = check_instances(&health, &serv, now).await;
+
+		assert_eq!(
+			transitions,
+			vec![HealthTransition {
+				servitor_name: "foo".to_string(),
+				healthy: true,
+				previous_since: before,
+				at: now,
+			}]
+		);
+		assert!(health.read().await["foo"].healthy);
+	}
+
+	#[tokio::test]
+	async fn given_servers_and_health_then_list_instances_provides_correct_data_to_callback() {
+		let since = Utc.with_ymd_and_hms(2025, 3, 6, 19, 0, 0).unwrap();
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar"
+				},
+				"OtherServer": {
+					"servitor": "foo",
+					"unit_name": "baz"
+				}
+			}
+		})));
+		let health = mock_health(&[("foo", true, since)]);
+		let serv = mock_handlers(&["foo", "unused"]);
+
+		list_instances(&data, &health, &serv, async |instances| {
+			assert_eq!(
+				*instances,
+				BTreeMap::from([
+					(
+						"foo".to_string(),
+						InstanceSummary {
+							health: Some(InstanceHealth {
+								healthy: true,
+								latency: Duration::ZERO,
+								checked_at: since,
+								since,
+							}),
+							server_count: 2,
+						}
+					),
+					(
+						"unused".to_string(),
+						InstanceSummary {
+							health: None,
+							server_count: 0,
+						}
+					),
+				])
+			)
+		})
+		.await;
+	}
+}
@@ -0,0 +1,504 @@
+use super::super::is_user_authorized;
+use super::{get_server_info_mut, ServerError};
+use crate::data::servitor::{IdlePolicy, IdleProbe, QueryConfig, ServitorAction};
+use crate::data::BotData;
+use crate::services::probe::Prober;
+use crate::services::servitor::{ServitorController, ServitorError};
+use chrono::{DateTime, TimeDelta, Utc};
+use log::{info, warn};
+use serenity::all::{ChannelId, Member, User, UserId};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// When each server was first seen idle, kept in memory by the idle task.
+pub type IdleStates = BTreeMap<String, DateTime<Utc>>;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum IdlePolicyError {
+	#[error(transparent)]
+	Server(#[from] ServerError),
+
+	#[error("server {server_name} has no idle policy")]
+	NotConfigured { server_name: String },
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SetIdlePolicyError {
+	#[error(transparent)]
+	Server(#[from] ServerError),
+
+	#[error("server {server_name} has no game query to count players with")]
+	NoQuery { server_name: String },
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum KeepAliveError {
+	#[error(transparent)]
+	Server(#[from] ServerError),
+
+	#[error("server {server_name} has no idle policy")]
+	NotConfigured { server_name: String },
+
+	#[error("User {user} is not authorized to keep Servitor server {server_name} alive")]
+	Unauthorized { user: UserId, server_name: String },
+
+	#[error("Keeping server {server_name} alive for {duration} ends after the latest representable date")]
+	TooLong { server_name: String, duration: TimeDelta },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct IdleShutdown {
+	pub server_name: String,
+	pub channel: ChannelId,
+	pub idle_since: DateTime<Utc>,
+	pub result: Result<(), ServitorError>,
+}
+
+pub async fn set_idle_policy(
+	data: &BotData,
+	server_name: &str,
+	probe: IdleProbe,
+	minutes: u32,
+	channel: ChannelId,
+) -> Result<(), SetIdlePolicyError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+
+	let server_info = get_server_info_mut(&mut data_write, server_name).await?;
+	if probe == IdleProbe::Query && server_info.query.is_none() {
+		return Err(SetIdlePolicyError::NoQuery {
+			server_name: server_name.to_string(),
+		});
+	}
+	server_info.idle = Some(IdlePolicy {
+		probe,
+		minutes,
+		channel,
+		keep_alive_until: None,
+	});
+
+	info!("Set idle policy of Servitor server {server_name} to stop after {minutes} minutes");
+
+	Ok(())
+}
+
+pub async fn remove_idle_policy(data: &BotData, server_name: &str) -> Result<(), IdlePolicyError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+
+	let server_info = get_server_info_mut(&mut data_write, server_name).await?;
+	server_info.idle.take().ok_or(IdlePolicyError::NotConfigured {
+		server_name: server_name.to_string(),
+	})?;
+
+	info!("Removed idle policy of Servitor server {server_name}");
+
+	Ok(())
+}
+
+/// Suppresses idle shutdowns until `now + duration`, for anyone allowed to start the server.
+pub async fn keep_alive(
+	data: &BotData,
+	server_name: &str,
+	duration: TimeDelta,
+	author: &User,
+	member: Option<&Member>,
+	now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, KeepAliveError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let groups = data_write.access_groups.clone();
+
+	let server_info = get_server_info_mut(&mut data_write, server_name).await?;
+
+	if !is_user_authorized(author, member, &server_info.for_action(ServitorAction::Start), &groups) {
+		return Err(KeepAliveError::Unauthorized {
+			user: author.id,
+			server_name: server_name.to_string(),
+		});
+	}
+
+	let policy = server_info.idle.as_mut().ok_or(KeepAliveError::NotConfigured {
+		server_name: server_name.to_string(),
+	})?;
+	let until = now.checked_add_signed(duration).ok_or(KeepAliveError::TooLong {
+		server_name: server_name.to_string(),
+		duration,
+	})?;
+	policy.keep_alive_until = Some(until);
+
+	info!("User {} kept Servitor server {server_name} alive until {until}", author.id);
+
+	Ok(until)
+}
+
+/// Probes every server with an idle policy and stops those idle for longer than allowed.
+///
+/// Servers whose probe can't tell whether they are idle, like connections on the bot's host for a remote Servitor, are
+/// never considered idle.
+pub async fn check_idle<S: ServitorController, P: Prober>(
+	data: &BotData,
+	servitor_handlers: &BTreeMap<String, S>,
+	prober: &P,
+	idle_states: &mut IdleStates,
+	now: DateTime<Utc>,
+) -> Vec<IdleShutdown> {
+	let policies: Vec<(String, String, String, IdlePolicy, Option<QueryConfig>)> = data
+		.read()
+		.await
+		.servitor
+		.iter()
+		.filter_map(|(name, info)| {
+			let policy = info.idle.clone()?;
+			Some((name.clone(), info.servitor.clone(), info.unit_name.clone(), policy, info.query.clone()))
+		})
+		.collect();
+
+	idle_states.retain(|name, _| policies.iter().any(|(server_name, ..)| server_name == name));
+
+	let mut shutdowns = Vec::new();
+	for (server_name, servitor, unit_name, policy, query) in policies {
+		let Some(handler) = servitor_handlers.get(&servitor) else {
+			continue;
+		};
+
+		if policy.keep_alive_until.is_some_and(|until| until > now) {
+			idle_states.remove(&server_name);
+			continue;
+		}
+
+		if matches!(policy.probe, IdleProbe::Connections { .. }) && !handler.is_local() {
+			warn!("Connections on this host don't show whether server {server_name} of remote Servitor {servitor} is idle");
+			idle_states.remove(&server_name);
+			continue;
+		}
+
+		match handler.status(&unit_name).await {
+			Ok(status) if status.state == "active" => {}
+			Ok(_) => {
+				idle_states.remove(&server_name);
+				continue;
+			}
+			Err(e) => {
+				warn!("Failed to get status of server {server_name} for idle check: {e}");
+				continue;
+			}
+		}
+
+		match prober.activity(&policy.probe, query.as_ref()).await {
+			Ok(0) => {}
+			Ok(_) => {
+				idle_states.remove(&server_name);
+				continue;
+			}
+			Err(e) => {
+				warn!("Failed to probe activity of server {server_name}: {e}");
+				idle_states.remove(&server_name);
+				continue;
+			}
+		}
+
+		let idle_since = *idle_states.entry(server_name.clone()).or_insert(now);
+		if now - idle_since < TimeDelta::minutes(policy.minutes.into()) {
+			continue;
+		}
+
+		info!("Stopping Servitor server {server_name}, idle since {idle_since}");
+		idle_states.remove(&server_name);
+		shutdowns.push(IdleShutdown {
+			result: handler.stop(&unit_name).await,
+			server_name,
+			channel: policy.channel,
+			idle_since,
+		});
+	}
+
+	shutdowns
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::controllers::tests::mock_author_dms;
+	use crate::data::tests::mock_data;
+	use crate::services::probe::tests::MockProber;
+	use crate::services::probe::ProbeError;
+	use crate::services::query::QueryError;
+	use crate::services::servitor::tests::controllers_from_bot_data;
+	use chrono::TimeZone;
+	use serde_json::json;
+
+	fn now() -> DateTime<Utc> {
+		Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap()
+	}
+
+	fn idle_data(keep_alive_until: Option<&str>) -> BotData {
+		mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": { "12345678901234567": ["start"] },
+					"query": { "kind": "minecraft", "address": "mc.example.com" },
+					"idle": {
+						"probe": { "kind": "query" },
+						"minutes": 30,
+						"channel": "1",
+						"keep_alive_until": keep_alive_until
+					}
+				}
+			}
+		})))
+	}
+
+	#[tokio::test]
+	async fn given_server_idle_for_less_than_limit_then_check_idle_only_tracks_it() {
+		let data = idle_data(None);
+		let serv = controllers_from_bot_data(&data).await;
+		let mut states = IdleStates::new();
+
+		let shutdowns = check_idle(&data, &serv, &MockProber::new(0), &mut states, now()).await;
+
+		assert_eq!(shutdowns, vec![]);
+		assert_eq!(states, BTreeMap::from([("SomeServer".to_string(), now())]));
+		serv["foo"].assert_called_times(0, 0, 0, 0, 1);
+	}
+
+	#[tokio::test]
+	async fn given_server_idle_past_limit_then_check_idle_stops_it() {
+		let data = idle_data(None);
+		let serv = controllers_from_bot_data(&data).await;
+		let idle_since = now() - TimeDelta::minutes(30);
+		let mut states = IdleStates::from([("SomeServer".to_string(), idle_since)]);
+
+		let shutdowns = check_idle(&data, &serv, &MockProber::new(0), &mut states, now()).await;
+
+		assert_eq!(
+			shutdowns,
+			vec![IdleShutdown {
+				server_name: "SomeServer".to_string(),
+				channel: ChannelId::new(1),
+				idle_since,
+				result: Ok(()),
+			}]
+		);
+		assert!(states.is_empty());
+		serv["foo"].assert_called_times(0, 1, 0, 0, 1);
+	}
+
+	#[tokio::test]
+	async fn given_activity_then_check_idle_resets_idle_time() {
+		let data = idle_data(None);
+		let serv = controllers_from_bot_data(&data).await;
+		let mut states = IdleStates::from([("SomeServer".to_string(), now() - TimeDelta::hours(2))]);
+
+		let shutdowns = check_idle(&data, &serv, &MockProber::new(3), &mut states, now()).await;
+
+		assert_eq!(shutdowns, vec![]);
+		assert!(states.is_empty());
+		serv["foo"].assert_called_times(0, 0, 0, 0, 1);
+	}
+
+	#[tokio::test]
+	async fn given_probe_error_then_check_idle_resets_idle_time() {
+		let data = idle_data(None);
+		let serv = controllers_from_bot_data(&data).await;
+		let prober = MockProber::new(0);
+		prober.set_activity(Err(ProbeError::Query(QueryError::Timeout)));
+		let mut states = IdleStates::from([("SomeServer".to_string(), now() - TimeDelta::hours(2))]);
+
+		let shutdowns = check_idle(&data, &serv, &prober, &mut states, now()).await;
+
+		assert_eq!(shutdowns, vec![]);
+		assert!(states.is_empty());
+		serv["foo"].assert_called_times(0, 0, 0, 0, 1);
+	}
+
+	fn connections_data() -> BotData {
+		mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"idle": {
+						"probe": { "kind": "connections", "port": 25565 },
+						"minutes": 30,
+						"channel": "1"
+					}
+				}
+			}
+		})))
+	}
+
+	#[tokio::test]
+	async fn given_connections_probe_of_remote_servitor_then_check_idle_never_stops_it() {
+		let data = connections_data();
+		let serv = controllers_from_bot_data(&data).await;
+		let mut states = IdleStates::from([("SomeServer".to_string(), now() - TimeDelta::hours(2))]);
+
+		let shutdowns = check_idle(&data, &serv, &MockProber::new(0), &mut states, now()).await;
+
+		assert_eq!(shutdowns, vec![]);
+		assert!(states.is_empty());
+		serv["foo"].assert_not_called();
+	}
+
+	#[tokio::test]
+	async fn given_connections_probe_of_local_servitor_then_check_idle_stops_it() {
+		let data = connections_data();
+		let serv = controllers_from_bot_data(&data).await;
+		serv["foo"].set_local(true);
+		let mut states = IdleStates::from([("SomeServer".to_string(), now() - TimeDelta::hours(2))]);
+
+		let shutdowns = check_idle(&data, &serv, &MockProber::new(0), &mut states, now()).await;
+
+		assert_eq!(shutdowns.len(), 1);
+		serv["foo"].assert_called_times(0, 1, 0, 0, 1);
+	}
+
+	#[tokio::test]
+	async fn given_keep_alive_then_check_idle_does_not_stop() {
+		let data = idle_data(Some("2025-03-06T21:00:00Z"));
+		let serv = controllers_from_bot_data(&data).await;
+		let mut states = IdleStates::from([("SomeServer".to_string(), now() - TimeDelta::hours(2))]);
+
+		let shutdowns = check_idle(&data, &serv, &MockProber::new(0), &mut states, now()).await;
+
+		assert_eq!(shutdowns, vec![]);
+		assert!(states.is_empty());
+		serv["foo"].assert_not_called();
+	}
+
+	#[tokio::test]
+	async fn given_authorized_user_then_keep_alive_stores_deadline() {
+		let data = idle_data(None);
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let result = keep_alive(&data, "SomeServer", TimeDelta::hours(2), &author, member.as_ref(), now()).await;
+
+		let until = now() + TimeDelta::hours(2);
+		assert_eq!(result, Ok(until));
+		assert_eq!(
+			data.read().await.servitor["SomeServer"].idle.as_ref().unwrap().keep_alive_until,
+			Some(until)
+		);
+	}
+
+	#[tokio::test]
+	async fn given_duration_past_latest_date_then_keep_alive_returns_error_and_keeps_deadline() {
+		let data = idle_data(None);
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let result =
+			keep_alive(&data, "SomeServer", TimeDelta::days(100_000_000), &author, member.as_ref(), now()).await;
+
+		assert_eq!(
+			result,
+			Err(KeepAliveError::TooLong {
+				server_name: "SomeServer".to_string(),
+				duration: TimeDelta::days(100_000_000),
+			})
+		);
+		assert_eq!(data.read().await.servitor["SomeServer"].idle.as_ref().unwrap().keep_alive_until, None);
+	}
+
+	#[tokio::test]
+	async fn given_unauthorized_user_then_keep_alive_returns_error() {
+		let data = idle_data(None);
+		let (author, member) = mock_author_dms(UserId::new(11111111111111111));
+
+		let result = keep_alive(&data, "SomeServer", TimeDelta::hours(2), &author, member.as_ref(), now()).await;
+
+		assert_eq!(
+			result,
+			Err(KeepAliveError::Unauthorized {
+				user: UserId::new(11111111111111111),
+				server_name: "SomeServer".to_string(),
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_no_policy_then_keep_alive_and_remove_return_not_configured() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": [12345678901234567u64]
+				}
+			}
+		})));
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let keep_alive = keep_alive(&data, "SomeServer", TimeDelta::hours(2), &author, member.as_ref(), now()).await;
+		let remove = remove_idle_policy(&data, "SomeServer").await;
+
+		assert_eq!(
+			keep_alive,
+			Err(KeepAliveError::NotConfigured {
+				server_name: "SomeServer".to_string()
+			})
+		);
+		assert_eq!(
+			remove,
+			Err(IdlePolicyError::NotConfigured {
+				server_name: "SomeServer".to_string()
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_server_then_set_idle_policy_stores_it() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar"
+				}
+			}
+		})));
+
+		let result = set_idle_policy(
+			&data,
+			"SomeServer",
+			IdleProbe::Connections { port: 27015 },
+			45,
+			ChannelId::new(1),
+		)
+		.await;
+
+		assert_eq!(result, Ok(()));
+		assert_eq!(
+			data.read().await.servitor["SomeServer"].idle,
+			Some(IdlePolicy {
+				probe: IdleProbe::Connections { port: 27015 },
+				minutes: 45,
+				channel: ChannelId::new(1),
+				keep_alive_until: None,
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_server_without_query_then_set_query_probe_returns_error() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar"
+				}
+			}
+		})));
+
+		let result = set_idle_policy(&data, "SomeServer", IdleProbe::Query, 45, ChannelId::new(1)).await;
+
+		assert_eq!(
+			result,
+			Err(SetIdlePolicyError::NoQuery {
+				server_name: "SomeServer".to_string()
+			})
+		);
+		assert_eq!(data.read().await.servitor["SomeServer"].idle, None);
+	}
+}
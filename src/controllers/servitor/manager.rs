@@ -0,0 +1,142 @@
+use super::{get_server_info_mut, ServerError};
+use crate::data::BotData;
+use log::info;
+use serenity::all::UserId;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AddManagerError {
+	#[error(transparent)]
+	Server(#[from] ServerError),
+
+	#[error("user {user} already manages server {server_name}")]
+	AlreadyManager { server_name: String, user: UserId },
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum RemoveManagerError {
+	#[error(transparent)]
+	Server(#[from] ServerError),
+
+	#[error("user {user} does not manage server {server_name}")]
+	NotManager { server_name: String, user: UserId },
+}
+
+pub async fn add_manager(data: &BotData, server_name: &str, user: UserId) -> Result<(), AddManagerError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let server_info = get_server_info_mut(&mut data_write, server_name).await?;
+
+	if server_info.managers.insert(user) {
+		info!("Made user {user} a manager of Servitor server {server_name}");
+		Ok(())
+	} else {
+		Err(AddManagerError::AlreadyManager {
+			server_name: server_name.into(),
+			user,
+		})
+	}
+}
+
+pub async fn remove_manager(data: &BotData, server_name: &str, user: UserId) -> Result<(), RemoveManagerError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let server_info = get_server_info_mut(&mut data_write, server_name).await?;
+
+	if server_info.managers.remove(&user) {
+		info!("Removed user {user} from the managers of Servitor server {server_name}");
+		Ok(())
+	} else {
+		Err(RemoveManagerError::NotManager {
+			server_name: server_name.into(),
+			user,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::data::tests::mock_data;
+	use serde_json::json;
+	use std::collections::BTreeSet;
+
+	fn manager_data() -> BotData {
+		mock_data(Some(json!({
+			"servitor": {
+				"ExistingServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"managers": [12345678901234567i64]
+				}
+			}
+		})))
+	}
+
+	#[tokio::test]
+	async fn given_nonexistent_server_then_add_manager_returns_error() {
+		let data = manager_data();
+
+		let result = add_manager(&data, "NonexistentServer", UserId::new(1)).await;
+
+		assert_eq!(
+			result,
+			Err(AddManagerError::Server(ServerError::DoesNotExist {
+				server_name: "NonexistentServer".into()
+			}))
+		);
+	}
+
+	#[tokio::test]
+	async fn given_new_manager_then_add_manager_stores_it() {
+		let data = manager_data();
+
+		let result = add_manager(&data, "ExistingServer", UserId::new(1)).await;
+
+		assert_eq!(result, Ok(()));
+		assert_eq!(
+			data.read().await.servitor["ExistingServer"].managers,
+			BTreeSet::from([UserId::new(1), UserId::new(12345678901234567)])
+		);
+	}
+
+	#[tokio::test]
+	async fn given_existing_manager_then_add_manager_returns_error() {
+		let data = manager_data();
+
+		let result = add_manager(&data, "ExistingServer", UserId::new(12345678901234567)).await;
+
+		assert_eq!(
+			result,
+			Err(AddManagerError::AlreadyManager {
+				server_name: "ExistingServer".into(),
+				user: UserId::new(12345678901234567)
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_existing_manager_then_remove_manager_removes_it() {
+		let data = manager_data();
+
+		let result = remove_manager(&data, "ExistingServer", UserId::new(12345678901234567)).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.servitor["ExistingServer"].managers.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_non_manager_then_remove_manager_returns_error() {
+		let data = manager_data();
+
+		let result = remove_manager(&data, "ExistingServer", UserId::new(1)).await;
+
+		assert_eq!(
+			result,
+			Err(RemoveManagerError::NotManager {
+				server_name: "ExistingServer".into(),
+				user: UserId::new(1)
+			})
+		);
+	}
+}
@@ -0,0 +1,155 @@
+use super::{get_server_info_mut, ServerError};
+use crate::data::servitor::QueryConfig;
+use crate::data::BotData;
+use crate::services::query::{GameInfo, GameQuerier, QueryError};
+use log::info;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, poise::ChoiceParameter)]
+pub enum QueryProtocol {
+	#[name = "a2s"]
+	A2s,
+	#[name = "minecraft"]
+	Minecraft,
+}
+
+impl QueryProtocol {
+	pub fn config(self, address: String) -> QueryConfig {
+		match self {
+			QueryProtocol::A2s => QueryConfig::A2s { address },
+			QueryProtocol::Minecraft => QueryConfig::Minecraft { address },
+		}
+	}
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum QueryConfigError {
+	#[error(transparent)]
+	Server(#[from] ServerError),
+
+	#[error("server {server_name} has no game query")]
+	NotConfigured { server_name: String },
+}
+
+pub async fn set_query(data: &BotData, server_name: &str, query: QueryConfig) -> Result<(), ServerError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+
+	let server_info = get_server_info_mut(&mut data_write, server_name).await?;
+	server_info.query = Some(query);
+
+	info!("Set game query of Servitor server {server_name}");
+
+	Ok(())
+}
+
+pub async fn remove_query(data: &BotData, server_name: &str) -> Result<(), QueryConfigError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+
+	let server_info = get_server_info_mut(&mut data_write, server_name).await?;
+	server_info.query.take().ok_or(QueryConfigError::NotConfigured {
+		server_name: server_name.to_string(),
+	})?;
+
+	info!("Removed game query of Servitor server {server_name}");
+
+	Ok(())
+}
+
+/// Queries the game behind a server, `None` if it has no query configured.
+pub async fn query_game<Q: GameQuerier>(
+	data: &BotData,
+	server_name: &str,
+	querier: &Q,
+) -> Option<Result<GameInfo, QueryError>> {
+	let config = data.read().await.servitor.get(server_name)?.query.clone()?;
+
+	Some(querier.query(&config).await)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::data::tests::mock_data;
+	use crate::services::query::tests::MockGameQuery;
+	use serde_json::json;
+
+	fn query_data(query: Option<serde_json::Value>) -> BotData {
+		mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"query": query
+				}
+			}
+		})))
+	}
+
+	#[tokio::test]
+	async fn given_server_with_query_then_query_game_returns_info() {
+		let data = query_data(Some(json!({ "kind": "minecraft", "address": "localhost" })));
+		let querier = MockGameQuery::new(Ok(MockGameQuery::default_info()));
+
+		let result = query_game(&data, "SomeServer", &querier).await;
+
+		assert_eq!(result, Some(Ok(MockGameQuery::default_info())));
+	}
+
+	#[tokio::test]
+	async fn given_query_error_then_query_game_returns_it() {
+		let data = query_data(Some(json!({ "kind": "a2s", "address": "localhost:27015" })));
+		let querier = MockGameQuery::new(Err(QueryError::Timeout));
+
+		let result = query_game(&data, "SomeServer", &querier).await;
+
+		assert_eq!(result, Some(Err(QueryError::Timeout)));
+	}
+
+	#[tokio::test]
+	async fn given_server_without_query_then_query_game_returns_none() {
+		let data = query_data(None);
+		let querier = MockGameQuery::new(Ok(MockGameQuery::default_info()));
+
+		assert_eq!(query_game(&data, "SomeServer", &querier).await, None);
+		assert_eq!(query_game(&data, "OtherServer", &querier).await, None);
+	}
+
+	#[tokio::test]
+	async fn given_server_then_set_query_stores_it() {
+		let data = query_data(None);
+		let query = QueryConfig::A2s {
+			address: "localhost:27015".to_string(),
+		};
+
+		let result = set_query(&data, "SomeServer", query.clone()).await;
+
+		assert_eq!(result, Ok(()));
+		assert_eq!(data.read().await.servitor["SomeServer"].query, Some(query));
+	}
+
+	#[tokio::test]
+	async fn given_no_query_then_remove_query_returns_not_configured() {
+		let data = query_data(None);
+
+		let result = remove_query(&data, "SomeServer").await;
+
+		assert_eq!(
+			result,
+			Err(QueryConfigError::NotConfigured {
+				server_name: "SomeServer".to_string()
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_query_then_remove_query_clears_it() {
+		let data = query_data(Some(json!({ "kind": "minecraft", "address": "localhost" })));
+
+		let result = remove_query(&data, "SomeServer").await;
+
+		assert_eq!(result, Ok(()));
+		assert_eq!(data.read().await.servitor["SomeServer"].query, None);
+	}
+}
@@ -0,0 +1,565 @@
+use super::super::is_user_authorized;
+use super::action::{self, ExecuteServitorActionError};
+use super::{get_server_info, ServerError};
+use crate::data::servitor::{SchedulableAction, Schedule, ScheduledAction, ServitorAction, ServitorSchedules};
+use crate::data::{next_id, BotData};
+use crate::services::servitor::ServitorController;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use croner::Cron;
+use log::info;
+use serenity::all::{ChannelId, GuildId, Member, User, UserId};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+const DEFAULT_TIMEZONE: &str = "UTC";
+const DATE_FORMATS: [&str; 4] = ["%Y-%m-%d %H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%dT%H:%M:%S"];
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ScheduleError {
+	#[error(transparent)]
+	Server(#[from] ServerError),
+
+	#[error("User {user} is not authorized to run {action} on Servitor server {server_name}")]
+	Unauthorized {
+		user: UserId,
+		server_name: String,
+		action: ServitorAction,
+	},
+
+	#[error("unknown timezone {timezone}")]
+	InvalidTimezone { timezone: String },
+
+	#[error("{when} is neither a date nor a cron expression")]
+	InvalidWhen { when: String },
+
+	#[error("{at} is in the past")]
+	InPast { at: DateTime<Utc> },
+
+	#[error("cron expression {expression} never fires")]
+	NeverFires { expression: String },
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CancelScheduleError {
+	#[error("scheduled action {id} does not exist")]
+	DoesNotExist { id: u64 },
+
+	#[error("User {user} is not allowed to cancel scheduled action {id}")]
+	Unauthorized { user: UserId, id: u64 },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ScheduleWhen {
+	pub schedule: Schedule,
+	pub next_run: DateTime<Utc>,
+}
+
+/// Reads `when` as a date in the timezone, or failing that as a five field cron expression.
+pub fn parse_when(when: &str, timezone: Option<&str>, now: DateTime<Utc>) -> Result<ScheduleWhen, ScheduleError> {
+	let when = when.trim();
+	let timezone = timezone.unwrap_or(DEFAULT_TIMEZONE).trim();
+	let tz: Tz = timezone.parse().map_err(|_| ScheduleError::InvalidTimezone {
+		timezone: timezone.to_string(),
+	})?;
+
+	if let Some(at) = parse_date(when, tz) {
+		if at <= now {
+			return Err(ScheduleError::InPast { at });
+		}
+		return Ok(ScheduleWhen {
+			schedule: Schedule::Once,
+			next_run: at,
+		});
+	}
+
+	Cron::new(when).parse().map_err(|_| ScheduleError::InvalidWhen {
+		when: when.to_string(),
+	})?;
+
+	let schedule = Schedule::Cron {
+		expression: when.to_string(),
+		timezone: tz.name().to_string(),
+	};
+	let next_run = next_run_after(&schedule, now).ok_or(ScheduleError::NeverFires {
+		expression: when.to_string(),
+	})?;
+
+	Ok(ScheduleWhen { schedule, next_run })
+}
+
+fn parse_date(when: &str, tz: Tz) -> Option<DateTime<Utc>> {
+	if let Ok(at) = DateTime::parse_from_rfc3339(when) {
+		return Some(at.with_timezone(&Utc));
+	}
+
+	DATE_FORMATS
+		.iter()
+		.find_map(|format| NaiveDateTime::parse_from_str(when, format).ok())
+		.and_then(|naive| tz.from_local_datetime(&naive).earliest())
+		.map(|at| at.with_timezone(&Utc))
+}
+
+/// The next time a recurring schedule fires after `after`, one-off schedules never fire again.
+fn next_run_after(schedule: &Schedule, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+	match schedule {
+		Schedule::Once => None,
+		Schedule::Cron { expression, timezone } => {
+			let tz: Tz = timezone.parse().ok()?;
+			Cron::new(expression)
+				.parse()
+				.ok()?
+				.find_next_occurrence(&after.with_timezone(&tz), false)
+				.ok()
+				.map(|at| at.with_timezone(&Utc))
+		}
+	}
+}
+
+pub async fn schedule(
+	data: &BotData,
+	server_name: &str,
+	action: SchedulableAction,
+	when: ScheduleWhen,
+	author: &User,
+	member: Option<&Member>,
+	channel: ChannelId,
+) -> Result<(u64, ScheduledAction), ScheduleError> {
+	let servitor_action = ServitorAction::from(action);
+	{
+		let read = data.read().await;
+		let server_info = get_server_info(&read, server_name).await?;
+
+		if !is_user_authorized(author, member, &server_info.for_action(servitor_action), &read.access_groups) {
+			return Err(ScheduleError::Unauthorized {
+				user: author.id,
+				server_name: server_name.to_string(),
+				action: servitor_action,
+			});
+		}
+	}
+
+	let scheduled = ScheduledAction {
+		server_name: server_name.to_string(),
+		action,
+		user: author.id,
+		guild: member.map(|m| m.guild_id),
+		channel,
+		schedule: when.schedule,
+		next_run: when.next_run,
+	};
+
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let data_write = &mut *data_write;
+
+	let id = next_id(&mut data_write.last_servitor_schedule, &data_write.servitor_schedules);
+	data_write.servitor_schedules.insert(id, scheduled.clone());
+
+	info!(
+		"User {} scheduled {servitor_action} for Servitor server {server_name} as #{id}, next run at {}",
+		author.id, scheduled.next_run
+	);
+
+	Ok((id, scheduled))
+}
+
+/// Lists the actions scheduled from `guild`, or from DMs when `None`, on servers still visible there.
+pub async fn list_schedules(data: &BotData, guild: Option<GuildId>) -> ServitorSchedules {
+	let read = data.read().await;
+
+	read.servitor_schedules
+		.iter()
+		.filter(|(_, scheduled)| {
+			scheduled.guild == guild
+				&& read.servitor.get(&scheduled.server_name).is_some_and(|info| info.scope.is_visible_in(guild))
+		})
+		.map(|(id, scheduled)| (*id, scheduled.clone()))
+		.collect()
+}
+
+/// Only whoever scheduled the action or someone allowed to run it on the server can cancel it.
+pub async fn cancel_schedule(
+	data: &BotData,
+	id: u64,
+	author: &User,
+	member: Option<&Member>,
+) -> Result<ScheduledAction, CancelScheduleError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+
+	let scheduled = data_write
+		.servitor_schedules
+		.get(&id)
+		.ok_or(CancelScheduleError::DoesNotExist { id })?;
+
+	let allowed = scheduled.user == author.id
+		|| data_write.servitor.get(&scheduled.server_name).is_some_and(|info| {
+			is_user_authorized(author, member, &info.for_action(scheduled.action.into()), &data_write.access_groups)
+		});
+
+	if !allowed {
+		return Err(CancelScheduleError::Unauthorized { user: author.id, id });
+	}
+
+	info!("User {} cancelled scheduled action #{id}", author.id);
+
+	Ok(data_write.servitor_schedules.remove(&id).expect("scheduled action was just found"))
+}
+
+/// Returns the actions due at `now`, moving recurring ones to their next run and dropping the rest.
+pub async fn take_due(data: &BotData, now: DateTime<Utc>) -> BTreeMap<u64, ScheduledAction> {
+	if !data.read().await.servitor_schedules.values().any(|s| s.next_run <= now) {
+		return BTreeMap::new();
+	}
+
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+
+	let due: BTreeMap<u64, ScheduledAction> = data_write
+		.servitor_schedules
+		.iter()
+		.filter(|(_, scheduled)| scheduled.next_run <= now)
+		.map(|(id, scheduled)| (*id, scheduled.clone()))
+		.collect();
+
+	for (id, scheduled) in &due {
+		match next_run_after(&scheduled.schedule, now) {
+			Some(next_run) => {
+				data_write.servitor_schedules.get_mut(id).unwrap().next_run = next_run;
+			}
+			None => {
+				data_write.servitor_schedules.remove(id);
+			}
+		}
+	}
+
+	due
+}
+
+/// Runs through the regular action controllers, so the scheduling user's authorization is checked again.
+pub async fn run_scheduled<S: ServitorController>(
+	data: &BotData,
+	servitor_handlers: &BTreeMap<String, S>,
+	scheduled: &ScheduledAction,
+	author: &User,
+	member: Option<&Member>,
+) -> Result<(), ExecuteServitorActionError> {
+	let server_name = &scheduled.server_name;
+
+	match scheduled.action {
+		SchedulableAction::Start => action::start(data, servitor_handlers, server_name, author, member).await,
+		SchedulableAction::Stop => action::stop(data, servitor_handlers, server_name, author, member).await,
+		SchedulableAction::Restart => action::restart(data, servitor_handlers, server_name, author, member).await,
+		SchedulableAction::Reload => action::reload(data, servitor_handlers, server_name, author, member).await,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::controllers::tests::{mock_author_dms, mock_author_guild};
+	use crate::data::tests::mock_data;
+	use crate::services::servitor::tests::controllers_from_bot_data;
+	use rstest::rstest;
+	use serde_json::json;
+	use serenity::all::RoleId;
+
+	fn now() -> DateTime<Utc> {
+		Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap()
+	}
+
+	fn scheduled_json(server_name: &str, schedule: serde_json::Value, next_run: &str) -> serde_json::Value {
+		json!({
+			"server_name": server_name,
+			"action": "restart",
+			"user": "12345678901234567",
+			"channel": "1",
+			"schedule": schedule,
+			"next_run": next_run
+		})
+	}
+
+	#[rstest]
+	#[case("2025-03-07 04:00", None, Utc.with_ymd_and_hms(2025, 3, 7, 4, 0, 0).unwrap())]
+	#[case("2025-03-07T04:00:30", Some("Europe/Berlin"), Utc.with_ymd_and_hms(2025, 3, 7, 3, 0, 30).unwrap())]
+	#[case("2025-03-07T04:00:00-03:00", Some("Europe/Berlin"), Utc.with_ymd_and_hms(2025, 3, 7, 7, 0, 0).unwrap())]
+	fn given_date_then_parse_when_returns_one_off_schedule(
+		#[case] when: &str,
+		#[case] timezone: Option<&str>,
+		#[case] expected: DateTime<Utc>,
+	) {
+		assert_eq!(
+			parse_when(when, timezone, now()),
+			Ok(ScheduleWhen {
+				schedule: Schedule::Once,
+				next_run: expected,
+			})
+		);
+	}
+
+	#[test]
+	fn given_cron_expression_then_parse_when_returns_next_run_in_timezone() {
+		let result = parse_when("0 4 * * *", Some("America/Sao_Paulo"), now());
+
+		assert_eq!(
+			result,
+			Ok(ScheduleWhen {
+				schedule: Schedule::Cron {
+					expression: "0 4 * * *".to_string(),
+					timezone: "America/Sao_Paulo".to_string(),
+				},
+				next_run: Utc.with_ymd_and_hms(2025, 3, 7, 7, 0, 0).unwrap(),
+			})
+		);
+	}
+
+	#[rstest]
+	#[case("2025-03-06 19:00", None, ScheduleError::InPast { at: Utc.with_ymd_and_hms(2025, 3, 6, 19, 0, 0).unwrap() })]
+	#[case("tomorrow", None, ScheduleError::InvalidWhen { when: "tomorrow".to_string() })]
+	#[case("0 4 * * *", Some("Mars/Olympus"), ScheduleError::InvalidTimezone { timezone: "Mars/Olympus".to_string() })]
+	fn given_invalid_input_then_parse_when_returns_error(
+		#[case] when: &str,
+		#[case] timezone: Option<&str>,
+		#[case] expected: ScheduleError,
+	) {
+		assert_eq!(parse_when(when, timezone, now()), Err(expected));
+	}
+
+	#[tokio::test]
+	async fn given_authorized_user_then_schedule_stores_action_with_next_id() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_roles": { "98765432109876543": ["restart"] }
+				}
+			},
+			"servitor_schedules": {
+				"4": scheduled_json("SomeServer", json!({ "kind": "once" }), "2025-03-07T04:00:00Z")
+			}
+		})));
+		let (author, member) =
+			mock_author_guild(UserId::new(12345678901234567), vec![RoleId::new(98765432109876543)]);
+		let when = parse_when("0 4 * * *", None, now()).unwrap();
+
+		let result = schedule(
+			&data,
+			"SomeServer",
+			SchedulableAction::Restart,
+			when,
+			&author,
+			member.as_ref(),
+			ChannelId::new(1),
+		)
+		.await;
+
+		let expected = ScheduledAction {
+			server_name: "SomeServer".to_string(),
+			action: SchedulableAction::Restart,
+			user: UserId::new(12345678901234567),
+			guild: member.map(|m| m.guild_id),
+			channel: ChannelId::new(1),
+			schedule: Schedule::Cron {
+				expression: "0 4 * * *".to_string(),
+				timezone: "UTC".to_string(),
+			},
+			next_run: Utc.with_ymd_and_hms(2025, 3, 7, 4, 0, 0).unwrap(),
+		};
+		assert_eq!(result, Ok((5, expected.clone())));
+		assert_eq!(data.read().await.servitor_schedules.get(&5), Some(&expected));
+	}
+
+	#[tokio::test]
+	async fn given_newest_schedule_cancelled_then_schedule_does_not_reuse_its_id() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": { "12345678901234567": ["restart"] }
+				}
+			},
+			"servitor_schedules": {
+				"4": scheduled_json("SomeServer", json!({ "kind": "once" }), "2025-03-07T04:00:00Z"),
+				"5": scheduled_json("SomeServer", json!({ "kind": "once" }), "2025-03-07T05:00:00Z")
+			},
+			"last_servitor_schedule": 5
+		})));
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+		let when = || parse_when("2025-03-07 06:00", None, now()).unwrap();
+
+		cancel_schedule(&data, 5, &author, member.as_ref()).await.unwrap();
+		let (id, _) = schedule(&data, "SomeServer", SchedulableAction::Restart, when(), &author, member.as_ref(), ChannelId::new(1))
+			.await
+			.unwrap();
+
+		assert_eq!(id, 6);
+		assert_eq!(
+			cancel_schedule(&data, 5, &author, member.as_ref()).await,
+			Err(CancelScheduleError::DoesNotExist { id: 5 })
+		);
+		assert!(data.read().await.servitor_schedules.contains_key(&6));
+	}
+
+	#[tokio::test]
+	async fn given_unauthorized_user_then_schedule_returns_error_and_does_not_store() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": { "12345678901234567": ["status"] }
+				}
+			}
+		})));
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+		let when = parse_when("0 4 * * *", None, now()).unwrap();
+
+		let result = schedule(
+			&data,
+			"SomeServer",
+			SchedulableAction::Stop,
+			when,
+			&author,
+			member.as_ref(),
+			ChannelId::new(1),
+		)
+		.await;
+
+		assert_eq!(
+			result,
+			Err(ScheduleError::Unauthorized {
+				user: UserId::new(12345678901234567),
+				server_name: "SomeServer".to_string(),
+				action: ServitorAction::Stop,
+			})
+		);
+		assert!(data.read().await.servitor_schedules.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_due_actions_then_take_due_advances_recurring_and_drops_one_off() {
+		let cron = json!({ "kind": "cron", "expression": "0 4 * * *", "timezone": "UTC" });
+		let data = mock_data(Some(json!({
+			"servitor_schedules": {
+				"1": scheduled_json("SomeServer", json!({ "kind": "once" }), "2025-03-06T19:00:00Z"),
+				"2": scheduled_json("SomeServer", cron, "2025-03-06T04:00:00Z"),
+				"3": scheduled_json("SomeServer", json!({ "kind": "once" }), "2025-03-07T04:00:00Z")
+			}
+		})));
+
+		let due = take_due(&data, now()).await;
+
+		assert_eq!(due.keys().copied().collect::<Vec<_>>(), vec![1, 2]);
+		let schedules = data.read().await.servitor_schedules.clone();
+		assert_eq!(schedules.keys().copied().collect::<Vec<_>>(), vec![2, 3]);
+		assert_eq!(schedules[&2].next_run, Utc.with_ymd_and_hms(2025, 3, 7, 4, 0, 0).unwrap());
+	}
+
+	#[tokio::test]
+	async fn given_schedules_of_two_guilds_then_list_schedules_only_returns_those_of_the_guild() {
+		let mut in_guild = scheduled_json("GuildServer", json!({ "kind": "once" }), "2025-03-07T04:00:00Z");
+		in_guild["guild"] = json!("1");
+		let mut in_other_guild = scheduled_json("OtherGuildServer", json!({ "kind": "once" }), "2025-03-07T04:00:00Z");
+		in_other_guild["guild"] = json!("2");
+		let mut on_hidden_server = scheduled_json("OtherGuildServer", json!({ "kind": "once" }), "2025-03-07T04:00:00Z");
+		on_hidden_server["guild"] = json!("1");
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"GlobalServer": { "servitor": "foo", "unit_name": "global" },
+				"GuildServer": { "servitor": "foo", "unit_name": "guild", "scope": { "guild": "1" } },
+				"OtherGuildServer": { "servitor": "foo", "unit_name": "other", "scope": { "guild": "2" } }
+			},
+			"servitor_schedules": {
+				"1": in_guild,
+				"2": in_other_guild,
+				"3": on_hidden_server,
+				"4": scheduled_json("GlobalServer", json!({ "kind": "once" }), "2025-03-07T04:00:00Z")
+			}
+		})));
+
+		let guild = list_schedules(&data, Some(GuildId::new(1))).await;
+		let dms = list_schedules(&data, None).await;
+
+		assert_eq!(guild.keys().copied().collect::<Vec<_>>(), vec![1]);
+		assert_eq!(dms.keys().copied().collect::<Vec<_>>(), vec![4]);
+	}
+
+	#[tokio::test]
+	async fn given_author_is_not_scheduler_nor_authorized_then_cancel_returns_error() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar"
+				}
+			},
+			"servitor_schedules": {
+				"1": scheduled_json("SomeServer", json!({ "kind": "once" }), "2025-03-07T04:00:00Z")
+			}
+		})));
+		let (author, member) = mock_author_dms(UserId::new(11111111111111111));
+
+		let result = cancel_schedule(&data, 1, &author, member.as_ref()).await;
+
+		assert_eq!(
+			result,
+			Err(CancelScheduleError::Unauthorized {
+				user: UserId::new(11111111111111111),
+				id: 1,
+			})
+		);
+		assert_eq!(data.read().await.servitor_schedules.len(), 1);
+	}
+
+	#[tokio::test]
+	async fn given_scheduler_then_cancel_removes_action() {
+		let data = mock_data(Some(json!({
+			"servitor_schedules": {
+				"1": scheduled_json("SomeServer", json!({ "kind": "once" }), "2025-03-07T04:00:00Z")
+			}
+		})));
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let result = cancel_schedule(&data, 1, &author, member.as_ref()).await;
+
+		assert!(result.is_ok());
+		assert!(data.read().await.servitor_schedules.is_empty());
+		assert_eq!(
+			cancel_schedule(&data, 1, &author, member.as_ref()).await,
+			Err(CancelScheduleError::DoesNotExist { id: 1 })
+		);
+	}
+
+	#[tokio::test]
+	async fn given_revoked_grant_then_run_scheduled_returns_unauthorized() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": { "12345678901234567": ["start"] }
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		let scheduled: ScheduledAction =
+			serde_json::from_value(scheduled_json("SomeServer", json!({ "kind": "once" }), "2025-03-06T19:00:00Z"))
+				.unwrap();
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let result = run_scheduled(&data, &serv, &scheduled, &author, member.as_ref()).await;
+
+		assert_eq!(
+			result,
+			Err(ExecuteServitorActionError::Unauthorized {
+				user: UserId::new(12345678901234567),
+				server_name: "SomeServer".to_string(),
+				action: ServitorAction::Restart,
+			})
+		);
+		serv["foo"].assert_not_called();
+	}
+}
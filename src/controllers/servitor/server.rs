@@ -1,9 +1,12 @@
-use super::{get_server_info, ServerError};
+use super::{get_server_info, get_server_info_mut, ServerError};
 use crate::controllers::servitor::server::AddServerError::InvalidServitor;
-use crate::data::servitor::{ServerInfo, ServitorData};
+use crate::components::is_valid_name;
+use crate::data::scope::Scope;
+use crate::data::servitor::ServerInfo;
 use crate::data::BotData;
 use crate::services::servitor::ServitorController;
 use log::info;
+use serenity::all::GuildId;
 use std::collections::BTreeMap;
 use std::ops::AsyncFnOnce;
 use thiserror::Error;
@@ -13,6 +16,9 @@ pub enum AddServerError {
 	#[error("no such servitor instance {name} configured")]
 	InvalidServitor { name: String },
 
+	#[error("server name {name} is too long")]
+	NameTooLong { name: String },
+
 	#[error(transparent)]
 	Server(#[from] ServerError),
 }
@@ -29,7 +35,12 @@ pub async fn add_server<S: ServitorController>(
 	name: &str,
 	servitor: &str,
 	unit_name: &str,
+	scope: Scope,
 ) -> Result<(), AddServerError> {
+	if !is_valid_name(name) {
+		return Err(AddServerError::NameTooLong { name: name.to_string() });
+	}
+
 	if !servitor_handlers.contains_key(servitor) {
 		return Err(InvalidServitor {
 			name: servitor.to_string(),
@@ -52,11 +63,21 @@ pub async fn add_server<S: ServitorController>(
 				unit_name: unit_name.to_string(),
 				authorized_users: Default::default(),
 				authorized_roles: Default::default(),
+				watch_channels: Default::default(),
+				skip_confirmation: false,
+				query: None,
+				idle: None,
+				scope,
+				managers: Default::default(),
+				user_expiries: Default::default(),
+				role_expiries: Default::default(),
+				authorized_groups: Default::default(),
+				denied: Default::default(),
 			},
 		);
 	}
 
-	info!("Added servitor server {name} with Servitor {servitor} and unit_name {unit_name}");
+	info!("Added servitor server {name} with Servitor {servitor}, unit_name {unit_name} and scope {scope:?}");
 
 	Ok(())
 }
@@ -72,6 +93,7 @@ pub async fn remove_server(data: &BotData, name: &str) -> Result<(), RemoveServe
 		let mut lock = data.write().await;
 		let mut data_write = lock.write();
 		data_write.servitor.remove(name);
+		data_write.servitor_schedules.retain(|_, scheduled| scheduled.server_name != name);
 	}
 
 	info!("Removed servitor server {name}");
@@ -79,22 +101,45 @@ pub async fn remove_server(data: &BotData, name: &str) -> Result<(), RemoveServe
 	Ok(())
 }
 
-pub trait ListServersCallback<T> = AsyncFnOnce(&ServitorData) -> T;
-pub async fn list_servers<T, F: ListServersCallback<T>>(data: &BotData, func: F) -> T {
-	let read = data.read().await;
+/// Whether destructive actions on the server should be confirmed first, servers that don't exist need no confirmation.
+pub async fn requires_confirmation(data: &BotData, name: &str) -> bool {
+	data.read()
+		.await
+		.servitor
+		.get(name)
+		.is_some_and(|info| !info.skip_confirmation)
+}
+
+pub async fn set_confirmation(data: &BotData, name: &str, enabled: bool) -> Result<(), ServerError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
 
-	func.async_call_once((&read.servitor,)).await
+	let server_info = get_server_info_mut(&mut data_write, name).await?;
+	server_info.skip_confirmation = !enabled;
+
+	info!("Set confirmation of destructive actions for servitor server {name} to {enabled}");
+
+	Ok(())
 }
 
 pub trait DescribeServerCallback<T> = AsyncFnOnce(Result<&ServerInfo, ServerError>, &str) -> T;
 pub async fn describe_server<T, F: DescribeServerCallback<T>>(
 	data: &BotData,
+	guild: Option<GuildId>,
 	name: &str,
 	func: F,
 ) -> T {
 	let read = data.read().await;
 
-	let server = get_server_info(&read, name).await;
+	// Servers of other guilds are hidden as if they did not exist
+	let server = get_server_info(&read, name)
+		.await
+		.and_then(|info| match info.scope.is_visible_in(guild) {
+			true => Ok(info),
+			false => Err(ServerError::DoesNotExist {
+				server_name: name.to_string(),
+			}),
+		});
 
 	func.async_call_once((server, name)).await
 }
@@ -104,6 +149,7 @@ mod tests {
 	use super::*;
 	use crate::data::servitor::ServerInfo;
 	use crate::data::tests::mock_data;
+	use crate::components::MAX_NAME_LENGTH;
 	use crate::services::servitor::tests::controllers_from_bot_data;
 	use serde_json::json;
 	use std::collections::BTreeMap;
@@ -116,7 +162,7 @@ mod tests {
 		})));
 		let serv = controllers_from_bot_data(&data).await;
 
-		let result = add_server(&data, &serv, "test", "NonExistingServitor", "some_name").await;
+		let result = add_server(&data, &serv, "test", "NonExistingServitor", "some_name", Scope::Global).await;
 
 		assert_eq!(
 			result,
@@ -128,6 +174,21 @@ mod tests {
 		serv.values().for_each(|s| s.assert_not_called());
 	}
 
+	#[tokio::test]
+	async fn given_too_long_name_then_add_server_returns_error_and_does_not_update_data() {
+		let data = mock_data(Some(json!({
+			"servitor": {}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		let name = "a".repeat(MAX_NAME_LENGTH + 1);
+
+		let result = add_server(&data, &serv, &name, "foo", "some_name", Scope::Global).await;
+
+		assert_eq!(result, Err(AddServerError::NameTooLong { name }));
+		assert_eq!(data.read().await.servitor, BTreeMap::new());
+		serv.values().for_each(|s| s.assert_not_called());
+	}
+
 	#[tokio::test]
 	async fn given_duplicate_name_then_add_server_returns_error_and_does_not_update_data() {
 		let data = mock_data(Some(json!({
@@ -140,7 +201,7 @@ mod tests {
 		})));
 		let serv = controllers_from_bot_data(&data).await;
 
-		let result = add_server(&data, &serv, "SomeServer", "foo", "some_name").await;
+		let result = add_server(&data, &serv, "SomeServer", "foo", "some_name", Scope::Global).await;
 
 		let expected_data = BTreeMap::from([(
 			"SomeServer".to_string(),
@@ -149,6 +210,16 @@ mod tests {
 				unit_name: "bar".to_string(),
 				authorized_users: Default::default(),
 				authorized_roles: Default::default(),
+				watch_channels: Default::default(),
+				skip_confirmation: false,
+				query: None,
+				idle: None,
+				scope: Default::default(),
+				managers: Default::default(),
+				user_expiries: Default::default(),
+				role_expiries: Default::default(),
+				authorized_groups: Default::default(),
+				denied: Default::default(),
 			},
 		)]);
 
@@ -175,7 +246,7 @@ mod tests {
 		})));
 		let serv = controllers_from_bot_data(&data).await;
 
-		let result = add_server(&data, &serv, "NewServer", "foo", "some_name").await;
+		let result = add_server(&data, &serv, "NewServer", "foo", "some_name", Scope::Global).await;
 
 		let expected_data = BTreeMap::from([
 			(
@@ -185,6 +256,16 @@ mod tests {
 					unit_name: "bar".to_string(),
 					authorized_users: Default::default(),
 					authorized_roles: Default::default(),
+					watch_channels: Default::default(),
+					skip_confirmation: false,
+					query: None,
+					idle: None,
+					scope: Default::default(),
+					managers: Default::default(),
+					user_expiries: Default::default(),
+					role_expiries: Default::default(),
+					authorized_groups: Default::default(),
+					denied: Default::default(),
 				},
 			),
 			(
@@ -194,6 +275,16 @@ mod tests {
 					unit_name: "some_name".to_string(),
 					authorized_users: Default::default(),
 					authorized_roles: Default::default(),
+					watch_channels: Default::default(),
+					skip_confirmation: false,
+					query: None,
+					idle: None,
+					scope: Default::default(),
+					managers: Default::default(),
+					user_expiries: Default::default(),
+					role_expiries: Default::default(),
+					authorized_groups: Default::default(),
+					denied: Default::default(),
 				},
 			),
 		]);
@@ -224,6 +315,16 @@ mod tests {
 				unit_name: "bar".to_string(),
 				authorized_users: Default::default(),
 				authorized_roles: Default::default(),
+				watch_channels: Default::default(),
+				skip_confirmation: false,
+				query: None,
+				idle: None,
+				scope: Default::default(),
+				managers: Default::default(),
+				user_expiries: Default::default(),
+				role_expiries: Default::default(),
+				authorized_groups: Default::default(),
+				denied: Default::default(),
 			},
 		)]);
 
@@ -254,31 +355,36 @@ mod tests {
 	}
 
 	#[tokio::test]
-	async fn given_servitor_data_then_list_servers_provides_correct_data_to_callback() {
+	async fn given_scheduled_actions_then_remove_server_drops_its_schedules() {
+		let scheduled = |server_name: &str| {
+			json!({
+				"server_name": server_name,
+				"action": "restart",
+				"user": "12345678901234567",
+				"channel": "1",
+				"schedule": { "kind": "once" },
+				"next_run": "2025-03-06T19:59:45Z"
+			})
+		};
 		let data = mock_data(Some(json!({
 			"servitor": {
 				"SomeServer": {
 					"servitor": "foo",
 					"unit_name": "bar"
 				}
+			},
+			"servitor_schedules": {
+				"1": scheduled("SomeServer"),
+				"2": scheduled("OtherServer")
 			}
 		})));
 
-		list_servers(&data, async |data| {
-			assert_eq!(
-				*data,
-				BTreeMap::from([(
-					"SomeServer".to_string(),
-					ServerInfo {
-						servitor: "foo".to_string(),
-						unit_name: "bar".to_string(),
-						authorized_users: Default::default(),
-						authorized_roles: Default::default(),
-					}
-				)])
-			)
-		})
-		.await;
+		remove_server(&data, "SomeServer").await.unwrap();
+
+		assert_eq!(
+			data.read().await.servitor_schedules.keys().copied().collect::<Vec<_>>(),
+			vec![2]
+		);
 	}
 
 	#[tokio::test]
@@ -292,7 +398,7 @@ mod tests {
 			}
 		})));
 
-		describe_server(&data, "NonExistingServer", async |result, name| {
+		describe_server(&data, None, "NonExistingServer", async |result, name| {
 			assert_eq!(name, "NonExistingServer");
 			assert_eq!(
 				result,
@@ -315,7 +421,7 @@ mod tests {
 			}
 		})));
 
-		describe_server(&data, "SomeServer", async |result, name| {
+		describe_server(&data, None, "SomeServer", async |result, name| {
 			assert_eq!(name, "SomeServer");
 			match result {
 				Ok(server) => assert_eq!(
@@ -325,11 +431,91 @@ mod tests {
 						unit_name: "bar".to_string(),
 						authorized_users: Default::default(),
 						authorized_roles: Default::default(),
+						watch_channels: Default::default(),
+						skip_confirmation: false,
+						query: None,
+						idle: None,
+						scope: Default::default(),
+						managers: Default::default(),
+						user_expiries: Default::default(),
+						role_expiries: Default::default(),
+						authorized_groups: Default::default(),
+						denied: Default::default(),
 					}
 				),
-				Err(_) => assert!(false, "received error when it was not expected"),
+				Err(_) => panic!("received error when it was not expected"),
 			}
 		})
 		.await;
 	}
+
+	#[tokio::test]
+	async fn given_servers_then_requires_confirmation_is_only_true_for_existing_servers_not_skipping_it() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar"
+				},
+				"SkippingServer": {
+					"servitor": "foo",
+					"unit_name": "baz",
+					"skip_confirmation": true
+				}
+			}
+		})));
+
+		assert!(requires_confirmation(&data, "SomeServer").await);
+		assert!(!requires_confirmation(&data, "SkippingServer").await);
+		assert!(!requires_confirmation(&data, "NonExistingServer").await);
+	}
+
+	#[tokio::test]
+	async fn given_invalid_server_name_then_set_confirmation_returns_error() {
+		let data = mock_data(None);
+
+		let result = set_confirmation(&data, "NonExistingServer", false).await;
+
+		assert_eq!(
+			result,
+			Err(ServerError::DoesNotExist {
+				server_name: "NonExistingServer".to_string()
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_existing_server_then_set_confirmation_toggles_skip_confirmation() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar"
+				}
+			}
+		})));
+
+		let disabled = set_confirmation(&data, "SomeServer", false).await;
+		let skipped = data.read().await.servitor["SomeServer"].skip_confirmation;
+		let enabled = set_confirmation(&data, "SomeServer", true).await;
+
+		assert_eq!(disabled, Ok(()));
+		assert!(skipped);
+		assert_eq!(enabled, Ok(()));
+		assert!(!data.read().await.servitor["SomeServer"].skip_confirmation);
+	}
+
+	#[tokio::test]
+	async fn given_scoped_server_then_describe_server_does_not_see_it_from_other_guild() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"GlobalServer": { "servitor": "foo", "unit_name": "bar" },
+				"GuildServer": { "servitor": "foo", "unit_name": "baz", "scope": { "guild": "1" } }
+			}
+		})));
+
+		let described = describe_server(&data, Some(GuildId::new(2)), "GuildServer", async |result, _| result.is_ok()).await;
+
+		assert!(!described);
+	}
 }
@@ -0,0 +1,483 @@
+use super::super::is_user_authorized;
+use super::{get_server_info_mut, ServerError};
+use crate::data::servitor::ServitorAction;
+use crate::data::BotData;
+use crate::services::servitor::{ServitorController, UnitStatus};
+use log::{info, warn};
+use serenity::all::{ChannelId, Member, User, UserId};
+use std::collections::{BTreeMap, BTreeSet};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum WatchError {
+	#[error(transparent)]
+	Server(#[from] ServerError),
+
+	#[error("User {user} is not authorized to watch Servitor server {server_name}")]
+	Unauthorized { user: UserId, server_name: String },
+
+	#[error("channel {channel} is already watching server {server_name}")]
+	AlreadyWatching {
+		server_name: String,
+		channel: ChannelId,
+	},
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum UnwatchError {
+	#[error(transparent)]
+	Server(#[from] ServerError),
+
+	#[error("User {user} is not authorized to unwatch Servitor server {server_name}")]
+	Unauthorized { user: UserId, server_name: String },
+
+	#[error("channel {channel} is not watching server {server_name}")]
+	NotWatching {
+		server_name: String,
+		channel: ChannelId,
+	},
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct WatchedUnit {
+	pub servitor: String,
+	pub unit_name: String,
+}
+
+pub type UnitStates = BTreeMap<WatchedUnit, UnitStatus>;
+
+#[derive(Debug, PartialEq)]
+pub struct UnitStateChange {
+	pub previous: UnitStatus,
+	pub current: UnitStatus,
+	pub watchers: BTreeMap<String, BTreeSet<ChannelId>>,
+}
+
+pub async fn watch(
+	data: &BotData,
+	server_name: &str,
+	channel: ChannelId,
+	author: &User,
+	member: Option<&Member>,
+) -> Result<(), WatchError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let groups = data_write.access_groups.clone();
+
+	let server_info = get_server_info_mut(&mut data_write, server_name).await?;
+
+	if !is_user_authorized(author, member, &server_info.for_action(ServitorAction::Status), &groups) {
+		return Err(WatchError::Unauthorized {
+			user: author.id,
+			server_name: server_name.to_string(),
+		});
+	}
+
+	if server_info.watch_channels.insert(channel) {
+		info!("Channel {channel} is now watching server {server_name}");
+		Ok(())
+	} else {
+		Err(WatchError::AlreadyWatching {
+			server_name: server_name.to_string(),
+			channel,
+		})
+	}
+}
+
+pub async fn unwatch(
+	data: &BotData,
+	server_name: &str,
+	channel: ChannelId,
+	author: &User,
+	member: Option<&Member>,
+) -> Result<(), UnwatchError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let groups = data_write.access_groups.clone();
+
+	let server_info = get_server_info_mut(&mut data_write, server_name).await?;
+
+	if !is_user_authorized(author, member, &server_info.for_action(ServitorAction::Status), &groups) {
+		return Err(UnwatchError::Unauthorized {
+			user: author.id,
+			server_name: server_name.to_string(),
+		});
+	}
+
+	if server_info.watch_channels.remove(&channel) {
+		info!("Channel {channel} is no longer watching server {server_name}");
+		Ok(())
+	} else {
+		Err(UnwatchError::NotWatching {
+			server_name: server_name.to_string(),
+			channel,
+		})
+	}
+}
+
+/// Polls the status of every watched unit once, even when several servers point at the same unit,
+/// and returns the units whose state or sub-state changed since the last poll.
+pub async fn poll_watched_units<S: ServitorController>(
+	data: &BotData,
+	servitor_handlers: &BTreeMap<String, S>,
+	last_states: &mut UnitStates,
+) -> Vec<UnitStateChange> {
+	let mut watched: BTreeMap<WatchedUnit, BTreeMap<String, BTreeSet<ChannelId>>> = BTreeMap::new();
+
+	for (server_name, server_info) in data.read().await.servitor.iter() {
+		if server_info.watch_channels.is_empty() {
+			continue;
+		}
+
+		let unit = WatchedUnit {
+			servitor: server_info.servitor.clone(),
+			unit_name: server_info.unit_name.clone(),
+		};
+
+		watched
+			.entry(unit)
+			.or_default()
+			.insert(server_name.clone(), server_info.watch_channels.clone());
+	}
+
+	last_states.retain(|unit, _| watched.contains_key(unit));
+
+	let mut changes = Vec::new();
+
+	for (unit, watchers) in watched {
+		let Some(handler) = servitor_handlers.get(&unit.servitor) else {
+			continue;
+		};
+
+		let current = match handler.status(&unit.unit_name).await {
+			Ok(status) => status,
+			Err(e) => {
+				warn!("Failed to poll status of unit {} on Servitor {}: {e}", unit.unit_name, unit.servitor);
+				continue;
+			}
+		};
+
+		if let Some(previous) = last_states.insert(unit, current.clone())
+			&& (previous.state != current.state || previous.sub_state != current.sub_state)
+		{
+			changes.push(UnitStateChange {
+				previous,
+				current,
+				watchers,
+			});
+		}
+	}
+
+	changes
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::controllers::tests::{mock_author_dms, mock_author_guild};
+	use crate::data::tests::mock_data;
+	use crate::services::servitor::tests::{controllers_from_bot_data, MockServitorController};
+	use crate::services::servitor::ServitorError;
+	use serde_json::json;
+	use serenity::all::RoleId;
+
+	#[tokio::test]
+	async fn given_non_existing_server_then_watch_returns_error() {
+		let data = mock_data(None);
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let result = watch(&data, "NonExistingServer", ChannelId::new(1), &author, member.as_ref()).await;
+
+		assert_eq!(
+			result,
+			Err(WatchError::Server(ServerError::DoesNotExist {
+				server_name: "NonExistingServer".to_string()
+			}))
+		);
+	}
+
+	#[tokio::test]
+	async fn given_unauthorized_user_then_watch_returns_error_and_does_not_modify_data() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": [98765432109876543u64]
+				}
+			}
+		})));
+		let (author, member) = mock_author_guild(UserId::new(12345678901234567), vec![]);
+
+		let result = watch(&data, "SomeServer", ChannelId::new(1), &author, member.as_ref()).await;
+
+		assert_eq!(
+			result,
+			Err(WatchError::Unauthorized {
+				user: UserId::new(12345678901234567),
+				server_name: "SomeServer".to_string()
+			})
+		);
+		assert!(data.read().await.servitor["SomeServer"].watch_channels.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_already_watching_channel_then_watch_returns_error() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": [12345678901234567u64],
+					"watch_channels": [1]
+				}
+			}
+		})));
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let result = watch(&data, "SomeServer", ChannelId::new(1), &author, member.as_ref()).await;
+
+		assert_eq!(
+			result,
+			Err(WatchError::AlreadyWatching {
+				server_name: "SomeServer".to_string(),
+				channel: ChannelId::new(1),
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_authorized_role_then_watch_returns_success_and_adds_channel() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_roles": [98765432109876543u64]
+				}
+			}
+		})));
+		let (author, member) = mock_author_guild(
+			UserId::new(12345678901234567),
+			vec![RoleId::new(98765432109876543)],
+		);
+
+		let result = watch(&data, "SomeServer", ChannelId::new(1), &author, member.as_ref()).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.servitor["SomeServer"]
+			.watch_channels
+			.contains(&ChannelId::new(1)));
+	}
+
+	#[tokio::test]
+	async fn given_not_watching_channel_then_unwatch_returns_error() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": [12345678901234567u64],
+					"watch_channels": [1]
+				}
+			}
+		})));
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let result = unwatch(&data, "SomeServer", ChannelId::new(2), &author, member.as_ref()).await;
+
+		assert_eq!(
+			result,
+			Err(UnwatchError::NotWatching {
+				server_name: "SomeServer".to_string(),
+				channel: ChannelId::new(2),
+			})
+		);
+		assert_eq!(data.read().await.servitor["SomeServer"].watch_channels.len(), 1);
+	}
+
+	#[tokio::test]
+	async fn given_unauthorized_user_then_unwatch_returns_error_and_does_not_modify_data() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"watch_channels": [1]
+				}
+			}
+		})));
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let result = unwatch(&data, "SomeServer", ChannelId::new(1), &author, member.as_ref()).await;
+
+		assert_eq!(
+			result,
+			Err(UnwatchError::Unauthorized {
+				user: UserId::new(12345678901234567),
+				server_name: "SomeServer".to_string()
+			})
+		);
+		assert_eq!(data.read().await.servitor["SomeServer"].watch_channels.len(), 1);
+	}
+
+	#[tokio::test]
+	async fn given_watching_channel_then_unwatch_returns_success_and_removes_channel() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": [12345678901234567u64],
+					"watch_channels": [1]
+				}
+			}
+		})));
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let result = unwatch(&data, "SomeServer", ChannelId::new(1), &author, member.as_ref()).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.servitor["SomeServer"].watch_channels.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_servers_sharing_a_unit_then_poll_checks_status_once_and_records_state() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"watch_channels": [1]
+				},
+				"OtherServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"watch_channels": [2]
+				},
+				"UnwatchedServer": {
+					"servitor": "foo",
+					"unit_name": "baz"
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		let mut last_states = UnitStates::new();
+
+		let changes = poll_watched_units(&data, &serv, &mut last_states).await;
+
+		assert_eq!(changes, vec![]);
+		assert_eq!(
+			last_states,
+			UnitStates::from([(
+				WatchedUnit {
+					servitor: "foo".to_string(),
+					unit_name: "bar".to_string(),
+				},
+				MockServitorController::default_status("bar")
+			)])
+		);
+		serv["foo"].assert_called_times(0, 0, 0, 0, 1);
+	}
+
+	#[tokio::test]
+	async fn given_changed_state_then_poll_returns_change_with_all_watchers() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"watch_channels": [1, 3]
+				},
+				"OtherServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"watch_channels": [2]
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		let unit = WatchedUnit {
+			servitor: "foo".to_string(),
+			unit_name: "bar".to_string(),
+		};
+		let mut previous = MockServitorController::default_status("bar");
+		previous.state = "failed".to_string();
+		previous.sub_state = "failed".to_string();
+		let mut last_states = UnitStates::from([(unit.clone(), previous.clone())]);
+
+		let changes = poll_watched_units(&data, &serv, &mut last_states).await;
+
+		assert_eq!(
+			changes,
+			vec![UnitStateChange {
+				previous,
+				current: MockServitorController::default_status("bar"),
+				watchers: BTreeMap::from([
+					(
+						"OtherServer".to_string(),
+						BTreeSet::from([ChannelId::new(2)])
+					),
+					(
+						"SomeServer".to_string(),
+						BTreeSet::from([ChannelId::new(1), ChannelId::new(3)])
+					),
+				]),
+			}]
+		);
+		assert_eq!(last_states[&unit], MockServitorController::default_status("bar"));
+	}
+
+	#[tokio::test]
+	async fn given_status_error_then_poll_keeps_previous_state_and_returns_no_changes() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"watch_channels": [1]
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		serv["foo"].set_error(ServitorError::InternalServerError).await;
+		let unit = WatchedUnit {
+			servitor: "foo".to_string(),
+			unit_name: "bar".to_string(),
+		};
+		let mut previous = MockServitorController::default_status("bar");
+		previous.state = "inactive".to_string();
+		let mut last_states = UnitStates::from([(unit.clone(), previous.clone())]);
+
+		let changes = poll_watched_units(&data, &serv, &mut last_states).await;
+
+		assert_eq!(changes, vec![]);
+		assert_eq!(last_states[&unit], previous);
+	}
+
+	#[tokio::test]
+	async fn given_unit_no_longer_watched_then_poll_forgets_its_state() {
+		let data = mock_data(Some(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar"
+				}
+			}
+		})));
+		let serv = controllers_from_bot_data(&data).await;
+		let mut last_states = UnitStates::from([(
+			WatchedUnit {
+				servitor: "foo".to_string(),
+				unit_name: "bar".to_string(),
+			},
+			MockServitorController::default_status("bar"),
+		)]);
+
+		let changes = poll_watched_units(&data, &serv, &mut last_states).await;
+
+		assert_eq!(changes, vec![]);
+		assert!(last_states.is_empty());
+		serv["foo"].assert_not_called();
+	}
+}
@@ -6,6 +6,7 @@ use crate::data::wake_on_lan::WakeOnLanMachineInfo;
 pub mod authorization;
 pub mod machine;
 pub mod wake;
+pub mod manager;
 
 #[derive(Debug, Error, PartialEq)]
 pub enum MachineError {
@@ -1,377 +1,59 @@
-use super::super::DiscordEntity;
-use super::{get_machine_info_mut, MachineError};
-use crate::data::BotData;
-use log::info;
-use serenity::all::{RoleId, UserId};
-use thiserror::Error;
+use super::super::authorization::{AuthorizedResource, Grantee};
+use super::MachineError;
+use crate::controllers::DiscordEntity;
+use crate::data::authorization::{set_expiry, Denials};
+use crate::data::wake_on_lan::WakeOnLanMachineInfo;
+use crate::data::Data;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Error, PartialEq)]
-pub enum AddPermissionError {
-	#[error(transparent)]
-	Machine(#[from] MachineError),
+impl AuthorizedResource for WakeOnLanMachineInfo {
+	type Grant = ();
+	type Error = MachineError;
 
-	#[error("{entity:?} is already permitted to wake machine {machine_name}")]
-	AlreadyAuthorized {
-		machine_name: String,
-		entity: DiscordEntity,
-	},
-}
-
-#[derive(Debug, Error, PartialEq)]
-pub enum RemovePermissionError {
-	#[error(transparent)]
-	Machine(#[from] MachineError),
-
-	#[error("{entity:?} is already not permitted to wake machine {machine_name}")]
-	AlreadyNotAuthorized {
-		machine_name: String,
-		entity: DiscordEntity,
-	},
-}
-
-pub async fn permit_user(
-	data: &BotData,
-	machine_name: &str,
-	user_id: UserId,
-) -> Result<(), AddPermissionError> {
-	let mut lock = data.write().await;
-	let mut data_write = lock.write();
-	let machine_info = get_machine_info_mut(&mut data_write, machine_name).await?;
-
-	if machine_info.authorized_users.insert(user_id) {
-		info!("Permitted user {user_id} to wake machine {machine_name}");
-		Ok(())
-	} else {
-		Err(AddPermissionError::AlreadyAuthorized {
-			machine_name: machine_name.into(),
-			entity: DiscordEntity::User(user_id),
-		})
-	}
-}
-
-pub async fn revoke_user(
-	data: &BotData,
-	machine_name: &str,
-	user_id: UserId,
-) -> Result<(), RemovePermissionError> {
-	let mut lock = data.write().await;
-	let mut data_write = lock.write();
-
-	let machine_info = get_machine_info_mut(&mut data_write, machine_name).await?;
-
-	if machine_info.authorized_users.remove(&user_id) {
-		info!("Revoked user's {user_id} permission to wake machine {machine_name}");
-		Ok(())
-	} else {
-		Err(RemovePermissionError::AlreadyNotAuthorized {
-			machine_name: machine_name.into(),
-			entity: DiscordEntity::User(user_id),
-		})
-	}
-}
+	const NOUN: &'static str = "machine";
 
-pub async fn permit_role(
-	data: &BotData,
-	machine_name: &str,
-	role_id: RoleId,
-) -> Result<(), AddPermissionError> {
-	let mut lock = data.write().await;
-	let mut data_write = lock.write();
-
-	let machine_info = get_machine_info_mut(&mut data_write, machine_name).await?;
-
-	if machine_info.authorized_roles.insert(role_id) {
-		info!("Permitted role {role_id} to wake machine {machine_name}");
-		Ok(())
-	} else {
-		Err(AddPermissionError::AlreadyAuthorized {
-			machine_name: machine_name.into(),
-			entity: DiscordEntity::Role(role_id),
-		})
+	fn all(data: &Data) -> &BTreeMap<String, Self> {
+		&data.wake_on_lan
 	}
-}
 
-pub async fn revoke_role(
-	data: &BotData,
-	machine_name: &str,
-	role_id: RoleId,
-) -> Result<(), RemovePermissionError> {
-	let mut lock = data.write().await;
-	let mut data_write = lock.write();
-
-	let machine_info = get_machine_info_mut(&mut data_write, machine_name).await?;
-
-	if machine_info.authorized_roles.remove(&role_id) {
-		info!("Revoked role {role_id}'s permission to wake machine {machine_name}");
-		Ok(())
-	} else {
-		Err(RemovePermissionError::AlreadyNotAuthorized {
-			machine_name: machine_name.into(),
-			entity: DiscordEntity::Role(role_id),
+	fn get_mut<'a>(data: &'a mut Data, name: &str) -> Result<&'a mut Self, MachineError> {
+		data.wake_on_lan.get_mut(name).ok_or(MachineError::DoesNotExist {
+			machine_name: name.into(),
 		})
 	}
-}
-
-#[cfg(test)]
-mod tests {
-	use super::*;
-	use crate::data::tests::mock_data;
-	use serde_json::json;
-
-	#[tokio::test]
-	async fn given_nonexistent_machine_then_permit_user_returns_error_and_does_not_modify_data() {
-		let data = mock_data(None);
-		let result = permit_user(&data, "NonExistentMachine", UserId::new(12345678901234567)).await;
-
-		assert_eq!(
-			result,
-			Err(AddPermissionError::Machine(MachineError::DoesNotExist {
-				machine_name: "NonExistentMachine".to_string()
-			}))
-		);
-		assert!(data.read().await.wake_on_lan.is_empty());
-	}
-
-	#[tokio::test]
-	async fn given_already_authorized_user_then_permit_user_returns_error_and_does_not_modify_data()
-	{
-		let data = mock_data(Some(json!({
-			"wake_on_lan": {
-				"ExistingMachine": {
-					"mac": [1, 2, 3, 4, 5, 6],
-					"authorized_users": [12345678901234567u64],
-					"authorized_roles": []
-				}
-			}
-		})));
-
-		let result = permit_user(&data, "ExistingMachine", UserId::new(12345678901234567)).await;
-
-		assert_eq!(
-			result,
-			Err(AddPermissionError::AlreadyAuthorized {
-				machine_name: "ExistingMachine".to_string(),
-				entity: DiscordEntity::User(UserId::new(12345678901234567)),
-			})
-		);
-		assert_eq!(
-			data.read().await.wake_on_lan["ExistingMachine"]
-				.authorized_users
-				.len(),
-			1
-		);
-	}
 
-	#[tokio::test]
-	async fn given_new_user_then_permit_user_returns_success_and_adds_user() {
-		let data = mock_data(Some(json!({
-			"wake_on_lan": {
-				"ExistingMachine": {
-					"mac": [1, 2, 3, 4, 5, 6],
-					"authorized_users": [],
-					"authorized_roles": []
-				}
-			}
-		})));
-
-		let result = permit_user(&data, "ExistingMachine", UserId::new(12345678901234567)).await;
-
-		assert_eq!(result, Ok(()));
-		assert!(data.read().await.wake_on_lan["ExistingMachine"]
-			.authorized_users
-			.contains(&UserId::new(12345678901234567)));
-	}
-
-	#[tokio::test]
-	async fn given_nonexistent_machine_then_revoke_user_returns_error_and_does_not_modify_data() {
-		let data = mock_data(None);
-
-		let result = revoke_user(&data, "NonExistentMachine", UserId::new(12345678901234567)).await;
-
-		assert_eq!(
-			result,
-			Err(RemovePermissionError::Machine(MachineError::DoesNotExist {
-				machine_name: "NonExistentMachine".to_string()
-			}))
-		);
-		assert!(data.read().await.wake_on_lan.is_empty());
-	}
-
-	#[tokio::test]
-	async fn given_non_authorized_user_then_revoke_user_returns_error_and_does_not_modify_data() {
-		let data = mock_data(Some(json!({
-			"wake_on_lan": {
-				"ExistingMachine": {
-					"mac": [1, 2, 3, 4, 5, 6],
-					"authorized_users": [12345678901234567u64],
-					"authorized_roles": []
-				}
-			}
-		})));
-
-		let result = revoke_user(&data, "ExistingMachine", UserId::new(76543210987654321)).await;
-
-		assert_eq!(
-			result,
-			Err(RemovePermissionError::AlreadyNotAuthorized {
-				machine_name: "ExistingMachine".to_string(),
-				entity: DiscordEntity::User(UserId::new(76543210987654321))
-			})
-		);
-		assert_eq!(
-			data.read().await.wake_on_lan["ExistingMachine"]
-				.authorized_users
-				.len(),
-			1
-		);
-	}
-
-	#[tokio::test]
-	async fn given_authorized_user_then_revoke_user_returns_success_and_removes_user() {
-		let data = mock_data(Some(json!({
-			"wake_on_lan": {
-				"ExistingMachine": {
-					"mac": [1, 2, 3, 4, 5, 6],
-					"authorized_users": [12345678901234567u64],
-					"authorized_roles": []
-				}
-			}
-		})));
-
-		let result = revoke_user(&data, "ExistingMachine", UserId::new(12345678901234567)).await;
-
-		assert_eq!(result, Ok(()));
-		assert!(!data.read().await.wake_on_lan["ExistingMachine"]
-			.authorized_users
-			.contains(&UserId::new(12345678901234567)));
-	}
-
-	#[tokio::test]
-	async fn given_nonexistent_machine_then_permit_role_returns_error_and_does_not_modify_data() {
-		let data = mock_data(None);
-
-		let result = permit_role(&data, "NonExistentMachine", RoleId::new(98765432109876543)).await;
-
-		assert_eq!(
-			result,
-			Err(AddPermissionError::Machine(MachineError::DoesNotExist {
-				machine_name: "NonExistentMachine".to_string()
-			}))
-		);
-		assert!(data.read().await.wake_on_lan.is_empty());
+	fn grant(&mut self, grantee: Grantee, _: &()) -> bool {
+		match grantee {
+			Grantee::User(id) => self.authorized_users.insert(id),
+			Grantee::Role(id) => self.authorized_roles.insert(id),
+			Grantee::Group(group) => self.authorized_groups.insert(group.into()),
+		}
 	}
 
-	#[tokio::test]
-	async fn given_already_authorized_role_then_permit_role_returns_error_and_does_not_modify_data()
-	{
-		let data = mock_data(Some(json!({
-			"wake_on_lan": {
-				"ExistingMachine": {
-					"mac": [1, 2, 3, 4, 5, 6],
-					"authorized_users": [],
-					"authorized_roles": [98765432109876543u64]
-				}
-			}
-		})));
-
-		let result = permit_role(&data, "ExistingMachine", RoleId::new(98765432109876543)).await;
-
-		assert_eq!(
-			result,
-			Err(AddPermissionError::AlreadyAuthorized {
-				machine_name: "ExistingMachine".to_string(),
-				entity: DiscordEntity::Role(RoleId::new(98765432109876543))
-			})
-		);
-		assert_eq!(
-			data.read().await.wake_on_lan["ExistingMachine"]
-				.authorized_roles
-				.len(),
-			1
-		);
+	fn revoke(&mut self, grantee: Grantee, _: &()) -> bool {
+		match grantee {
+			Grantee::User(id) => self.authorized_users.remove(&id),
+			Grantee::Role(id) => self.authorized_roles.remove(&id),
+			Grantee::Group(group) => self.authorized_groups.remove(group),
+		}
 	}
 
-	#[tokio::test]
-	async fn given_new_role_then_permit_role_returns_success_and_adds_role() {
-		let data = mock_data(Some(json!({
-			"wake_on_lan": {
-				"ExistingMachine": {
-					"mac": [1, 2, 3, 4, 5, 6],
-					"authorized_users": [],
-					"authorized_roles": []
-				}
-			}
-		})));
-
-		let result = permit_role(&data, "ExistingMachine", RoleId::new(98765432109876543)).await;
-
-		assert_eq!(result, Ok(()));
-		assert!(data.read().await.wake_on_lan["ExistingMachine"]
-			.authorized_roles
-			.contains(&RoleId::new(98765432109876543)));
+	fn set_expiry(&mut self, entity: DiscordEntity, _: &(), expires: Option<DateTime<Utc>>) -> bool {
+		match entity {
+			DiscordEntity::User(id) => set_expiry(&mut self.user_expiries, id, expires),
+			DiscordEntity::Role(id) => set_expiry(&mut self.role_expiries, id, expires),
+		}
 	}
 
-	#[tokio::test]
-	async fn given_nonexistent_machine_then_revoke_role_returns_error_and_does_not_modify_data() {
-		let data = mock_data(None);
-
-		let result = revoke_role(&data, "NonExistentMachine", RoleId::new(98765432109876543)).await;
-
-		assert_eq!(
-			result,
-			Err(RemovePermissionError::Machine(MachineError::DoesNotExist {
-				machine_name: "NonExistentMachine".to_string()
-			}))
-		);
-		assert!(data.read().await.wake_on_lan.is_empty());
+	fn forget_expiry(&mut self, entity: DiscordEntity, _: &()) {
+		match entity {
+			DiscordEntity::User(id) => self.user_expiries.remove(&id),
+			DiscordEntity::Role(id) => self.role_expiries.remove(&id),
+		};
 	}
 
-	#[tokio::test]
-	async fn given_non_authorized_role_then_revoke_role_returns_error_and_does_not_modify_data() {
-		let data = mock_data(Some(json!({
-			"wake_on_lan": {
-				"ExistingMachine": {
-					"mac": [1, 2, 3, 4, 5, 6],
-					"authorized_users": [],
-					"authorized_roles": [12345678901234567u64]
-				}
-			}
-		})));
-
-		let result = revoke_role(&data, "ExistingMachine", RoleId::new(98765432109876543)).await;
-
-		assert_eq!(
-			result,
-			Err(RemovePermissionError::AlreadyNotAuthorized {
-				machine_name: "ExistingMachine".to_string(),
-				entity: DiscordEntity::Role(RoleId::new(98765432109876543))
-			})
-		);
-		assert_eq!(
-			data.read().await.wake_on_lan["ExistingMachine"]
-				.authorized_roles
-				.len(),
-			1
-		);
-	}
-
-	#[tokio::test]
-	async fn given_authorized_role_then_revoke_role_returns_success_and_removes_role() {
-		let data = mock_data(Some(json!({
-			"wake_on_lan": {
-				"ExistingMachine": {
-					"mac": [1, 2, 3, 4, 5, 6],
-					"authorized_users": [],
-					"authorized_roles": [98765432109876543u64]
-				}
-			}
-		})));
-
-		let result = revoke_role(&data, "ExistingMachine", RoleId::new(98765432109876543)).await;
-
-		assert_eq!(result, Ok(()));
-		assert!(!data.read().await.wake_on_lan["ExistingMachine"]
-			.authorized_roles
-			.contains(&RoleId::new(98765432109876543)));
+	fn denials_mut(&mut self) -> &mut Denials {
+		&mut self.denied
 	}
 }
@@ -1,8 +1,11 @@
-use super::{get_machine_info, MachineError};
-use crate::data::wake_on_lan::{WakeOnLanData, WakeOnLanMachineInfo};
+use super::{get_machine_info, get_machine_info_mut, MachineError};
+use crate::components::is_valid_name;
+use crate::data::scope::Scope;
+use crate::data::wake_on_lan::WakeOnLanMachineInfo;
 use crate::data::BotData;
 use crate::errors::InvalidMacError;
 use log::info;
+use serenity::all::GuildId;
 use std::ops::AsyncFnOnce;
 use thiserror::Error;
 
@@ -13,6 +16,9 @@ pub enum AddMachineError {
 
 	#[error(transparent)]
 	InvalidMac(#[from] InvalidMacError),
+
+	#[error("machine name {name} is too long")]
+	NameTooLong { name: String },
 }
 
 #[derive(Debug, Error, PartialEq)]
@@ -21,7 +27,11 @@ pub enum RemoveMachineError {
 	Machine(#[from] MachineError),
 }
 
-pub async fn add_machine(data: &BotData, name: &str, mac: &str) -> Result<(), AddMachineError> {
+pub async fn add_machine(data: &BotData, name: &str, mac: &str, scope: Scope) -> Result<(), AddMachineError> {
+	if !is_valid_name(name) {
+		return Err(AddMachineError::NameTooLong { name: name.into() });
+	}
+
 	{
 		let read = data.read().await;
 		if read.wake_on_lan.contains_key(name) {
@@ -42,11 +52,18 @@ pub async fn add_machine(data: &BotData, name: &str, mac: &str) -> Result<(), Ad
 				mac: mac_address,
 				authorized_users: Default::default(),
 				authorized_roles: Default::default(),
+				scope,
+				managers: Default::default(),
+				user_expiries: Default::default(),
+				role_expiries: Default::default(),
+				authorized_groups: Default::default(),
+				denied: Default::default(),
+				address: None,
 			},
 		);
 	}
 
-	info!("Added machine {name} with MAC {mac}");
+	info!("Added machine {name} with MAC {mac} and scope {scope:?}");
 
 	Ok(())
 }
@@ -72,23 +89,40 @@ pub async fn remove_machine(data: &BotData, name: &str) -> Result<(), RemoveMach
 	Ok(())
 }
 
-pub trait ListMachinesCallback<T> = AsyncFnOnce(&WakeOnLanData) -> T;
-pub async fn list_machines<T, F: ListMachinesCallback<T>>(data: &BotData, func: F) -> T {
-	let read = data.read().await;
+/// Sets where the dashboard checks whether the machine is online, `None` stops checking it.
+pub async fn set_address(data: &BotData, name: &str, address: Option<String>) -> Result<(), MachineError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let machine_info = get_machine_info_mut(&mut data_write, name).await?;
 
-	func.async_call_once((&read.wake_on_lan,)).await
+	match &address {
+		Some(address) => info!("Set address of machine {name} to {address}"),
+		None => info!("Removed address of machine {name}"),
+	}
+	machine_info.address = address;
+
+	Ok(())
 }
 
 pub trait DescribeMachineCallback<T> =
 	AsyncFnOnce(Result<&WakeOnLanMachineInfo, MachineError>, &str) -> T;
 pub async fn describe_machine<T, F: DescribeMachineCallback<T>>(
 	data: &BotData,
+	guild: Option<GuildId>,
 	name: &str,
 	func: F,
 ) -> T {
 	let read = data.read().await;
 
-	let machine = get_machine_info(&read, name).await;
+	// Machines of other guilds are hidden as if they did not exist
+	let machine = get_machine_info(&read, name)
+		.await
+		.and_then(|info| match info.scope.is_visible_in(guild) {
+			true => Ok(info),
+			false => Err(MachineError::DoesNotExist {
+				machine_name: name.into(),
+			}),
+		});
 
 	func.async_call_once((machine, name)).await
 }
@@ -96,6 +130,7 @@ pub async fn describe_machine<T, F: DescribeMachineCallback<T>>(
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::components::MAX_NAME_LENGTH;
 	use crate::data::tests::mock_data;
 	use crate::services::wake_on_lan::MacAddress;
 	use serde_json::json;
@@ -113,7 +148,7 @@ mod tests {
 			}
 		})));
 
-		let result = add_machine(&data, "SomeMachine".into(), "00:00:00:00:00:01".into()).await;
+		let result = add_machine(&data, "SomeMachine", "00:00:00:00:00:01", Scope::Global).await;
 
 		let mut expected_data = BTreeMap::new();
 		expected_data.insert(
@@ -122,6 +157,13 @@ mod tests {
 				mac: MacAddress([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
 				authorized_users: Default::default(),
 				authorized_roles: Default::default(),
+				scope: Default::default(),
+				managers: Default::default(),
+				user_expiries: Default::default(),
+				role_expiries: Default::default(),
+				authorized_groups: Default::default(),
+				denied: Default::default(),
+				address: None,
 			},
 		);
 
@@ -134,11 +176,54 @@ mod tests {
 		assert_eq!(data.read().await.wake_on_lan, expected_data);
 	}
 
+	#[tokio::test]
+	async fn given_too_long_name_then_add_machine_returns_error_and_does_not_update_data() {
+		let data = mock_data(None);
+		let name = "a".repeat(MAX_NAME_LENGTH + 1);
+
+		let result = add_machine(&data, &name, "00:00:00:00:00:01", Scope::Global).await;
+
+		assert_eq!(result, Err(AddMachineError::NameTooLong { name }));
+		assert!(data.read().await.wake_on_lan.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_invalid_machine_name_then_set_address_returns_error() {
+		let data = mock_data(None);
+
+		let result = set_address(&data, "NonExistingMachine", Some("host:22".to_string())).await;
+
+		assert_eq!(
+			result,
+			Err(MachineError::DoesNotExist {
+				machine_name: "NonExistingMachine".into()
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_existing_machine_then_set_address_sets_and_removes_it() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"SomeMachine": { "mac": [1, 2, 3, 4, 5, 6] }
+			}
+		})));
+
+		let set = set_address(&data, "SomeMachine", Some("host:22".to_string())).await;
+		let address = data.read().await.wake_on_lan["SomeMachine"].address.clone();
+		let removed = set_address(&data, "SomeMachine", None).await;
+
+		assert_eq!(set, Ok(()));
+		assert_eq!(address, Some("host:22".to_string()));
+		assert_eq!(removed, Ok(()));
+		assert_eq!(data.read().await.wake_on_lan["SomeMachine"].address, None);
+	}
+
 	#[tokio::test]
 	async fn given_invalid_mac_then_add_machine_returns_error_and_does_not_update_data() {
 		let data = mock_data(None);
 
-		let result = add_machine(&data, "NewMachine", "invalid_mac").await;
+		let result = add_machine(&data, "NewMachine", "invalid_mac", Scope::Global).await;
 
 		assert_eq!(
 			result,
@@ -156,7 +241,7 @@ mod tests {
 	async fn given_mac_with_invalid_hex_then_add_machine_returns_error_and_does_not_update_data() {
 		let data = mock_data(None);
 
-		let result = add_machine(&data, "NewMachine", "AA:BB:CC:DD:EE:PP").await;
+		let result = add_machine(&data, "NewMachine", "AA:BB:CC:DD:EE:PP", Scope::Global).await;
 
 		assert_eq!(
 			result,
@@ -171,7 +256,7 @@ mod tests {
 	async fn given_valid_input_then_add_machine_returns_success_and_inserts_new_machine() {
 		let data = mock_data(None);
 
-		let result = add_machine(&data, "NewMachine", "00:00:00:00:00:01").await;
+		let result = add_machine(&data, "NewMachine", "00:00:00:00:00:01", Scope::Global).await;
 
 		let mut expected_data = BTreeMap::new();
 		expected_data.insert(
@@ -180,6 +265,13 @@ mod tests {
 				mac: MacAddress([0x00, 0x00, 0x00, 0x00, 0x00, 0x01]),
 				authorized_users: Default::default(),
 				authorized_roles: Default::default(),
+				scope: Default::default(),
+				managers: Default::default(),
+				user_expiries: Default::default(),
+				role_expiries: Default::default(),
+				authorized_groups: Default::default(),
+				denied: Default::default(),
+				address: None,
 			},
 		);
 
@@ -209,6 +301,13 @@ mod tests {
 				mac: MacAddress([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
 				authorized_users: Default::default(),
 				authorized_roles: Default::default(),
+				scope: Default::default(),
+				managers: Default::default(),
+				user_expiries: Default::default(),
+				role_expiries: Default::default(),
+				authorized_groups: Default::default(),
+				denied: Default::default(),
+				address: None,
 			},
 		);
 
@@ -241,34 +340,6 @@ mod tests {
 		assert_eq!(data.read().await.wake_on_lan, expected_data);
 	}
 
-	#[tokio::test]
-	async fn given_wake_on_lan_data_then_list_machines_provides_correct_data_to_callback() {
-		let data = mock_data(Some(json!({
-			"wake_on_lan": {
-				"ExistingMachine": {
-					"mac": [1, 2, 3, 4, 5, 6],
-					"authorized_users": [],
-					"authorized_roles": []
-				}
-			}
-		})));
-
-		list_machines(&data, async |data| {
-			assert_eq!(
-				*data,
-				BTreeMap::from([(
-					"ExistingMachine".to_string(),
-					WakeOnLanMachineInfo {
-						mac: MacAddress([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
-						authorized_users: Default::default(),
-						authorized_roles: Default::default(),
-					}
-				)])
-			)
-		})
-		.await;
-	}
-
 	#[tokio::test]
 	async fn given_nonexistent_machine_then_describe_machine_callbacks_with_error() {
 		let data = mock_data(Some(json!({
@@ -281,7 +352,7 @@ mod tests {
 			}
 		})));
 
-		describe_machine(&data, "NonExistentMachine", async |result, name| {
+		describe_machine(&data, None, "NonExistentMachine", async |result, name| {
 			assert_eq!(name, "NonExistentMachine");
 			assert_eq!(
 				result,
@@ -305,16 +376,51 @@ mod tests {
 			}
 		})));
 
-		describe_machine(&data, "ExistingMachine", async |result, name| {
+		describe_machine(&data, None, "ExistingMachine", async |result, name| {
 			assert_eq!(name, "ExistingMachine");
 			match result {
 				Ok(machine) => assert_eq!(
 					machine.mac,
 					MacAddress([0x01, 0x02, 0x03, 0x04, 0x05, 0x06])
 				),
-				Err(_) => assert!(false, "received error when it was not expected"),
+				Err(_) => panic!("received error when it was not expected"),
 			}
 		})
 		.await;
 	}
+
+	fn scoped_machines() -> BotData {
+		mock_data(Some(json!({
+			"wake_on_lan": {
+				"GlobalMachine": { "mac": [1, 2, 3, 4, 5, 6] },
+				"GuildMachine": { "mac": [1, 2, 3, 4, 5, 7], "scope": { "guild": "1" } },
+				"OtherGuildMachine": { "mac": [1, 2, 3, 4, 5, 8], "scope": { "guild": "2" } }
+			}
+		})))
+	}
+
+	#[tokio::test]
+	async fn given_machine_of_other_guild_then_describe_machine_callbacks_with_error() {
+		let data = scoped_machines();
+
+		describe_machine(&data, Some(GuildId::new(1)), "OtherGuildMachine", async |result, _| {
+			assert_eq!(
+				result,
+				Err(MachineError::DoesNotExist {
+					machine_name: "OtherGuildMachine".into(),
+				})
+			);
+		})
+		.await;
+	}
+
+	#[tokio::test]
+	async fn given_guild_scope_then_add_machine_stores_it() {
+		let data = mock_data(None);
+
+		let result = add_machine(&data, "NewMachine", "00:00:00:00:00:01", Scope::Guild(GuildId::new(1))).await;
+
+		assert_eq!(result, Ok(()));
+		assert_eq!(data.read().await.wake_on_lan["NewMachine"].scope, Scope::Guild(GuildId::new(1)));
+	}
 }
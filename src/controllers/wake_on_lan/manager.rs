@@ -0,0 +1,141 @@
+use super::{get_machine_info_mut, MachineError};
+use crate::data::BotData;
+use log::info;
+use serenity::all::UserId;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AddManagerError {
+	#[error(transparent)]
+	Machine(#[from] MachineError),
+
+	#[error("user {user} already manages machine {machine_name}")]
+	AlreadyManager { machine_name: String, user: UserId },
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum RemoveManagerError {
+	#[error(transparent)]
+	Machine(#[from] MachineError),
+
+	#[error("user {user} does not manage machine {machine_name}")]
+	NotManager { machine_name: String, user: UserId },
+}
+
+pub async fn add_manager(data: &BotData, machine_name: &str, user: UserId) -> Result<(), AddManagerError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let machine_info = get_machine_info_mut(&mut data_write, machine_name).await?;
+
+	if machine_info.managers.insert(user) {
+		info!("Made user {user} a manager of machine {machine_name}");
+		Ok(())
+	} else {
+		Err(AddManagerError::AlreadyManager {
+			machine_name: machine_name.into(),
+			user,
+		})
+	}
+}
+
+pub async fn remove_manager(data: &BotData, machine_name: &str, user: UserId) -> Result<(), RemoveManagerError> {
+	let mut lock = data.write().await;
+	let mut data_write = lock.write();
+	let machine_info = get_machine_info_mut(&mut data_write, machine_name).await?;
+
+	if machine_info.managers.remove(&user) {
+		info!("Removed user {user} from the managers of machine {machine_name}");
+		Ok(())
+	} else {
+		Err(RemoveManagerError::NotManager {
+			machine_name: machine_name.into(),
+			user,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::data::tests::mock_data;
+	use serde_json::json;
+	use std::collections::BTreeSet;
+
+	fn manager_data() -> BotData {
+		mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"managers": [12345678901234567i64]
+				}
+			}
+		})))
+	}
+
+	#[tokio::test]
+	async fn given_nonexistent_machine_then_add_manager_returns_error() {
+		let data = manager_data();
+
+		let result = add_manager(&data, "NonexistentMachine", UserId::new(1)).await;
+
+		assert_eq!(
+			result,
+			Err(AddManagerError::Machine(MachineError::DoesNotExist {
+				machine_name: "NonexistentMachine".into()
+			}))
+		);
+	}
+
+	#[tokio::test]
+	async fn given_new_manager_then_add_manager_stores_it() {
+		let data = manager_data();
+
+		let result = add_manager(&data, "ExistingMachine", UserId::new(1)).await;
+
+		assert_eq!(result, Ok(()));
+		assert_eq!(
+			data.read().await.wake_on_lan["ExistingMachine"].managers,
+			BTreeSet::from([UserId::new(1), UserId::new(12345678901234567)])
+		);
+	}
+
+	#[tokio::test]
+	async fn given_existing_manager_then_add_manager_returns_error() {
+		let data = manager_data();
+
+		let result = add_manager(&data, "ExistingMachine", UserId::new(12345678901234567)).await;
+
+		assert_eq!(
+			result,
+			Err(AddManagerError::AlreadyManager {
+				machine_name: "ExistingMachine".into(),
+				user: UserId::new(12345678901234567)
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_existing_manager_then_remove_manager_removes_it() {
+		let data = manager_data();
+
+		let result = remove_manager(&data, "ExistingMachine", UserId::new(12345678901234567)).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.wake_on_lan["ExistingMachine"].managers.is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_non_manager_then_remove_manager_returns_error() {
+		let data = manager_data();
+
+		let result = remove_manager(&data, "ExistingMachine", UserId::new(1)).await;
+
+		assert_eq!(
+			result,
+			Err(RemoveManagerError::NotManager {
+				machine_name: "ExistingMachine".into(),
+				user: UserId::new(1)
+			})
+		);
+	}
+}
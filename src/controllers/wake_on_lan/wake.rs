@@ -24,19 +24,22 @@ pub async fn wake<S: MagicPacketSender>(
 	machine_name: &str,
 	sender: &S,
 ) -> Result<(), WakeError> {
-	let data_read = data.read().await;
-
-	let machine_info = get_machine_info(&data_read, machine_name).await?;
+	let magic_packet = {
+		let data_read = data.read().await;
+		let machine_info = get_machine_info(&data_read, machine_name).await?;
+
+		if !is_user_authorized(author, member, machine_info, &data_read.access_groups) {
+			return Err(WakeError::Unauthorized {
+				user: author.id.to_owned(),
+				machine_name: machine_name.to_string(),
+			});
+		}
 
-	if !is_user_authorized(author, member, machine_info) {
-		return Err(WakeError::Unauthorized {
-			user: author.id.to_owned(),
-			machine_name: machine_name.to_string(),
-		});
-	}
+		MagicPacket::from_mac(&machine_info.mac)
+	};
 
 	sender
-		.send(&MagicPacket::from_mac(&machine_info.mac))
+		.send(&magic_packet)
 		.await
 		.map_err(|e| WakeError::Io { kind: e.kind() })
 }
@@ -45,11 +48,15 @@ pub async fn wake<S: MagicPacketSender>(
 mod tests {
 	use super::super::super::tests::{mock_author_dms, mock_author_guild};
 	use super::*;
+	use crate::controllers::authorization::permit;
+	use crate::data::wake_on_lan::WakeOnLanMachineInfo;
+	use crate::controllers::DiscordEntity;
 	use crate::data::tests::mock_data;
 	use crate::services::wake_on_lan::MacAddress;
 	use serde_json::json;
-	use serenity::all::RoleId;
+	use serenity::all::{GuildId, RoleId};
 	use std::cell::Cell;
+	use std::time::Duration;
 
 	#[derive(Default)]
 	struct MockMagicPacketSender {
@@ -277,4 +284,183 @@ mod tests {
 		assert_eq!(result, Ok(()));
 		sender.assert_packet_sent(&expected_magic_packet);
 	}
+
+	/// Writes to the data while "sending", which only completes if `wake` let go of its read lock.
+	struct WritingMagicPacketSender<'a> {
+		data: &'a BotData,
+	}
+
+	impl MagicPacketSender for WritingMagicPacketSender<'_> {
+		async fn send(&self, _magic_packet: &MagicPacket) -> std::io::Result<()> {
+			let user = DiscordEntity::User(UserId::new(11111111111111111));
+			let write = permit::<WakeOnLanMachineInfo>(self.data, "ExistingMachine", user, (), None);
+			match tokio::time::timeout(Duration::from_millis(100), write).await {
+				Ok(_) => Ok(()),
+				Err(e) => Err(std::io::Error::new(std::io::ErrorKind::TimedOut, e)),
+			}
+		}
+	}
+
+	#[tokio::test]
+	async fn given_send_in_flight_then_writes_to_data_proceed() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [12345678901234567i64]
+				}
+			}
+		})));
+		let sender = WritingMagicPacketSender { data: &data };
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let result = wake(&data, &author, member.as_ref(), "ExistingMachine", &sender).await;
+
+		assert_eq!(result, Ok(()));
+		assert!(data.read().await.wake_on_lan["ExistingMachine"]
+			.authorized_users
+			.contains(&UserId::new(11111111111111111)));
+	}
+
+	#[tokio::test]
+	async fn given_machine_of_other_guild_then_wake_returns_error() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [12345678901234567i64],
+					"scope": { "guild": "2" }
+				}
+			}
+		})));
+		let sender = MockMagicPacketSender::default();
+		let (author, mut member) = mock_author_guild(UserId::new(12345678901234567), vec![]);
+		member.as_mut().unwrap().guild_id = GuildId::new(3);
+
+		let in_other_guild = wake(&data, &author, member.as_ref(), "ExistingMachine", &sender).await;
+		let in_dms = wake(&data, &author, None, "ExistingMachine", &sender).await;
+
+		let unauthorized = Err(WakeError::Unauthorized {
+			user: UserId::new(12345678901234567),
+			machine_name: "ExistingMachine".to_owned(),
+		});
+		assert_eq!(in_other_guild, unauthorized);
+		assert_eq!(in_dms, unauthorized);
+		sender.assert_no_packet_sent();
+	}
+
+	#[tokio::test]
+	async fn given_machine_of_invoking_guild_then_wake_sends_packet() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_roles": [98765432109876543i64],
+					"scope": { "guild": "2" }
+				}
+			}
+		})));
+		let sender = MockMagicPacketSender::default();
+		let (author, mut member) =
+			mock_author_guild(UserId::new(12345678901234567), vec![RoleId::new(98765432109876543)]);
+		member.as_mut().unwrap().guild_id = GuildId::new(2);
+
+		let result = wake(&data, &author, member.as_ref(), "ExistingMachine", &sender).await;
+
+		assert_eq!(result, Ok(()));
+	}
+
+	#[tokio::test]
+	async fn given_expired_grants_then_wake_returns_error() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [12345678901234567i64],
+					"authorized_roles": [98765432109876543i64],
+					"user_expiries": { "12345678901234567": "2000-01-01T00:00:00Z" },
+					"role_expiries": { "98765432109876543": "2000-01-01T00:00:00Z" }
+				}
+			}
+		})));
+		let sender = MockMagicPacketSender::default();
+		let (author, member) =
+			mock_author_guild(UserId::new(12345678901234567), vec![RoleId::new(98765432109876543)]);
+
+		let result = wake(&data, &author, member.as_ref(), "ExistingMachine", &sender).await;
+
+		assert_eq!(
+			result,
+			Err(WakeError::Unauthorized {
+				user: UserId::new(12345678901234567),
+				machine_name: "ExistingMachine".to_owned(),
+			})
+		);
+		sender.assert_no_packet_sent();
+	}
+
+	#[tokio::test]
+	async fn given_grant_not_yet_expired_then_wake_sends_packet() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [12345678901234567i64],
+					"user_expiries": { "12345678901234567": "2999-01-01T00:00:00Z" }
+				}
+			}
+		})));
+		let sender = MockMagicPacketSender::default();
+		let (author, member) = mock_author_dms(UserId::new(12345678901234567));
+
+		let result = wake(&data, &author, member.as_ref(), "ExistingMachine", &sender).await;
+
+		assert_eq!(result, Ok(()));
+	}
+
+	#[tokio::test]
+	async fn given_member_of_allowed_role_but_denied_user_then_wake_returns_error() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_roles": [98765432109876543i64],
+					"denied": { "users": { "12345678901234567": "Spamming wakes" } }
+				}
+			}
+		})));
+		let sender = MockMagicPacketSender::default();
+		let (author, member) = mock_author_guild(UserId::new(12345678901234567), vec![RoleId::new(98765432109876543)]);
+
+		let result = wake(&data, &author, member.as_ref(), "ExistingMachine", &sender).await;
+
+		assert_eq!(
+			result,
+			Err(WakeError::Unauthorized {
+				user: UserId::new(12345678901234567),
+				machine_name: "ExistingMachine".to_string()
+			})
+		);
+		sender.assert_no_packet_sent();
+	}
+
+	#[tokio::test]
+	async fn given_allowed_user_with_denied_role_then_wake_returns_error() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"ExistingMachine": {
+					"mac": [1, 2, 3, 4, 5, 6],
+					"authorized_users": [12345678901234567i64],
+					"denied": { "roles": { "98765432109876543": null } }
+				}
+			}
+		})));
+		let sender = MockMagicPacketSender::default();
+		let (author, member) = mock_author_guild(UserId::new(12345678901234567), vec![RoleId::new(98765432109876543)]);
+
+		let result = wake(&data, &author, member.as_ref(), "ExistingMachine", &sender).await;
+
+		assert!(matches!(result, Err(WakeError::Unauthorized { .. })));
+		sender.assert_no_packet_sent();
+	}
 }
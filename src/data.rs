@@ -1,7 +1,14 @@
 mod persistent_data;
 
+use log::info;
+use poise::serenity_prelude as serenity;
+use scope::Scope;
 use serde::{Deserialize, Serialize};
-use servitor::ServitorData;
+use access::{AccessRequests, ApprovalClaims};
+use admin::AdminData;
+use group::AccessGroups;
+use servitor::{ServitorDashboards, ServitorData, ServitorSchedules};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use wake_on_lan::WakeOnLanData;
@@ -9,23 +16,89 @@ use wake_on_lan::WakeOnLanData;
 pub mod servitor;
 pub mod wake_on_lan;
 pub mod authorization;
+pub mod scope;
+pub mod admin;
+pub mod access;
+pub mod group;
 
 pub use persistent_data::*;
 
+/// Bumped whenever existing data needs to be migrated on startup.
+pub const DATA_VERSION: u32 = 3;
+
 #[derive(Deserialize, Serialize, Default)]
 pub struct Data {
 	#[serde(default)]
-	pub wake_on_lan: WakeOnLanData,
+	pub version: u32,
 	#[serde(default)]
+	pub wake_on_lan: WakeOnLanData,
+	#[serde(default, deserialize_with = "servitor::deserialize_servers")]
 	pub servitor: ServitorData,
+	#[serde(default)]
+	pub servitor_dashboards: ServitorDashboards,
+	#[serde(default)]
+	pub servitor_schedules: ServitorSchedules,
+	/// Highest scheduled action ID handed out so far, see [`next_id`].
+	#[serde(default)]
+	pub last_servitor_schedule: u64,
+	#[serde(default)]
+	pub admins: AdminData,
+	#[serde(default)]
+	pub access_requests: AccessRequests,
+	/// Highest access request ID handed out so far, see [`next_id`].
+	#[serde(default)]
+	pub last_access_request: u64,
+	#[serde(default)]
+	pub approval_claims: ApprovalClaims,
+	#[serde(default)]
+	pub access_groups: AccessGroups,
 }
 
 pub type BotData = Arc<RwLock<PersistentJson<Data>>>;
 
+/// Hands out the ID after `last` and every key of `taken`, so that an ID is never reused once its entry is removed.
+pub fn next_id<T>(last: &mut u64, taken: &BTreeMap<u64, T>) -> u64 {
+	let id = taken.keys().next_back().map_or(*last, |taken| (*taken).max(*last)) + 1;
+	*last = id;
+	id
+}
+
+impl Data {
+	pub fn needs_migration(&self) -> bool {
+		self.version < DATA_VERSION
+	}
+
+	/// Brings data written by older versions up to date, `guild` owns the machines and servers that predate scoping.
+	pub fn migrate(&mut self, guild: Option<serenity::GuildId>) {
+		if self.version < 1 {
+			let scope = Scope::of(guild);
+			self.wake_on_lan.values_mut().for_each(|machine| machine.scope = scope);
+			self.servitor.values_mut().for_each(|server| server.scope = scope);
+			info!(
+				"Assigned {} machines and {} servers to scope {scope:?}",
+				self.wake_on_lan.len(),
+				self.servitor.len()
+			);
+		}
+
+		if self.version < 2 {
+			self.last_access_request = self.access_requests.keys().next_back().copied().unwrap_or_default();
+			info!("Continuing access request IDs after {}", self.last_access_request);
+		}
+
+		if self.version < 3 {
+			self.last_servitor_schedule = self.servitor_schedules.keys().next_back().copied().unwrap_or_default();
+			info!("Continuing scheduled action IDs after {}", self.last_servitor_schedule);
+		}
+
+		self.version = DATA_VERSION;
+	}
+}
+
 #[cfg(test)]
 pub mod tests {
 	use super::*;
-	use serde_json::Value;
+	use serde_json::{json, Value};
 	use std::io::Write;
 	use tempfile::NamedTempFile;
 	pub fn mock_data(initial_data: Option<Value>) -> BotData {
@@ -41,4 +114,86 @@ pub mod tests {
 
 		Arc::new(RwLock::new(persistent_data))
 	}
+
+	#[tokio::test]
+	async fn given_unversioned_data_then_migrate_assigns_entries_to_guild() {
+		let data = mock_data(Some(json!({
+			"wake_on_lan": {
+				"SomeMachine": { "mac": [1, 2, 3, 4, 5, 6] }
+			},
+			"servitor": {
+				"SomeServer": { "servitor": "foo", "unit_name": "bar" }
+			}
+		})));
+		let mut lock = data.write().await;
+		assert!(lock.needs_migration());
+
+		lock.write().migrate(Some(serenity::GuildId::new(1)));
+
+		let guild = Scope::Guild(serenity::GuildId::new(1));
+		assert_eq!(lock.wake_on_lan["SomeMachine"].scope, guild);
+		assert_eq!(lock.servitor["SomeServer"].scope, guild);
+		assert!(!lock.needs_migration());
+	}
+
+	#[tokio::test]
+	async fn given_data_before_request_counter_then_migrate_continues_after_pending_requests() {
+		let data = mock_data(Some(json!({
+			"version": 1,
+			"access_requests": {
+				"4": {
+					"user": "1",
+					"resource": { "kind": "machine", "name": "SomeMachine" },
+					"requested_at": "2025-03-06T19:59:45Z"
+				}
+			}
+		})));
+		let mut lock = data.write().await;
+		assert!(lock.needs_migration());
+
+		lock.write().migrate(None);
+
+		assert_eq!(lock.last_access_request, 4);
+		assert!(!lock.needs_migration());
+	}
+
+	#[tokio::test]
+	async fn given_data_before_schedule_counter_then_migrate_continues_after_scheduled_actions() {
+		let data = mock_data(Some(json!({
+			"version": 2,
+			"servitor_schedules": {
+				"7": {
+					"server_name": "SomeServer",
+					"action": "restart",
+					"user": "1",
+					"channel": "1",
+					"schedule": { "kind": "once" },
+					"next_run": "2025-03-07T04:00:00Z"
+				}
+			}
+		})));
+		let mut lock = data.write().await;
+		assert!(lock.needs_migration());
+
+		lock.write().migrate(None);
+
+		assert_eq!(lock.last_servitor_schedule, 7);
+		assert_eq!(lock.last_access_request, 0);
+		assert!(!lock.needs_migration());
+	}
+
+	#[tokio::test]
+	async fn given_current_data_then_migrate_keeps_scopes() {
+		let data = mock_data(Some(json!({
+			"version": DATA_VERSION,
+			"servitor": {
+				"SomeServer": { "servitor": "foo", "unit_name": "bar" }
+			}
+		})));
+		let mut lock = data.write().await;
+
+		lock.write().migrate(Some(serenity::GuildId::new(1)));
+
+		assert_eq!(lock.servitor["SomeServer"].scope, Scope::Global);
+	}
 }
@@ -0,0 +1,61 @@
+use super::servitor::ServitorAction;
+use chrono::{DateTime, Utc};
+use poise::serenity_prelude as serenity;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// What an access request asks for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum AccessResource {
+	Machine { name: String },
+	Server { name: String, action: ServitorAction },
+}
+
+/// A pending request of an unauthorized user, waiting for an admin or manager of the resource to review it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessRequest {
+	pub user: serenity::UserId,
+	/// Guild the request was made from, reviewers answering from DMs are checked as members of it.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub guild: Option<serenity::GuildId>,
+	pub resource: AccessResource,
+	pub requested_at: DateTime<Utc>,
+}
+
+pub type AccessRequests = BTreeMap<u64, AccessRequest>;
+
+/// Messages of single use approvals that someone already decided on.
+pub type ApprovalClaims = BTreeSet<serenity::MessageId>;
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn given_server_request_then_serializes_tagged_resource() {
+		let request = AccessRequest {
+			user: serenity::UserId::new(1),
+			guild: None,
+			resource: AccessResource::Server {
+				name: "SomeServer".to_string(),
+				action: ServitorAction::Start,
+			},
+			requested_at: DateTime::from_timestamp(1741291185, 0).unwrap(),
+		};
+
+		let value = serde_json::to_value(&request).unwrap();
+
+		assert_eq!(
+			value,
+			json!({
+				"user": "1",
+				"resource": { "kind": "server", "name": "SomeServer", "action": "start" },
+				"requested_at": "2025-03-06T19:59:45Z"
+			})
+		);
+		assert_eq!(serde_json::from_value::<AccessRequest>(value).unwrap(), request);
+	}
+}
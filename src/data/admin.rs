@@ -0,0 +1,59 @@
+use super::scope::Scope;
+use poise::serenity_prelude as serenity;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Users and roles that may run management commands besides the bot owners, within their scope.
+#[derive(Serialize, Deserialize, Default)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub struct AdminData {
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	#[serde(default)]
+	pub users: BTreeMap<serenity::UserId, Scope>,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	#[serde(default)]
+	pub roles: BTreeMap<serenity::RoleId, Scope>,
+}
+
+impl AdminData {
+	/// Scopes the author administers, through their own entry or through any of their roles.
+	pub fn scopes_of<'a>(
+		&'a self,
+		user: serenity::UserId,
+		roles: &'a [serenity::RoleId],
+	) -> impl Iterator<Item = Scope> + 'a {
+		self.users
+			.get(&user)
+			.into_iter()
+			.chain(roles.iter().filter_map(|role| self.roles.get(role)))
+			.copied()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn given_user_and_role_entries_then_scopes_of_collects_both() {
+		let admins: AdminData = serde_json::from_value(json!({
+			"users": { "1": "global" },
+			"roles": { "10": { "guild": "100" }, "11": "global" }
+		}))
+		.unwrap();
+
+		let scopes = admins
+			.scopes_of(serenity::UserId::new(1), &[serenity::RoleId::new(10), serenity::RoleId::new(12)])
+			.collect::<Vec<_>>();
+
+		assert_eq!(scopes, vec![Scope::Global, Scope::Guild(serenity::GuildId::new(100))]);
+	}
+
+	#[test]
+	fn given_unknown_user_without_roles_then_scopes_of_is_empty() {
+		let admins = AdminData::default();
+
+		assert_eq!(admins.scopes_of(serenity::UserId::new(1), &[]).count(), 0);
+	}
+}
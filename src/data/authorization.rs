@@ -1,7 +1,97 @@
+use super::scope::Scope;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use serenity::all::{RoleId, UserId};
-use std::collections::BTreeSet;
+use std::collections::BTreeMap;
+
+/// When temporary grants stop applying, entities without an entry never expire.
+pub type GrantExpiries<T> = BTreeMap<T, DateTime<Utc>>;
+
+/// Users and roles refused access regardless of what they are granted, with the reason they were denied, if any.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Denials {
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	#[serde(default)]
+	pub users: BTreeMap<UserId, Option<String>>,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	#[serde(default)]
+	pub roles: BTreeMap<RoleId, Option<String>>,
+}
+
+impl Denials {
+	pub fn is_empty(&self) -> bool {
+		self.users.is_empty() && self.roles.is_empty()
+	}
+
+	/// Whether the user, if any, or any of the roles is denied.
+	pub fn denies(&self, user: Option<UserId>, roles: &[RoleId]) -> bool {
+		user.is_some_and(|user| self.users.contains_key(&user)) || roles.iter().any(|role| self.roles.contains_key(role))
+	}
+}
 
 pub trait AuthorizationInfo {
-	fn authorized_users(&self) -> &BTreeSet<UserId>;
-	fn authorized_roles(&self) -> &BTreeSet<RoleId>;
+	fn allows_user(&self, user: UserId, now: DateTime<Utc>) -> bool;
+	fn allows_role(&self, role: RoleId, now: DateTime<Utc>) -> bool;
+	fn allows_group(&self, group: &str) -> bool;
+	fn denials(&self) -> &Denials;
+	fn scope(&self) -> Scope;
+}
+
+pub fn is_expired<T: Ord>(expiries: &GrantExpiries<T>, id: &T, now: DateTime<Utc>) -> bool {
+	expiries.get(id).is_some_and(|expiry| *expiry <= now)
+}
+
+/// Sets the expiry of `id`, or makes it permanent when `None`, returning whether that changed anything.
+pub fn set_expiry<T: Ord>(expiries: &mut GrantExpiries<T>, id: T, expires: Option<DateTime<Utc>>) -> bool {
+	match expires {
+		Some(at) => expiries.insert(id, at) != Some(at),
+		None => expiries.remove(&id).is_some(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::TimeZone;
+
+	fn now() -> DateTime<Utc> {
+		Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap()
+	}
+
+	#[test]
+	fn given_expiries_then_is_expired_only_for_past_ones() {
+		let expiries = GrantExpiries::from([
+			(UserId::new(1), now()),
+			(UserId::new(2), now() + chrono::TimeDelta::seconds(1)),
+		]);
+
+		assert!(is_expired(&expiries, &UserId::new(1), now()));
+		assert!(!is_expired(&expiries, &UserId::new(2), now()));
+		assert!(!is_expired(&expiries, &UserId::new(3), now()));
+	}
+
+	#[test]
+	fn given_expiry_then_set_expiry_reports_changes() {
+		let mut expiries = GrantExpiries::new();
+
+		assert!(set_expiry(&mut expiries, UserId::new(1), Some(now())));
+		assert!(!set_expiry(&mut expiries, UserId::new(1), Some(now())));
+		assert!(set_expiry(&mut expiries, UserId::new(1), None));
+		assert!(!set_expiry(&mut expiries, UserId::new(1), None));
+		assert!(expiries.is_empty());
+	}
+
+	#[test]
+	fn given_denials_then_denies_users_and_members_of_denied_roles() {
+		let denials = Denials {
+			users: BTreeMap::from([(UserId::new(1), Some("Spamming".to_string()))]),
+			roles: BTreeMap::from([(RoleId::new(10), None)]),
+		};
+
+		assert!(denials.denies(Some(UserId::new(1)), &[]));
+		assert!(denials.denies(Some(UserId::new(2)), &[RoleId::new(11), RoleId::new(10)]));
+		assert!(denials.denies(None, &[RoleId::new(10)]));
+		assert!(!denials.denies(Some(UserId::new(2)), &[RoleId::new(11)]));
+		assert!(!denials.denies(None, &[]));
+	}
 }
@@ -0,0 +1,37 @@
+use poise::serenity_prelude as serenity;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub type AccessGroups = BTreeMap<String, AccessGroup>;
+
+/// Users and roles that are granted access together, changes apply to every resource the group is granted on.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccessGroup {
+	#[serde(skip_serializing_if = "BTreeSet::is_empty")]
+	#[serde(default)]
+	pub users: BTreeSet<serenity::UserId>,
+	#[serde(skip_serializing_if = "BTreeSet::is_empty")]
+	#[serde(default)]
+	pub roles: BTreeSet<serenity::RoleId>,
+}
+
+impl AccessGroup {
+	pub fn contains(&self, user: serenity::UserId, roles: &[serenity::RoleId]) -> bool {
+		self.users.contains(&user) || roles.iter().any(|role| self.roles.contains(role))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn given_group_then_contains_its_users_and_members_of_its_roles() {
+		let group: AccessGroup = serde_json::from_value(json!({ "users": ["1"], "roles": ["10"] })).unwrap();
+
+		assert!(group.contains(serenity::UserId::new(1), &[]));
+		assert!(group.contains(serenity::UserId::new(2), &[serenity::RoleId::new(11), serenity::RoleId::new(10)]));
+		assert!(!group.contains(serenity::UserId::new(2), &[serenity::RoleId::new(11)]));
+	}
+}
@@ -15,7 +15,7 @@ pub struct PersistentWriteGuard<'a, T: PersistentData> {
 	path: &'a Path,
 }
 
-impl<'a, T: PersistentData> PersistentWriteGuard<'a, T> {
+impl<T: PersistentData> PersistentWriteGuard<'_, T> {
 	fn write_to_file(&self) -> Result<()> {
 		let json = if cfg!(debug_assertions) {
 			serde_json::to_string_pretty(&*self.data)?
@@ -35,21 +35,21 @@ impl<'a, T: PersistentData> PersistentWriteGuard<'a, T> {
 	}
 }
 
-impl<'a, T: PersistentData> Deref for PersistentWriteGuard<'a, T> {
+impl<T: PersistentData> Deref for PersistentWriteGuard<'_, T> {
 	type Target = T;
 
 	fn deref(&self) -> &Self::Target {
-		&self.data
+		self.data
 	}
 }
 
-impl<'a, T: PersistentData> DerefMut for PersistentWriteGuard<'a, T> {
+impl<T: PersistentData> DerefMut for PersistentWriteGuard<'_, T> {
 	fn deref_mut(&mut self) -> &mut Self::Target {
-		&mut self.data
+		self.data
 	}
 }
 
-impl<'a, T: PersistentData> Drop for PersistentWriteGuard<'a, T> {
+impl<T: PersistentData> Drop for PersistentWriteGuard<'_, T> {
 	fn drop(&mut self) {
 		if let Err(e) = self.write_to_file() {
 			error!("Failed to write persistent data to file {}: {}", self.path.display(), e);
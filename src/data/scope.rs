@@ -0,0 +1,60 @@
+use poise::serenity_prelude as serenity;
+use serde::{Deserialize, Serialize};
+
+/// Where a machine or server can be seen and used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+	/// Usable from every guild and from DMs.
+	#[default]
+	Global,
+	Guild(serenity::GuildId),
+}
+
+impl Scope {
+	/// The scope of resources created from `guild`, or from DMs when `None`.
+	pub fn of(guild: Option<serenity::GuildId>) -> Self {
+		guild.map_or(Scope::Global, Scope::Guild)
+	}
+
+	pub fn is_global(&self) -> bool {
+		*self == Scope::Global
+	}
+
+	pub fn is_visible_in(&self, guild: Option<serenity::GuildId>) -> bool {
+		match self {
+			Scope::Global => true,
+			Scope::Guild(id) => guild == Some(*id),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+	use serde_json::json;
+
+	#[rstest]
+	#[case(Scope::Global, None, true)]
+	#[case(Scope::Global, Some(1), true)]
+	#[case(Scope::Guild(serenity::GuildId::new(1)), Some(1), true)]
+	#[case(Scope::Guild(serenity::GuildId::new(1)), Some(2), false)]
+	#[case(Scope::Guild(serenity::GuildId::new(1)), None, false)]
+	fn given_scope_then_is_visible_only_in_its_guild(
+		#[case] scope: Scope,
+		#[case] guild: Option<u64>,
+		#[case] expected: bool,
+	) {
+		assert_eq!(scope.is_visible_in(guild.map(serenity::GuildId::new)), expected);
+	}
+
+	#[test]
+	fn given_scopes_then_serialize_as_global_or_guild_id() {
+		assert_eq!(serde_json::to_value(Scope::Global).unwrap(), json!("global"));
+		assert_eq!(
+			serde_json::to_value(Scope::Guild(serenity::GuildId::new(123))).unwrap(),
+			json!({ "guild": "123" })
+		);
+	}
+}
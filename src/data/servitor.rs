@@ -1,28 +1,488 @@
-use crate::data::authorization::AuthorizationInfo;
+use crate::data::authorization::{is_expired, AuthorizationInfo, Denials, GrantExpiries};
+use crate::data::scope::Scope;
+use chrono::{DateTime, Utc};
 use poise::serenity_prelude as serenity;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
 
 pub type ServitorData = BTreeMap<String, ServerInfo>;
+pub type ServitorDashboards = BTreeMap<serenity::ChannelId, Dashboard>;
+pub type ActionGrants<T> = BTreeMap<T, BTreeSet<ServitorAction>>;
+/// When temporary grants of single actions stop applying, actions without an entry never expire.
+pub type ActionExpiries<T> = BTreeMap<T, GrantExpiries<ServitorAction>>;
+pub type ServitorSchedules = BTreeMap<u64, ScheduledAction>;
 
 #[derive(Serialize, Deserialize)]
 #[cfg_attr(test, derive(Debug, PartialEq))]
 pub struct ServerInfo {
 	pub servitor: String,
 	pub unit_name: String,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	#[serde(default, deserialize_with = "deserialize_grants")]
+	pub authorized_users: ActionGrants<serenity::UserId>,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	#[serde(default, deserialize_with = "deserialize_grants")]
+	pub authorized_roles: ActionGrants<serenity::RoleId>,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	#[serde(default, deserialize_with = "deserialize_expiries")]
+	pub user_expiries: ActionExpiries<serenity::UserId>,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	#[serde(default, deserialize_with = "deserialize_expiries")]
+	pub role_expiries: ActionExpiries<serenity::RoleId>,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	#[serde(default)]
+	pub authorized_groups: ActionGrants<String>,
+	#[serde(skip_serializing_if = "Denials::is_empty")]
+	#[serde(default)]
+	pub denied: Denials,
 	#[serde(skip_serializing_if = "BTreeSet::is_empty")]
 	#[serde(default)]
-	pub authorized_users: BTreeSet<serenity::UserId>,
+	pub watch_channels: BTreeSet<serenity::ChannelId>,
+	#[serde(skip_serializing_if = "is_false")]
+	#[serde(default)]
+	pub skip_confirmation: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub idle: Option<IdlePolicy>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub query: Option<QueryConfig>,
+	#[serde(skip_serializing_if = "Scope::is_global")]
+	#[serde(default)]
+	pub scope: Scope,
+	/// Users allowed to manage the settings and grants of this server, without being admins.
 	#[serde(skip_serializing_if = "BTreeSet::is_empty")]
 	#[serde(default)]
-	pub authorized_roles: BTreeSet<serenity::RoleId>,
+	pub managers: BTreeSet<serenity::UserId>,
+}
+
+fn is_false(value: &bool) -> bool {
+	!value
+}
+
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Serialize,
+	Deserialize,
+	poise::ChoiceParameter,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ServitorAction {
+	#[name = "status"]
+	Status,
+	#[name = "start"]
+	Start,
+	#[name = "stop"]
+	Stop,
+	#[name = "restart"]
+	Restart,
+	#[name = "reload"]
+	Reload,
+	#[name = "logs"]
+	Logs,
+}
+
+impl ServitorAction {
+	pub const ALL: [ServitorAction; 6] = [
+		ServitorAction::Status,
+		ServitorAction::Start,
+		ServitorAction::Stop,
+		ServitorAction::Restart,
+		ServitorAction::Reload,
+		ServitorAction::Logs,
+	];
+
+	pub fn all() -> BTreeSet<ServitorAction> {
+		BTreeSet::from(Self::ALL)
+	}
+}
+
+impl fmt::Display for ServitorAction {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let name = match self {
+			ServitorAction::Status => "status",
+			ServitorAction::Start => "start",
+			ServitorAction::Stop => "stop",
+			ServitorAction::Restart => "restart",
+			ServitorAction::Reload => "reload",
+			ServitorAction::Logs => "logs",
+		};
+		write!(f, "{name}")
+	}
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("unknown Servitor action {0}")]
+pub struct ParseServitorActionError(pub String);
+
+impl FromStr for ServitorAction {
+	type Err = ParseServitorActionError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		Self::ALL
+			.into_iter()
+			.find(|action| action.to_string().eq_ignore_ascii_case(s))
+			.ok_or_else(|| ParseServitorActionError(s.to_string()))
+	}
+}
+
+/// Parses a comma separated list of actions, where `all` or an empty list means every action.
+pub fn parse_actions(s: &str) -> Result<BTreeSet<ServitorAction>, ParseServitorActionError> {
+	let actions = s
+		.split(',')
+		.map(str::trim)
+		.filter(|a| !a.is_empty())
+		.map(|a| match a.eq_ignore_ascii_case("all") {
+			true => Ok(ServitorAction::all()),
+			false => a.parse().map(|action| BTreeSet::from([action])),
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	if actions.is_empty() {
+		Ok(ServitorAction::all())
+	} else {
+		Ok(actions.into_iter().flatten().collect())
+	}
+}
+
+/// Grants used to be a plain list of IDs allowed to do anything, those are read as having every action.
+fn deserialize_grants<'de, D, T>(deserializer: D) -> Result<ActionGrants<T>, D::Error>
+where
+	D: Deserializer<'de>,
+	T: Deserialize<'de> + Ord,
+{
+	#[derive(Deserialize)]
+	#[serde(untagged)]
+	enum Grants<T: Ord> {
+		Legacy(BTreeSet<T>),
+		PerAction(ActionGrants<T>),
+	}
+
+	Ok(match Grants::deserialize(deserializer)? {
+		Grants::Legacy(ids) => ids.into_iter().map(|id| (id, ServitorAction::all())).collect(),
+		Grants::PerAction(grants) => grants,
+	})
+}
+
+/// Expiries used to apply to everything granted to an entity, those are read as the expiry of every action.
+fn deserialize_expiries<'de, D, T>(deserializer: D) -> Result<ActionExpiries<T>, D::Error>
+where
+	D: Deserializer<'de>,
+	T: Deserialize<'de> + Ord,
+{
+	#[derive(Deserialize)]
+	#[serde(untagged)]
+	enum Expiries<T: Ord> {
+		Legacy(GrantExpiries<T>),
+		PerAction(ActionExpiries<T>),
+	}
+
+	Ok(match Expiries::deserialize(deserializer)? {
+		Expiries::Legacy(expiries) => expiries
+			.into_iter()
+			.map(|(id, expiry)| (id, ServitorAction::ALL.iter().map(|action| (*action, expiry)).collect()))
+			.collect(),
+		Expiries::PerAction(expiries) => expiries,
+	})
+}
+
+/// Drops the expiries of actions that are not granted, like those of legacy expiries read as every action.
+fn retain_granted_expiries<T: Ord>(expiries: &mut ActionExpiries<T>, grants: &ActionGrants<T>) {
+	expiries.retain(|id, actions| {
+		actions.retain(|action, _| grants.get(id).is_some_and(|granted| granted.contains(action)));
+		!actions.is_empty()
+	});
+}
+
+fn is_action_expired<T: Ord>(expiries: &ActionExpiries<T>, id: &T, action: ServitorAction, now: DateTime<Utc>) -> bool {
+	expiries.get(id).is_some_and(|actions| is_expired(actions, &action, now))
+}
+
+/// Expiries of actions that are not granted are dropped, legacy expiries being read as the expiry of every action.
+pub(super) fn deserialize_servers<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ServitorData, D::Error> {
+	let mut servers = ServitorData::deserialize(deserializer)?;
+
+	for info in servers.values_mut() {
+		retain_granted_expiries(&mut info.user_expiries, &info.authorized_users);
+		retain_granted_expiries(&mut info.role_expiries, &info.authorized_roles);
+	}
+
+	Ok(servers)
+}
+
+/// How to ask the game running on the server for its players, the address being `host` or `host:port`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum QueryConfig {
+	A2s { address: String },
+	Minecraft { address: String },
+}
+
+/// Stops the server once the probe saw no activity for `minutes`, unless kept alive until a later time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdlePolicy {
+	pub probe: IdleProbe,
+	pub minutes: u32,
+	pub channel: serenity::ChannelId,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub keep_alive_until: Option<DateTime<Utc>>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdleProbe {
+	/// Players counted by the server's game query.
+	Query,
+	/// Established TCP connections to a port on the bot's host, only meaningful for servers of a local Servitor.
+	Connections { port: u16 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, poise::ChoiceParameter)]
+#[serde(rename_all = "lowercase")]
+pub enum SchedulableAction {
+	#[name = "start"]
+	Start,
+	#[name = "stop"]
+	Stop,
+	#[name = "restart"]
+	Restart,
+	#[name = "reload"]
+	Reload,
+}
+
+impl From<SchedulableAction> for ServitorAction {
+	fn from(action: SchedulableAction) -> Self {
+		match action {
+			SchedulableAction::Start => ServitorAction::Start,
+			SchedulableAction::Stop => ServitorAction::Stop,
+			SchedulableAction::Restart => ServitorAction::Restart,
+			SchedulableAction::Reload => ServitorAction::Reload,
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Schedule {
+	Once,
+	Cron { expression: String, timezone: String },
+}
+
+/// A dashboard message showing the servers and machines visible in `guild`, or in DMs when `None`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Dashboard {
+	pub message: serenity::MessageId,
+	#[serde(default)]
+	pub guild: Option<serenity::GuildId>,
+}
+
+/// An action run on behalf of `user` at `next_run`, its result is posted to `channel`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledAction {
+	pub server_name: String,
+	pub action: SchedulableAction,
+	pub user: serenity::UserId,
+	#[serde(default)]
+	pub guild: Option<serenity::GuildId>,
+	pub channel: serenity::ChannelId,
+	pub schedule: Schedule,
+	pub next_run: DateTime<Utc>,
+}
+
+pub struct ServerActionInfo<'a> {
+	server_info: &'a ServerInfo,
+	action: ServitorAction,
+}
+
+impl ServerInfo {
+	pub fn for_action(&self, action: ServitorAction) -> ServerActionInfo<'_> {
+		ServerActionInfo {
+			server_info: self,
+			action,
+		}
+	}
+}
+
+/// Allows whoever may run any action on the server, checking a single action goes through [`ServerInfo::for_action`].
 impl AuthorizationInfo for ServerInfo {
-	fn authorized_users(&self) -> &BTreeSet<serenity::UserId> {
-		&self.authorized_users
+	fn allows_user(&self, user: serenity::UserId, now: DateTime<Utc>) -> bool {
+		self.authorized_users.get(&user).is_some_and(|actions| {
+			actions.iter().any(|action| !is_action_expired(&self.user_expiries, &user, *action, now))
+		})
+	}
+	fn allows_role(&self, role: serenity::RoleId, now: DateTime<Utc>) -> bool {
+		self.authorized_roles.get(&role).is_some_and(|actions| {
+			actions.iter().any(|action| !is_action_expired(&self.role_expiries, &role, *action, now))
+		})
+	}
+	fn allows_group(&self, group: &str) -> bool {
+		self.authorized_groups.get(group).is_some_and(|actions| !actions.is_empty())
+	}
+	fn denials(&self) -> &Denials {
+		&self.denied
+	}
+	fn scope(&self) -> Scope {
+		self.scope
 	}
-	fn authorized_roles(&self) -> &BTreeSet<serenity::RoleId> {
-		&self.authorized_roles
+}
+
+impl AuthorizationInfo for ServerActionInfo<'_> {
+	fn allows_user(&self, user: serenity::UserId, now: DateTime<Utc>) -> bool {
+		self.server_info
+			.authorized_users
+			.get(&user)
+			.is_some_and(|actions| actions.contains(&self.action))
+			&& !is_action_expired(&self.server_info.user_expiries, &user, self.action, now)
+	}
+	fn allows_role(&self, role: serenity::RoleId, now: DateTime<Utc>) -> bool {
+		self.server_info
+			.authorized_roles
+			.get(&role)
+			.is_some_and(|actions| actions.contains(&self.action))
+			&& !is_action_expired(&self.server_info.role_expiries, &role, self.action, now)
+	}
+	fn allows_group(&self, group: &str) -> bool {
+		self.server_info
+			.authorized_groups
+			.get(group)
+			.is_some_and(|actions| actions.contains(&self.action))
+	}
+	fn denials(&self) -> &Denials {
+		&self.server_info.denied
+	}
+	fn scope(&self) -> Scope {
+		self.server_info.scope
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn given_legacy_grant_lists_then_deserialize_as_all_actions() {
+		let info: ServerInfo = serde_json::from_value(json!({
+			"servitor": "foo",
+			"unit_name": "bar",
+			"authorized_users": [12345678901234567u64],
+			"authorized_roles": ["98765432109876543"]
+		}))
+		.unwrap();
+
+		assert_eq!(
+			info.authorized_users,
+			BTreeMap::from([(serenity::UserId::new(12345678901234567), ServitorAction::all())])
+		);
+		assert_eq!(
+			info.authorized_roles,
+			BTreeMap::from([(serenity::RoleId::new(98765432109876543), ServitorAction::all())])
+		);
+	}
+
+	#[test]
+	fn given_legacy_expiries_then_deserialize_as_expiries_of_the_granted_actions() {
+		let data: crate::data::Data = serde_json::from_value(json!({
+			"servitor": {
+				"SomeServer": {
+					"servitor": "foo",
+					"unit_name": "bar",
+					"authorized_users": { "12345678901234567": ["status", "start"] },
+					"user_expiries": { "12345678901234567": "2025-03-06T19:59:45Z", "23456789012345678": "2025-03-06T19:59:45Z" }
+				}
+			}
+		}))
+		.unwrap();
+		let expiry = "2025-03-06T19:59:45Z".parse::<DateTime<Utc>>().unwrap();
+
+		assert_eq!(
+			data.servitor["SomeServer"].user_expiries,
+			BTreeMap::from([(
+				serenity::UserId::new(12345678901234567),
+				BTreeMap::from([(ServitorAction::Status, expiry), (ServitorAction::Start, expiry)])
+			)])
+		);
+	}
+
+	#[test]
+	fn given_per_action_grants_then_roundtrip_through_json() {
+		let info: ServerInfo = serde_json::from_value(json!({
+			"servitor": "foo",
+			"unit_name": "bar",
+			"authorized_users": {
+				"12345678901234567": ["status", "logs"]
+			}
+		}))
+		.unwrap();
+
+		assert_eq!(
+			info.authorized_users,
+			BTreeMap::from([(
+				serenity::UserId::new(12345678901234567),
+				BTreeSet::from([ServitorAction::Status, ServitorAction::Logs])
+			)])
+		);
+		assert_eq!(
+			serde_json::to_value(&info).unwrap(),
+			json!({
+				"servitor": "foo",
+				"unit_name": "bar",
+				"authorized_users": {
+					"12345678901234567": ["status", "logs"]
+				}
+			})
+		);
+	}
+
+	#[test]
+	fn given_partly_expired_actions_then_server_allows_user_until_every_action_expired() {
+		let info: ServerInfo = serde_json::from_value(json!({
+			"servitor": "foo",
+			"unit_name": "bar",
+			"authorized_users": { "1": ["status", "start"], "2": ["start"] },
+			"user_expiries": {
+				"1": { "start": "2025-03-06T19:00:00Z" },
+				"2": { "start": "2025-03-06T19:00:00Z" }
+			}
+		}))
+		.unwrap();
+		let now = "2025-03-06T20:00:00Z".parse().unwrap();
+
+		assert!(info.allows_user(serenity::UserId::new(1), now));
+		assert!(!info.allows_user(serenity::UserId::new(2), now));
+		assert!(!info.allows_user(serenity::UserId::new(3), now));
+	}
+
+	#[test]
+	fn given_action_list_then_parse_actions_returns_actions() {
+		assert_eq!(
+			parse_actions(" Start, stop,,status "),
+			Ok(BTreeSet::from([
+				ServitorAction::Status,
+				ServitorAction::Start,
+				ServitorAction::Stop
+			]))
+		);
+		assert_eq!(parse_actions("all"), Ok(ServitorAction::all()));
+		assert_eq!(parse_actions(""), Ok(ServitorAction::all()));
+	}
+
+	#[test]
+	fn given_unknown_action_then_parse_actions_returns_error() {
+		assert_eq!(
+			parse_actions("start,explode"),
+			Err(ParseServitorActionError("explode".to_string()))
+		);
 	}
 }
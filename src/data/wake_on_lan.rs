@@ -1,4 +1,6 @@
-use crate::data::authorization::AuthorizationInfo;
+use crate::data::authorization::{is_expired, AuthorizationInfo, Denials, GrantExpiries};
+use chrono::{DateTime, Utc};
+use crate::data::scope::Scope;
 use crate::services::wake_on_lan::MacAddress;
 use poise::serenity_prelude as serenity;
 use serde::{Deserialize, Serialize};
@@ -16,13 +18,45 @@ pub struct WakeOnLanMachineInfo {
 	#[serde(skip_serializing_if = "BTreeSet::is_empty")]
 	#[serde(default)]
 	pub authorized_roles: BTreeSet<serenity::RoleId>,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	#[serde(default)]
+	pub user_expiries: GrantExpiries<serenity::UserId>,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	#[serde(default)]
+	pub role_expiries: GrantExpiries<serenity::RoleId>,
+	#[serde(skip_serializing_if = "BTreeSet::is_empty")]
+	#[serde(default)]
+	pub authorized_groups: BTreeSet<String>,
+	#[serde(skip_serializing_if = "Denials::is_empty")]
+	#[serde(default)]
+	pub denied: Denials,
+	#[serde(skip_serializing_if = "Scope::is_global")]
+	#[serde(default)]
+	pub scope: Scope,
+	/// Users allowed to manage who may wake this machine, without being admins.
+	#[serde(skip_serializing_if = "BTreeSet::is_empty")]
+	#[serde(default)]
+	pub managers: BTreeSet<serenity::UserId>,
+	/// Where the machine accepts connections while it is online, like host:port.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(default)]
+	pub address: Option<String>,
 }
 
 impl AuthorizationInfo for WakeOnLanMachineInfo {
-	fn authorized_users(&self) -> &BTreeSet<serenity::UserId> {
-		&self.authorized_users
+	fn allows_user(&self, user: serenity::UserId, now: DateTime<Utc>) -> bool {
+		self.authorized_users.contains(&user) && !is_expired(&self.user_expiries, &user, now)
+	}
+	fn allows_role(&self, role: serenity::RoleId, now: DateTime<Utc>) -> bool {
+		self.authorized_roles.contains(&role) && !is_expired(&self.role_expiries, &role, now)
+	}
+	fn allows_group(&self, group: &str) -> bool {
+		self.authorized_groups.contains(group)
+	}
+	fn denials(&self) -> &Denials {
+		&self.denied
 	}
-	fn authorized_roles(&self) -> &BTreeSet<serenity::RoleId> {
-		&self.authorized_roles
+	fn scope(&self) -> Scope {
+		self.scope
 	}
 }
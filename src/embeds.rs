@@ -1,4 +1,7 @@
 use poise::serenity_prelude::{Colour, CreateEmbed};
+use crate::components::MAX_NAME_LENGTH;
+
+pub const DISCORD_MAX_EMBED_DESCRIPTION_LENGTH: usize = 4096;
 
 pub fn success<S: AsRef<str>>(title: S, description: impl Into<String>) -> CreateEmbed {
 	pub const COLOR_SUCCESS: Colour = Colour(0x77b255);
@@ -41,6 +44,32 @@ pub fn invalid_servitor_server<S: AsRef<str>>(server_name: S) -> CreateEmbed {
 	)
 }
 
+pub fn name_too_long<S: AsRef<str>>(name: S) -> CreateEmbed {
+	let name = name.as_ref();
+	error(
+		"Name too long",
+		format!("{name} is longer than {MAX_NAME_LENGTH} characters, try a shorter name"),
+	)
+}
+
+pub fn invalid_group<S: AsRef<str>>(group: S) -> CreateEmbed {
+	let group = group.as_ref();
+	error("Invalid Group", format!("No access group with name {group} exists"))
+}
+
+pub fn missing_entity<S: AsRef<str>>(kind: S) -> CreateEmbed {
+	let kind = kind.as_ref();
+	error(format!("Missing {kind}"), "Pick exactly one user or role")
+}
+
+pub fn invalid_duration<S: AsRef<str>>(duration: S) -> CreateEmbed {
+	let duration = duration.as_ref();
+	error(
+		"Invalid duration",
+		format!("`{duration}` is not a duration, use something like `90m`, `2h` or `1d12h`"),
+	)
+}
+
 fn create_embed(title: impl Into<String>, description: impl Into<String>, color: impl Into<Colour>) -> CreateEmbed {
 	CreateEmbed::default()
 		.title(title)
@@ -0,0 +1,119 @@
+mod access;
+mod servitor;
+mod wake_on_lan;
+
+use crate::bot::{BotError, BotState};
+use crate::components::ComponentId;
+use crate::views::confirmation as view_confirmation;
+use log::debug;
+use poise::serenity_prelude as serenity;
+use serenity::{
+	ComponentInteraction, ComponentInteractionCollector, CreateAllowedMentions, CreateEmbed,
+	CreateInteractionResponse, CreateInteractionResponseMessage, EditInteractionResponse, UserId,
+};
+use std::collections::HashSet;
+use std::time::Duration;
+
+pub const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub async fn handle_component(
+	ctx: &serenity::Context,
+	interaction: &ComponentInteraction,
+	state: &BotState,
+	owners: &HashSet<UserId>,
+) -> Result<(), BotError> {
+	let Ok(id) = interaction.data.custom_id.parse::<ComponentId>() else {
+		// Components such as confirmation buttons are handled by whoever is collecting them
+		debug!("Ignoring component {}", interaction.data.custom_id);
+		return Ok(());
+	};
+
+	match id {
+		ComponentId::Servitor {
+			action,
+			server_name,
+		} => servitor::handle_action(ctx, interaction, state, action, &server_name).await,
+		ComponentId::Wake { machine_name } => {
+			wake_on_lan::handle_wake(ctx, interaction, state, &machine_name).await
+		}
+		ComponentId::RequestAccess { resource } => {
+			access::handle_request(ctx, interaction, state, owners, resource).await
+		}
+		ComponentId::ReviewAccess { request, decision } => {
+			access::handle_review(ctx, interaction, state, owners, request, decision).await
+		}
+		ComponentId::AskApproval { resource } => access::handle_ask(ctx, interaction, resource).await,
+		ComponentId::ReviewApproval {
+			requester,
+			approve,
+			resource,
+		} => access::handle_approval(ctx, interaction, state, requester, approve, resource).await,
+	}
+}
+
+fn no_mentions() -> CreateAllowedMentions {
+	CreateAllowedMentions::default().empty_users().empty_roles()
+}
+
+/// Same as the command confirmation, answering the component interaction with an ephemeral prompt.
+async fn confirm(
+	ctx: &serenity::Context,
+	interaction: &ComponentInteraction,
+	prompt: CreateEmbed,
+) -> Result<bool, BotError> {
+	let confirm_id = format!("{}-confirm", interaction.id);
+	let cancel_id = format!("{}-cancel", interaction.id);
+
+	interaction
+		.create_response(
+			ctx,
+			CreateInteractionResponse::Message(
+				CreateInteractionResponseMessage::new()
+					.embed(prompt)
+					.components(view_confirmation::confirmation_buttons(&confirm_id, &cancel_id))
+					.ephemeral(true)
+					.allowed_mentions(no_mentions()),
+			),
+		)
+		.await?;
+
+	let ids = [confirm_id.clone(), cancel_id];
+	let answer = ComponentInteractionCollector::new(ctx)
+		.author_id(interaction.user.id)
+		.channel_id(interaction.channel_id)
+		.timeout(CONFIRMATION_TIMEOUT)
+		.filter(move |i| ids.contains(&i.data.custom_id))
+		.await;
+
+	let Some(answer) = answer else {
+		interaction
+			.edit_response(
+				ctx,
+				EditInteractionResponse::new()
+					.embed(view_confirmation::timed_out_embed())
+					.components(vec![]),
+			)
+			.await?;
+		return Ok(false);
+	};
+
+	let confirmed = answer.data.custom_id == confirm_id;
+	let embed = if confirmed {
+		view_confirmation::confirmed_embed()
+	} else {
+		view_confirmation::cancelled_embed()
+	};
+
+	answer
+		.create_response(
+			ctx,
+			CreateInteractionResponse::UpdateMessage(
+				CreateInteractionResponseMessage::new()
+					.embed(embed)
+					.components(vec![]),
+			),
+		)
+		.await?;
+
+	Ok(confirmed)
+}
@@ -0,0 +1,287 @@
+use super::no_mentions;
+use crate::bot::{BotError, BotState};
+use crate::controllers::access::{self as ctrl_access, AccessDecision, RequestKey};
+use crate::controllers::wake_on_lan::wake as ctrl_wake;
+use crate::data::access::{AccessRequest, AccessResource};
+use crate::data::servitor::ServitorAction;
+use crate::services::wake_on_lan::UdpMagicPacketSender;
+use crate::views::access as view_access;
+use crate::views::wake_on_lan::wake as view_wake;
+use chrono::Utc;
+use log::{debug, error, info, warn};
+use poise::serenity_prelude as serenity;
+use serenity::{
+	ComponentInteraction, CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
+	EditInteractionResponse, UserId,
+};
+use std::collections::HashSet;
+
+pub async fn handle_request(
+	ctx: &serenity::Context,
+	interaction: &ComponentInteraction,
+	state: &BotState,
+	owners: &HashSet<UserId>,
+	resource: AccessResource,
+) -> Result<(), BotError> {
+	let result = ctrl_access::request_access(
+		&state.data,
+		interaction.user.id,
+		interaction.guild_id,
+		resource.clone(),
+		Utc::now(),
+	)
+	.await;
+	let id = result.as_ref().ok().copied();
+
+	interaction
+		.create_response(
+			ctx,
+			CreateInteractionResponse::Message(
+				CreateInteractionResponseMessage::new()
+					.embed(view_access::request_access_embed(result, &resource))
+					.ephemeral(true)
+					.allowed_mentions(no_mentions()),
+			),
+		)
+		.await?;
+
+	if let Some(id) = id
+		&& let Some(request) = ctrl_access::pending_request(&state.data, id).await
+	{
+		send_to_reviewers(ctx, state, owners, id, &request).await;
+	}
+
+	Ok(())
+}
+
+/// Posts the request to the approvals channel if there is one, otherwise DMs everyone that can approve it.
+async fn send_to_reviewers(
+	ctx: &serenity::Context,
+	state: &BotState,
+	owners: &HashSet<UserId>,
+	id: u64,
+	request: &AccessRequest,
+) {
+	let message = || {
+		CreateMessage::new()
+			.embed(view_access::access_request_embed(id, request))
+			.components(view_access::review_access_buttons(&RequestKey::new(id, request), state.access_duration))
+			.allowed_mentions(no_mentions())
+	};
+
+	if let Some(channel) = state.access_channel {
+		if let Err(e) = channel.send_message(ctx, message()).await {
+			error!("Failed to post access request {id} to channel {channel}: {e}");
+		}
+		return;
+	}
+
+	let approvers = ctrl_access::approvers(&state.data, owners, &request.resource).await;
+	if approvers.is_empty() {
+		warn!("Nobody can review access request {id}");
+	}
+
+	for approver in approvers {
+		if let Err(e) = approver.direct_message(ctx, message()).await {
+			error!("Failed to send access request {id} to user {approver}: {e}");
+		}
+	}
+}
+
+pub async fn handle_review(
+	ctx: &serenity::Context,
+	interaction: &ComponentInteraction,
+	state: &BotState,
+	owners: &HashSet<UserId>,
+	key: RequestKey,
+	decision: AccessDecision,
+) -> Result<(), BotError> {
+	let request = key.id;
+	// Reviews from DMs or from another guild's approvals channel are checked as a member of the requester's guild
+	let guild = ctrl_access::pending_request(&state.data, request)
+		.await
+		.and_then(|pending| pending.guild);
+	let member = match (guild, interaction.member.as_ref()) {
+		(Some(guild), Some(member)) if member.guild_id == guild => Some(member.clone()),
+		(Some(guild), _) => guild.member(ctx, interaction.user.id).await.ok(),
+		(None, member) => member.cloned(),
+	};
+
+	let expires = (decision == AccessDecision::ApproveTemporarily).then(|| Utc::now() + state.access_duration);
+	let result = ctrl_access::review_access(
+		&state.data,
+		&key,
+		&interaction.user,
+		member.as_ref(),
+		owners.contains(&interaction.user.id),
+		decision,
+		expires,
+	)
+	.await;
+
+	let Ok(reviewed) = &result else {
+		let embed = view_access::review_access_embed(result, request, interaction.user.id, decision, expires);
+		interaction
+			.create_response(
+				ctx,
+				CreateInteractionResponse::Message(
+					CreateInteractionResponseMessage::new()
+						.embed(embed)
+						.ephemeral(true)
+						.allowed_mentions(no_mentions()),
+				),
+			)
+			.await?;
+		return Ok(());
+	};
+
+	let requester = reviewed.user;
+	let notice = CreateMessage::new().embed(view_access::access_decision_embed(reviewed, decision, expires));
+	let embed = view_access::review_access_embed(result, request, interaction.user.id, decision, expires);
+
+	interaction
+		.create_response(
+			ctx,
+			CreateInteractionResponse::UpdateMessage(
+				CreateInteractionResponseMessage::new()
+					.embed(embed)
+					.components(vec![])
+					.allowed_mentions(no_mentions()),
+			),
+		)
+		.await?;
+
+	if let Err(e) = requester.direct_message(ctx, notice).await {
+		error!("Failed to tell user {requester} about the review of access request {request}: {e}");
+	}
+
+	Ok(())
+}
+
+/// Posts a public request for a single use, for anyone allowed to use the resource to approve.
+pub async fn handle_ask(
+	ctx: &serenity::Context,
+	interaction: &ComponentInteraction,
+	resource: AccessResource,
+) -> Result<(), BotError> {
+	let requester = interaction.user.id;
+
+	interaction
+		.create_response(
+			ctx,
+			CreateInteractionResponse::Message(
+				CreateInteractionResponseMessage::new()
+					.embed(view_access::approval_request_embed(requester, &resource))
+					.components(view_access::approval_buttons(requester, &resource))
+					.allowed_mentions(no_mentions()),
+			),
+		)
+		.await?;
+
+	Ok(())
+}
+
+/// Runs the requested action as the approver, who must be allowed to run it, or drops the request.
+///
+/// The requester may withdraw their own request by denying it.
+pub async fn handle_approval(
+	ctx: &serenity::Context,
+	interaction: &ComponentInteraction,
+	state: &BotState,
+	requester: UserId,
+	approve: bool,
+	resource: AccessResource,
+) -> Result<(), BotError> {
+	if let AccessResource::Server {
+		action: ServitorAction::Status | ServitorAction::Logs,
+		..
+	} = resource
+	{
+		debug!("Rejecting single use approval of read only {resource:?}");
+		interaction
+			.create_response(
+				ctx,
+				CreateInteractionResponse::Message(
+					CreateInteractionResponseMessage::new()
+						.embed(view_access::approval_unsupported_embed(&resource))
+						.ephemeral(true)
+						.allowed_mentions(no_mentions()),
+				),
+			)
+			.await?;
+		return Ok(());
+	}
+
+	let reviewer = &interaction.user;
+	let withdrawn = !approve && reviewer.id == requester;
+	if !withdrawn && !ctrl_access::can_approve(&state.data, &resource, reviewer, interaction.member.as_ref()).await {
+		interaction
+			.create_response(
+				ctx,
+				CreateInteractionResponse::Message(
+					CreateInteractionResponseMessage::new()
+						.embed(view_access::approval_unauthorized_embed(&resource))
+						.ephemeral(true)
+						.allowed_mentions(no_mentions()),
+				),
+			)
+			.await?;
+		return Ok(());
+	}
+
+	if !ctrl_access::claim_approval(&state.data, interaction.message.id, Utc::now()).await {
+		debug!("User {} clicked single use approval {} after it was decided", reviewer.id, interaction.message.id);
+		interaction
+			.create_response(
+				ctx,
+				CreateInteractionResponse::Message(
+					CreateInteractionResponseMessage::new()
+						.embed(view_access::approval_decided_embed())
+						.ephemeral(true),
+				),
+			)
+			.await?;
+		return Ok(());
+	}
+
+	if !approve {
+		info!("User {} denied user {requester} a single use of {resource:?}", reviewer.id);
+		interaction
+			.create_response(
+				ctx,
+				CreateInteractionResponse::UpdateMessage(
+					CreateInteractionResponseMessage::new()
+						.embed(view_access::approval_denied_embed(requester, &resource, reviewer.id))
+						.components(vec![])
+						.allowed_mentions(no_mentions()),
+				),
+			)
+			.await?;
+		return Ok(());
+	}
+
+	const SENDER: UdpMagicPacketSender = UdpMagicPacketSender {};
+
+	interaction.defer(ctx).await?;
+	info!("User {} approved a single use of {resource:?} by user {requester}", reviewer.id);
+
+	let embed = match &resource {
+		AccessResource::Machine { name } => {
+			let result = ctrl_wake::wake(&state.data, reviewer, interaction.member.as_ref(), name, &SENDER).await;
+			view_wake::wake_embed(result, name)
+		}
+		AccessResource::Server { name, action } => super::servitor::execute(interaction, state, *action, name).await.0,
+	};
+
+	interaction
+		.edit_response(
+			ctx,
+			EditInteractionResponse::new()
+				.embed(view_access::approved_embed(embed, requester, reviewer.id))
+				.components(vec![])
+				.allowed_mentions(no_mentions()),
+		)
+		.await?;
+
+	Ok(())
+}
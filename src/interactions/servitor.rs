@@ -0,0 +1,183 @@
+use super::{confirm, no_mentions};
+use crate::bot::{BotError, BotState};
+use crate::controllers::servitor::action as ctrl_serv_act;
+use crate::controllers::servitor::query as ctrl_serv_qry;
+use crate::data::servitor::ServitorAction;
+use crate::views::servitor::action as view_serv_act;
+use log::debug;
+use poise::serenity_prelude as serenity;
+use serenity::{
+	ComponentInteraction, CreateActionRow, CreateEmbed, CreateInteractionResponse,
+	CreateInteractionResponseFollowup, CreateInteractionResponseMessage, EditInteractionResponse,
+};
+
+pub async fn handle_action(
+	ctx: &serenity::Context,
+	interaction: &ComponentInteraction,
+	state: &BotState,
+	action: ServitorAction,
+	server_name: &str,
+) -> Result<(), BotError> {
+	match action {
+		ServitorAction::Status => refresh(ctx, interaction, state, server_name).await,
+		ServitorAction::Start
+		| ServitorAction::Stop
+		| ServitorAction::Restart
+		| ServitorAction::Reload => run(ctx, interaction, state, action, server_name).await,
+		ServitorAction::Logs => {
+			debug!("Rejecting unsupported logs button for Servitor server {server_name}");
+			interaction
+				.create_response(
+					ctx,
+					CreateInteractionResponse::Message(
+						CreateInteractionResponseMessage::new()
+							.embed(view_serv_act::unsupported_button_embed(action))
+							.ephemeral(true),
+					),
+				)
+				.await?;
+			Ok(())
+		}
+	}
+}
+
+async fn refresh(
+	ctx: &serenity::Context,
+	interaction: &ComponentInteraction,
+	state: &BotState,
+	server_name: &str,
+) -> Result<(), BotError> {
+	interaction
+		.create_response(ctx, CreateInteractionResponse::Acknowledge)
+		.await?;
+
+	let result = ctrl_serv_act::status(
+		&state.data,
+		&state.servitor,
+		server_name,
+		&interaction.user,
+		interaction.member.as_ref(),
+	)
+	.await;
+
+	if result.is_ok() {
+		let game = ctrl_serv_qry::query_game(&state.data, server_name, &state.game_query).await;
+		let embed = view_serv_act::game_info_fields(view_serv_act::status_embed(result, server_name), game);
+		interaction
+			.edit_response(
+				ctx,
+				EditInteractionResponse::new()
+					.embed(embed)
+					.components(view_serv_act::status_buttons(server_name)),
+			)
+			.await?;
+	} else {
+		// Keep the last good status around for everyone else, only the clicking user sees the error
+		let buttons = view_serv_act::action_buttons(&result);
+		interaction
+			.create_followup(
+				ctx,
+				CreateInteractionResponseFollowup::new()
+					.embed(view_serv_act::status_embed(result, server_name))
+					.components(buttons)
+					.ephemeral(true)
+					.allowed_mentions(no_mentions()),
+			)
+			.await?;
+	}
+
+	Ok(())
+}
+
+async fn run(
+	ctx: &serenity::Context,
+	interaction: &ComponentInteraction,
+	state: &BotState,
+	action: ServitorAction,
+	server_name: &str,
+) -> Result<(), BotError> {
+	let needs_confirmation = matches!(action, ServitorAction::Stop | ServitorAction::Restart)
+		&& ctrl_serv_act::requires_confirmation(
+			&state.data,
+			server_name,
+			action,
+			&interaction.user,
+			interaction.member.as_ref(),
+		)
+		.await;
+
+	if needs_confirmation {
+		let prompt = view_serv_act::confirm_action_embed(server_name, action);
+		if !confirm(ctx, interaction, prompt).await? {
+			return Ok(());
+		}
+	} else {
+		interaction.defer(ctx).await?;
+	}
+
+	let (embed, buttons) = execute(interaction, state, action, server_name).await;
+
+	if !buttons.is_empty() {
+		// Only the clicking user may ask for access, everyone else keeps the status message as it was
+		interaction
+			.create_followup(
+				ctx,
+				CreateInteractionResponseFollowup::new()
+					.embed(embed)
+					.components(buttons)
+					.ephemeral(true)
+					.allowed_mentions(no_mentions()),
+			)
+			.await?;
+	} else if needs_confirmation {
+		interaction
+			.create_followup(
+				ctx,
+				CreateInteractionResponseFollowup::new()
+					.embed(embed)
+					.allowed_mentions(no_mentions()),
+			)
+			.await?;
+	} else {
+		interaction
+			.edit_response(
+				ctx,
+				EditInteractionResponse::new()
+					.embed(embed)
+					.allowed_mentions(no_mentions()),
+			)
+			.await?;
+	}
+
+	Ok(())
+}
+
+pub(super) async fn execute(
+	interaction: &ComponentInteraction,
+	state: &BotState,
+	action: ServitorAction,
+	server_name: &str,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+	let (data, servitor) = (&state.data, &state.servitor);
+	let (author, member) = (&interaction.user, interaction.member.as_ref());
+
+	let result = match action {
+		ServitorAction::Start => ctrl_serv_act::start(data, servitor, server_name, author, member).await,
+		ServitorAction::Stop => ctrl_serv_act::stop(data, servitor, server_name, author, member).await,
+		ServitorAction::Restart => ctrl_serv_act::restart(data, servitor, server_name, author, member).await,
+		ServitorAction::Reload => ctrl_serv_act::reload(data, servitor, server_name, author, member).await,
+		ServitorAction::Status | ServitorAction::Logs => {
+			unreachable!("{action} is not run through buttons")
+		}
+	};
+
+	let buttons = view_serv_act::action_buttons(&result);
+	let embed = match action {
+		ServitorAction::Start => view_serv_act::start_embed(result, server_name),
+		ServitorAction::Stop => view_serv_act::stop_embed(result, server_name),
+		ServitorAction::Restart => view_serv_act::restart_embed(result, server_name),
+		_ => view_serv_act::reload_embed(result, server_name),
+	};
+
+	(embed, buttons)
+}
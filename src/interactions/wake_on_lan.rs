@@ -0,0 +1,54 @@
+use super::no_mentions;
+use crate::bot::{BotError, BotState};
+use crate::services::wake_on_lan::UdpMagicPacketSender;
+use crate::{controllers, views};
+use poise::serenity_prelude as serenity;
+use serenity::{ComponentInteraction, CreateInteractionResponseFollowup, EditInteractionResponse};
+
+pub async fn handle_wake(
+	ctx: &serenity::Context,
+	interaction: &ComponentInteraction,
+	state: &BotState,
+	machine_name: &str,
+) -> Result<(), BotError> {
+	const SENDER: UdpMagicPacketSender = UdpMagicPacketSender {};
+
+	interaction.defer(ctx).await?;
+
+	let result = controllers::wake_on_lan::wake::wake(
+		&state.data,
+		&interaction.user,
+		interaction.member.as_ref(),
+		machine_name,
+		&SENDER,
+	)
+	.await;
+
+	let buttons = views::wake_on_lan::wake::wake_buttons(&result);
+	let embed = views::wake_on_lan::wake::wake_embed(result, machine_name);
+
+	if buttons.is_empty() {
+		interaction
+			.edit_response(
+				ctx,
+				EditInteractionResponse::new()
+					.embed(embed)
+					.allowed_mentions(no_mentions()),
+			)
+			.await?;
+	} else {
+		// Only the clicking user may ask for access, everyone else keeps the machine's message as it was
+		interaction
+			.create_followup(
+				ctx,
+				CreateInteractionResponseFollowup::new()
+					.embed(embed)
+					.components(buttons)
+					.ephemeral(true)
+					.allowed_mentions(no_mentions()),
+			)
+			.await?;
+	}
+
+	Ok(())
+}
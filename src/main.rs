@@ -13,12 +13,15 @@ use tracing_subscriber::filter::LevelFilter;
 
 mod bot;
 mod commands;
+mod components;
 mod config;
 mod controllers;
 mod data;
 mod embeds;
 mod errors;
+mod interactions;
 mod services;
+mod tasks;
 mod views;
 
 #[tokio::main]
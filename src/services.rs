@@ -1,2 +1,4 @@
 pub mod wake_on_lan;
 pub mod servitor;
+pub mod probe;
+pub mod query;
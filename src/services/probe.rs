@@ -0,0 +1,171 @@
+use super::query::{GameQuerier, QueryError};
+use crate::data::servitor::{IdleProbe, QueryConfig};
+use std::io::ErrorKind;
+use thiserror::Error;
+
+const PROC_NET_TCP: [&str; 2] = ["/proc/net/tcp", "/proc/net/tcp6"];
+const TCP_ESTABLISHED: &str = "01";
+
+pub trait Prober = ActivityProbe + Send + Sync;
+pub trait ActivityProbe {
+	/// How many players or connections the probe currently sees, zero meaning idle.
+	///
+	/// `query` is the game query of the probed server, if it has one.
+	async fn activity(&self, probe: &IdleProbe, query: Option<&QueryConfig>) -> Result<u32, ProbeError>;
+}
+
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum ProbeError {
+	#[error("failed to read {path}: {error}")]
+	Read { path: String, error: String },
+
+	#[error("this host has no TCP connection tables to read")]
+	Unsupported,
+
+	#[error("the server has no game query to count players with")]
+	NoQuery,
+
+	#[error(transparent)]
+	Query(#[from] QueryError),
+}
+
+/// Probes game servers through their game query, or the host the bot runs on for connections.
+pub struct SystemProber<Q> {
+	query: Q,
+}
+
+impl<Q: GameQuerier> SystemProber<Q> {
+	pub fn new(query: Q) -> Self {
+		Self { query }
+	}
+}
+
+impl<Q: GameQuerier> ActivityProbe for SystemProber<Q> {
+	async fn activity(&self, probe: &IdleProbe, query: Option<&QueryConfig>) -> Result<u32, ProbeError> {
+		match probe {
+			IdleProbe::Query => {
+				let config = query.ok_or(ProbeError::NoQuery)?;
+				Ok(self.query.query(config).await?.players)
+			}
+			IdleProbe::Connections { port } => count_connections(*port).await,
+		}
+	}
+}
+
+/// Counts established connections on a local port, failing where there are no `/proc/net/tcp` tables to read.
+async fn count_connections(port: u16) -> Result<u32, ProbeError> {
+	let mut tables = 0;
+	let mut connections = 0;
+	for path in PROC_NET_TCP {
+		match tokio::fs::read_to_string(path).await {
+			Ok(table) => {
+				tables += 1;
+				connections += count_established(&table, port);
+			}
+			Err(e) if e.kind() == ErrorKind::NotFound => {}
+			Err(e) => {
+				return Err(ProbeError::Read {
+					path: path.to_string(),
+					error: e.to_string(),
+				})
+			}
+		}
+	}
+
+	if tables == 0 {
+		return Err(ProbeError::Unsupported);
+	}
+	Ok(connections)
+}
+
+/// Counts established connections on a local port in a `/proc/net/tcp` style table.
+fn count_established(table: &str, port: u16) -> u32 {
+	table
+		.lines()
+		.skip(1)
+		.filter_map(|line| {
+			let mut fields = line.split_whitespace();
+			let local_address = fields.nth(1)?;
+			let state = fields.nth(1)?;
+			let local_port = u16::from_str_radix(local_address.rsplit(':').next()?, 16).ok()?;
+			Some(local_port == port && state == TCP_ESTABLISHED)
+		})
+		.filter(|&established| established)
+		.count() as u32
+}
+
+#[cfg(test)]
+pub mod tests {
+	use super::*;
+	use crate::services::query::tests::MockGameQuery;
+	use std::sync::Mutex;
+
+	const TCP_TABLE: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:63DD 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 31337 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:63DD 0100007F:D431 01 00000000:00000000 00:00000000 00000000  1000        0 31338 1 0000000000000000 20 4 30 10 -1
+   2: 0A00000A:63DD 0B00000A:C350 01 00000000:00000000 02:000A7A1B 00000000  1000        0 31339 2 0000000000000000 20 4 0 10 -1
+   3: 0A00000A:0016 0B00000A:C351 01 00000000:00000000 02:000A7A1B 00000000     0        0 31340 2 0000000000000000 20 4 0 10 -1
+   4: 0A00000A:63DD 0B00000A:C352 06 00000000:00000000 03:00000DAC 00000000     0        0 0 3 0000000000000000
+";
+
+	const TCP6_TABLE: &str = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0000000000000000FFFF00000A00000A:63DD 0000000000000000FFFF00000B00000A:C353 01 00000000:00000000 00:00000000 00000000  1000        0 41337 1 0000000000000000 20 4 30 10 -1
+";
+
+	pub struct MockProber {
+		activity: Mutex<Result<u32, ProbeError>>,
+	}
+
+	impl MockProber {
+		pub fn new(activity: u32) -> Self {
+			Self {
+				activity: Mutex::new(Ok(activity)),
+			}
+		}
+
+		pub fn set_activity(&self, activity: Result<u32, ProbeError>) {
+			*self.activity.lock().unwrap() = activity;
+		}
+	}
+
+	impl ActivityProbe for MockProber {
+		async fn activity(&self, _probe: &IdleProbe, _query: Option<&QueryConfig>) -> Result<u32, ProbeError> {
+			self.activity.lock().unwrap().clone()
+		}
+	}
+
+	#[tokio::test]
+	async fn given_query_probe_then_system_prober_counts_players() {
+		let prober = SystemProber::new(MockGameQuery::new(Ok(MockGameQuery::default_info())));
+		let query = QueryConfig::Minecraft {
+			address: "mc.example.com".to_string(),
+		};
+
+		assert_eq!(prober.activity(&IdleProbe::Query, Some(&query)).await, Ok(3));
+		assert_eq!(prober.activity(&IdleProbe::Query, None).await, Err(ProbeError::NoQuery));
+	}
+
+	#[tokio::test]
+	async fn given_failing_query_then_system_prober_returns_error() {
+		let prober = SystemProber::new(MockGameQuery::new(Err(QueryError::Timeout)));
+		let query = QueryConfig::A2s {
+			address: "127.0.0.1".to_string(),
+		};
+
+		let result = prober.activity(&IdleProbe::Query, Some(&query)).await;
+
+		assert_eq!(result, Err(ProbeError::Query(QueryError::Timeout)));
+	}
+
+	#[test]
+	fn given_tcp_table_then_count_established_only_counts_established_on_port() {
+		assert_eq!(count_established(TCP_TABLE, 25565), 2);
+		assert_eq!(count_established(TCP_TABLE, 22), 1);
+		assert_eq!(count_established(TCP_TABLE, 27015), 0);
+	}
+
+	#[test]
+	fn given_tcp6_table_then_count_established_reads_port() {
+		assert_eq!(count_established(TCP6_TABLE, 25565), 1);
+	}
+}
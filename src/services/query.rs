@@ -0,0 +1,126 @@
+use crate::data::servitor::QueryConfig;
+use std::time::Duration;
+use thiserror::Error;
+
+mod a2s;
+mod minecraft;
+
+pub trait GameQuerier = GameQuery + Send + Sync;
+pub trait GameQuery {
+	async fn query(&self, config: &QueryConfig) -> Result<GameInfo, QueryError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameInfo {
+	pub players: u32,
+	pub max_players: u32,
+	pub map: Option<String>,
+	pub motd: Option<String>,
+	pub version: String,
+}
+
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum QueryError {
+	#[error("the game server did not answer in time")]
+	Timeout,
+	#[error("failed to reach the game server: {0}")]
+	Io(String),
+	#[error("the game server sent a malformed response: {0}")]
+	Malformed(String),
+}
+
+impl From<std::io::Error> for QueryError {
+	fn from(error: std::io::Error) -> Self {
+		QueryError::Io(error.to_string())
+	}
+}
+
+/// Queries game servers over the network with the protocol of each config.
+pub struct NetworkGameQuery {
+	timeout: Duration,
+}
+
+impl NetworkGameQuery {
+	pub fn new(timeout: Duration) -> Self {
+		Self { timeout }
+	}
+}
+
+impl GameQuery for NetworkGameQuery {
+	async fn query(&self, config: &QueryConfig) -> Result<GameInfo, QueryError> {
+		let query = async {
+			match config {
+				QueryConfig::A2s { address } => a2s::query(address).await,
+				QueryConfig::Minecraft { address } => minecraft::query(address).await,
+			}
+		};
+
+		tokio::time::timeout(self.timeout, query)
+			.await
+			.map_err(|_| QueryError::Timeout)?
+	}
+}
+
+/// Splits `host:port`, falling back to the protocol's default port when there is none.
+fn split_address(address: &str, default_port: u16) -> Result<(&str, u16), QueryError> {
+	match address.rsplit_once(':') {
+		Some((host, port)) => port
+			.parse()
+			.map(|port| (host, port))
+			.map_err(|_| QueryError::Io(format!("invalid port in address {address}"))),
+		None => Ok((address, default_port)),
+	}
+}
+
+#[cfg(test)]
+pub mod tests {
+	use super::*;
+	use std::sync::Mutex;
+
+	pub struct MockGameQuery {
+		result: Mutex<Result<GameInfo, QueryError>>,
+	}
+
+	impl MockGameQuery {
+		pub fn new(result: Result<GameInfo, QueryError>) -> Self {
+			Self {
+				result: Mutex::new(result),
+			}
+		}
+
+		pub fn default_info() -> GameInfo {
+			GameInfo {
+				players: 3,
+				max_players: 20,
+				map: None,
+				motd: Some("A Minecraft Server".to_string()),
+				version: "1.21.4".to_string(),
+			}
+		}
+	}
+
+	impl GameQuery for MockGameQuery {
+		async fn query(&self, _config: &QueryConfig) -> Result<GameInfo, QueryError> {
+			self.result.lock().unwrap().clone()
+		}
+	}
+
+	#[test]
+	fn given_addresses_then_split_address_uses_default_port_when_missing() {
+		assert_eq!(split_address("mc.example.com:25566", 25565), Ok(("mc.example.com", 25566)));
+		assert_eq!(split_address("mc.example.com", 25565), Ok(("mc.example.com", 25565)));
+		assert!(split_address("mc.example.com:port", 25565).is_err());
+	}
+
+	#[tokio::test]
+	async fn given_unresponsive_server_then_query_times_out() {
+		let socket = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+		let config = QueryConfig::A2s {
+			address: socket.local_addr().unwrap().to_string(),
+		};
+
+		let result = NetworkGameQuery::new(Duration::from_millis(50)).query(&config).await;
+
+		assert_eq!(result, Err(QueryError::Timeout));
+	}
+}
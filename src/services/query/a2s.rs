@@ -0,0 +1,171 @@
+//! Source engine `A2S_INFO` query, see <https://developer.valvesoftware.com/wiki/Server_queries>.
+
+use super::{split_address, GameInfo, QueryError};
+use tokio::net::UdpSocket;
+
+const DEFAULT_PORT: u16 = 27015;
+const SINGLE_PACKET: [u8; 4] = [0xFF; 4];
+const INFO_REQUEST: &[u8] = b"\xFF\xFF\xFF\xFFTSource Engine Query\0";
+const CHALLENGE_HEADER: u8 = b'A';
+const INFO_HEADER: u8 = b'I';
+const MAX_PACKET_SIZE: usize = 1400;
+
+#[derive(Debug, PartialEq)]
+enum Response {
+	Challenge([u8; 4]),
+	Info(GameInfo),
+}
+
+pub async fn query(address: &str) -> Result<GameInfo, QueryError> {
+	let (host, port) = split_address(address, DEFAULT_PORT)?;
+	let socket = UdpSocket::bind("0.0.0.0:0").await?;
+	socket.connect((host, port)).await?;
+
+	let mut request = INFO_REQUEST.to_vec();
+	let mut buffer = [0u8; MAX_PACKET_SIZE];
+
+	// Servers may ask to repeat the request with a challenge once, answering with the info afterwards
+	for _ in 0..2 {
+		socket.send(&request).await?;
+		let length = socket.recv(&mut buffer).await?;
+
+		match parse_response(&buffer[..length])? {
+			Response::Info(info) => return Ok(info),
+			Response::Challenge(challenge) => {
+				request = INFO_REQUEST.to_vec();
+				request.extend_from_slice(&challenge);
+			}
+		}
+	}
+
+	Err(QueryError::Malformed("server kept answering with challenges".to_string()))
+}
+
+fn parse_response(packet: &[u8]) -> Result<Response, QueryError> {
+	let mut reader = Reader(packet);
+
+	if reader.take(4)? != SINGLE_PACKET {
+		return Err(QueryError::Malformed("split or unknown packet".to_string()));
+	}
+
+	match reader.byte()? {
+		CHALLENGE_HEADER => Ok(Response::Challenge(reader.take(4)?.try_into().unwrap())),
+		INFO_HEADER => {
+			let _protocol = reader.byte()?;
+			let _name = reader.string()?;
+			let map = reader.string()?;
+			let _folder = reader.string()?;
+			let _game = reader.string()?;
+			let _app_id = reader.take(2)?;
+			let players = reader.byte()?;
+			let max_players = reader.byte()?;
+			let bots = reader.byte()?;
+			let _server_type = reader.byte()?;
+			let _environment = reader.byte()?;
+			let _visibility = reader.byte()?;
+			let _vac = reader.byte()?;
+			let version = reader.string()?;
+
+			Ok(Response::Info(GameInfo {
+				players: players.saturating_sub(bots).into(),
+				max_players: max_players.into(),
+				map: Some(map),
+				motd: None,
+				version,
+			}))
+		}
+		header => Err(QueryError::Malformed(format!("unexpected header {header:#04x}"))),
+	}
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+	fn take(&mut self, count: usize) -> Result<&'a [u8], QueryError> {
+		if self.0.len() < count {
+			return Err(QueryError::Malformed("response ended early".to_string()));
+		}
+		let (taken, rest) = self.0.split_at(count);
+		self.0 = rest;
+		Ok(taken)
+	}
+
+	fn byte(&mut self) -> Result<u8, QueryError> {
+		Ok(self.take(1)?[0])
+	}
+
+	fn string(&mut self) -> Result<String, QueryError> {
+		let end = self
+			.0
+			.iter()
+			.position(|&b| b == 0)
+			.ok_or(QueryError::Malformed("unterminated string".to_string()))?;
+		let string = String::from_utf8_lossy(self.take(end)?).into_owned();
+		self.take(1)?;
+		Ok(string)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Shaped like a Team Fortress 2 dedicated server response with 5 players, one of them a bot, and trailing EDF fields.
+	const INFO_RESPONSE: &[u8] = b"\xFF\xFF\xFF\xFFI\x11Gjallar TF2\0ctf_2fort\0tf\0Team Fortress\0\xB8\x01\x05\x18\x01dl\x00\x01\
+		9162192\0\xB1\x87\x69\x01\x40\x1F\x55\x00\x00\x00\x00\x00\x00\x00\x00";
+	const CHALLENGE_RESPONSE: &[u8] = b"\xFF\xFF\xFF\xFFA\x4E\x2A\x91\x07";
+
+	fn expected_info() -> GameInfo {
+		GameInfo {
+			players: 4,
+			max_players: 24,
+			map: Some("ctf_2fort".to_string()),
+			motd: None,
+			version: "9162192".to_string(),
+		}
+	}
+
+	#[test]
+	fn given_info_fixture_then_parse_response_returns_info() {
+		assert_eq!(parse_response(INFO_RESPONSE), Ok(Response::Info(expected_info())));
+	}
+
+	#[test]
+	fn given_challenge_fixture_then_parse_response_returns_challenge() {
+		assert_eq!(
+			parse_response(CHALLENGE_RESPONSE),
+			Ok(Response::Challenge([0x4E, 0x2A, 0x91, 0x07]))
+		);
+	}
+
+	#[test]
+	fn given_truncated_or_split_packets_then_parse_response_returns_malformed() {
+		assert!(matches!(parse_response(&INFO_RESPONSE[..20]), Err(QueryError::Malformed(_))));
+		assert!(matches!(
+			parse_response(b"\xFE\xFF\xFF\xFF\x01\x00\x00\x00"),
+			Err(QueryError::Malformed(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn given_server_requiring_challenge_then_query_repeats_request_with_it() {
+		let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+		let address = server.local_addr().unwrap().to_string();
+		let stand_in = tokio::spawn(async move {
+			let mut buffer = [0u8; MAX_PACKET_SIZE];
+
+			let (length, client) = server.recv_from(&mut buffer).await.unwrap();
+			assert_eq!(&buffer[..length], INFO_REQUEST);
+			server.send_to(CHALLENGE_RESPONSE, client).await.unwrap();
+
+			let (length, client) = server.recv_from(&mut buffer).await.unwrap();
+			assert_eq!(&buffer[..length], [INFO_REQUEST, &CHALLENGE_RESPONSE[5..]].concat());
+			server.send_to(INFO_RESPONSE, client).await.unwrap();
+		});
+
+		let result = query(&address).await;
+
+		stand_in.await.unwrap();
+		assert_eq!(result, Ok(expected_info()));
+	}
+}
@@ -0,0 +1,245 @@
+//! Minecraft Server List Ping, see <https://minecraft.wiki/w/Java_Edition_protocol/Server_List_Ping>.
+
+use super::{split_address, GameInfo, QueryError};
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const DEFAULT_PORT: u16 = 25565;
+/// Servers answer the status of any version, -1 is the convention for "just pinging".
+const PROTOCOL_VERSION: i32 = -1;
+const NEXT_STATE_STATUS: i32 = 1;
+const HANDSHAKE_PACKET: i32 = 0x00;
+const STATUS_PACKET: i32 = 0x00;
+const MAX_RESPONSE_LENGTH: usize = 1 << 20;
+
+#[derive(Deserialize)]
+struct Status {
+	version: Version,
+	players: Players,
+	#[serde(default)]
+	description: Value,
+}
+
+#[derive(Deserialize)]
+struct Version {
+	name: String,
+}
+
+#[derive(Deserialize)]
+struct Players {
+	online: u32,
+	max: u32,
+}
+
+pub async fn query(address: &str) -> Result<GameInfo, QueryError> {
+	let (host, port) = split_address(address, DEFAULT_PORT)?;
+	let mut stream = TcpStream::connect((host, port)).await?;
+
+	stream.write_all(&handshake(host, port)).await?;
+	stream.write_all(&packet(STATUS_PACKET, &[])).await?;
+
+	let json = read_status(&mut stream).await?;
+	parse_status(&json)
+}
+
+fn handshake(host: &str, port: u16) -> Vec<u8> {
+	let mut payload = Vec::new();
+	write_varint(&mut payload, PROTOCOL_VERSION);
+	write_varint(&mut payload, host.len() as i32);
+	payload.extend_from_slice(host.as_bytes());
+	payload.extend_from_slice(&port.to_be_bytes());
+	write_varint(&mut payload, NEXT_STATE_STATUS);
+	packet(HANDSHAKE_PACKET, &payload)
+}
+
+fn packet(id: i32, payload: &[u8]) -> Vec<u8> {
+	let mut body = Vec::new();
+	write_varint(&mut body, id);
+	body.extend_from_slice(payload);
+
+	let mut packet = Vec::new();
+	write_varint(&mut packet, body.len() as i32);
+	packet.extend_from_slice(&body);
+	packet
+}
+
+async fn read_status<R: AsyncRead + Unpin>(reader: &mut R) -> Result<String, QueryError> {
+	let _length = read_varint(reader).await?;
+	let id = read_varint(reader).await?;
+	if id != STATUS_PACKET {
+		return Err(QueryError::Malformed(format!("unexpected packet {id:#04x}")));
+	}
+
+	let length = read_varint(reader).await?;
+	let length = usize::try_from(length)
+		.ok()
+		.filter(|&length| length <= MAX_RESPONSE_LENGTH)
+		.ok_or(QueryError::Malformed(format!("invalid status length {length}")))?;
+
+	let mut json = vec![0u8; length];
+	reader.read_exact(&mut json).await?;
+	String::from_utf8(json).map_err(|_| QueryError::Malformed("status is not UTF-8".to_string()))
+}
+
+fn parse_status(json: &str) -> Result<GameInfo, QueryError> {
+	let status: Status = serde_json::from_str(json).map_err(|e| QueryError::Malformed(e.to_string()))?;
+
+	let mut motd = String::new();
+	flatten_text(&status.description, &mut motd);
+	let motd = strip_formatting(&motd);
+
+	Ok(GameInfo {
+		players: status.players.online,
+		max_players: status.players.max,
+		map: None,
+		motd: (!motd.trim().is_empty()).then(|| motd.trim().to_string()),
+		version: status.version.name,
+	})
+}
+
+/// Descriptions are either plain strings or chat components with nested `extra` parts.
+fn flatten_text(component: &Value, text: &mut String) {
+	match component {
+		Value::String(s) => text.push_str(s),
+		Value::Object(object) => {
+			if let Some(Value::String(s)) = object.get("text") {
+				text.push_str(s);
+			}
+			if let Some(Value::Array(extra)) = object.get("extra") {
+				extra.iter().for_each(|part| flatten_text(part, text));
+			}
+		}
+		Value::Array(parts) => parts.iter().for_each(|part| flatten_text(part, text)),
+		_ => {}
+	}
+}
+
+/// Drops legacy `§` colour and style codes.
+fn strip_formatting(text: &str) -> String {
+	let mut stripped = String::with_capacity(text.len());
+	let mut chars = text.chars();
+	while let Some(c) = chars.next() {
+		if c == '§' {
+			chars.next();
+		} else {
+			stripped.push(c);
+		}
+	}
+	stripped
+}
+
+fn write_varint(buffer: &mut Vec<u8>, value: i32) {
+	let mut value = value as u32;
+	loop {
+		if value & !0x7F == 0 {
+			buffer.push(value as u8);
+			return;
+		}
+		buffer.push((value & 0x7F) as u8 | 0x80);
+		value >>= 7;
+	}
+}
+
+async fn read_varint<R: AsyncRead + Unpin>(reader: &mut R) -> Result<i32, QueryError> {
+	let mut value = 0u32;
+	for position in 0..5 {
+		let byte = reader.read_u8().await?;
+		value |= u32::from(byte & 0x7F) << (7 * position);
+		if byte & 0x80 == 0 {
+			return Ok(value as i32);
+		}
+	}
+	Err(QueryError::Malformed("varint is too long".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+	use tokio::net::TcpListener;
+
+	/// Status of a vanilla 1.21.4 server, with a chat component description using `extra` and a formatting code.
+	const STATUS_JSON: &str = r#"{"version":{"name":"1.21.4","protocol":769},"enforcesSecureChat":true,"description":{"text":"","extra":[{"text":"§aGjallar "},{"text":"Survival","bold":true}]},"players":{"max":20,"online":3,"sample":[{"name":"Angelin01","id":"4566e69f-c907-48ee-8d71-d7ba5aa00d20"}]}}"#;
+
+	fn status_response() -> Vec<u8> {
+		let mut payload = Vec::new();
+		write_varint(&mut payload, STATUS_JSON.len() as i32);
+		payload.extend_from_slice(STATUS_JSON.as_bytes());
+		packet(STATUS_PACKET, &payload)
+	}
+
+	#[rstest]
+	#[case(0, &[0x00])]
+	#[case(127, &[0x7F])]
+	#[case(128, &[0x80, 0x01])]
+	#[case(25565, &[0xDD, 0xC7, 0x01])]
+	#[case(-1, &[0xFF, 0xFF, 0xFF, 0xFF, 0x0F])]
+	#[tokio::test]
+	async fn given_value_then_varint_roundtrips_through_fixture(#[case] value: i32, #[case] bytes: &[u8]) {
+		let mut buffer = Vec::new();
+		write_varint(&mut buffer, value);
+
+		assert_eq!(buffer, bytes);
+		assert_eq!(read_varint(&mut &*buffer).await, Ok(value));
+	}
+
+	#[test]
+	fn given_host_then_handshake_matches_fixture() {
+		assert_eq!(
+			handshake("localhost", 25565),
+			b"\x13\x00\xFF\xFF\xFF\xFF\x0F\x09localhost\x63\xDD\x01"
+		);
+	}
+
+	#[test]
+	fn given_status_fixture_then_parse_status_returns_info() {
+		assert_eq!(
+			parse_status(STATUS_JSON),
+			Ok(GameInfo {
+				players: 3,
+				max_players: 20,
+				map: None,
+				motd: Some("Gjallar Survival".to_string()),
+				version: "1.21.4".to_string(),
+			})
+		);
+	}
+
+	#[test]
+	fn given_plain_description_then_parse_status_uses_it_as_motd() {
+		let json = r#"{"version":{"name":"Paper 1.20.1"},"players":{"max":50,"online":0},"description":"A Minecraft Server"}"#;
+
+		let info = parse_status(json).unwrap();
+
+		assert_eq!(info.motd, Some("A Minecraft Server".to_string()));
+		assert_eq!(info.version, "Paper 1.20.1");
+	}
+
+	#[test]
+	fn given_invalid_json_then_parse_status_returns_malformed() {
+		assert!(matches!(parse_status(r#"{"players":{}}"#), Err(QueryError::Malformed(_))));
+	}
+
+	#[tokio::test]
+	async fn given_stand_in_server_then_query_reads_status() {
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let port = listener.local_addr().unwrap().port();
+		let stand_in = tokio::spawn(async move {
+			let (mut socket, _) = listener.accept().await.unwrap();
+			let expected_request = [handshake("127.0.0.1", port), packet(STATUS_PACKET, &[])].concat();
+
+			let mut request = vec![0u8; expected_request.len()];
+			socket.read_exact(&mut request).await.unwrap();
+			assert_eq!(request, expected_request);
+
+			socket.write_all(&status_response()).await.unwrap();
+		});
+
+		let result = query(&format!("127.0.0.1:{port}")).await;
+
+		stand_in.await.unwrap();
+		assert_eq!(result.map(|info| info.players), Ok(3));
+	}
+}
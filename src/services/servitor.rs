@@ -1,12 +1,22 @@
 use reqwest::header::{HeaderMap, HeaderValue};
-use reqwest::{header, Client, Response, StatusCode};
+use log::warn;
+use reqwest::{header, Client, RequestBuilder, Response, StatusCode};
 use secrecy::{ExposeSecret, SecretString};
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use thiserror::Error;
 
+mod breaker;
+mod local;
+mod tls;
+
+use breaker::CircuitBreaker;
+pub use local::LocalServitorController;
+pub use tls::ServitorTlsOptions;
+
 pub trait ServitorController = ServitorHandler + Send + Sync;
 pub trait ServitorHandler {
 	async fn start(&self, unit_name: &str) -> Result<(), ServitorError>;
@@ -14,8 +24,10 @@ pub trait ServitorHandler {
 	async fn restart(&self, unit_name: &str) -> Result<(), ServitorError>;
 	async fn reload(&self, unit_name: &str) -> Result<(), ServitorError>;
 	async fn status(&self, unit_name: &str) -> Result<UnitStatus, ServitorError>;
-	#[allow(dead_code)]
+	async fn logs(&self, unit_name: &str, lines: u32) -> Result<UnitLogs, ServitorError>;
 	async fn health(&self) -> bool;
+	/// Whether the units run on the same host as the bot.
+	fn is_local(&self) -> bool;
 }
 
 #[derive(Debug, Clone, Deserialize, PartialEq)]
@@ -26,9 +38,29 @@ pub struct UnitStatus {
 	pub since: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct UnitLogs {
+	pub service: String,
+	pub lines: Vec<String>,
+}
+
 pub struct HttpServitorController {
 	base_url: String,
 	http: Client,
+	retries: u32,
+	backoff: Duration,
+	breaker: CircuitBreaker,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpServitorOptions {
+	pub timeout: Duration,
+	pub connect_timeout: Duration,
+	pub retries: u32,
+	pub backoff: Duration,
+	pub failures: u32,
+	pub cooldown: Duration,
+	pub tls: ServitorTlsOptions,
 }
 
 #[derive(Debug, Error)]
@@ -45,6 +77,8 @@ pub enum ServitorError {
 		status_code: Option<StatusCode>,
 		error: Option<Arc<reqwest::Error>>,
 	},
+	#[error("The Servitor instance is unavailable until {retry_at}")]
+	Unavailable { retry_at: DateTime<Utc> },
 }
 
 impl PartialEq for ServitorError {
@@ -65,6 +99,7 @@ impl PartialEq for ServitorError {
 			) => {
 				s1 == s2 && e1.as_ref().map(|e| e.to_string()) == e2.as_ref().map(|e| e.to_string())
 			}
+			(Self::Unavailable { retry_at: r1 }, Self::Unavailable { retry_at: r2 }) => r1 == r2,
 			_ => false,
 		}
 	}
@@ -80,12 +115,21 @@ impl From<reqwest::Error> for ServitorError {
 }
 
 impl HttpServitorController {
-	pub fn new(base_url: &str, token: Option<&SecretString>) -> anyhow::Result<Self> {
+	pub fn new(
+		base_url: &str,
+		token: Option<&SecretString>,
+		options: HttpServitorOptions,
+	) -> anyhow::Result<Self> {
 		static USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
 		let mut client = Client::builder()
 			.user_agent(USER_AGENT)
-			.timeout(Duration::from_secs(2));
+			.timeout(options.timeout)
+			.connect_timeout(options.connect_timeout);
+
+		if !options.tls.is_default() {
+			client = client.use_preconfigured_tls(tls::client_config(&options.tls)?);
+		}
 
 		if let Some(token) = token {
 			let mut auth_header =
@@ -96,12 +140,81 @@ impl HttpServitorController {
 			client = client.default_headers(headers);
 		}
 
+		let base_url = base_url.trim_end_matches('/').to_string();
+
 		Ok(Self {
-			base_url: base_url.trim_end_matches('/').to_string(),
+			breaker: CircuitBreaker::new(&base_url, options.failures, options.cooldown),
+			base_url,
 			http: client.build()?,
+			retries: options.retries,
+			backoff: options.backoff,
 		})
 	}
 
+	/// Fails fast while the instance is considered down, otherwise runs the request and records whether it reached it.
+	async fn guarded<T>(
+		&self,
+		request: impl Future<Output = Result<T, ServitorError>>,
+	) -> Result<T, ServitorError> {
+		self.breaker
+			.acquire()
+			.map_err(|retry_at| ServitorError::Unavailable { retry_at })?;
+
+		let result = request.await;
+		self.breaker
+			.record(!result.as_ref().is_err_and(Self::is_outage));
+		result
+	}
+
+	fn is_outage(error: &ServitorError) -> bool {
+		match error {
+			ServitorError::Unexpected {
+				status_code: Some(status),
+				..
+			} => Self::is_retryable_status(*status),
+			ServitorError::Unexpected {
+				error: Some(e), ..
+			} => Self::is_transport_error(e),
+			_ => false,
+		}
+	}
+
+	/// Only for requests that are safe to repeat, retries transport errors and gateway errors with exponential backoff.
+	async fn send_idempotent(
+		&self,
+		request: impl Fn() -> RequestBuilder,
+	) -> Result<Response, reqwest::Error> {
+		let mut attempt = 0;
+		loop {
+			let retry = match request().send().await {
+				Ok(response) if attempt < self.retries && Self::is_retryable_status(response.status()) => {
+					format!("status {}", response.status())
+				}
+				Err(e) if attempt < self.retries && Self::is_transport_error(&e) => e.to_string(),
+				result => return result,
+			};
+
+			let delay = self.backoff.saturating_mul(2u32.saturating_pow(attempt));
+			attempt += 1;
+			warn!(
+				"Retrying request to Servitor {} in {delay:?} ({attempt}/{}) after {retry}",
+				self.base_url, self.retries
+			);
+			tokio::time::sleep(delay).await;
+		}
+	}
+
+	fn is_transport_error(error: &reqwest::Error) -> bool {
+		error.is_connect() || error.is_timeout() || error.is_request()
+	}
+
+	fn is_retryable_status(status: StatusCode) -> bool {
+		matches!(
+			status,
+			StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+		)
+	}
+
 	fn check_response(response: Response) -> Result<Response, ServitorError> {
 		match response.status() {
 			StatusCode::OK => Ok(response),
@@ -117,9 +230,12 @@ impl HttpServitorController {
 
 	async fn post_request(&self, action: &str, unit_name: &str) -> Result<(), ServitorError> {
 		let url = format!("{}/api/v1/services/{}/{}", self.base_url, unit_name, action);
-		let response = self.http.post(&url).send().await?;
-		Self::check_response(response)?;
-		Ok(())
+		self.guarded(async {
+			let response = self.http.post(&url).send().await?;
+			Self::check_response(response)?;
+			Ok(())
+		})
+		.await
 	}
 }
 
@@ -142,20 +258,101 @@ impl ServitorHandler for HttpServitorController {
 
 	async fn status(&self, unit_name: &str) -> Result<UnitStatus, ServitorError> {
 		let url = format!("{}/api/v1/services/{}/status", self.base_url, unit_name);
-		let response = self.http.get(&url).send().await?;
-		let status = Self::check_response(response)?.json::<UnitStatus>().await?;
-		Ok(status)
+		self.guarded(async {
+			let response = self.send_idempotent(|| self.http.get(&url)).await?;
+			let status = Self::check_response(response)?.json::<UnitStatus>().await?;
+			Ok(status)
+		})
+		.await
+	}
+
+	async fn logs(&self, unit_name: &str, lines: u32) -> Result<UnitLogs, ServitorError> {
+		let url = format!("{}/api/v1/services/{}/logs", self.base_url, unit_name);
+		self.guarded(async {
+			let response = self
+				.send_idempotent(|| self.http.get(&url).query(&[("lines", lines)]))
+				.await?;
+			let logs = Self::check_response(response)?.json::<UnitLogs>().await?;
+			Ok(logs)
+		})
+		.await
 	}
 
 	async fn health(&self) -> bool {
 		let url = format!("{}/health", self.base_url);
 
-		self.http
-			.get(&url)
-			.send()
+		self.send_idempotent(|| self.http.get(&url))
 			.await
 			.map_or(false, |r| r.error_for_status().is_ok())
 	}
+
+	fn is_local(&self) -> bool {
+		false
+	}
+}
+
+/// Any of the configurable kinds of Servitor instances, the handler traits are not object safe.
+pub enum ServitorBackend {
+	Http(HttpServitorController),
+	Local(LocalServitorController),
+}
+
+impl ServitorHandler for ServitorBackend {
+	async fn start(&self, unit_name: &str) -> Result<(), ServitorError> {
+		match self {
+			Self::Http(controller) => controller.start(unit_name).await,
+			Self::Local(controller) => controller.start(unit_name).await,
+		}
+	}
+
+	async fn stop(&self, unit_name: &str) -> Result<(), ServitorError> {
+		match self {
+			Self::Http(controller) => controller.stop(unit_name).await,
+			Self::Local(controller) => controller.stop(unit_name).await,
+		}
+	}
+
+	async fn restart(&self, unit_name: &str) -> Result<(), ServitorError> {
+		match self {
+			Self::Http(controller) => controller.restart(unit_name).await,
+			Self::Local(controller) => controller.restart(unit_name).await,
+		}
+	}
+
+	async fn reload(&self, unit_name: &str) -> Result<(), ServitorError> {
+		match self {
+			Self::Http(controller) => controller.reload(unit_name).await,
+			Self::Local(controller) => controller.reload(unit_name).await,
+		}
+	}
+
+	async fn status(&self, unit_name: &str) -> Result<UnitStatus, ServitorError> {
+		match self {
+			Self::Http(controller) => controller.status(unit_name).await,
+			Self::Local(controller) => controller.status(unit_name).await,
+		}
+	}
+
+	async fn logs(&self, unit_name: &str, lines: u32) -> Result<UnitLogs, ServitorError> {
+		match self {
+			Self::Http(controller) => controller.logs(unit_name, lines).await,
+			Self::Local(controller) => controller.logs(unit_name, lines).await,
+		}
+	}
+
+	async fn health(&self) -> bool {
+		match self {
+			Self::Http(controller) => controller.health().await,
+			Self::Local(controller) => controller.health().await,
+		}
+	}
+
+	fn is_local(&self) -> bool {
+		match self {
+			Self::Http(controller) => controller.is_local(),
+			Self::Local(controller) => controller.is_local(),
+		}
+	}
 }
 
 #[cfg(test)]
@@ -163,9 +360,11 @@ pub mod tests {
 	use super::*;
 	use crate::data::BotData;
 	use std::collections::BTreeMap;
-	use std::sync::atomic::{AtomicUsize, Ordering};
+	use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 	use std::sync::Arc;
 	use chrono::TimeZone;
+	use tokio::io::{AsyncReadExt, AsyncWriteExt};
+	use tokio::net::TcpListener;
 	use tokio::sync::Mutex;
 
 	pub async fn controllers_from_bot_data(
@@ -174,8 +373,8 @@ pub mod tests {
 		data.read()
 			.await
 			.servitor
-			.iter()
-			.map(|(_, server_info)| (server_info.servitor.clone(), MockServitorController::new()))
+			.values()
+			.map(|server_info| (server_info.servitor.clone(), MockServitorController::new()))
 			.collect()
 	}
 
@@ -185,7 +384,13 @@ pub mod tests {
 		called_restart: Arc<AtomicUsize>,
 		called_reload: Arc<AtomicUsize>,
 		called_status: Arc<AtomicUsize>,
+		called_logs: Arc<AtomicUsize>,
 		error: Arc<Mutex<Option<ServitorError>>>,
+		healthy: Arc<AtomicBool>,
+		local: Arc<AtomicBool>,
+		delay: Arc<Mutex<Duration>>,
+		in_flight: Arc<AtomicUsize>,
+		max_in_flight: Arc<AtomicUsize>,
 	}
 
 	impl MockServitorController {
@@ -196,12 +401,19 @@ pub mod tests {
 				called_restart: Arc::new(AtomicUsize::new(0)),
 				called_reload: Arc::new(AtomicUsize::new(0)),
 				called_status: Arc::new(AtomicUsize::new(0)),
+				called_logs: Arc::new(AtomicUsize::new(0)),
 				error: Arc::new(Mutex::new(None)),
+				healthy: Arc::new(AtomicBool::new(true)),
+				local: Arc::new(AtomicBool::new(false)),
+				delay: Arc::new(Mutex::new(Duration::ZERO)),
+				in_flight: Arc::new(AtomicUsize::new(0)),
+				max_in_flight: Arc::new(AtomicUsize::new(0)),
 			}
 		}
 
 		pub fn assert_not_called(&self) {
 			self.assert_called_times(0, 0, 0, 0, 0);
+			self.assert_logs_called_times(0);
 		}
 
 		pub fn assert_called_times(
@@ -239,11 +451,42 @@ pub mod tests {
 			);
 		}
 
+		pub fn assert_logs_called_times(&self, logs: usize) {
+			assert_eq!(
+				self.called_logs.load(Ordering::Relaxed),
+				logs,
+				"Logs called incorrect times"
+			);
+		}
+
 		pub async fn set_error(&self, error: ServitorError) {
 			*self.error.lock().await = Some(error);
 		}
 
+		pub fn set_healthy(&self, healthy: bool) {
+			self.healthy.store(healthy, Ordering::Relaxed);
+		}
+
+		pub fn set_local(&self, local: bool) {
+			self.local.store(local, Ordering::Relaxed);
+		}
+
+		/// Makes every call take this long, so tests can observe how many run at the same time.
+		pub async fn set_delay(&self, delay: Duration) {
+			*self.delay.lock().await = delay;
+		}
+
+		pub fn max_in_flight(&self) -> usize {
+			self.max_in_flight.load(Ordering::SeqCst)
+		}
+
 		async fn check_for_error(&self) -> Result<(), ServitorError> {
+			let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+			self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+			let delay = *self.delay.lock().await;
+			tokio::time::sleep(delay).await;
+			self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
 			if let Some(err) = self.error.lock().await.clone() {
 				Err(err)
 			} else {
@@ -259,6 +502,13 @@ pub mod tests {
 				since: Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap(),
 			}
 		}
+
+		pub fn default_logs(unit_name: &str, lines: u32) -> UnitLogs {
+			UnitLogs {
+				service: unit_name.to_string(),
+				lines: (1..=lines).map(|i| format!("log line {i}")).collect(),
+			}
+		}
 	}
 
 	impl ServitorHandler for MockServitorController {
@@ -289,8 +539,227 @@ pub mod tests {
 			Ok(Self::default_status(unit_name))
 		}
 
+		async fn logs(&self, unit_name: &str, lines: u32) -> Result<UnitLogs, ServitorError> {
+			self.called_logs.fetch_add(1, Ordering::Relaxed);
+			self.check_for_error().await?;
+
+			Ok(Self::default_logs(unit_name, lines))
+		}
+
 		async fn health(&self) -> bool {
-			true
+			self.healthy.load(Ordering::Relaxed)
+		}
+
+		fn is_local(&self) -> bool {
+			self.local.load(Ordering::Relaxed)
+		}
+	}
+
+	#[derive(Clone)]
+	enum StubResponse {
+		Respond { status: u16, body: &'static str },
+		Delayed { delay: Duration, status: u16, body: &'static str },
+		Drop,
+	}
+
+	/// Minimal HTTP server answering each request with the next response, repeating the last one once exhausted.
+	async fn stub_server(responses: Vec<StubResponse>) -> (String, Arc<AtomicUsize>) {
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let url = format!("http://{}", listener.local_addr().unwrap());
+		let hits = Arc::new(AtomicUsize::new(0));
+
+		let server_hits = hits.clone();
+		tokio::spawn(async move {
+			loop {
+				let Ok((mut socket, _)) = listener.accept().await else {
+					return;
+				};
+				let hit = server_hits.fetch_add(1, Ordering::SeqCst);
+				let response = responses[hit.min(responses.len() - 1)].clone();
+
+				tokio::spawn(async move {
+					let mut request = Vec::new();
+					let mut buffer = [0u8; 1024];
+					while !request.ends_with(b"\r\n\r\n") {
+						match socket.read(&mut buffer).await {
+							Ok(0) | Err(_) => return,
+							Ok(n) => request.extend_from_slice(&buffer[..n]),
+						}
+					}
+
+					let (status, body) = match response {
+						StubResponse::Respond { status, body } => (status, body),
+						StubResponse::Delayed { delay, status, body } => {
+							tokio::time::sleep(delay).await;
+							(status, body)
+						}
+						StubResponse::Drop => return,
+					};
+
+					let response = format!(
+						"HTTP/1.1 {status} Stub\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+						body.len()
+					);
+					let _ = socket.write_all(response.as_bytes()).await;
+				});
+			}
+		});
+
+		(url, hits)
+	}
+
+	const STATUS_BODY: &str = r#"{"service":"bar.service","state":"active","sub_state":"running","since":"2025-03-06T19:59:45Z"}"#;
+
+	fn http_controller(url: &str, timeout: Duration, retries: u32) -> HttpServitorController {
+		HttpServitorController::new(
+			url,
+			None,
+			HttpServitorOptions {
+				timeout,
+				connect_timeout: timeout,
+				retries,
+				backoff: Duration::from_millis(1),
+				failures: 2,
+				cooldown: Duration::from_secs(60),
+				tls: ServitorTlsOptions::default(),
+			},
+		)
+		.unwrap()
+	}
+
+	fn expected_status() -> UnitStatus {
+		UnitStatus {
+			service: "bar.service".to_string(),
+			state: "active".to_string(),
+			sub_state: "running".to_string(),
+			since: Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap(),
+		}
+	}
+
+	#[tokio::test]
+	async fn given_unavailable_then_ok_then_status_retries_and_succeeds() {
+		let (url, hits) = stub_server(vec![
+			StubResponse::Respond { status: 503, body: "" },
+			StubResponse::Respond { status: 200, body: STATUS_BODY },
+		])
+		.await;
+		let controller = http_controller(&url, Duration::from_secs(2), 2);
+
+		let result = controller.status("bar").await;
+
+		assert_eq!(result, Ok(expected_status()));
+		assert_eq!(hits.load(Ordering::SeqCst), 2);
+	}
+
+	#[tokio::test]
+	async fn given_dropped_connection_then_status_retries_and_succeeds() {
+		let (url, hits) = stub_server(vec![
+			StubResponse::Drop,
+			StubResponse::Respond { status: 200, body: STATUS_BODY },
+		])
+		.await;
+		let controller = http_controller(&url, Duration::from_secs(2), 1);
+
+		let result = controller.status("bar").await;
+
+		assert_eq!(result, Ok(expected_status()));
+		assert_eq!(hits.load(Ordering::SeqCst), 2);
+	}
+
+	#[tokio::test]
+	async fn given_always_unavailable_then_status_gives_up_after_retries() {
+		let (url, hits) = stub_server(vec![StubResponse::Respond { status: 503, body: "" }]).await;
+		let controller = http_controller(&url, Duration::from_secs(2), 2);
+
+		let result = controller.status("bar").await;
+
+		assert!(matches!(
+			result,
+			Err(ServitorError::Unexpected {
+				status_code: Some(StatusCode::SERVICE_UNAVAILABLE),
+				..
+			})
+		));
+		assert_eq!(hits.load(Ordering::SeqCst), 3);
+	}
+
+	#[tokio::test]
+	async fn given_bad_request_then_status_does_not_retry() {
+		let (url, hits) = stub_server(vec![StubResponse::Respond { status: 400, body: "" }]).await;
+		let controller = http_controller(&url, Duration::from_secs(2), 2);
+
+		let result = controller.status("bar").await;
+
+		assert_eq!(result, Err(ServitorError::BadRequest));
+		assert_eq!(hits.load(Ordering::SeqCst), 1);
+	}
+
+	#[tokio::test]
+	async fn given_unavailable_then_start_does_not_retry() {
+		let (url, hits) = stub_server(vec![
+			StubResponse::Respond { status: 503, body: "" },
+			StubResponse::Respond { status: 200, body: "" },
+		])
+		.await;
+		let controller = http_controller(&url, Duration::from_secs(2), 2);
+
+		let result = controller.start("bar").await;
+
+		assert!(matches!(result, Err(ServitorError::Unexpected { .. })));
+		assert_eq!(hits.load(Ordering::SeqCst), 1);
+	}
+
+	#[tokio::test]
+	async fn given_slow_start_then_result_depends_on_configured_timeout() {
+		let slow = StubResponse::Delayed {
+			delay: Duration::from_millis(300),
+			status: 200,
+			body: "",
+		};
+		let (url, hits) = stub_server(vec![slow]).await;
+
+		let short = http_controller(&url, Duration::from_millis(100), 2).start("bar").await;
+		let long = http_controller(&url, Duration::from_secs(2), 2).start("bar").await;
+
+		assert!(matches!(short, Err(ServitorError::Unexpected { .. })));
+		assert_eq!(long, Ok(()));
+		assert_eq!(hits.load(Ordering::SeqCst), 2);
+	}
+
+	#[tokio::test]
+	async fn given_dropped_connection_then_health_retries_and_reports_healthy() {
+		let (url, hits) = stub_server(vec![
+			StubResponse::Drop,
+			StubResponse::Respond { status: 200, body: "" },
+		])
+		.await;
+		let controller = http_controller(&url, Duration::from_secs(2), 1);
+
+		assert!(controller.health().await);
+		assert_eq!(hits.load(Ordering::SeqCst), 2);
+	}
+
+	#[tokio::test]
+	async fn given_consecutive_outages_then_requests_fail_fast_until_cooldown() {
+		let (url, hits) = stub_server(vec![StubResponse::Respond { status: 503, body: "" }]).await;
+		let controller = http_controller(&url, Duration::from_secs(2), 0);
+
+		controller.status("bar").await.unwrap_err();
+		controller.start("bar").await.unwrap_err();
+		let result = controller.status("bar").await;
+
+		assert!(matches!(result, Err(ServitorError::Unavailable { retry_at }) if retry_at > Utc::now()));
+		assert_eq!(hits.load(Ordering::SeqCst), 2);
+	}
+
+	#[tokio::test]
+	async fn given_unit_errors_then_circuit_stays_closed() {
+		let (url, hits) = stub_server(vec![StubResponse::Respond { status: 400, body: "" }]).await;
+		let controller = http_controller(&url, Duration::from_secs(2), 0);
+
+		for _ in 0..3 {
+			assert_eq!(controller.start("bar").await, Err(ServitorError::BadRequest));
 		}
+		assert_eq!(hits.load(Ordering::SeqCst), 3);
 	}
 }
@@ -0,0 +1,178 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use log::{info, warn};
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CircuitState {
+	Closed { failures: u32 },
+	Open { until: DateTime<Utc> },
+	/// A single trial request is let through, anything else keeps failing fast until it settles or `until` passes.
+	HalfOpen { until: DateTime<Utc> },
+}
+
+/// Stops sending requests to a Servitor instance after consecutive failures, periodically letting one through to
+/// check if it came back.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+	name: String,
+	failures: u32,
+	cooldown: TimeDelta,
+	state: Mutex<CircuitState>,
+}
+
+impl CircuitBreaker {
+	/// A `failures` of `0` disables the breaker.
+	pub fn new(name: &str, failures: u32, cooldown: Duration) -> Self {
+		Self {
+			name: name.to_string(),
+			failures,
+			cooldown: TimeDelta::from_std(cooldown).unwrap_or(TimeDelta::MAX),
+			state: Mutex::new(CircuitState::Closed { failures: 0 }),
+		}
+	}
+
+	/// Returns when the next request will be allowed if the circuit is open.
+	pub fn acquire(&self) -> Result<(), DateTime<Utc>> {
+		self.acquire_at(Utc::now())
+	}
+
+	pub fn record(&self, success: bool) {
+		self.record_at(success, Utc::now())
+	}
+
+	/// Cooldowns too long to represent never end.
+	fn reopen_at(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+		now.checked_add_signed(self.cooldown).unwrap_or(DateTime::<Utc>::MAX_UTC)
+	}
+
+	fn acquire_at(&self, now: DateTime<Utc>) -> Result<(), DateTime<Utc>> {
+		let mut state = self.state.lock().unwrap();
+
+		match *state {
+			CircuitState::Closed { .. } => Ok(()),
+			CircuitState::Open { until } | CircuitState::HalfOpen { until } if until <= now => {
+				info!("Circuit for Servitor {} is half-open, sending a trial request", self.name);
+				*state = CircuitState::HalfOpen {
+					until: self.reopen_at(now),
+				};
+				Ok(())
+			}
+			CircuitState::Open { until } | CircuitState::HalfOpen { until } => Err(until),
+		}
+	}
+
+	fn record_at(&self, success: bool, now: DateTime<Utc>) {
+		if self.failures == 0 {
+			return;
+		}
+
+		let mut state = self.state.lock().unwrap();
+
+		*state = match (*state, success) {
+			(CircuitState::Closed { .. }, true) => CircuitState::Closed { failures: 0 },
+			(CircuitState::Closed { failures }, false) if failures + 1 < self.failures => {
+				CircuitState::Closed { failures: failures + 1 }
+			}
+			(CircuitState::Open { .. } | CircuitState::HalfOpen { .. }, true) => {
+				info!("Circuit for Servitor {} closed, the instance is reachable again", self.name);
+				CircuitState::Closed { failures: 0 }
+			}
+			(_, false) => {
+				let until = self.reopen_at(now);
+				warn!("Circuit for Servitor {} opened until {until} after consecutive failures", self.name);
+				CircuitState::Open { until }
+			}
+		};
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::TimeZone;
+
+	fn now() -> DateTime<Utc> {
+		Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap()
+	}
+
+	fn open_breaker() -> CircuitBreaker {
+		let breaker = CircuitBreaker::new("foo", 3, Duration::from_secs(30));
+		for _ in 0..3 {
+			breaker.acquire_at(now()).unwrap();
+			breaker.record_at(false, now());
+		}
+		breaker
+	}
+
+	#[test]
+	fn given_fewer_failures_than_threshold_then_circuit_stays_closed() {
+		let breaker = CircuitBreaker::new("foo", 3, Duration::from_secs(30));
+
+		breaker.record_at(false, now());
+		breaker.record_at(false, now());
+		breaker.record_at(true, now());
+		breaker.record_at(false, now());
+		breaker.record_at(false, now());
+
+		assert_eq!(breaker.acquire_at(now()), Ok(()));
+	}
+
+	#[test]
+	fn given_consecutive_failures_then_circuit_opens_until_cooldown() {
+		let breaker = open_breaker();
+
+		assert_eq!(breaker.acquire_at(now()), Err(now() + TimeDelta::seconds(30)));
+	}
+
+	#[test]
+	fn given_cooldown_passed_then_circuit_lets_single_trial_through() {
+		let breaker = open_breaker();
+		let later = now() + TimeDelta::seconds(30);
+
+		assert_eq!(breaker.acquire_at(later), Ok(()));
+		assert_eq!(breaker.acquire_at(later), Err(later + TimeDelta::seconds(30)));
+	}
+
+	#[test]
+	fn given_successful_trial_then_circuit_closes() {
+		let breaker = open_breaker();
+		let later = now() + TimeDelta::seconds(30);
+
+		breaker.acquire_at(later).unwrap();
+		breaker.record_at(true, later);
+
+		assert_eq!(breaker.acquire_at(later), Ok(()));
+	}
+
+	#[test]
+	fn given_failed_trial_then_circuit_opens_again() {
+		let breaker = open_breaker();
+		let later = now() + TimeDelta::seconds(30);
+
+		breaker.acquire_at(later).unwrap();
+		breaker.record_at(false, later);
+
+		assert_eq!(breaker.acquire_at(later), Err(later + TimeDelta::seconds(30)));
+	}
+
+	#[test]
+	fn given_disabled_breaker_then_circuit_never_opens() {
+		let breaker = CircuitBreaker::new("foo", 0, Duration::from_secs(30));
+
+		for _ in 0..10 {
+			breaker.record_at(false, now());
+		}
+
+		assert_eq!(breaker.acquire_at(now()), Ok(()));
+	}
+
+	#[test]
+	fn given_huge_cooldown_then_circuit_stays_open_forever() {
+		let breaker = CircuitBreaker::new("foo", 1, Duration::MAX);
+
+		breaker.record_at(false, now());
+
+		assert_eq!(breaker.acquire_at(now()), Err(DateTime::<Utc>::MAX_UTC));
+	}
+}
@@ -0,0 +1,378 @@
+use super::{ServitorError, ServitorHandler, UnitLogs, UnitStatus};
+use chrono::{DateTime, Utc};
+use log::warn;
+use std::collections::HashMap;
+use std::io;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+/// `systemctl` exits with this when the unit is not loaded, see `systemctl(1)`.
+const EXIT_UNIT_NOT_FOUND: i32 = 5;
+
+pub trait CommandRunner = RunCommand + Send + Sync;
+pub trait RunCommand {
+	async fn run(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandOutput {
+	pub code: Option<i32>,
+	pub stdout: String,
+	pub stderr: String,
+}
+
+impl CommandOutput {
+	fn success(&self) -> bool {
+		self.code == Some(0)
+	}
+}
+
+pub struct SystemCommandRunner;
+
+impl RunCommand for SystemCommandRunner {
+	async fn run(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput> {
+		let output = Command::new(program)
+			.args(args)
+			.stdin(Stdio::null())
+			.kill_on_drop(true)
+			.output()
+			.await?;
+
+		Ok(CommandOutput {
+			code: output.status.code(),
+			stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+			stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+		})
+	}
+}
+
+/// Controls units through the system manager of the host the bot runs on, without a Servitor instance.
+pub struct LocalServitorController<R = SystemCommandRunner> {
+	runner: R,
+	timeout: Duration,
+}
+
+impl LocalServitorController {
+	pub fn new(timeout: Duration) -> Self {
+		Self::with_runner(SystemCommandRunner, timeout)
+	}
+}
+
+impl<R: CommandRunner> LocalServitorController<R> {
+	pub fn with_runner(runner: R, timeout: Duration) -> Self {
+		Self { runner, timeout }
+	}
+
+	async fn run(&self, program: &str, args: &[&str]) -> Result<CommandOutput, ServitorError> {
+		match tokio::time::timeout(self.timeout, self.runner.run(program, args)).await {
+			Ok(Ok(output)) => Ok(output),
+			Ok(Err(e)) => {
+				warn!("Failed to run {program}: {e}");
+				Err(ServitorError::InternalServerError)
+			}
+			Err(_) => {
+				warn!("Running {program} timed out after {:?}", self.timeout);
+				Err(ServitorError::InternalServerError)
+			}
+		}
+	}
+
+	async fn systemctl_action(&self, action: &str, unit_name: &str) -> Result<(), ServitorError> {
+		check_unit_name(unit_name)?;
+		let output = self
+			.run("systemctl", &["--no-ask-password", action, "--", unit_name])
+			.await?;
+		check_output(&output, unit_name)
+	}
+}
+
+impl<R: CommandRunner> ServitorHandler for LocalServitorController<R> {
+	async fn start(&self, unit_name: &str) -> Result<(), ServitorError> {
+		self.systemctl_action("start", unit_name).await
+	}
+
+	async fn stop(&self, unit_name: &str) -> Result<(), ServitorError> {
+		self.systemctl_action("stop", unit_name).await
+	}
+
+	async fn restart(&self, unit_name: &str) -> Result<(), ServitorError> {
+		self.systemctl_action("restart", unit_name).await
+	}
+
+	async fn reload(&self, unit_name: &str) -> Result<(), ServitorError> {
+		self.systemctl_action("reload", unit_name).await
+	}
+
+	async fn status(&self, unit_name: &str) -> Result<UnitStatus, ServitorError> {
+		check_unit_name(unit_name)?;
+		let output = self
+			.run(
+				"systemctl",
+				&[
+					"show",
+					"--timestamp=unix",
+					"--property=Id,LoadState,ActiveState,SubState,StateChangeTimestamp",
+					"--",
+					unit_name,
+				],
+			)
+			.await?;
+		check_output(&output, unit_name)?;
+
+		parse_status(&output.stdout, unit_name)
+	}
+
+	async fn logs(&self, unit_name: &str, lines: u32) -> Result<UnitLogs, ServitorError> {
+		check_unit_name(unit_name)?;
+		// The count of --lines is optional, so it must be passed with an equals sign
+		let lines = format!("--lines={lines}");
+		let output = self
+			.run(
+				"journalctl",
+				&["--no-pager", "--output=short-iso", &lines, "--unit", unit_name],
+			)
+			.await?;
+		check_output(&output, unit_name)?;
+
+		Ok(UnitLogs {
+			service: unit_name.to_string(),
+			lines: output
+				.stdout
+				.lines()
+				.filter(|line| !line.starts_with("-- "))
+				.map(str::to_string)
+				.collect(),
+		})
+	}
+
+	async fn health(&self) -> bool {
+		// A degraded system only has failed units, the manager itself is still usable
+		match self.run("systemctl", &["is-system-running"]).await {
+			Ok(output) => matches!(output.stdout.trim(), "running" | "degraded"),
+			Err(_) => false,
+		}
+	}
+
+	fn is_local(&self) -> bool {
+		true
+	}
+}
+
+/// Rejects names that `systemctl` could mistake for options or that are not unit names at all.
+fn check_unit_name(unit_name: &str) -> Result<(), ServitorError> {
+	let valid = !unit_name.is_empty()
+		&& !unit_name.starts_with('-')
+		&& unit_name
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '@' | ':' | '\\'));
+
+	match valid {
+		true => Ok(()),
+		false => Err(ServitorError::BadRequest),
+	}
+}
+
+fn check_output(output: &CommandOutput, unit_name: &str) -> Result<(), ServitorError> {
+	if output.success() {
+		return Ok(());
+	}
+
+	let stderr = output.stderr.trim();
+	warn!("Command for unit {unit_name} failed with code {:?}: {stderr}", output.code);
+
+	if output.code == Some(EXIT_UNIT_NOT_FOUND) {
+		Err(ServitorError::BadRequest)
+	} else if stderr.contains("Access denied") || stderr.contains("authentication required") {
+		Err(ServitorError::Unauthorized)
+	} else {
+		Err(ServitorError::InternalServerError)
+	}
+}
+
+fn parse_status(stdout: &str, unit_name: &str) -> Result<UnitStatus, ServitorError> {
+	let properties: HashMap<&str, &str> = stdout.lines().filter_map(|line| line.split_once('=')).collect();
+
+	if properties.get("LoadState").is_none_or(|&state| state == "not-found") {
+		return Err(ServitorError::BadRequest);
+	}
+
+	let since = properties
+		.get("StateChangeTimestamp")
+		.and_then(|timestamp| timestamp.strip_prefix('@'))
+		.and_then(|seconds| seconds.parse().ok())
+		.and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+		.unwrap_or(DateTime::<Utc>::UNIX_EPOCH);
+
+	Ok(UnitStatus {
+		service: properties.get("Id").unwrap_or(&unit_name).to_string(),
+		state: properties.get("ActiveState").unwrap_or(&"unknown").to_string(),
+		sub_state: properties.get("SubState").unwrap_or(&"unknown").to_string(),
+		since,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::TimeZone;
+	use rstest::rstest;
+	use std::sync::Mutex;
+
+	struct MockCommandRunner {
+		calls: Mutex<Vec<Vec<String>>>,
+		output: io::Result<CommandOutput>,
+	}
+
+	impl MockCommandRunner {
+		fn new(code: i32, stdout: &str, stderr: &str) -> Self {
+			Self {
+				calls: Mutex::new(Vec::new()),
+				output: Ok(CommandOutput {
+					code: Some(code),
+					stdout: stdout.to_string(),
+					stderr: stderr.to_string(),
+				}),
+			}
+		}
+
+		fn calls(&self) -> Vec<Vec<String>> {
+			self.calls.lock().unwrap().clone()
+		}
+	}
+
+	impl RunCommand for MockCommandRunner {
+		async fn run(&self, program: &str, args: &[&str]) -> io::Result<CommandOutput> {
+			let call = std::iter::once(program).chain(args.iter().copied()).map(str::to_string).collect();
+			self.calls.lock().unwrap().push(call);
+
+			match &self.output {
+				Ok(output) => Ok(output.clone()),
+				Err(e) => Err(io::Error::new(e.kind(), e.to_string())),
+			}
+		}
+	}
+
+	fn controller(runner: MockCommandRunner) -> LocalServitorController<MockCommandRunner> {
+		LocalServitorController::with_runner(runner, Duration::from_secs(1))
+	}
+
+	#[tokio::test]
+	async fn given_start_then_runs_systemctl_start_for_unit() {
+		let local = controller(MockCommandRunner::new(0, "", ""));
+
+		let result = local.start("minecraft.service").await;
+
+		assert_eq!(result, Ok(()));
+		assert_eq!(
+			local.runner.calls(),
+			vec![vec!["systemctl", "--no-ask-password", "start", "--", "minecraft.service"]]
+		);
+	}
+
+	#[rstest]
+	#[case(EXIT_UNIT_NOT_FOUND, "Failed to start foo.service: Unit foo.service not found.", ServitorError::BadRequest)]
+	#[case(1, "Failed to stop foo.service: Access denied", ServitorError::Unauthorized)]
+	#[case(1, "Failed to stop foo.service: Interactive authentication required.", ServitorError::Unauthorized)]
+	#[case(1, "Job for foo.service failed because the control process exited with error code.", ServitorError::InternalServerError)]
+	#[tokio::test]
+	async fn given_failing_systemctl_then_stop_maps_error(
+		#[case] code: i32,
+		#[case] stderr: &str,
+		#[case] expected: ServitorError,
+	) {
+		let local = controller(MockCommandRunner::new(code, "", stderr));
+
+		assert_eq!(local.stop("foo.service").await, Err(expected));
+	}
+
+	#[rstest]
+	#[case("")]
+	#[case("--help")]
+	#[case("foo service")]
+	#[case("foo;reboot")]
+	#[tokio::test]
+	async fn given_invalid_unit_name_then_does_not_run_anything(#[case] unit_name: &str) {
+		let local = controller(MockCommandRunner::new(0, "", ""));
+
+		assert_eq!(local.restart(unit_name).await, Err(ServitorError::BadRequest));
+		assert!(local.runner.calls().is_empty());
+	}
+
+	#[tokio::test]
+	async fn given_loaded_unit_then_status_parses_systemctl_show() {
+		let stdout = "Id=minecraft.service\nLoadState=loaded\nActiveState=active\nSubState=running\n\
+			StateChangeTimestamp=@1741291185\n";
+		let local = controller(MockCommandRunner::new(0, stdout, ""));
+
+		let result = local.status("minecraft").await;
+
+		assert_eq!(
+			result,
+			Ok(UnitStatus {
+				service: "minecraft.service".to_string(),
+				state: "active".to_string(),
+				sub_state: "running".to_string(),
+				since: Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap(),
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn given_unknown_unit_then_status_returns_bad_request() {
+		let stdout = "Id=foo.service\nLoadState=not-found\nActiveState=inactive\nSubState=dead\nStateChangeTimestamp=\n";
+		let local = controller(MockCommandRunner::new(0, stdout, ""));
+
+		assert_eq!(local.status("foo").await, Err(ServitorError::BadRequest));
+	}
+
+	#[tokio::test]
+	async fn given_logs_then_runs_journalctl_and_skips_notices() {
+		let stdout = "-- No entries --\n2025-03-06T19:59:45+0000 host java[123]: Done (5.2s)!\n";
+		let local = controller(MockCommandRunner::new(0, stdout, ""));
+
+		let result = local.logs("minecraft.service", 20).await;
+
+		assert_eq!(
+			result,
+			Ok(UnitLogs {
+				service: "minecraft.service".to_string(),
+				lines: vec!["2025-03-06T19:59:45+0000 host java[123]: Done (5.2s)!".to_string()],
+			})
+		);
+		assert_eq!(
+			local.runner.calls(),
+			vec![vec![
+				"journalctl",
+				"--no-pager",
+				"--output=short-iso",
+				"--lines=20",
+				"--unit",
+				"minecraft.service"
+			]]
+		);
+	}
+
+	#[rstest]
+	#[case(0, "running\n", true)]
+	#[case(1, "degraded\n", true)]
+	#[case(1, "stopping\n", false)]
+	#[tokio::test]
+	async fn given_system_state_then_health_reports_it(#[case] code: i32, #[case] stdout: &str, #[case] expected: bool) {
+		let local = controller(MockCommandRunner::new(code, stdout, ""));
+
+		assert_eq!(local.health().await, expected);
+	}
+
+	#[tokio::test]
+	async fn given_missing_systemctl_then_returns_internal_error() {
+		let runner = MockCommandRunner {
+			calls: Mutex::new(Vec::new()),
+			output: Err(io::Error::new(io::ErrorKind::NotFound, "No such file or directory")),
+		};
+		let local = controller(runner);
+
+		assert_eq!(local.start("foo.service").await, Err(ServitorError::InternalServerError));
+		assert!(!local.health().await);
+	}
+}
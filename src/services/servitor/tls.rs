@@ -0,0 +1,217 @@
+use anyhow::{anyhow, bail, Context};
+use ring::digest::{digest, SHA256};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{CertificateError, ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServitorTlsOptions {
+	pub ca: Option<PathBuf>,
+	pub cert: Option<PathBuf>,
+	pub key: Option<PathBuf>,
+	pub fingerprint: Option<String>,
+}
+
+impl ServitorTlsOptions {
+	pub fn is_default(&self) -> bool {
+		*self == Self::default()
+	}
+}
+
+pub fn client_config(options: &ServitorTlsOptions) -> anyhow::Result<ClientConfig> {
+	let provider = Arc::new(rustls::crypto::ring::default_provider());
+	let builder = ClientConfig::builder_with_provider(provider.clone()).with_safe_default_protocol_versions()?;
+
+	if options.fingerprint.is_some() && options.ca.is_some() {
+		bail!("a pinned fingerprint replaces CA validation, it can't be configured together with a CA bundle");
+	}
+
+	let builder = match &options.fingerprint {
+		Some(fingerprint) => builder
+			.dangerous()
+			.with_custom_certificate_verifier(Arc::new(PinnedCertificateVerifier {
+				fingerprint: parse_fingerprint(fingerprint)?,
+				provider,
+			})),
+		None => builder.with_root_certificates(root_store(options.ca.as_ref())?),
+	};
+
+	let mut config = match (&options.cert, &options.key) {
+		(Some(cert), Some(key)) => {
+			let certs = CertificateDer::pem_file_iter(cert)
+				.and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+				.with_context(|| format!("failed to read client certificate {}", cert.display()))?;
+			let key = PrivateKeyDer::from_pem_file(key)
+				.with_context(|| format!("failed to read client key {}", key.display()))?;
+			builder.with_client_auth_cert(certs, key)?
+		}
+		(None, None) => builder.with_no_client_auth(),
+		_ => bail!("a client certificate and key must be configured together"),
+	};
+
+	config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+	Ok(config)
+}
+
+fn root_store(ca: Option<&PathBuf>) -> anyhow::Result<RootCertStore> {
+	let mut roots = RootCertStore::from_iter(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+
+	if let Some(ca) = ca {
+		for cert in CertificateDer::pem_file_iter(ca).with_context(|| format!("failed to read CA bundle {}", ca.display()))? {
+			roots.add(cert.with_context(|| format!("invalid certificate in CA bundle {}", ca.display()))?)?;
+		}
+	}
+
+	Ok(roots)
+}
+
+/// Accepts a SHA-256 fingerprint as hex digits, optionally separated by `:` like most tools print them.
+fn parse_fingerprint(fingerprint: &str) -> anyhow::Result<[u8; 32]> {
+	let hex: String = fingerprint.chars().filter(|&c| c != ':').collect();
+
+	if !hex.is_ascii() || hex.len() != 64 {
+		bail!("certificate fingerprint {fingerprint} is not a SHA-256 fingerprint");
+	}
+
+	let mut bytes = [0u8; 32];
+	for (i, byte) in bytes.iter_mut().enumerate() {
+		*byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+			.map_err(|_| anyhow!("certificate fingerprint {fingerprint} is not hexadecimal"))?;
+	}
+
+	Ok(bytes)
+}
+
+/// Trusts exactly the certificate with the pinned fingerprint instead of validating it against CAs.
+#[derive(Debug)]
+struct PinnedCertificateVerifier {
+	fingerprint: [u8; 32],
+	provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for PinnedCertificateVerifier {
+	fn verify_server_cert(
+		&self,
+		end_entity: &CertificateDer<'_>,
+		_intermediates: &[CertificateDer<'_>],
+		_server_name: &ServerName<'_>,
+		_ocsp_response: &[u8],
+		_now: UnixTime,
+	) -> Result<ServerCertVerified, rustls::Error> {
+		if digest(&SHA256, end_entity).as_ref() == self.fingerprint {
+			Ok(ServerCertVerified::assertion())
+		} else {
+			Err(rustls::Error::InvalidCertificate(
+				CertificateError::ApplicationVerificationFailure,
+			))
+		}
+	}
+
+	fn verify_tls12_signature(
+		&self,
+		message: &[u8],
+		cert: &CertificateDer<'_>,
+		dss: &DigitallySignedStruct,
+	) -> Result<HandshakeSignatureValid, rustls::Error> {
+		verify_tls12_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+	}
+
+	fn verify_tls13_signature(
+		&self,
+		message: &[u8],
+		cert: &CertificateDer<'_>,
+		dss: &DigitallySignedStruct,
+	) -> Result<HandshakeSignatureValid, rustls::Error> {
+		verify_tls13_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+	}
+
+	fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+		self.provider.signature_verification_algorithms.supported_schemes()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const FINGERPRINT: &str = "2c:f2:4d:ba:5f:b0:a3:0e:26:e8:3b:2a:c5:b9:e2:9e:1b:16:1e:5c:1f:a7:42:5e:73:04:33:62:93:8b:98:24";
+
+	#[test]
+	fn given_colon_separated_fingerprint_then_parse_fingerprint_returns_bytes() {
+		let fingerprint = parse_fingerprint(FINGERPRINT).unwrap();
+
+		assert_eq!(fingerprint, digest(&SHA256, b"hello").as_ref());
+		assert_eq!(parse_fingerprint(&FINGERPRINT.replace(':', "")).unwrap(), fingerprint);
+	}
+
+	#[test]
+	fn given_invalid_fingerprint_then_parse_fingerprint_returns_error() {
+		assert!(parse_fingerprint("2c:f2:4d").is_err());
+		assert!(parse_fingerprint(&"zz".repeat(32)).is_err());
+		assert!(parse_fingerprint(&format!("é{}", "0".repeat(62))).is_err());
+	}
+
+	#[test]
+	fn given_pinned_fingerprint_then_verifier_only_accepts_matching_certificate() {
+		let verifier = PinnedCertificateVerifier {
+			fingerprint: parse_fingerprint(FINGERPRINT).unwrap(),
+			provider: Arc::new(rustls::crypto::ring::default_provider()),
+		};
+		let server_name = ServerName::try_from("example.com").unwrap();
+		let verify = |cert: &'static [u8]| {
+			verifier.verify_server_cert(&CertificateDer::from(cert), &[], &server_name, &[], UnixTime::now())
+		};
+
+		assert!(verify(b"hello").is_ok());
+		assert_eq!(
+			verify(b"goodbye").unwrap_err(),
+			rustls::Error::InvalidCertificate(CertificateError::ApplicationVerificationFailure)
+		);
+	}
+
+	#[test]
+	fn given_only_client_certificate_then_client_config_returns_error() {
+		let options = ServitorTlsOptions {
+			cert: Some(PathBuf::from("client.pem")),
+			..Default::default()
+		};
+
+		assert!(client_config(&options).is_err());
+	}
+
+	#[test]
+	fn given_missing_ca_bundle_then_client_config_returns_error() {
+		let options = ServitorTlsOptions {
+			ca: Some(PathBuf::from("/nonexistent/ca.pem")),
+			..Default::default()
+		};
+
+		assert!(client_config(&options).is_err());
+	}
+
+	#[test]
+	fn given_fingerprint_then_client_config_builds() {
+		let options = ServitorTlsOptions {
+			fingerprint: Some(FINGERPRINT.to_string()),
+			..Default::default()
+		};
+
+		assert!(client_config(&options).is_ok());
+	}
+
+	#[test]
+	fn given_fingerprint_and_ca_bundle_then_client_config_returns_error() {
+		let options = ServitorTlsOptions {
+			ca: Some(PathBuf::from("ca.pem")),
+			fingerprint: Some(FINGERPRINT.to_string()),
+			..Default::default()
+		};
+
+		assert!(client_config(&options).is_err());
+	}
+}
@@ -4,8 +4,9 @@ use std::fmt;
 use std::net::Ipv4Addr;
 use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
+use std::time::Duration;
 use tokio::io;
-use tokio::net::UdpSocket;
+use tokio::net::{TcpStream, UdpSocket};
 
 const MAC_ADDRESS_SIZE: usize = 6;
 const HEADER_SIZE: usize = 6;
@@ -108,9 +109,59 @@ impl MagicPacketSender for UdpMagicPacketSender {
 	}
 }
 
+pub trait MachineProbe {
+	async fn is_online(&self, address: &str) -> bool;
+}
+
+/// Counts a machine as online when its address answers a TCP connect, even if only to refuse it.
+#[derive(Clone, Copy)]
+pub struct TcpMachineProbe {
+	timeout: Duration,
+}
+
+impl TcpMachineProbe {
+	pub fn new(timeout: Duration) -> Self {
+		Self { timeout }
+	}
+}
+
+impl MachineProbe for TcpMachineProbe {
+	async fn is_online(&self, address: &str) -> bool {
+		match tokio::time::timeout(self.timeout, TcpStream::connect(address)).await {
+			Ok(Ok(_)) => true,
+			Ok(Err(e)) => e.kind() == io::ErrorKind::ConnectionRefused,
+			Err(_) => false,
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use tokio::net::TcpListener;
+
+	#[tokio::test]
+	async fn given_listening_address_then_machine_is_online() {
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let address = listener.local_addr().unwrap().to_string();
+
+		assert!(TcpMachineProbe::new(Duration::from_secs(1)).is_online(&address).await);
+	}
+
+	#[tokio::test]
+	async fn given_refusing_address_then_machine_is_online() {
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let address = listener.local_addr().unwrap().to_string();
+		drop(listener);
+
+		assert!(TcpMachineProbe::new(Duration::from_secs(1)).is_online(&address).await);
+	}
+
+	#[tokio::test]
+	async fn given_invalid_address_then_machine_is_offline() {
+		assert!(!TcpMachineProbe::new(Duration::from_secs(1)).is_online("no port").await);
+	}
+
 	#[tokio::test]
 	async fn given_invalid_mac_with_non_hex_characters_then_returns_invalid_hex_string_error() {
 		let result = MacAddress::from_str("AA:BB:CC:DD:EE:GG");
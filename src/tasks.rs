@@ -0,0 +1,2 @@
+pub mod servitor;
+pub mod grants;
@@ -0,0 +1,36 @@
+use crate::controllers::grants::prune_expired;
+use crate::controllers::DiscordEntity;
+use crate::data::BotData;
+use crate::views::grants::expired_grant_embed;
+use chrono::Utc;
+use log::{error, info};
+use serenity::all::{CreateMessage, Http};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+pub async fn prune_expired_grants(http: Arc<Http>, data: BotData, interval: Duration, notify: bool) {
+	info!("Pruning expired grants every {interval:?}");
+
+	let mut ticker = tokio::time::interval(interval);
+	ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+	loop {
+		ticker.tick().await;
+
+		for grant in prune_expired(&data, Utc::now()).await {
+			// Roles have nobody in particular to tell
+			let DiscordEntity::User(user) = grant.entity else {
+				continue;
+			};
+			if !notify {
+				continue;
+			}
+
+			let message = CreateMessage::new().embed(expired_grant_embed(&grant));
+			if let Err(e) = user.direct_message(&http, message).await {
+				error!("Failed to notify user {user} of their expired grant: {e}");
+			}
+		}
+	}
+}
@@ -0,0 +1,5 @@
+pub mod health;
+pub mod watch;
+pub mod dashboard;
+pub mod schedule;
+pub mod idle;
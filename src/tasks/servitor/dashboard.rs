@@ -0,0 +1,64 @@
+use crate::bot::ServitorHandlers;
+use crate::controllers::servitor::dashboard::{list_dashboards, remove_dashboard, snapshot};
+use crate::data::servitor::Dashboard;
+use crate::data::BotData;
+use crate::services::wake_on_lan::TcpMachineProbe;
+use crate::views::servitor::dashboard::dashboard_embed;
+use chrono::Utc;
+use log::{error, info, warn};
+use serenity::all::{EditMessage, Http, HttpError};
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+pub async fn refresh_dashboards(
+	http: Arc<Http>,
+	data: BotData,
+	servitor_handlers: ServitorHandlers,
+	machine_probe: TcpMachineProbe,
+	interval: Duration,
+) {
+	info!("Refreshing Servitor dashboards every {interval:?}");
+
+	let mut ticker = tokio::time::interval(interval);
+	ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+	loop {
+		ticker.tick().await;
+
+		let dashboards = list_dashboards(&data).await;
+		if dashboards.is_empty() {
+			continue;
+		}
+
+		// Dashboards of the same guild show the same thing, so only snapshot each guild once
+		let mut embeds = BTreeMap::new();
+
+		for (channel, Dashboard { message, guild }) in dashboards {
+			let embed = match embeds.entry(guild) {
+				Entry::Occupied(entry) => entry.into_mut(),
+				Entry::Vacant(entry) => {
+					let snapshot = snapshot(&data, guild, &servitor_handlers, &machine_probe).await;
+					entry.insert(dashboard_embed(&snapshot, Utc::now()))
+				}
+			};
+
+			let result = channel
+				.edit_message(&http, message, EditMessage::new().embed(embed.clone()))
+				.await;
+
+			match result {
+				Ok(_) => {}
+				Err(serenity::Error::Http(HttpError::UnsuccessfulRequest(response)))
+					if response.status_code.as_u16() == 404 =>
+				{
+					warn!("Dashboard message {message} in channel {channel} no longer exists, forgetting it");
+					let _ = remove_dashboard(&data, channel).await;
+				}
+				Err(e) => error!("Failed to refresh dashboard message {message} in channel {channel}: {e}"),
+			}
+		}
+	}
+}
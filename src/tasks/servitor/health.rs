@@ -0,0 +1,39 @@
+use crate::bot::ServitorHandlers;
+use crate::controllers::servitor::health::{check_instances, ServitorHealth};
+use crate::views::servitor::health::health_transition_embed;
+use chrono::Utc;
+use log::{error, info};
+use serenity::all::{ChannelId, CreateMessage, Http};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+pub async fn monitor_health(
+	http: Arc<Http>,
+	servitor_handlers: ServitorHandlers,
+	health: ServitorHealth,
+	interval: Duration,
+	alert_channel: Option<ChannelId>,
+) {
+	info!("Monitoring Servitor instances health every {interval:?}");
+
+	let mut ticker = tokio::time::interval(interval);
+	ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+	loop {
+		ticker.tick().await;
+
+		let transitions = check_instances(&health, &servitor_handlers, Utc::now()).await;
+
+		let Some(channel) = alert_channel else {
+			continue;
+		};
+
+		for transition in transitions {
+			let message = CreateMessage::new().embed(health_transition_embed(&transition));
+			if let Err(e) = channel.send_message(&http, message).await {
+				error!("Failed to send Servitor health alert to channel {channel}: {e}");
+			}
+		}
+	}
+}
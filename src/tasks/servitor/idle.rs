@@ -0,0 +1,43 @@
+use crate::bot::ServitorHandlers;
+use crate::controllers::servitor::idle::{check_idle, IdleStates};
+use crate::data::BotData;
+use crate::services::probe::SystemProber;
+use crate::services::query::NetworkGameQuery;
+use crate::views::servitor::idle::idle_shutdown_embed;
+use chrono::Utc;
+use log::{error, info};
+use serenity::all::{CreateMessage, Http};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+pub async fn stop_idle_servers(
+	http: Arc<Http>,
+	data: BotData,
+	servitor_handlers: ServitorHandlers,
+	interval: Duration,
+	query_timeout: Duration,
+) {
+	info!("Checking Servitor servers for idleness every {interval:?}");
+
+	let prober = SystemProber::new(NetworkGameQuery::new(query_timeout));
+	let mut idle_states = IdleStates::new();
+	let mut ticker = tokio::time::interval(interval);
+	ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+	loop {
+		ticker.tick().await;
+
+		let shutdowns = check_idle(&data, &servitor_handlers, &prober, &mut idle_states, Utc::now()).await;
+
+		for shutdown in shutdowns {
+			let message = CreateMessage::new().embed(idle_shutdown_embed(&shutdown));
+			if let Err(e) = shutdown.channel.send_message(&http, message).await {
+				error!(
+					"Failed to send idle shutdown of server {} to channel {}: {e}",
+					shutdown.server_name, shutdown.channel
+				);
+			}
+		}
+	}
+}
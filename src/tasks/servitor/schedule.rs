@@ -0,0 +1,68 @@
+use crate::bot::ServitorHandlers;
+use crate::controllers::servitor::schedule::{run_scheduled, take_due};
+use crate::data::servitor::ScheduledAction;
+use crate::data::BotData;
+use crate::views::servitor::schedule::scheduled_run_embed;
+use chrono::Utc;
+use log::{error, info};
+use serenity::all::{CreateAllowedMentions, CreateMessage, Http};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+pub async fn run_scheduled_actions(
+	http: Arc<Http>,
+	data: BotData,
+	servitor_handlers: ServitorHandlers,
+	interval: Duration,
+) {
+	info!("Checking scheduled Servitor actions every {interval:?}");
+
+	let mut ticker = tokio::time::interval(interval);
+	ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+	loop {
+		ticker.tick().await;
+
+		// Each run gets its own task, so a slow Servitor instance doesn't hold up the other due actions
+		for (id, scheduled) in take_due(&data, Utc::now()).await {
+			tokio::spawn(run_scheduled_action(
+				http.clone(),
+				data.clone(),
+				servitor_handlers.clone(),
+				id,
+				scheduled,
+			));
+		}
+	}
+}
+
+async fn run_scheduled_action(
+	http: Arc<Http>,
+	data: BotData,
+	servitor_handlers: ServitorHandlers,
+	id: u64,
+	scheduled: ScheduledAction,
+) {
+	let author = match scheduled.user.to_user(&http).await {
+		Ok(author) => author,
+		Err(e) => {
+			error!("Failed to fetch user {} of scheduled action #{id}, skipping it: {e}", scheduled.user);
+			return;
+		}
+	};
+	let member = match scheduled.guild {
+		Some(guild) => guild.member(&http, scheduled.user).await.ok(),
+		None => None,
+	};
+
+	info!("Running scheduled action #{id} for Servitor server {}", scheduled.server_name);
+	let result = run_scheduled(&data, &servitor_handlers, &scheduled, &author, member.as_ref()).await;
+
+	let message = CreateMessage::new()
+		.embed(scheduled_run_embed(result, id, &scheduled))
+		.allowed_mentions(CreateAllowedMentions::default().empty_users().empty_roles());
+	if let Err(e) = scheduled.channel.send_message(&http, message).await {
+		error!("Failed to send result of scheduled action #{id} to channel {}: {e}", scheduled.channel);
+	}
+}
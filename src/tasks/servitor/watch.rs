@@ -0,0 +1,39 @@
+use crate::bot::ServitorHandlers;
+use crate::controllers::servitor::watch::{poll_watched_units, UnitStates};
+use crate::data::BotData;
+use crate::views::servitor::watch::state_change_embed;
+use log::{error, info};
+use serenity::all::{CreateMessage, Http};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+pub async fn notify_state_changes(
+	http: Arc<Http>,
+	data: BotData,
+	servitor_handlers: ServitorHandlers,
+	interval: Duration,
+) {
+	info!("Polling watched Servitor servers every {interval:?}");
+
+	let mut last_states = UnitStates::new();
+	let mut ticker = tokio::time::interval(interval);
+	ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+	loop {
+		ticker.tick().await;
+
+		let changes = poll_watched_units(&data, &servitor_handlers, &mut last_states).await;
+
+		for change in changes {
+			for (server_name, channels) in &change.watchers {
+				for channel in channels {
+					let message = CreateMessage::new().embed(state_change_embed(&change, server_name));
+					if let Err(e) = channel.send_message(&http, message).await {
+						error!("Failed to send state change of server {server_name} to channel {channel}: {e}");
+					}
+				}
+			}
+		}
+	}
+}
@@ -1,8 +1,21 @@
+use crate::controllers::DiscordEntity;
+use crate::data::authorization::Denials;
+use crate::data::scope::Scope;
+use chrono::{DateTime, Utc};
+use serenity::all::CreateEmbed;
 use std::collections::BTreeSet;
 use std::fmt::Display;
 
 pub mod wake_on_lan;
 pub mod servitor;
+pub mod confirmation;
+pub mod admin;
+pub mod grants;
+pub mod access;
+pub mod group;
+pub mod audit;
+pub mod pagination;
+pub mod authorization;
 
 fn format_list<T: Display, F: Fn(&T) -> String>(list: &BTreeSet<T>, formatter: F) -> String {
 	if list.is_empty() {
@@ -11,3 +24,47 @@ fn format_list<T: Display, F: Fn(&T) -> String>(list: &BTreeSet<T>, formatter: F
 		list.iter().map(formatter).collect::<Vec<_>>().join(", ")
 	}
 }
+
+fn mention(entity: DiscordEntity) -> String {
+	match entity {
+		DiscordEntity::User(id) => format!("<@{id}>"),
+		DiscordEntity::Role(id) => format!("<@&{id}>"),
+	}
+}
+
+fn format_denials(denials: &Denials) -> String {
+	let format = |mention: String, reason: &Option<String>| match reason {
+		Some(reason) => format!("{mention} ({reason})"),
+		None => mention,
+	};
+	let users = denials.users.iter().map(|(id, reason)| format(format!("<@{id}>"), reason));
+	let roles = denials.roles.iter().map(|(id, reason)| format(format!("<@&{id}>"), reason));
+	let denied = users.chain(roles).collect::<Vec<_>>();
+
+	if denied.is_empty() {
+		"None".to_string()
+	} else {
+		denied.join(", ")
+	}
+}
+
+fn format_scope(scope: Scope) -> String {
+	match scope {
+		Scope::Global => "Global".to_string(),
+		Scope::Guild(id) => format!("Guild `{id}`"),
+	}
+}
+
+fn format_expiring(mention: String, expiry: Option<&DateTime<Utc>>) -> String {
+	match expiry {
+		Some(expiry) => format!("{mention} until <t:{}:f>", expiry.timestamp()),
+		None => mention,
+	}
+}
+
+fn expiry_field(embed: CreateEmbed, expires: Option<DateTime<Utc>>) -> CreateEmbed {
+	match expires {
+		Some(expires) => embed.field("Expires", format!("<t:{}:R>", expires.timestamp()), true),
+		None => embed,
+	}
+}
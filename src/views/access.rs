@@ -0,0 +1,416 @@
+use super::expiry_field;
+use crate::components::ComponentId;
+use crate::controllers::access::{AccessDecision, RequestAccessError, RequestKey, ReviewAccessError};
+use crate::controllers::servitor::ServerError;
+use crate::controllers::wake_on_lan::MachineError;
+use crate::data::access::{AccessRequest, AccessResource};
+use crate::data::servitor::ServitorAction;
+use crate::embeds;
+use chrono::{DateTime, TimeDelta, Utc};
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton, CreateEmbed, UserId};
+
+fn describe_resource(resource: &AccessResource) -> String {
+	match resource {
+		AccessResource::Machine { name } => format!("wake machine {name}"),
+		AccessResource::Server { name, action } => format!("run `{action}` on the Servitor server {name}"),
+	}
+}
+
+fn resource_name(resource: &AccessResource) -> &str {
+	match resource {
+		AccessResource::Machine { name } | AccessResource::Server { name, .. } => name,
+	}
+}
+
+/// Offers asking the admins for a standing grant, or anyone allowed for a single use when it runs something.
+pub fn request_access_buttons(resource: AccessResource) -> Vec<CreateActionRow> {
+	let single_use = match &resource {
+		AccessResource::Machine { .. } => true,
+		AccessResource::Server { action, .. } => !matches!(action, ServitorAction::Status | ServitorAction::Logs),
+	};
+
+	let mut buttons = vec![];
+	if single_use {
+		let id = ComponentId::AskApproval {
+			resource: resource.clone(),
+		};
+		buttons.push(CreateButton::new(id.to_string()).label("Ask for approval").style(ButtonStyle::Primary));
+	}
+	let id = ComponentId::RequestAccess { resource };
+	buttons.push(CreateButton::new(id.to_string()).label("Request access").style(ButtonStyle::Secondary));
+
+	vec![CreateActionRow::Buttons(buttons)]
+}
+
+pub fn request_access_embed(result: Result<u64, RequestAccessError>, resource: &AccessResource) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success(
+			"Access requested",
+			format!("Asked the admins for permission to {}, you will get a DM once they decide", describe_resource(resource)),
+		),
+		Err(e) => match e {
+			RequestAccessError::Machine(_) => embeds::invalid_machine(resource_name(resource)),
+			RequestAccessError::Server(_) => embeds::invalid_servitor_server(resource_name(resource)),
+			RequestAccessError::AlreadyRequested { .. } => embeds::error(
+				"Already requested",
+				format!("You already asked for permission to {}, wait for the admins to decide", describe_resource(resource)),
+			),
+		},
+	}
+}
+
+pub fn access_request_embed(id: u64, request: &AccessRequest) -> CreateEmbed {
+	embeds::info(
+		format!("Access request #{id}"),
+		format!("<@{}> asks for permission to {}", request.user, describe_resource(&request.resource)),
+	)
+	.field("Requested", format!("<t:{}:R>", request.requested_at.timestamp()), true)
+}
+
+pub fn review_access_buttons(request: &RequestKey, temporary: TimeDelta) -> Vec<CreateActionRow> {
+	let button = |decision: AccessDecision, label: String, style: ButtonStyle| {
+		let id = ComponentId::ReviewAccess {
+			request: request.clone(),
+			decision,
+		};
+		CreateButton::new(id.to_string()).label(label).style(style)
+	};
+
+	vec![CreateActionRow::Buttons(vec![
+		button(AccessDecision::Approve, "Approve".to_string(), ButtonStyle::Success),
+		button(
+			AccessDecision::ApproveTemporarily,
+			format!("Approve for {}h", temporary.num_hours()),
+			ButtonStyle::Primary,
+		),
+		button(AccessDecision::Deny, "Deny".to_string(), ButtonStyle::Danger),
+	])]
+}
+
+pub fn review_access_embed(
+	result: Result<AccessRequest, ReviewAccessError>,
+	id: u64,
+	reviewer: UserId,
+	decision: AccessDecision,
+	expires: Option<DateTime<Utc>>,
+) -> CreateEmbed {
+	match result {
+		Ok(request) if decision == AccessDecision::Deny => embeds::info(
+			format!("Access request #{id} denied"),
+			format!(
+				"<@{reviewer}> denied <@{}> permission to {}",
+				request.user,
+				describe_resource(&request.resource)
+			),
+		),
+		Ok(request) => expiry_field(
+			embeds::success(
+				format!("Access request #{id} approved"),
+				format!(
+					"<@{reviewer}> allowed <@{}> to {}",
+					request.user,
+					describe_resource(&request.resource)
+				),
+			),
+			expires,
+		),
+		Err(e) => match e {
+			ReviewAccessError::DoesNotExist { .. } => embeds::error(
+				"Invalid access request",
+				format!("Access request #{id} was already reviewed or no longer exists"),
+			),
+			ReviewAccessError::Unauthorized { .. } => embeds::error(
+				"Unauthorized",
+				format!("You are not allowed to review access request #{id}"),
+			),
+			ReviewAccessError::Machine(
+				MachineError::DoesNotExist { machine_name } | MachineError::AlreadyExists { machine_name },
+			) => embeds::invalid_machine(machine_name),
+			ReviewAccessError::Server(
+				ServerError::DoesNotExist { server_name } | ServerError::AlreadyExists { server_name },
+			) => embeds::invalid_servitor_server(server_name),
+		},
+	}
+}
+
+/// Tells the requester what was decided about their request.
+pub fn access_decision_embed(
+	request: &AccessRequest,
+	decision: AccessDecision,
+	expires: Option<DateTime<Utc>>,
+) -> CreateEmbed {
+	let resource = describe_resource(&request.resource);
+	match decision {
+		AccessDecision::Deny => embeds::error("Access denied", format!("Your request for permission to {resource} was denied")),
+		AccessDecision::Approve | AccessDecision::ApproveTemporarily => expiry_field(
+			embeds::success("Access granted", format!("Your request for permission to {resource} was approved")),
+			expires,
+		),
+	}
+}
+
+pub fn approval_request_embed(requester: UserId, resource: &AccessResource) -> CreateEmbed {
+	embeds::info(
+		"Approval requested",
+		format!(
+			"<@{requester}> asks someone allowed to {} to approve it once",
+			describe_resource(resource)
+		),
+	)
+}
+
+pub fn approval_buttons(requester: UserId, resource: &AccessResource) -> Vec<CreateActionRow> {
+	let button = |approve: bool, label: &str, style: ButtonStyle| {
+		let id = ComponentId::ReviewApproval {
+			requester,
+			approve,
+			resource: resource.clone(),
+		};
+		CreateButton::new(id.to_string()).label(label).style(style)
+	};
+
+	vec![CreateActionRow::Buttons(vec![
+		button(true, "Approve", ButtonStyle::Success),
+		button(false, "Deny", ButtonStyle::Danger),
+	])]
+}
+
+/// Marks the result of an approved single use with who asked for it and who approved it.
+pub fn approved_embed(embed: CreateEmbed, requester: UserId, approver: UserId) -> CreateEmbed {
+	embed
+		.field("Requested by", format!("<@{requester}>"), true)
+		.field("Approved by", format!("<@{approver}>"), true)
+}
+
+pub fn approval_denied_embed(requester: UserId, resource: &AccessResource, reviewer: UserId) -> CreateEmbed {
+	embeds::info(
+		"Approval denied",
+		format!("<@{reviewer}> denied <@{requester}> permission to {} once", describe_resource(resource)),
+	)
+}
+
+pub fn approval_unauthorized_embed(resource: &AccessResource) -> CreateEmbed {
+	embeds::error(
+		"Unauthorized",
+		format!("Only someone allowed to {} can decide on this request", describe_resource(resource)),
+	)
+}
+
+pub fn approval_unsupported_embed(resource: &AccessResource) -> CreateEmbed {
+	embeds::error(
+		"Unsupported approval",
+		format!("Only actions can be approved once, request access to {} instead", describe_resource(resource)),
+	)
+}
+
+pub fn approval_decided_embed() -> CreateEmbed {
+	embeds::error("Already decided", "Someone else already decided on this request")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serenity::all::Colour;
+
+	fn request() -> AccessRequest {
+		AccessRequest {
+			user: UserId::new(1),
+			guild: None,
+			resource: AccessResource::Server {
+				name: "SomeServer".to_string(),
+				action: ServitorAction::Start,
+			},
+			requested_at: DateTime::from_timestamp(1741291185, 0).unwrap(),
+		}
+	}
+
+	#[test]
+	fn given_machine_then_request_access_buttons_carry_resource() {
+		let resource = AccessResource::Machine {
+			name: "SomeMachine".to_string(),
+		};
+
+		let buttons = request_access_buttons(resource);
+
+		let expected_buttons = vec![CreateActionRow::Buttons(vec![
+			CreateButton::new("once:ask:wol:SomeMachine")
+				.label("Ask for approval")
+				.style(ButtonStyle::Primary),
+			CreateButton::new("access:request:wol:SomeMachine")
+				.label("Request access")
+				.style(ButtonStyle::Secondary),
+		])];
+
+		assert_eq!(buttons, expected_buttons);
+	}
+
+	#[test]
+	fn given_read_only_action_then_request_access_buttons_skip_single_use() {
+		let resource = AccessResource::Server {
+			name: "SomeServer".to_string(),
+			action: ServitorAction::Logs,
+		};
+
+		let buttons = request_access_buttons(resource);
+
+		let expected_buttons = vec![CreateActionRow::Buttons(vec![CreateButton::new(
+			"access:request:servitor:logs:SomeServer",
+		)
+		.label("Request access")
+		.style(ButtonStyle::Secondary)])];
+
+		assert_eq!(buttons, expected_buttons);
+	}
+
+	#[test]
+	fn given_requester_then_approval_buttons_carry_requester_and_resource() {
+		let buttons = approval_buttons(UserId::new(1), &request().resource);
+
+		let expected_buttons = vec![CreateActionRow::Buttons(vec![
+			CreateButton::new("once:approve:1:servitor:start:SomeServer")
+				.label("Approve")
+				.style(ButtonStyle::Success),
+			CreateButton::new("once:deny:1:servitor:start:SomeServer")
+				.label("Deny")
+				.style(ButtonStyle::Danger),
+		])];
+
+		assert_eq!(buttons, expected_buttons);
+	}
+
+	#[test]
+	fn given_read_only_action_then_approval_unsupported_embed_points_to_access_requests() {
+		let resource = AccessResource::Server {
+			name: "SomeServer".to_string(),
+			action: ServitorAction::Logs,
+		};
+
+		let embed = approval_unsupported_embed(&resource);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Unsupported approval")
+			.colour(Colour(0xdd2e44))
+			.description("Only actions can be approved once, request access to run `logs` on the Servitor server SomeServer instead");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_approved_result_then_embed_names_requester_and_approver() {
+		let embed = approved_embed(embeds::success("Machine woken", "Machine SomeMachine woken"), UserId::new(1), UserId::new(2));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Machine woken")
+			.colour(Colour(0x77b255))
+			.description("Machine SomeMachine woken")
+			.field("Requested by", "<@1>", true)
+			.field("Approved by", "<@2>", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_denied_approval_then_embed_names_reviewer() {
+		let embed = approval_denied_embed(UserId::new(1), &request().resource, UserId::new(2));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Approval denied")
+			.colour(Colour(0x55acee))
+			.description("<@2> denied <@1> permission to run `start` on the Servitor server SomeServer once");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_duplicate_request_then_request_access_embed_is_error() {
+		let result = Err(RequestAccessError::AlreadyRequested {
+			user: UserId::new(1),
+			request: 3,
+		});
+
+		let embed = request_access_embed(result, &request().resource);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Already requested")
+			.colour(Colour(0xdd2e44))
+			.description("You already asked for permission to run `start` on the Servitor server SomeServer, wait for the admins to decide");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_request_then_access_request_embed_mentions_requester() {
+		let embed = access_request_embed(3, &request());
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Access request #3")
+			.colour(Colour(0x55acee))
+			.description("<@1> asks for permission to run `start` on the Servitor server SomeServer")
+			.field("Requested", "<t:1741291185:R>", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_request_then_review_buttons_offer_each_decision_for_it() {
+		let buttons = review_access_buttons(&RequestKey::new(3, &request()), TimeDelta::hours(24));
+
+		let expected_buttons = vec![CreateActionRow::Buttons(vec![
+			CreateButton::new("access:approve:3:1:servitor:start:SomeServer")
+				.label("Approve")
+				.style(ButtonStyle::Success),
+			CreateButton::new("access:approve-temporarily:3:1:servitor:start:SomeServer")
+				.label("Approve for 24h")
+				.style(ButtonStyle::Primary),
+			CreateButton::new("access:deny:3:1:servitor:start:SomeServer")
+				.label("Deny")
+				.style(ButtonStyle::Danger),
+		])];
+
+		assert_eq!(buttons, expected_buttons);
+	}
+
+	#[test]
+	fn given_temporary_approval_then_review_embed_shows_expiry() {
+		let expires = DateTime::from_timestamp(1741377585, 0);
+
+		let embed = review_access_embed(Ok(request()), 3, UserId::new(2), AccessDecision::ApproveTemporarily, expires);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Access request #3 approved")
+			.colour(Colour(0x77b255))
+			.description("<@2> allowed <@1> to run `start` on the Servitor server SomeServer")
+			.field("Expires", "<t:1741377585:R>", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_unauthorized_reviewer_then_review_embed_is_error() {
+		let result = Err(ReviewAccessError::Unauthorized {
+			user: UserId::new(2),
+			request: 3,
+		});
+
+		let embed = review_access_embed(result, 3, UserId::new(2), AccessDecision::Approve, None);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Unauthorized")
+			.colour(Colour(0xdd2e44))
+			.description("You are not allowed to review access request #3");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_denial_then_decision_embed_tells_requester() {
+		let embed = access_decision_embed(&request(), AccessDecision::Deny, None);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Access denied")
+			.colour(Colour(0xdd2e44))
+			.description("Your request for permission to run `start` on the Servitor server SomeServer was denied");
+
+		assert_eq!(embed, expected_embed);
+	}
+}
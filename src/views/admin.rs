@@ -0,0 +1,98 @@
+use super::{format_scope, mention};
+use crate::controllers::admin::AdminError;
+use crate::controllers::DiscordEntity;
+use crate::data::scope::Scope;
+use crate::embeds;
+use serenity::all::CreateEmbed;
+
+pub fn add_admin_embed(result: Result<(), AdminError>, entity: DiscordEntity, scope: Scope) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success("Admin added", "Successfully added admin!")
+			.field("Admin", mention(entity), true)
+			.field("Scope", format_scope(scope), true),
+		Err(_) => embeds::error(
+			"Already admin",
+			format!("{} is already an admin with scope {}", mention(entity), format_scope(scope)),
+		),
+	}
+}
+
+pub fn remove_admin_embed(result: Result<(), AdminError>, entity: DiscordEntity) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success("Admin removed", "Successfully removed admin!").field("Admin", mention(entity), true),
+		Err(_) => embeds::error("Not admin", format!("{} is not an admin", mention(entity))),
+	}
+}
+
+pub fn missing_entity_embed() -> CreateEmbed {
+	embeds::error("Missing admin", "Pick exactly one user or role")
+}
+
+pub fn missing_permission_embed() -> CreateEmbed {
+	embeds::error("Missing permission", "You are not allowed to manage this")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serenity::all::{Colour, GuildId, RoleId, UserId};
+
+	#[test]
+	fn given_successful_add_admin_then_reply_with_admin_and_scope() {
+		let embed = add_admin_embed(
+			Ok(()),
+			DiscordEntity::Role(RoleId::new(98765432109876543)),
+			Scope::Guild(GuildId::new(1)),
+		);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Admin added")
+			.colour(Colour(0x77b255))
+			.description("Successfully added admin!")
+			.field("Admin", "<@&98765432109876543>", true)
+			.field("Scope", "Guild `1`", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_add_admin_error_then_reply_with_already_admin() {
+		let entity = DiscordEntity::User(UserId::new(12345678901234567));
+
+		let embed = add_admin_embed(Err(AdminError::AlreadyAdmin { entity }), entity, Scope::Global);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Already admin")
+			.colour(Colour(0xdd2e44))
+			.description("<@12345678901234567> is already an admin with scope Global");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_successful_remove_admin_then_reply_with_admin() {
+		let embed = remove_admin_embed(Ok(()), DiscordEntity::User(UserId::new(12345678901234567)));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Admin removed")
+			.colour(Colour(0x77b255))
+			.description("Successfully removed admin!")
+			.field("Admin", "<@12345678901234567>", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_remove_admin_error_then_reply_with_not_admin() {
+		let entity = DiscordEntity::Role(RoleId::new(98765432109876543));
+
+		let embed = remove_admin_embed(Err(AdminError::NotAdmin { entity }), entity);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Not admin")
+			.colour(Colour(0xdd2e44))
+			.description("<@&98765432109876543> is not an admin");
+
+		assert_eq!(embed, expected_embed);
+	}
+}
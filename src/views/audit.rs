@@ -0,0 +1,224 @@
+use super::mention;
+use super::pagination::numbered_page;
+use super::servitor::format_actions;
+use crate::controllers::audit::{AccessSource, AuditedResource, ResourceAccess, RevokedAccess};
+use crate::controllers::DiscordEntity;
+use crate::embeds;
+use serenity::all::{CreateEmbed, UserId};
+use std::collections::BTreeSet;
+
+const RESOURCES_PER_PAGE: usize = 10;
+
+fn describe_source(source: &AccessSource) -> String {
+	match source {
+		AccessSource::Direct => "directly".to_string(),
+		AccessSource::Role(id) => format!("through <@&{id}>"),
+		AccessSource::Group(name) => format!("through group `{name}`"),
+	}
+}
+
+fn describe_access(access: &ResourceAccess) -> String {
+	let (resource, sources) = match &access.resource {
+		AuditedResource::Machine(name) => (
+			format!("Machine `{name}`"),
+			access.sources.keys().map(describe_source).collect::<Vec<_>>(),
+		),
+		AuditedResource::Server(name) => (
+			format!("Servitor server `{name}`"),
+			access
+				.sources
+				.iter()
+				.map(|(source, actions)| format!("{} {}", format_actions(actions), describe_source(source)))
+				.collect::<Vec<_>>(),
+		),
+	};
+	let sources = sources.join("; ");
+
+	match (access.denied, sources.is_empty()) {
+		(false, _) => format!("- {resource}: {sources}"),
+		(true, true) => format!("- {resource}: **denied**"),
+		(true, false) => format!("- {resource}: **denied**, ignoring {sources}"),
+	}
+}
+
+/// The report split into pages, which is a single page saying so when there is nothing to report.
+pub fn audit_pages(subject: DiscordEntity, report: &[ResourceAccess]) -> Vec<CreateEmbed> {
+	let subject = match subject {
+		DiscordEntity::User(_) => mention(subject),
+		DiscordEntity::Role(_) => format!("members of {}", mention(subject)),
+	};
+
+	if report.is_empty() {
+		return vec![embeds::info(
+			"Access report",
+			format!("Nothing here is granted to or denied from {subject}"),
+		)];
+	}
+
+	let pages = report.len().div_ceil(RESOURCES_PER_PAGE);
+	report
+		.chunks(RESOURCES_PER_PAGE)
+		.enumerate()
+		.map(|(page, chunk)| {
+			let lines = chunk.iter().map(describe_access).collect::<Vec<_>>().join("\n");
+			let embed = embeds::info("Access report", format!("What {subject} may do here:\n{lines}"));
+			numbered_page(embed, page, pages)
+		})
+		.collect()
+}
+
+pub fn confirm_revoke_all_embed(user: UserId) -> CreateEmbed {
+	embeds::info(
+		"Revoke all access?",
+		format!(
+			"This removes every grant <@{user}> was given directly in every guild, along with the machines and servers \
+they manage, their access groups and their pending access requests"
+		),
+	)
+}
+
+pub fn revoke_all_embed(revoked: &RevokedAccess, user: UserId) -> CreateEmbed {
+	if revoked.is_empty() {
+		return embeds::info("Nothing to revoke", format!("<@{user}> had nothing granted directly"));
+	}
+
+	let list = |names: &BTreeSet<String>| {
+		if names.is_empty() {
+			"None".to_string()
+		} else {
+			names.iter().map(|name| format!("`{name}`")).collect::<Vec<_>>().join(", ")
+		}
+	};
+
+	embeds::success("Access revoked", format!("Successfully revoked all access of <@{user}>!"))
+		.field("Machines", list(&revoked.machines), false)
+		.field("Servitor servers", list(&revoked.servers), false)
+		.field("Groups", list(&revoked.groups), false)
+		.field("Dropped access requests", revoked.requests.to_string(), false)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::data::servitor::ServitorAction;
+	use serenity::all::{Colour, CreateEmbedFooter, RoleId};
+	use std::collections::BTreeMap;
+
+	fn machine_access(name: &str) -> ResourceAccess {
+		ResourceAccess {
+			resource: AuditedResource::Machine(name.to_string()),
+			sources: BTreeMap::from([(AccessSource::Direct, BTreeSet::new())]),
+			denied: false,
+		}
+	}
+
+	#[test]
+	fn given_empty_report_then_audit_pages_says_nothing_is_granted() {
+		let pages = audit_pages(DiscordEntity::Role(RoleId::new(98765432109876543)), &[]);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Access report")
+			.colour(Colour(0x55acee))
+			.description("Nothing here is granted to or denied from members of <@&98765432109876543>");
+
+		assert_eq!(pages, vec![expected_embed]);
+	}
+
+	#[test]
+	fn given_report_then_audit_pages_describes_every_source() {
+		let report = vec![
+			ResourceAccess {
+				resource: AuditedResource::Machine("Desktop".to_string()),
+				sources: BTreeMap::from([
+					(AccessSource::Direct, BTreeSet::new()),
+					(AccessSource::Role(RoleId::new(98765432109876543)), BTreeSet::new()),
+				]),
+				denied: false,
+			},
+			ResourceAccess {
+				resource: AuditedResource::Machine("Laptop".to_string()),
+				sources: BTreeMap::from([(AccessSource::Group("Friends".to_string()), BTreeSet::new())]),
+				denied: true,
+			},
+			ResourceAccess {
+				resource: AuditedResource::Server("Minecraft".to_string()),
+				sources: BTreeMap::from([
+					(AccessSource::Direct, BTreeSet::from([ServitorAction::Start, ServitorAction::Stop])),
+					(AccessSource::Group("Friends".to_string()), ServitorAction::all()),
+				]),
+				denied: false,
+			},
+			ResourceAccess {
+				resource: AuditedResource::Server("Factorio".to_string()),
+				sources: BTreeMap::new(),
+				denied: true,
+			},
+		];
+
+		let pages = audit_pages(DiscordEntity::User(UserId::new(12345678901234567)), &report);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Access report")
+			.colour(Colour(0x55acee))
+			.description(
+				"What <@12345678901234567> may do here:\n\
+- Machine `Desktop`: directly; through <@&98765432109876543>\n\
+- Machine `Laptop`: **denied**, ignoring through group `Friends`\n\
+- Servitor server `Minecraft`: `start`, `stop` directly; all actions through group `Friends`\n\
+- Servitor server `Factorio`: **denied**",
+			);
+
+		assert_eq!(pages, vec![expected_embed]);
+	}
+
+	#[test]
+	fn given_long_report_then_audit_pages_splits_it() {
+		let report = (0..11).map(|i| machine_access(&format!("Machine{i:02}"))).collect::<Vec<_>>();
+
+		let pages = audit_pages(DiscordEntity::User(UserId::new(12345678901234567)), &report);
+
+		let expected_last_page = CreateEmbed::default()
+			.title(":information_source: Access report")
+			.colour(Colour(0x55acee))
+			.description("What <@12345678901234567> may do here:\n- Machine `Machine10`: directly")
+			.footer(CreateEmbedFooter::new("Page 2/2"));
+
+		assert_eq!(pages.len(), 2);
+		assert_eq!(pages[1], expected_last_page);
+	}
+
+	#[test]
+	fn given_revoked_access_then_revoke_all_embed_lists_it() {
+		let revoked = RevokedAccess {
+			machines: BTreeSet::from(["Desktop".to_string()]),
+			servers: BTreeSet::new(),
+			groups: BTreeSet::from(["Friends".to_string()]),
+			requests: 1,
+		};
+
+		let embed = revoke_all_embed(&revoked, UserId::new(12345678901234567));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Access revoked")
+			.colour(Colour(0x77b255))
+			.description("Successfully revoked all access of <@12345678901234567>!")
+			.field("Machines", "`Desktop`", false)
+			.field("Servitor servers", "None", false)
+			.field("Groups", "`Friends`", false)
+			.field("Dropped access requests", "1", false);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_nothing_revoked_then_revoke_all_embed_says_so() {
+		let embed = revoke_all_embed(&RevokedAccess::default(), UserId::new(12345678901234567));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Nothing to revoke")
+			.colour(Colour(0x55acee))
+			.description("<@12345678901234567> had nothing granted directly");
+
+		assert_eq!(embed, expected_embed);
+	}
+}
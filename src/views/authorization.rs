@@ -0,0 +1,192 @@
+use super::{expiry_field, mention};
+use crate::controllers::authorization::{
+	AddDenialError, AddDenialErrorOf, AddGroupPermissionError, AddGroupPermissionErrorOf, AddPermissionError,
+	AddPermissionErrorOf, AuthorizedResource, RemoveDenialError, RemoveDenialErrorOf, RemoveGroupPermissionError,
+	RemoveGroupPermissionErrorOf, RemovePermissionError, RemovePermissionErrorOf,
+};
+use crate::controllers::DiscordEntity;
+use crate::embeds;
+use chrono::{DateTime, Utc};
+use serenity::all::CreateEmbed;
+
+/// How grants on a kind of resource are shown.
+pub trait AuthorizationView: AuthorizedResource {
+	/// Name of the field holding the resource name.
+	const FIELD: &'static str;
+
+	/// What the grant permits, as in "permitted to wake".
+	fn describe_grant(grant: &Self::Grant) -> String;
+
+	fn invalid_resource_embed(name: &str) -> CreateEmbed;
+
+	/// What the resource list shows after the name of the resource.
+	fn list_entry(&self) -> String;
+}
+
+fn entity_kind(entity: DiscordEntity) -> &'static str {
+	match entity {
+		DiscordEntity::User(_) => "User",
+		DiscordEntity::Role(_) => "Role",
+	}
+}
+
+pub fn list_embed<'a, R: AuthorizationView + 'a>(resources: impl IntoIterator<Item = (&'a String, &'a R)>) -> CreateEmbed {
+	let mut resources = resources.into_iter().peekable();
+	let description = if resources.peek().is_none() {
+		format!("There are no {}s configured", R::NOUN)
+	} else {
+		let resource_list = resources
+			.map(|(name, info)| format!("- {name}: {}", info.list_entry()))
+			.collect::<Vec<String>>()
+			.join("\n");
+		format!("Configured {}s:\n{resource_list}", R::NOUN)
+	};
+
+	embeds::info(format!("{} list", R::FIELD), description)
+}
+
+pub fn permit_embed<R: AuthorizationView>(
+	result: Result<(), AddPermissionErrorOf<R>>,
+	name: &str,
+	entity: DiscordEntity,
+	grant: &R::Grant,
+	expires: Option<DateTime<Utc>>,
+) -> CreateEmbed {
+	let kind = entity_kind(entity);
+	let grant = R::describe_grant(grant);
+	match result {
+		Ok(_) => expiry_field(
+			embeds::success(
+				format!("{kind} permitted"),
+				format!("Successfully permitted {} to {grant} the {}!", kind.to_lowercase(), R::NOUN),
+			)
+			.field(R::FIELD, name, true)
+			.field(kind, mention(entity), true),
+			expires,
+		),
+		Err(e) => match e {
+			AddPermissionError::Resource(_) => R::invalid_resource_embed(name),
+			AddPermissionError::AlreadyAuthorized { .. } => embeds::error(
+				format!("{kind} already permitted"),
+				format!("{kind} {} is already permitted to {grant} {} {name}", mention(entity), R::NOUN),
+			),
+		},
+	}
+}
+
+pub fn revoke_embed<R: AuthorizationView>(
+	result: Result<(), RemovePermissionErrorOf<R>>,
+	name: &str,
+	entity: DiscordEntity,
+	grant: &R::Grant,
+) -> CreateEmbed {
+	let kind = entity_kind(entity);
+	let grant = R::describe_grant(grant);
+	match result {
+		Ok(_) => embeds::success(
+			format!("{kind} permission revoked"),
+			format!("Successfully revoked {}'s permission to {grant} the {}!", kind.to_lowercase(), R::NOUN),
+		)
+		.field(R::FIELD, name, true)
+		.field(kind, mention(entity), true),
+		Err(e) => match e {
+			RemovePermissionError::Resource(_) => R::invalid_resource_embed(name),
+			RemovePermissionError::AlreadyNotAuthorized { .. } => embeds::error(
+				format!("{kind} not permitted"),
+				format!("{kind} {} is already not permitted to {grant} {} {name}", mention(entity), R::NOUN),
+			),
+		},
+	}
+}
+
+pub fn permit_group_embed<R: AuthorizationView>(
+	result: Result<(), AddGroupPermissionErrorOf<R>>,
+	name: &str,
+	group: &str,
+	grant: &R::Grant,
+) -> CreateEmbed {
+	let grant = R::describe_grant(grant);
+	match result {
+		Ok(_) => embeds::success(
+			"Group permitted",
+			format!("Successfully permitted access group to {grant} the {}!", R::NOUN),
+		)
+		.field(R::FIELD, name, true)
+		.field("Group", group, true),
+		Err(e) => match e {
+			AddGroupPermissionError::Resource(_) => R::invalid_resource_embed(name),
+			AddGroupPermissionError::Group(_) => embeds::invalid_group(group),
+			AddGroupPermissionError::AlreadyAuthorized { .. } => embeds::error(
+				"Group already permitted",
+				format!("Access group {group} is already permitted to {grant} {} {name}", R::NOUN),
+			),
+		},
+	}
+}
+
+pub fn revoke_group_embed<R: AuthorizationView>(
+	result: Result<(), RemoveGroupPermissionErrorOf<R>>,
+	name: &str,
+	group: &str,
+	grant: &R::Grant,
+) -> CreateEmbed {
+	let grant = R::describe_grant(grant);
+	match result {
+		Ok(_) => embeds::success(
+			"Group permission revoked",
+			format!("Successfully revoked access group's permission to {grant} the {}!", R::NOUN),
+		)
+		.field(R::FIELD, name, true)
+		.field("Group", group, true),
+		Err(e) => match e {
+			RemoveGroupPermissionError::Resource(_) => R::invalid_resource_embed(name),
+			RemoveGroupPermissionError::AlreadyNotAuthorized { .. } => embeds::error(
+				"Group not permitted",
+				format!("Access group {group} is already not permitted to {grant} {} {name}", R::NOUN),
+			),
+		},
+	}
+}
+
+pub fn deny_embed<R: AuthorizationView>(
+	result: Result<(), AddDenialErrorOf<R>>,
+	name: &str,
+	entity: DiscordEntity,
+	reason: Option<&str>,
+) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success(
+			"Denied",
+			format!("Successfully denied from the {}, whatever they are permitted!", R::NOUN),
+		)
+		.field(R::FIELD, name, true)
+		.field("Denied", mention(entity), true)
+		.field("Reason", reason.unwrap_or("None given"), true),
+		Err(e) => match e {
+			AddDenialError::Resource(_) => R::invalid_resource_embed(name),
+			AddDenialError::AlreadyDenied { .. } => embeds::error(
+				"Already denied",
+				format!("{} is already denied from {} {name}", mention(entity), R::NOUN),
+			),
+		},
+	}
+}
+
+pub fn lift_denial_embed<R: AuthorizationView>(
+	result: Result<(), RemoveDenialErrorOf<R>>,
+	name: &str,
+	entity: DiscordEntity,
+) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success("Denial lifted", format!("Successfully lifted denial from the {}!", R::NOUN))
+			.field(R::FIELD, name, true)
+			.field("Denied", mention(entity), true),
+		Err(e) => match e {
+			RemoveDenialError::Resource(_) => R::invalid_resource_embed(name),
+			RemoveDenialError::NotDenied { .. } => embeds::error(
+				"Not denied",
+				format!("{} is not denied from {} {name}", mention(entity), R::NOUN),
+			),
+		},
+	}
+}
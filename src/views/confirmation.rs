@@ -0,0 +1,59 @@
+use crate::embeds;
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton, CreateEmbed};
+
+pub fn confirmation_buttons(confirm_id: &str, cancel_id: &str) -> Vec<CreateActionRow> {
+	vec![CreateActionRow::Buttons(vec![
+		CreateButton::new(confirm_id)
+			.label("Confirm")
+			.style(ButtonStyle::Danger),
+		CreateButton::new(cancel_id)
+			.label("Cancel")
+			.style(ButtonStyle::Secondary),
+	])]
+}
+
+pub fn confirmed_embed() -> CreateEmbed {
+	embeds::success("Confirmed", "Running the requested action")
+}
+
+pub fn cancelled_embed() -> CreateEmbed {
+	embeds::info("Cancelled", "Nothing was done")
+}
+
+pub fn timed_out_embed() -> CreateEmbed {
+	embeds::info("Confirmation timed out", "Nothing was done, run the command again if you still want to")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serenity::all::Colour;
+
+	#[test]
+	fn given_ids_then_confirmation_buttons_has_confirm_and_cancel() {
+		let buttons = confirmation_buttons("1-confirm", "1-cancel");
+
+		let expected_buttons = vec![CreateActionRow::Buttons(vec![
+			CreateButton::new("1-confirm")
+				.label("Confirm")
+				.style(ButtonStyle::Danger),
+			CreateButton::new("1-cancel")
+				.label("Cancel")
+				.style(ButtonStyle::Secondary),
+		])];
+
+		assert_eq!(buttons, expected_buttons);
+	}
+
+	#[test]
+	fn given_timeout_then_reply_with_timed_out() {
+		let embed = timed_out_embed();
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Confirmation timed out")
+			.colour(Colour(0x55acee))
+			.description("Nothing was done, run the command again if you still want to");
+
+		assert_eq!(embed, expected_embed);
+	}
+}
@@ -0,0 +1,39 @@
+use crate::controllers::grants::{ExpiredGrant, GrantResource};
+use super::servitor::format_actions;
+use crate::embeds;
+use serenity::all::CreateEmbed;
+
+pub fn expired_grant_embed(grant: &ExpiredGrant) -> CreateEmbed {
+	let resource = match &grant.resource {
+		GrantResource::Machine(name) => format!("machine {name}"),
+		GrantResource::Server(name, actions) => format!("{} on Servitor server {name}", format_actions(actions)),
+	};
+
+	embeds::info("Access expired", format!("Your temporary access to {resource} has expired"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::controllers::DiscordEntity;
+	use crate::data::servitor::ServitorAction;
+	use serenity::all::{Colour, UserId};
+	use std::collections::BTreeSet;
+
+	#[test]
+	fn given_expired_server_grant_then_embed_names_the_server() {
+		let grant = ExpiredGrant {
+			resource: GrantResource::Server("SomeServer".to_string(), BTreeSet::from([ServitorAction::Start])),
+			entity: DiscordEntity::User(UserId::new(1)),
+		};
+
+		let embed = expired_grant_embed(&grant);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Access expired")
+			.colour(Colour(0x55acee))
+			.description("Your temporary access to `start` on Servitor server SomeServer has expired");
+
+		assert_eq!(embed, expected_embed);
+	}
+}
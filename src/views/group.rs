@@ -0,0 +1,188 @@
+use super::{format_list, mention};
+use crate::controllers::group::{AddMemberError, GroupError, RemoveMemberError};
+use crate::controllers::DiscordEntity;
+use crate::data::group::AccessGroups;
+use crate::embeds;
+use serenity::all::CreateEmbed;
+
+pub fn create_group_embed(result: Result<(), GroupError>, name: &str) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success("Group created", "Successfully created access group!").field("Group", name, true),
+		Err(_) => embeds::error("Group already exists", format!("Access group {name} already exists")),
+	}
+}
+
+pub fn delete_group_embed(result: Result<(), GroupError>, name: &str) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success(
+			"Group deleted",
+			"Successfully deleted access group along with everything it was permitted to do!",
+		)
+		.field("Group", name, true),
+		Err(_) => embeds::invalid_group(name),
+	}
+}
+
+pub fn add_member_embed(result: Result<(), AddMemberError>, name: &str, entity: DiscordEntity) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success("Member added", "Successfully added member to access group!")
+			.field("Group", name, true)
+			.field("Member", mention(entity), true),
+		Err(e) => match e {
+			AddMemberError::Group(_) => embeds::invalid_group(name),
+			AddMemberError::AlreadyMember { .. } => embeds::error(
+				"Already member",
+				format!("{} is already in access group {name}", mention(entity)),
+			),
+		},
+	}
+}
+
+pub fn remove_member_embed(result: Result<(), RemoveMemberError>, name: &str, entity: DiscordEntity) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success("Member removed", "Successfully removed member from access group!")
+			.field("Group", name, true)
+			.field("Member", mention(entity), true),
+		Err(e) => match e {
+			RemoveMemberError::Group(_) => embeds::invalid_group(name),
+			RemoveMemberError::NotMember { .. } => embeds::error(
+				"Not member",
+				format!("{} is not in access group {name}", mention(entity)),
+			),
+		},
+	}
+}
+
+pub fn missing_member_embed() -> CreateEmbed {
+	embeds::error("Missing member", "Pick exactly one user or role")
+}
+
+pub fn list_groups_embed(groups: &AccessGroups) -> CreateEmbed {
+	let description = if groups.is_empty() {
+		"There are no access groups".to_string()
+	} else {
+		let group_list = groups
+			.iter()
+			.map(|(name, group)| {
+				let users = format_list(&group.users, |id| format!("<@{id}>"));
+				let roles = format_list(&group.roles, |id| format!("<@&{id}>"));
+				format!("- {name}: Users: {users}, Roles: {roles}")
+			})
+			.collect::<Vec<_>>()
+			.join("\n");
+		format!("Access groups:\n{group_list}")
+	};
+
+	embeds::info("Access group list", description)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::data::group::AccessGroup;
+	use serenity::all::{Colour, RoleId, UserId};
+	use std::collections::BTreeSet;
+
+	#[test]
+	fn given_create_group_error_then_reply_with_already_exists() {
+		let result = Err(GroupError::AlreadyExists {
+			group: "Friends".to_string(),
+		});
+
+		let embed = create_group_embed(result, "Friends");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Group already exists")
+			.colour(Colour(0xdd2e44))
+			.description("Access group Friends already exists");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_delete_group_error_then_reply_with_invalid_group() {
+		let result = Err(GroupError::DoesNotExist {
+			group: "Family".to_string(),
+		});
+
+		let embed = delete_group_embed(result, "Family");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Invalid Group")
+			.colour(Colour(0xdd2e44))
+			.description("No access group with name Family exists");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_successful_add_member_then_reply_with_group_and_member() {
+		let embed = add_member_embed(Ok(()), "Friends", DiscordEntity::Role(RoleId::new(98765432109876543)));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Member added")
+			.colour(Colour(0x77b255))
+			.description("Successfully added member to access group!")
+			.field("Group", "Friends", true)
+			.field("Member", "<@&98765432109876543>", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_remove_member_error_with_non_member_then_reply_with_not_member() {
+		let entity = DiscordEntity::User(UserId::new(12345678901234567));
+		let result = Err(RemoveMemberError::NotMember {
+			group: "Friends".to_string(),
+			entity,
+		});
+
+		let embed = remove_member_embed(result, "Friends", entity);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Not member")
+			.colour(Colour(0xdd2e44))
+			.description("<@12345678901234567> is not in access group Friends");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_no_groups_then_list_groups_says_so() {
+		let embed = list_groups_embed(&AccessGroups::new());
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Access group list")
+			.colour(Colour(0x55acee))
+			.description("There are no access groups");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_groups_then_list_groups_shows_their_members() {
+		let groups = AccessGroups::from([
+			(
+				"Friends".to_string(),
+				AccessGroup {
+					users: BTreeSet::from([UserId::new(12345678901234567)]),
+					roles: BTreeSet::from([RoleId::new(98765432109876543)]),
+				},
+			),
+			("Others".to_string(), AccessGroup::default()),
+		]);
+
+		let embed = list_groups_embed(&groups);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Access group list")
+			.colour(Colour(0x55acee))
+			.description(
+				"Access groups:\n\
+- Friends: Users: <@12345678901234567>, Roles: <@&98765432109876543>\n\
+- Others: Users: None, Roles: None",
+			);
+
+		assert_eq!(embed, expected_embed);
+	}
+}
@@ -0,0 +1,81 @@
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter};
+
+/// Numbers the page in its footer, unless it is the only one.
+pub fn numbered_page(embed: CreateEmbed, page: usize, pages: usize) -> CreateEmbed {
+	if pages > 1 {
+		embed.footer(CreateEmbedFooter::new(format!("Page {}/{pages}", page + 1)))
+	} else {
+		embed
+	}
+}
+
+pub fn pagination_buttons(previous_id: &str, next_id: &str, page: usize, pages: usize) -> Vec<CreateActionRow> {
+	vec![CreateActionRow::Buttons(vec![
+		CreateButton::new(previous_id)
+			.label("Previous")
+			.style(ButtonStyle::Secondary)
+			.disabled(page == 0),
+		CreateButton::new(next_id)
+			.label("Next")
+			.style(ButtonStyle::Secondary)
+			.disabled(page + 1 >= pages),
+	])]
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::embeds;
+
+	#[test]
+	fn given_first_page_then_only_next_is_enabled() {
+		let buttons = pagination_buttons("1-previous", "1-next", 0, 3);
+
+		let expected_buttons = vec![CreateActionRow::Buttons(vec![
+			CreateButton::new("1-previous")
+				.label("Previous")
+				.style(ButtonStyle::Secondary)
+				.disabled(true),
+			CreateButton::new("1-next")
+				.label("Next")
+				.style(ButtonStyle::Secondary)
+				.disabled(false),
+		])];
+
+		assert_eq!(buttons, expected_buttons);
+	}
+
+	#[test]
+	fn given_last_page_then_only_previous_is_enabled() {
+		let buttons = pagination_buttons("1-previous", "1-next", 2, 3);
+
+		let expected_buttons = vec![CreateActionRow::Buttons(vec![
+			CreateButton::new("1-previous")
+				.label("Previous")
+				.style(ButtonStyle::Secondary)
+				.disabled(false),
+			CreateButton::new("1-next")
+				.label("Next")
+				.style(ButtonStyle::Secondary)
+				.disabled(true),
+		])];
+
+		assert_eq!(buttons, expected_buttons);
+	}
+
+	#[test]
+	fn given_several_pages_then_numbered_page_has_footer() {
+		let embed = numbered_page(embeds::info("Title", "Description"), 1, 3);
+
+		let expected_embed = embeds::info("Title", "Description").footer(CreateEmbedFooter::new("Page 2/3"));
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_single_page_then_numbered_page_has_no_footer() {
+		let embed = numbered_page(embeds::info("Title", "Description"), 0, 1);
+
+		assert_eq!(embed, embeds::info("Title", "Description"));
+	}
+}
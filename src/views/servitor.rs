@@ -1,3 +1,53 @@
+use crate::data::servitor::{IdlePolicy, IdleProbe, QueryConfig, ServitorAction};
+use std::collections::BTreeSet;
+
 pub mod server;
 pub mod authorization;
 pub mod action;
+pub mod health;
+pub mod watch;
+pub mod dashboard;
+pub mod schedule;
+pub mod idle;
+pub mod query;
+pub mod bulk;
+pub mod manager;
+
+pub(super) fn format_actions(actions: &BTreeSet<ServitorAction>) -> String {
+	if *actions == ServitorAction::all() {
+		"all actions".to_string()
+	} else {
+		actions
+			.iter()
+			.map(|action| format!("`{action}`"))
+			.collect::<Vec<_>>()
+			.join(", ")
+	}
+}
+
+fn describe_probe(probe: &IdleProbe) -> String {
+	match probe {
+		IdleProbe::Query => "players in the game query".to_string(),
+		IdleProbe::Connections { port } => format!("connections on port {port}"),
+	}
+}
+
+fn describe_idle_policy(policy: Option<&IdlePolicy>) -> String {
+	let Some(policy) = policy else {
+		return "None".to_string();
+	};
+
+	let description = format!("After {} minutes without {}", policy.minutes, describe_probe(&policy.probe));
+	match policy.keep_alive_until {
+		Some(until) => format!("{description}, kept alive until <t:{}:f>", until.timestamp()),
+		None => description,
+	}
+}
+
+fn describe_query(query: Option<&QueryConfig>) -> String {
+	match query {
+		Some(QueryConfig::A2s { address }) => format!("A2S at `{address}`"),
+		Some(QueryConfig::Minecraft { address }) => format!("Minecraft at `{address}`"),
+		None => "None".to_string(),
+	}
+}
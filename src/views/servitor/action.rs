@@ -1,7 +1,14 @@
+use crate::components::ComponentId;
 use crate::controllers::servitor::action::ExecuteServitorActionError;
-use crate::embeds;
-use crate::services::servitor::{ServitorError, UnitStatus};
-use serenity::all::{CreateEmbed, CreateEmbedFooter};
+use crate::data::access::AccessResource;
+use crate::data::servitor::ServitorAction;
+use crate::embeds::{self, DISCORD_MAX_EMBED_DESCRIPTION_LENGTH};
+use crate::views::access::request_access_buttons;
+use crate::services::query::{GameInfo, QueryError};
+use crate::services::servitor::{ServitorError, UnitLogs, UnitStatus};
+use serenity::all::{
+	ButtonStyle, CreateActionRow, CreateAttachment, CreateButton, CreateEmbed, CreateEmbedFooter,
+};
 
 pub fn start_embed(
 	result: Result<(), ExecuteServitorActionError>,
@@ -48,6 +55,104 @@ pub fn status_embed(
 	}
 }
 
+pub fn status_buttons(server_name: &str) -> Vec<CreateActionRow> {
+	let button = |action: ServitorAction, label: &str, style: ButtonStyle| {
+		let id = ComponentId::Servitor {
+			action,
+			server_name: server_name.to_string(),
+		};
+		CreateButton::new(id.to_string()).label(label).style(style)
+	};
+
+	vec![CreateActionRow::Buttons(vec![
+		button(ServitorAction::Start, "Start", ButtonStyle::Success),
+		button(ServitorAction::Stop, "Stop", ButtonStyle::Danger),
+		button(ServitorAction::Restart, "Restart", ButtonStyle::Primary),
+		button(ServitorAction::Status, "Refresh", ButtonStyle::Secondary),
+	])]
+}
+
+/// Offers unauthorized users to ask for access to the action they tried.
+pub fn action_buttons<T>(result: &Result<T, ExecuteServitorActionError>) -> Vec<CreateActionRow> {
+	match result {
+		Err(ExecuteServitorActionError::Unauthorized {
+			server_name, action, ..
+		}) => request_access_buttons(AccessResource::Server {
+			name: server_name.clone(),
+			action: *action,
+		}),
+		_ => vec![],
+	}
+}
+
+/// Adds what the game query reported to a status embed, if the server has one configured.
+pub fn game_info_fields(embed: CreateEmbed, game: Option<Result<GameInfo, QueryError>>) -> CreateEmbed {
+	match game {
+		None => embed,
+		Some(Ok(info)) => {
+			let mut embed = embed.field("Players", format!("{}/{}", info.players, info.max_players), true);
+			if let Some(map) = info.map {
+				embed = embed.field("Map", map, true);
+			}
+			if let Some(motd) = info.motd {
+				embed = embed.field("MOTD", motd, true);
+			}
+			embed.field("Version", info.version, true)
+		}
+		Some(Err(e)) => embed.field("Game query", format!("Unavailable: {e}"), false),
+	}
+}
+
+pub fn logs_embed(
+	result: Result<UnitLogs, ExecuteServitorActionError>,
+	server_name: &str,
+) -> (CreateEmbed, Option<CreateAttachment>) {
+	match result {
+		Ok(logs) if logs.lines.is_empty() => (
+			embeds::info(
+				"Servitor server logs",
+				format!("There are no logs for Servitor server {server_name}"),
+			),
+			None,
+		),
+		Ok(logs) => {
+			let joined = logs.lines.join("\n");
+			let code_block = format!("```\n{}\n```", joined.replace("```", "`\u{200b}``"));
+
+			if code_block.chars().count() <= DISCORD_MAX_EMBED_DESCRIPTION_LENGTH {
+				(embeds::info("Servitor server logs", code_block), None)
+			} else {
+				let filename = format!("{server_name}.log");
+				(
+					embeds::info(
+						"Servitor server logs",
+						format!(
+							"The last {} log lines for Servitor server {server_name} are too long for a message, see the attached {filename}",
+							logs.lines.len()
+						),
+					),
+					Some(CreateAttachment::bytes(joined.into_bytes(), filename)),
+				)
+			}
+		}
+		Err(e) => (servitor_error_embed(e, server_name), None),
+	}
+}
+
+pub fn unsupported_button_embed(action: ServitorAction) -> CreateEmbed {
+	embeds::error(
+		"Unsupported button",
+		format!("`{action}` can't be run from a button, use `/servitor {action}` instead"),
+	)
+}
+
+pub fn confirm_action_embed(server_name: &str, action: ServitorAction) -> CreateEmbed {
+	embeds::info(
+		format!("Confirm {action}"),
+		format!("Are you sure you want to run `{action}` on the Servitor server {server_name}?"),
+	)
+}
+
 fn action_embed(
 	result: Result<(), ExecuteServitorActionError>,
 	server_name: &str,
@@ -70,9 +175,9 @@ fn servitor_error_embed(error: ExecuteServitorActionError, server_name: &str) ->
 			format!("The server {server_name} was configured with Servitor {servitor_name}, but that Servitor instance no longer exists. \
 				 Please contact the bot owner!")
 		),
-		ExecuteServitorActionError::Unauthorized { .. } => embeds::error(
+		ExecuteServitorActionError::Unauthorized { action, .. } => embeds::error(
 			"Unauthorized",
-			format!("You are not authorized to operate the Servitor server {server_name}")
+			format!("You are not authorized to run `{action}` on the Servitor server {server_name}")
 		),
 		ExecuteServitorActionError::Servitor(se) => match se {
 			ServitorError::BadRequest => embeds::internal_error(
@@ -93,6 +198,10 @@ fn servitor_error_embed(error: ExecuteServitorActionError, server_name: &str) ->
 				"Received an unexpected error while trying to communicate with the Servitor instance, \
 					something is really wrong!"
 			),
+			ServitorError::Unavailable { retry_at } => embeds::error(
+				"Servitor Unavailable",
+				format!("The Servitor instance of server {server_name} is unreachable, try again <t:{}:R>", retry_at.timestamp())
+			),
 		}
 	}
 }
@@ -177,6 +286,7 @@ mod test {
 		let result = Err(ExecuteServitorActionError::Unauthorized {
 			user: UserId::new(12345678901234567),
 			server_name: "SomeServer".to_string(),
+			action: ServitorAction::Start,
 		});
 
 		let embed = function(result, "SomeServer");
@@ -184,7 +294,7 @@ mod test {
 		let expected_embed = CreateEmbed::default()
 			.title(":x: Unauthorized")
 			.colour(Colour(0xdd2e44))
-			.description("You are not authorized to operate the Servitor server SomeServer");
+			.description("You are not authorized to run `start` on the Servitor server SomeServer");
 
 		assert_eq!(embed, expected_embed);
 	}
@@ -296,6 +406,34 @@ mod test {
 		assert_eq!(embed, expected_embed);
 	}
 
+	#[rstest]
+	#[case(start_embed)]
+	#[case(stop_embed)]
+	#[case(restart_embed)]
+	#[case(reload_embed)]
+	#[case(status_embed)]
+	#[test]
+	fn given_action_with_servitor_unavailable_then_reply_with_retry_time<
+		T: Debug + PartialEq,
+	>(
+		#[case] function: impl FnOnce(Result<T, ExecuteServitorActionError>, &str) -> CreateEmbed,
+	) {
+		let result = Err(ExecuteServitorActionError::Servitor(
+			ServitorError::Unavailable {
+				retry_at: Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap(),
+			},
+		));
+
+		let embed = function(result, "SomeServer");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Servitor Unavailable")
+			.colour(Colour(0xdd2e44))
+			.description("The Servitor instance of server SomeServer is unreachable, try again <t:1741291185:R>");
+
+		assert_eq!(embed, expected_embed);
+	}
+
 	#[rstest]
 	#[case(start_embed, "start")]
 	#[case(stop_embed, "stop")]
@@ -340,4 +478,179 @@ mod test {
 
 		assert_eq!(embed, expected_embed);
 	}
+
+	#[test]
+	fn given_game_info_then_status_embed_lists_players_and_details() {
+		let game = Some(Ok(GameInfo {
+			players: 4,
+			max_players: 24,
+			map: Some("ctf_2fort".to_string()),
+			motd: None,
+			version: "9162192".to_string(),
+		}));
+
+		let embed = game_info_fields(CreateEmbed::default(), game);
+
+		let expected_embed = CreateEmbed::default()
+			.field("Players", "4/24", true)
+			.field("Map", "ctf_2fort", true)
+			.field("Version", "9162192", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_game_query_error_then_status_embed_marks_it_unavailable() {
+		let embed = game_info_fields(CreateEmbed::default(), Some(Err(QueryError::Timeout)));
+
+		let expected_embed =
+			CreateEmbed::default().field("Game query", "Unavailable: the game server did not answer in time", false);
+
+		assert_eq!(embed, expected_embed);
+		assert_eq!(game_info_fields(CreateEmbed::default(), None), CreateEmbed::default());
+	}
+
+	#[test]
+	fn given_logs_with_unauthorized_then_reply_with_unauthorized_to_view_logs() {
+		let result = Err(ExecuteServitorActionError::Unauthorized {
+			user: UserId::new(12345678901234567),
+			server_name: "SomeServer".to_string(),
+			action: ServitorAction::Logs,
+		});
+
+		let (embed, attachment) = logs_embed(result, "SomeServer");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Unauthorized")
+			.colour(Colour(0xdd2e44))
+			.description("You are not authorized to run `logs` on the Servitor server SomeServer");
+
+		assert_eq!(embed, expected_embed);
+		assert_eq!(attachment, None);
+	}
+
+	#[test]
+	fn given_logs_without_lines_then_reply_with_no_logs() {
+		let result = Ok(UnitLogs {
+			service: "bar.service".to_string(),
+			lines: vec![],
+		});
+
+		let (embed, attachment) = logs_embed(result, "SomeServer");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Servitor server logs")
+			.colour(Colour(0x55acee))
+			.description("There are no logs for Servitor server SomeServer");
+
+		assert_eq!(embed, expected_embed);
+		assert_eq!(attachment, None);
+	}
+
+	#[test]
+	fn given_short_logs_then_reply_with_code_block() {
+		let result = Ok(UnitLogs {
+			service: "bar.service".to_string(),
+			lines: vec!["first line".to_string(), "sneaky ``` line".to_string()],
+		});
+
+		let (embed, attachment) = logs_embed(result, "SomeServer");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Servitor server logs")
+			.colour(Colour(0x55acee))
+			.description("```\nfirst line\nsneaky `\u{200b}`` line\n```");
+
+		assert_eq!(embed, expected_embed);
+		assert_eq!(attachment, None);
+	}
+
+	#[test]
+	fn given_long_logs_then_reply_with_attachment() {
+		let lines: Vec<String> = (0..100).map(|i| format!("{i:0>80}")).collect();
+		let result = Ok(UnitLogs {
+			service: "bar.service".to_string(),
+			lines: lines.clone(),
+		});
+
+		let (embed, attachment) = logs_embed(result, "SomeServer");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Servitor server logs")
+			.colour(Colour(0x55acee))
+			.description(
+				"The last 100 log lines for Servitor server SomeServer are too long for a message, see the attached SomeServer.log",
+			);
+
+		assert_eq!(embed, expected_embed);
+		assert_eq!(
+			attachment,
+			Some(CreateAttachment::bytes(lines.join("\n").into_bytes(), "SomeServer.log"))
+		);
+	}
+
+	#[test]
+	fn given_action_then_confirm_action_embed_asks_for_confirmation() {
+		let embed = confirm_action_embed("SomeServer", ServitorAction::Stop);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Confirm stop")
+			.colour(Colour(0x55acee))
+			.description("Are you sure you want to run `stop` on the Servitor server SomeServer?");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_server_then_status_buttons_route_to_its_actions() {
+		let buttons = status_buttons("SomeServer");
+
+		let expected_buttons = vec![CreateActionRow::Buttons(vec![
+			CreateButton::new("servitor:start:SomeServer")
+				.label("Start")
+				.style(ButtonStyle::Success),
+			CreateButton::new("servitor:stop:SomeServer")
+				.label("Stop")
+				.style(ButtonStyle::Danger),
+			CreateButton::new("servitor:restart:SomeServer")
+				.label("Restart")
+				.style(ButtonStyle::Primary),
+			CreateButton::new("servitor:status:SomeServer")
+				.label("Refresh")
+				.style(ButtonStyle::Secondary),
+		])];
+
+		assert_eq!(buttons, expected_buttons);
+	}
+
+	#[test]
+	fn given_unauthorized_action_then_action_buttons_request_that_action() {
+		let result: Result<(), _> = Err(ExecuteServitorActionError::Unauthorized {
+			user: UserId::new(12345678901234567),
+			server_name: "SomeServer".to_string(),
+			action: ServitorAction::Stop,
+		});
+
+		let buttons = action_buttons(&result);
+
+		let expected_buttons = vec![CreateActionRow::Buttons(vec![
+			CreateButton::new("once:ask:servitor:stop:SomeServer")
+				.label("Ask for approval")
+				.style(ButtonStyle::Primary),
+			CreateButton::new("access:request:servitor:stop:SomeServer")
+				.label("Request access")
+				.style(ButtonStyle::Secondary),
+		])];
+
+		assert_eq!(buttons, expected_buttons);
+	}
+
+	#[test]
+	fn given_other_action_error_then_action_buttons_are_empty() {
+		let result: Result<(), _> = Err(ExecuteServitorActionError::Server(ServerError::DoesNotExist {
+			server_name: "SomeServer".to_string(),
+		}));
+
+		assert!(action_buttons(&result).is_empty());
+	}
 }
@@ -1,117 +1,66 @@
-use crate::controllers::servitor::authorization::{AddPermissionError, RemovePermissionError};
+use super::super::authorization::AuthorizationView;
+use super::format_actions;
+use crate::data::servitor::{ParseServitorActionError, ServerInfo, ServitorAction};
 use crate::embeds;
-use serenity::all::{RoleId, UserId};
 use serenity::builder::CreateEmbed;
+use std::collections::BTreeSet;
 
-pub fn permit_user_embed(
-	result: Result<(), AddPermissionError>,
-	server_name: &str,
-	user_id: UserId,
-) -> CreateEmbed {
-	match result {
-		Ok(_) => embeds::success(
-			"User permitted",
-			"Successfully permitted user to operate the Servitor server!",
-		)
-		.field("Servitor server", server_name, true)
-		.field("User", format!("<@{user_id}>"), true),
-		Err(e) => match e {
-			AddPermissionError::Server(_) => embeds::invalid_servitor_server(server_name),
-			AddPermissionError::AlreadyAuthorized { .. } => embeds::error(
-				"User already permitted",
-				format!("User <@{user_id}> is already permitted to operate Servitor server {server_name}"),
-			),
-		},
+impl AuthorizationView for ServerInfo {
+	const FIELD: &'static str = "Servitor server";
+
+	fn describe_grant(actions: &BTreeSet<ServitorAction>) -> String {
+		format!("run {} on", format_actions(actions))
 	}
-}
 
-pub fn revoke_user_embed(
-	result: Result<(), RemovePermissionError>,
-	server_name: &str,
-	user_id: UserId,
-) -> CreateEmbed {
-	match result {
-		Ok(_) => embeds::success(
-			"User permission revoked",
-			"Successfully revoked user's permission to operate the Servitor server!",
-		)
-		.field("Servitor server", server_name, true)
-		.field("User", format!("<@{user_id}>"), true),
-		Err(e) => match e {
-			RemovePermissionError::Server(_) => embeds::invalid_servitor_server(server_name),
-			RemovePermissionError::AlreadyNotAuthorized { .. } => embeds::error(
-				"User not permitted",
-				format!(
-					"User <@{user_id}> is already not permitted to operate Servitor server {server_name}"
-				),
-			),
-		},
+	fn invalid_resource_embed(name: &str) -> CreateEmbed {
+		embeds::invalid_servitor_server(name)
 	}
-}
 
-pub fn permit_role_embed(
-	result: Result<(), AddPermissionError>,
-	server_name: &str,
-	role_id: RoleId,
-) -> CreateEmbed {
-	match result {
-		Ok(_) => embeds::success(
-			"Role permitted",
-			"Successfully permitted role to operate the Servitor server!",
-		)
-			.field("Servitor server", server_name, true)
-			.field("Role", format!("<@&{role_id}>"), true),
-
-		Err(e) => match e {
-			AddPermissionError::Server(_) => embeds::invalid_servitor_server(server_name),
-			AddPermissionError::AlreadyAuthorized { .. } => embeds::error(
-				"Role already permitted",
-				format!(
-					"Role <@&{role_id}> is already permitted to operate Servitor server {server_name}"
-				),
-			),
-		},
+	fn list_entry(&self) -> String {
+		format!("{} - `{}`", self.servitor, self.unit_name)
 	}
 }
 
-pub fn revoke_role_embed(
-	result: Result<(), RemovePermissionError>,
-	server_name: &str,
-	role_id: RoleId,
-) -> CreateEmbed {
-	match result {
-		Ok(_) => embeds::success(
-			"Role permission revoked",
-			"Successfully revoked role's permission to operate the Servitor server!",
-		)
-			.field("Servitor server", server_name, true)
-			.field("Role", format!("<@&{role_id}>"), true),
-
-		Err(e) => match e {
-			RemovePermissionError::Server(_) => embeds::invalid_servitor_server(server_name),
-			RemovePermissionError::AlreadyNotAuthorized { .. } => embeds::error(
-				"Role not permitted",
-				format!(
-					"Role <@&{role_id}> is already not permitted to operate Servitor server {server_name}"
-				),
-			),
-		},
-	}
+pub fn invalid_actions_embed(error: ParseServitorActionError) -> CreateEmbed {
+	let ParseServitorActionError(action) = error;
+	let valid = ServitorAction::ALL
+		.iter()
+		.map(|action| format!("`{action}`"))
+		.collect::<Vec<_>>()
+		.join(", ");
+	embeds::error(
+		"Invalid action",
+		format!("`{action}` is not a Servitor action, valid actions are `all`, {valid}"),
+	)
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::controllers::servitor::ServerError;
+	use crate::controllers::authorization::{
+		AddGroupPermissionError, AddPermissionError, RemovePermissionError,
+	};
 	use crate::controllers::DiscordEntity;
-	use serenity::all::Colour;
+	use crate::views::authorization::{
+		deny_embed, lift_denial_embed, permit_embed, permit_group_embed, revoke_embed, revoke_group_embed,
+	};
+	use crate::controllers::servitor::ServerError;
+	use crate::data::servitor::ServitorAction;
+	use std::collections::BTreeSet;
+	use serenity::all::{Colour, RoleId, UserId};
 
 	#[test]
 	fn given_permit_user_error_with_nonexistent_server_then_reply_with_error_no_server() {
-		let result = Err(AddPermissionError::Server(ServerError::DoesNotExist {
+		let result = Err(AddPermissionError::Resource(ServerError::DoesNotExist {
 			server_name: "NonExistingServer".to_string(),
 		}));
-		let embed = permit_user_embed(result, "NonExistingServer", UserId::new(12345678901234567));
+		let embed = permit_embed::<ServerInfo>(
+			result,
+			"NonExistingServer",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			&ServitorAction::all(),
+			None,
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: Invalid Servitor server")
@@ -124,16 +73,23 @@ mod tests {
 	#[test]
 	fn given_permit_user_error_with_already_authorized_then_reply_with_error_already_authorized() {
 		let result = Err(AddPermissionError::AlreadyAuthorized {
-			server_name: "SomeServer".to_string(),
+			name: "SomeServer".to_string(),
 			entity: DiscordEntity::User(UserId::new(12345678901234567)),
+			grant: ServitorAction::all(),
 		});
-		let embed = permit_user_embed(result, "SomeServer", UserId::new(12345678901234567));
+		let embed = permit_embed::<ServerInfo>(
+			result,
+			"SomeServer",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			&ServitorAction::all(),
+			None,
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: User already permitted")
 			.colour(Colour(0xdd2e44))
 			.description(
-				"User <@12345678901234567> is already permitted to operate Servitor server SomeServer",
+				"User <@12345678901234567> is already permitted to run all actions on Servitor server SomeServer",
 			);
 
 		assert_eq!(embed, expected_embed);
@@ -141,12 +97,18 @@ mod tests {
 
 	#[test]
 	fn given_successful_permit_user_then_should_reply_with_success_info() {
-		let embed = permit_user_embed(Ok(()), "SomeServer", UserId::new(12345678901234567));
+		let embed = permit_embed::<ServerInfo>(
+			Ok(()),
+			"SomeServer",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			&ServitorAction::all(),
+			None,
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":white_check_mark: User permitted")
 			.colour(Colour(0x77b255))
-			.description("Successfully permitted user to operate the Servitor server!")
+			.description("Successfully permitted user to run all actions on the Servitor server!")
 			.field("Servitor server", "SomeServer", true)
 			.field("User", "<@12345678901234567>", true);
 
@@ -155,10 +117,15 @@ mod tests {
 
 	#[test]
 	fn given_revoke_user_error_with_nonexistent_server_then_reply_with_error_no_server() {
-		let result = Err(RemovePermissionError::Server(ServerError::DoesNotExist {
+		let result = Err(RemovePermissionError::Resource(ServerError::DoesNotExist {
 			server_name: "NonExistentServer".to_string(),
 		}));
-		let embed = revoke_user_embed(result, "NonExistentServer", UserId::new(12345678901234567));
+		let embed = revoke_embed::<ServerInfo>(
+			result,
+			"NonExistentServer",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			&ServitorAction::all(),
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: Invalid Servitor server")
@@ -171,16 +138,22 @@ mod tests {
 	#[test]
 	fn given_revoke_user_error_with_already_unauthorized_then_reply_with_already_unauthorized() {
 		let result = Err(RemovePermissionError::AlreadyNotAuthorized {
-			server_name: "SomeServer".to_string(),
+			name: "SomeServer".to_string(),
 			entity: DiscordEntity::User(UserId::new(76543210987654321)),
+			grant: ServitorAction::all(),
 		});
-		let embed = revoke_user_embed(result, "SomeServer", UserId::new(76543210987654321));
+		let embed = revoke_embed::<ServerInfo>(
+			result,
+			"SomeServer",
+			DiscordEntity::User(UserId::new(76543210987654321)),
+			&ServitorAction::all(),
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: User not permitted")
 			.colour(Colour(0xdd2e44))
 			.description(
-				"User <@76543210987654321> is already not permitted to operate Servitor server SomeServer",
+				"User <@76543210987654321> is already not permitted to run all actions on Servitor server SomeServer",
 			);
 
 		assert_eq!(embed, expected_embed);
@@ -188,12 +161,17 @@ mod tests {
 
 	#[test]
 	fn given_successful_revoke_user_then_should_reply_with_success_info() {
-		let embed = revoke_user_embed(Ok(()), "SomeServer", UserId::new(12345678901234567));
+		let embed = revoke_embed::<ServerInfo>(
+			Ok(()),
+			"SomeServer",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			&ServitorAction::all(),
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":white_check_mark: User permission revoked")
 			.colour(Colour(0x77b255))
-			.description("Successfully revoked user's permission to operate the Servitor server!")
+			.description("Successfully revoked user's permission to run all actions on the Servitor server!")
 			.field("Servitor server", "SomeServer", true)
 			.field("User", "<@12345678901234567>", true);
 
@@ -202,10 +180,16 @@ mod tests {
 
 	#[test]
 	fn given_permit_role_error_with_nonexistent_server_then_reply_with_error_no_server() {
-		let result = Err(AddPermissionError::Server(ServerError::DoesNotExist {
+		let result = Err(AddPermissionError::Resource(ServerError::DoesNotExist {
 			server_name: "NonExistingServer".to_string(),
 		}));
-		let embed = permit_role_embed(result, "NonExistingServer", RoleId::new(987654321098765432));
+		let embed = permit_embed::<ServerInfo>(
+			result,
+			"NonExistingServer",
+			DiscordEntity::Role(RoleId::new(987654321098765432)),
+			&ServitorAction::all(),
+			None,
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: Invalid Servitor server")
@@ -218,16 +202,23 @@ mod tests {
 	#[test]
 	fn given_permit_role_error_with_already_authorized_then_reply_with_error_already_authorized() {
 		let result = Err(AddPermissionError::AlreadyAuthorized {
-			server_name: "SomeServer".to_string(),
+			name: "SomeServer".to_string(),
 			entity: DiscordEntity::Role(RoleId::new(987654321098765432)),
+			grant: ServitorAction::all(),
 		});
-		let embed = permit_role_embed(result, "SomeServer", RoleId::new(987654321098765432));
+		let embed = permit_embed::<ServerInfo>(
+			result,
+			"SomeServer",
+			DiscordEntity::Role(RoleId::new(987654321098765432)),
+			&ServitorAction::all(),
+			None,
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: Role already permitted")
 			.colour(Colour(0xdd2e44))
 			.description(
-				"Role <@&987654321098765432> is already permitted to operate Servitor server SomeServer",
+				"Role <@&987654321098765432> is already permitted to run all actions on Servitor server SomeServer",
 			);
 
 		assert_eq!(embed, expected_embed);
@@ -235,12 +226,18 @@ mod tests {
 
 	#[test]
 	fn given_successful_permit_role_then_should_reply_with_success_info() {
-		let embed = permit_role_embed(Ok(()), "SomeServer", RoleId::new(987654321098765432));
+		let embed = permit_embed::<ServerInfo>(
+			Ok(()),
+			"SomeServer",
+			DiscordEntity::Role(RoleId::new(987654321098765432)),
+			&ServitorAction::all(),
+			None,
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":white_check_mark: Role permitted")
 			.colour(Colour(0x77b255))
-			.description("Successfully permitted role to operate the Servitor server!")
+			.description("Successfully permitted role to run all actions on the Servitor server!")
 			.field("Servitor server", "SomeServer", true)
 			.field("Role", "<@&987654321098765432>", true);
 
@@ -249,10 +246,15 @@ mod tests {
 
 	#[test]
 	fn given_revoke_role_error_with_nonexistent_server_then_reply_with_error_no_server() {
-		let result = Err(RemovePermissionError::Server(ServerError::DoesNotExist {
+		let result = Err(RemovePermissionError::Resource(ServerError::DoesNotExist {
 			server_name: "NonExistentServer".to_string(),
 		}));
-		let embed = revoke_role_embed(result, "NonExistentServer", RoleId::new(12345678901234567));
+		let embed = revoke_embed::<ServerInfo>(
+			result,
+			"NonExistentServer",
+			DiscordEntity::Role(RoleId::new(12345678901234567)),
+			&ServitorAction::all(),
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: Invalid Servitor server")
@@ -265,16 +267,22 @@ mod tests {
 	#[test]
 	fn given_revoke_role_error_with_already_unauthorized_then_reply_with_already_unauthorized() {
 		let result = Err(RemovePermissionError::AlreadyNotAuthorized {
-			server_name: "SomeServer".to_string(),
+			name: "SomeServer".to_string(),
 			entity: DiscordEntity::Role(RoleId::new(76543210987654321)),
+			grant: ServitorAction::all(),
 		});
-		let embed = revoke_role_embed(result, "SomeServer", RoleId::new(76543210987654321));
+		let embed = revoke_embed::<ServerInfo>(
+			result,
+			"SomeServer",
+			DiscordEntity::Role(RoleId::new(76543210987654321)),
+			&ServitorAction::all(),
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: Role not permitted")
 			.colour(Colour(0xdd2e44))
 			.description(
-				"Role <@&76543210987654321> is already not permitted to operate Servitor server SomeServer",
+				"Role <@&76543210987654321> is already not permitted to run all actions on Servitor server SomeServer",
 			);
 
 		assert_eq!(embed, expected_embed);
@@ -282,15 +290,186 @@ mod tests {
 
 	#[test]
 	fn given_successful_revoke_role_then_should_reply_with_success_info() {
-		let embed = revoke_role_embed(Ok(()), "SomeServer", RoleId::new(12345678901234567));
+		let embed = revoke_embed::<ServerInfo>(
+			Ok(()),
+			"SomeServer",
+			DiscordEntity::Role(RoleId::new(12345678901234567)),
+			&ServitorAction::all(),
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":white_check_mark: Role permission revoked")
 			.colour(Colour(0x77b255))
-			.description("Successfully revoked role's permission to operate the Servitor server!")
+			.description("Successfully revoked role's permission to run all actions on the Servitor server!")
 			.field("Servitor server", "SomeServer", true)
 			.field("Role", "<@&12345678901234567>", true);
 
 		assert_eq!(embed, expected_embed);
 	}
+
+	#[test]
+	fn given_successful_permit_user_for_logs_then_should_reply_with_success_info() {
+		let embed = permit_embed::<ServerInfo>(
+			Ok(()),
+			"SomeServer",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			&BTreeSet::from([ServitorAction::Logs]),
+			None,
+		);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: User permitted")
+			.colour(Colour(0x77b255))
+			.description("Successfully permitted user to run `logs` on the Servitor server!")
+			.field("Servitor server", "SomeServer", true)
+			.field("User", "<@12345678901234567>", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_revoke_role_for_logs_with_already_unauthorized_then_reply_with_already_unauthorized() {
+		let result = Err(RemovePermissionError::AlreadyNotAuthorized {
+			name: "SomeServer".to_string(),
+			entity: DiscordEntity::Role(RoleId::new(76543210987654321)),
+			grant: BTreeSet::from([ServitorAction::Logs]),
+		});
+		let embed = revoke_embed::<ServerInfo>(
+			result,
+			"SomeServer",
+			DiscordEntity::Role(RoleId::new(76543210987654321)),
+			&BTreeSet::from([ServitorAction::Logs]),
+		);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Role not permitted")
+			.colour(Colour(0xdd2e44))
+			.description(
+				"Role <@&76543210987654321> is already not permitted to run `logs` on Servitor server SomeServer",
+			);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_permit_user_with_some_actions_then_reply_lists_actions() {
+		let embed = permit_embed::<ServerInfo>(
+			Ok(()),
+			"SomeServer",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			&BTreeSet::from([ServitorAction::Status, ServitorAction::Restart]),
+			None,
+		);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: User permitted")
+			.colour(Colour(0x77b255))
+			.description("Successfully permitted user to run `status`, `restart` on the Servitor server!")
+			.field("Servitor server", "SomeServer", true)
+			.field("User", "<@12345678901234567>", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_invalid_action_then_reply_with_valid_actions() {
+		let embed = invalid_actions_embed(ParseServitorActionError("explode".to_string()));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Invalid action")
+			.colour(Colour(0xdd2e44))
+			.description(
+				"`explode` is not a Servitor action, valid actions are `all`, \
+`status`, `start`, `stop`, `restart`, `reload`, `logs`",
+			);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_successful_permit_group_then_should_reply_with_success_info() {
+		let actions = BTreeSet::from([ServitorAction::Start]);
+		let embed = permit_group_embed::<ServerInfo>(Ok(()), "SomeServer", "Friends", &actions);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Group permitted")
+			.colour(Colour(0x77b255))
+			.description("Successfully permitted access group to run `start` on the Servitor server!")
+			.field("Servitor server", "SomeServer", true)
+			.field("Group", "Friends", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_permit_group_error_with_already_authorized_then_reply_with_error_already_authorized() {
+		let result = Err(AddGroupPermissionError::AlreadyAuthorized {
+			name: "SomeServer".to_string(),
+			group: "Friends".to_string(),
+			grant: ServitorAction::all(),
+		});
+		let embed = permit_group_embed::<ServerInfo>(
+			result,
+			"SomeServer",
+			"Friends",
+			&ServitorAction::all(),
+		);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Group already permitted")
+			.colour(Colour(0xdd2e44))
+			.description("Access group Friends is already permitted to run all actions on Servitor server SomeServer");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_successful_revoke_group_then_should_reply_with_success_info() {
+		let embed = revoke_group_embed::<ServerInfo>(
+			Ok(()),
+			"SomeServer",
+			"Friends",
+			&ServitorAction::all(),
+		);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Group permission revoked")
+			.colour(Colour(0x77b255))
+			.description("Successfully revoked access group's permission to run all actions on the Servitor server!")
+			.field("Servitor server", "SomeServer", true)
+			.field("Group", "Friends", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_successful_deny_without_reason_then_reply_with_none_given() {
+		let entity = DiscordEntity::Role(RoleId::new(98765432109876543));
+		let embed = deny_embed::<ServerInfo>(Ok(()), "SomeServer", entity, None);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Denied")
+			.colour(Colour(0x77b255))
+			.description("Successfully denied from the Servitor server, whatever they are permitted!")
+			.field("Servitor server", "SomeServer", true)
+			.field("Denied", "<@&98765432109876543>", true)
+			.field("Reason", "None given", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_successful_lift_denial_then_reply_with_success_info() {
+		let entity = DiscordEntity::User(UserId::new(12345678901234567));
+		let embed = lift_denial_embed::<ServerInfo>(Ok(()), "SomeServer", entity);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Denial lifted")
+			.colour(Colour(0x77b255))
+			.description("Successfully lifted denial from the Servitor server!")
+			.field("Servitor server", "SomeServer", true)
+			.field("Denied", "<@12345678901234567>", true);
+
+		assert_eq!(embed, expected_embed);
+	}
 }
@@ -0,0 +1,158 @@
+use crate::controllers::servitor::action::ExecuteServitorActionError;
+use crate::controllers::servitor::bulk::{BulkOutcome, BulkResults};
+use crate::data::servitor::ServitorAction;
+use crate::embeds;
+use crate::services::servitor::ServitorError;
+use serenity::all::CreateEmbed;
+use std::collections::BTreeSet;
+
+pub fn bulk_embed(results: BulkResults, action: ServitorAction) -> CreateEmbed {
+	if results.is_empty() {
+		return embeds::error("No servers", "No Servitor servers were given");
+	}
+
+	let failures = results.values().filter(|r| r.is_err()).count();
+	let title = format!("Servitor bulk {action}");
+	let description = results
+		.iter()
+		.map(|(server_name, result)| match result {
+			Ok(BulkOutcome::Done) => format!("- :white_check_mark: {server_name}"),
+			Ok(BulkOutcome::Status(status)) => format!(
+				"- :white_check_mark: {server_name}: `{}` (`{}`)",
+				status.state, status.sub_state
+			),
+			Err(e) => format!("- :x: {server_name}: {}", failure_reason(e)),
+		})
+		.collect::<Vec<_>>()
+		.join("\n");
+
+	if failures == 0 {
+		embeds::success(title, description)
+	} else if failures == results.len() {
+		embeds::error(title, description)
+	} else {
+		embeds::info(title, description)
+	}
+}
+
+pub fn confirm_bulk_embed(servers: &BTreeSet<String>, action: ServitorAction) -> CreateEmbed {
+	let server_names = servers.iter().cloned().collect::<Vec<_>>().join(", ");
+	embeds::info(
+		format!("Confirm bulk {action}"),
+		format!("Are you sure you want to run `{action}` on the Servitor servers {server_names}?"),
+	)
+}
+
+fn failure_reason(error: &ExecuteServitorActionError) -> String {
+	match error {
+		ExecuteServitorActionError::Server(_) => "no such server".to_string(),
+		ExecuteServitorActionError::InvalidServitor { servitor_name, .. } => {
+			format!("Servitor instance {servitor_name} no longer exists")
+		}
+		ExecuteServitorActionError::Unauthorized { .. } => "not authorized".to_string(),
+		ExecuteServitorActionError::Servitor(ServitorError::Unavailable { retry_at }) => {
+			format!("Servitor unreachable, try again <t:{}:R>", retry_at.timestamp())
+		}
+		ExecuteServitorActionError::Servitor(e) => e.to_string(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::controllers::servitor::ServerError;
+	use crate::services::servitor::UnitStatus;
+	use chrono::{TimeZone, Utc};
+	use serenity::all::{Colour, UserId};
+	use std::collections::BTreeMap;
+
+	#[test]
+	fn given_no_results_then_reply_with_no_servers() {
+		let embed = bulk_embed(BTreeMap::new(), ServitorAction::Start);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: No servers")
+			.colour(Colour(0xdd2e44))
+			.description("No Servitor servers were given");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_all_successful_then_reply_with_success_list() {
+		let results = BTreeMap::from([
+			("One".to_string(), Ok(BulkOutcome::Done)),
+			("Two".to_string(), Ok(BulkOutcome::Done)),
+		]);
+
+		let embed = bulk_embed(results, ServitorAction::Restart);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Servitor bulk restart")
+			.colour(Colour(0x77b255))
+			.description("- :white_check_mark: One\n- :white_check_mark: Two");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_partial_failures_then_reply_with_mixed_list() {
+		let results = BTreeMap::from([
+			(
+				"One".to_string(),
+				Ok(BulkOutcome::Status(UnitStatus {
+					service: "bar.service".to_string(),
+					state: "active".to_string(),
+					sub_state: "running".to_string(),
+					since: Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap(),
+				})),
+			),
+			(
+				"Two".to_string(),
+				Err(ExecuteServitorActionError::Unauthorized {
+					user: UserId::new(1),
+					server_name: "Two".to_string(),
+					action: ServitorAction::Status,
+				}),
+			),
+			(
+				"Three".to_string(),
+				Err(ExecuteServitorActionError::Servitor(ServitorError::Unavailable {
+					retry_at: Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap(),
+				})),
+			),
+		]);
+
+		let embed = bulk_embed(results, ServitorAction::Status);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Servitor bulk status")
+			.colour(Colour(0x55acee))
+			.description(
+				"- :white_check_mark: One: `active` (`running`)\n\
+				- :x: Three: Servitor unreachable, try again <t:1741291185:R>\n\
+				- :x: Two: not authorized",
+			);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_all_failed_then_reply_with_error_list() {
+		let results = BTreeMap::from([(
+			"Missing".to_string(),
+			Err(ExecuteServitorActionError::Server(ServerError::DoesNotExist {
+				server_name: "Missing".to_string(),
+			})),
+		)]);
+
+		let embed = bulk_embed(results, ServitorAction::Stop);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Servitor bulk stop")
+			.colour(Colour(0xdd2e44))
+			.description("- :x: Missing: no such server");
+
+		assert_eq!(embed, expected_embed);
+	}
+}
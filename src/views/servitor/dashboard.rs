@@ -0,0 +1,237 @@
+use crate::controllers::servitor::dashboard::{DashboardSnapshot, RemoveDashboardError};
+use crate::embeds::{self, DISCORD_MAX_EMBED_DESCRIPTION_LENGTH};
+use chrono::{DateTime, Utc};
+use serenity::all::{ChannelId, CreateEmbed, CreateEmbedFooter};
+
+/// Room for the section headings, leaving the rest of the description to the two sections in equal parts.
+const SECTION_LENGTH: usize = (DISCORD_MAX_EMBED_DESCRIPTION_LENGTH - 64) / 2;
+/// Room kept for the line that counts the lines left out of a section.
+const OMITTED_LENGTH: usize = 32;
+
+/// Joins as many lines as fit in `budget` characters, summing up the rest in a last line.
+fn fit_lines(lines: Vec<String>, budget: usize) -> String {
+	let length = |line: &String| line.chars().count() + 1;
+	if lines.iter().map(length).sum::<usize>() <= budget {
+		return lines.join("\n");
+	}
+
+	let mut used = OMITTED_LENGTH;
+	let shown = lines
+		.iter()
+		.take_while(|line| {
+			used += length(line);
+			used <= budget
+		})
+		.count();
+
+	let omitted = lines.len() - shown;
+	let mut fitted = lines;
+	fitted.truncate(shown);
+	fitted.push(format!("- … and {omitted} more"));
+	fitted.join("\n")
+}
+
+pub fn dashboard_embed(snapshot: &DashboardSnapshot, updated_at: DateTime<Utc>) -> CreateEmbed {
+	let servers = if snapshot.servers.is_empty() {
+		"There are no servitor servers configured".to_string()
+	} else {
+		let lines = snapshot
+			.servers
+			.iter()
+			.map(|(name, status)| match status {
+				Some(status) => format!(
+					"- {name}: `{}` (`{}`) since <t:{}:R>",
+					status.state,
+					status.sub_state,
+					status.since.timestamp()
+				),
+				None => format!("- {name}: status unavailable"),
+			})
+			.collect();
+		fit_lines(lines, SECTION_LENGTH)
+	};
+
+	let machines = if snapshot.machines.is_empty() {
+		"There are no machines configured".to_string()
+	} else {
+		let lines = snapshot
+			.machines
+			.iter()
+			.map(|(name, online)| match online {
+				Some(true) => format!("- {name}: :green_circle: Online"),
+				Some(false) => format!("- {name}: :red_circle: Offline"),
+				None => format!("- {name}: :white_circle: Unknown"),
+			})
+			.collect();
+		fit_lines(lines, SECTION_LENGTH)
+	};
+
+	embeds::info(
+		"Servitor dashboard",
+		format!("**Servers**\n{servers}\n\n**Machines**\n{machines}"),
+	)
+	.footer(CreateEmbedFooter::new("Last updated:"))
+	.timestamp(updated_at)
+}
+
+pub fn create_dashboard_embed(channel: ChannelId) -> CreateEmbed {
+	embeds::success(
+		"Dashboard created",
+		format!("The Servitor dashboard in <#{channel}> will be refreshed periodically"),
+	)
+}
+
+pub fn remove_dashboard_embed(result: Result<(), RemoveDashboardError>, channel: ChannelId) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success(
+			"Dashboard removed",
+			format!("The Servitor dashboard in <#{channel}> will no longer be refreshed"),
+		),
+		Err(RemoveDashboardError::DoesNotExist { .. }) => embeds::error(
+			"No dashboard",
+			format!("There is no Servitor dashboard in <#{channel}>"),
+		),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::services::servitor::UnitStatus;
+	use chrono::TimeZone;
+	use serenity::all::Colour;
+	use std::collections::BTreeMap;
+
+	#[test]
+	fn given_empty_snapshot_then_dashboard_shows_nothing_configured() {
+		let snapshot = DashboardSnapshot {
+			servers: BTreeMap::new(),
+			machines: BTreeMap::new(),
+		};
+		let updated_at = Utc.with_ymd_and_hms(2025, 3, 6, 20, 0, 0).unwrap();
+
+		let embed = dashboard_embed(&snapshot, updated_at);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Servitor dashboard")
+			.colour(Colour(0x55acee))
+			.description(
+				"**Servers**\n\
+There are no servitor servers configured\n\n\
+**Machines**\n\
+There are no machines configured",
+			)
+			.footer(CreateEmbedFooter::new("Last updated:"))
+			.timestamp(updated_at);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_snapshot_then_dashboard_lists_servers_and_machines() {
+		let since = Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap();
+		let updated_at = Utc.with_ymd_and_hms(2025, 3, 6, 20, 0, 0).unwrap();
+		let snapshot = DashboardSnapshot {
+			servers: BTreeMap::from([
+				(
+					"ServerOne".to_string(),
+					Some(UnitStatus {
+						service: "one.service".to_string(),
+						state: "active".to_string(),
+						sub_state: "running".to_string(),
+						since,
+					}),
+				),
+				("ServerTwo".to_string(), None),
+			]),
+			machines: BTreeMap::from([
+				("MachineOne".to_string(), Some(true)),
+				("MachineThree".to_string(), None),
+				("MachineTwo".to_string(), Some(false)),
+			]),
+		};
+
+		let embed = dashboard_embed(&snapshot, updated_at);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Servitor dashboard")
+			.colour(Colour(0x55acee))
+			.description(
+				"**Servers**\n\
+- ServerOne: `active` (`running`) since <t:1741291185:R>\n\
+- ServerTwo: status unavailable\n\n\
+**Machines**\n\
+- MachineOne: :green_circle: Online\n\
+- MachineThree: :white_circle: Unknown\n\
+- MachineTwo: :red_circle: Offline",
+			)
+			.footer(CreateEmbedFooter::new("Last updated:"))
+			.timestamp(updated_at);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_large_fleet_then_dashboard_fits_in_description_limit_and_keeps_both_sections() {
+		let updated_at = Utc.with_ymd_and_hms(2025, 3, 6, 20, 0, 0).unwrap();
+		let snapshot = DashboardSnapshot {
+			servers: (0..500).map(|i| (format!("Server{i:03}"), None)).collect(),
+			machines: (0..500).map(|i| (format!("Machine{i:03}"), Some(true))).collect(),
+		};
+
+		let embed = serde_json::to_value(dashboard_embed(&snapshot, updated_at)).unwrap();
+		let description = embed["description"].as_str().unwrap();
+
+		assert!(description.chars().count() <= DISCORD_MAX_EMBED_DESCRIPTION_LENGTH);
+		assert!(description.contains("- Server000: status unavailable\n"));
+		assert!(description.contains("more\n\n**Machines**\n- Machine000: :green_circle: Online\n"));
+		assert!(description.ends_with("more"));
+	}
+
+	#[test]
+	fn given_lines_over_budget_then_fit_lines_counts_the_omitted_ones() {
+		let lines = (0..10).map(|i| format!("- Line{i}")).collect();
+
+		assert_eq!(fit_lines(lines, OMITTED_LENGTH + 16), "- Line0\n- Line1\n- … and 8 more");
+	}
+
+	#[test]
+	fn given_created_dashboard_then_reply_with_success_info() {
+		let embed = create_dashboard_embed(ChannelId::new(1));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Dashboard created")
+			.colour(Colour(0x77b255))
+			.description("The Servitor dashboard in <#1> will be refreshed periodically");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_remove_dashboard_without_dashboard_then_reply_with_error() {
+		let result = Err(RemoveDashboardError::DoesNotExist {
+			channel: ChannelId::new(1),
+		});
+
+		let embed = remove_dashboard_embed(result, ChannelId::new(1));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: No dashboard")
+			.colour(Colour(0xdd2e44))
+			.description("There is no Servitor dashboard in <#1>");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_removed_dashboard_then_reply_with_success_info() {
+		let embed = remove_dashboard_embed(Ok(()), ChannelId::new(1));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Dashboard removed")
+			.colour(Colour(0x77b255))
+			.description("The Servitor dashboard in <#1> will no longer be refreshed");
+
+		assert_eq!(embed, expected_embed);
+	}
+}
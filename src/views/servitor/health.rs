@@ -0,0 +1,164 @@
+use crate::controllers::servitor::health::{HealthTransition, InstanceSummary};
+use crate::embeds;
+use serenity::builder::CreateEmbed;
+use std::collections::BTreeMap;
+
+pub fn list_instances_embed(instances: &BTreeMap<String, InstanceSummary>) -> CreateEmbed {
+	let description = if instances.is_empty() {
+		"There are no Servitor instances configured".to_string()
+	} else {
+		let instance_list = instances
+			.iter()
+			.map(|(name, summary)| {
+				let health = match &summary.health {
+					Some(health) if health.healthy => format!(
+						":green_circle: Healthy ({} ms) since <t:{}:R>",
+						health.latency.as_millis(),
+						health.since.timestamp()
+					),
+					Some(health) => format!(
+						":red_circle: Unreachable since <t:{}:R>",
+						health.since.timestamp()
+					),
+					None => ":white_circle: Unknown".to_string(),
+				};
+				format!("- {name}: {health} - {} server(s)", summary.server_count)
+			})
+			.collect::<Vec<String>>()
+			.join("\n");
+		format!("Configured instances:\n{instance_list}")
+	};
+
+	embeds::info("Servitor instance list", description)
+}
+
+pub fn health_transition_embed(transition: &HealthTransition) -> CreateEmbed {
+	let name = &transition.servitor_name;
+	let previous_since = transition.previous_since.timestamp();
+
+	if transition.healthy {
+		embeds::success(
+			"Servitor instance recovered",
+			format!("Servitor instance {name} is healthy again, it was unreachable since <t:{previous_since}:f>"),
+		)
+	} else {
+		embeds::error(
+			"Servitor instance unreachable",
+			format!("Servitor instance {name} is no longer healthy, it was healthy since <t:{previous_since}:f>"),
+		)
+	}
+	.timestamp(transition.at)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::controllers::servitor::health::InstanceHealth;
+	use chrono::{TimeZone, Utc};
+	use serenity::all::Colour;
+	use std::time::Duration;
+
+	#[test]
+	fn given_no_instances_then_list_instances_replies_with_empty_response() {
+		let embed = list_instances_embed(&BTreeMap::new());
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Servitor instance list")
+			.colour(Colour(0x55acee))
+			.description("There are no Servitor instances configured");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_some_instances_then_list_instances_replies_with_formatted_list() {
+		let since = Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap();
+		let instances = BTreeMap::from([
+			(
+				"InstanceOne".to_string(),
+				InstanceSummary {
+					health: Some(InstanceHealth {
+						healthy: true,
+						latency: Duration::from_millis(42),
+						checked_at: since,
+						since,
+					}),
+					server_count: 2,
+				},
+			),
+			(
+				"InstanceThree".to_string(),
+				InstanceSummary {
+					health: None,
+					server_count: 0,
+				},
+			),
+			(
+				"InstanceTwo".to_string(),
+				InstanceSummary {
+					health: Some(InstanceHealth {
+						healthy: false,
+						latency: Duration::from_millis(2000),
+						checked_at: since,
+						since,
+					}),
+					server_count: 1,
+				},
+			),
+		]);
+
+		let embed = list_instances_embed(&instances);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Servitor instance list")
+			.colour(Colour(0x55acee))
+			.description(
+				"Configured instances:\n\
+- InstanceOne: :green_circle: Healthy (42 ms) since <t:1741291185:R> - 2 server(s)\n\
+- InstanceThree: :white_circle: Unknown - 0 server(s)\n\
+- InstanceTwo: :red_circle: Unreachable since <t:1741291185:R> - 1 server(s)",
+			);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_down_transition_then_reply_with_unreachable_alert() {
+		let transition = HealthTransition {
+			servitor_name: "foo".to_string(),
+			healthy: false,
+			previous_since: Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap(),
+			at: Utc.with_ymd_and_hms(2025, 3, 6, 20, 0, 0).unwrap(),
+		};
+
+		let embed = health_transition_embed(&transition);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Servitor instance unreachable")
+			.colour(Colour(0xdd2e44))
+			.description("Servitor instance foo is no longer healthy, it was healthy since <t:1741291185:f>")
+			.timestamp(Utc.with_ymd_and_hms(2025, 3, 6, 20, 0, 0).unwrap());
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_up_transition_then_reply_with_recovered_alert() {
+		let transition = HealthTransition {
+			servitor_name: "foo".to_string(),
+			healthy: true,
+			previous_since: Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap(),
+			at: Utc.with_ymd_and_hms(2025, 3, 6, 20, 0, 0).unwrap(),
+		};
+
+		let embed = health_transition_embed(&transition);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Servitor instance recovered")
+			.colour(Colour(0x77b255))
+			.description("Servitor instance foo is healthy again, it was unreachable since <t:1741291185:f>")
+			.timestamp(Utc.with_ymd_and_hms(2025, 3, 6, 20, 0, 0).unwrap());
+
+		assert_eq!(embed, expected_embed);
+	}
+}
@@ -0,0 +1,228 @@
+use super::describe_probe;
+use crate::controllers::servitor::idle::{IdlePolicyError, IdleShutdown, KeepAliveError, SetIdlePolicyError};
+use crate::data::servitor::IdleProbe;
+use crate::embeds;
+use chrono::{DateTime, Utc};
+use serenity::all::CreateEmbed;
+
+pub fn set_idle_policy_embed(
+	result: Result<(), SetIdlePolicyError>,
+	server_name: &str,
+	probe: &IdleProbe,
+	minutes: u32,
+) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success(
+			"Idle policy set",
+			format!(
+				"Servitor server {server_name} will be stopped after {minutes} minutes without {}",
+				describe_probe(probe)
+			),
+		),
+		Err(e) => match e {
+			SetIdlePolicyError::Server(_) => embeds::invalid_servitor_server(server_name),
+			SetIdlePolicyError::NoQuery { .. } => embeds::error(
+				"No game query",
+				format!("Servitor server {server_name} has no game query to count players with, set one with `/servitor query set` first"),
+			),
+		},
+	}
+}
+
+pub fn remove_idle_policy_embed(result: Result<(), IdlePolicyError>, server_name: &str) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success(
+			"Idle policy removed",
+			format!("Servitor server {server_name} will no longer be stopped when idle"),
+		),
+		Err(e) => match e {
+			IdlePolicyError::Server(_) => embeds::invalid_servitor_server(server_name),
+			IdlePolicyError::NotConfigured { .. } => not_configured(server_name),
+		},
+	}
+}
+
+pub fn keep_alive_embed(result: Result<DateTime<Utc>, KeepAliveError>, server_name: &str, duration: &str) -> CreateEmbed {
+	match result {
+		Ok(until) => embeds::success(
+			"Keeping server alive",
+			format!(
+				"Servitor server {server_name} will not be stopped for being idle until <t:{}:f>",
+				until.timestamp()
+			),
+		),
+		Err(e) => match e {
+			KeepAliveError::Server(_) => embeds::invalid_servitor_server(server_name),
+			KeepAliveError::NotConfigured { .. } => not_configured(server_name),
+			KeepAliveError::Unauthorized { .. } => embeds::error(
+				"Unauthorized",
+				format!("You are not authorized to keep the Servitor server {server_name} alive"),
+			),
+			KeepAliveError::TooLong { .. } => embeds::invalid_duration(duration),
+		},
+	}
+}
+
+pub fn idle_shutdown_embed(shutdown: &IdleShutdown) -> CreateEmbed {
+	let server_name = &shutdown.server_name;
+	let idle_since = shutdown.idle_since.timestamp();
+
+	match &shutdown.result {
+		Ok(_) => embeds::info(
+			"Idle server stopped",
+			format!("Servitor server {server_name} was idle since <t:{idle_since}:f> and has been stopped"),
+		),
+		Err(e) => embeds::error(
+			"Failed to stop idle server",
+			format!("Servitor server {server_name} was idle since <t:{idle_since}:f> but stopping it failed: {e}"),
+		),
+	}
+}
+
+fn not_configured(server_name: &str) -> CreateEmbed {
+	embeds::error(
+		"No idle policy",
+		format!("Servitor server {server_name} has no idle policy"),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::services::servitor::ServitorError;
+	use chrono::{TimeDelta, TimeZone};
+	use serenity::all::{ChannelId, Colour, UserId};
+
+	#[test]
+	fn given_successful_set_idle_policy_then_reply_with_probe_description() {
+		let embed = set_idle_policy_embed(Ok(()), "SomeServer", &IdleProbe::Connections { port: 25565 }, 30);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Idle policy set")
+			.colour(Colour(0x77b255))
+			.description("Servitor server SomeServer will be stopped after 30 minutes without connections on port 25565");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_set_query_probe_without_query_then_reply_with_no_game_query() {
+		let result = Err(SetIdlePolicyError::NoQuery {
+			server_name: "SomeServer".to_string(),
+		});
+
+		let embed = set_idle_policy_embed(result, "SomeServer", &IdleProbe::Query, 30);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: No game query")
+			.colour(Colour(0xdd2e44))
+			.description(
+				"Servitor server SomeServer has no game query to count players with, set one with `/servitor query set` first",
+			);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_remove_without_policy_then_reply_with_no_idle_policy() {
+		let result = Err(IdlePolicyError::NotConfigured {
+			server_name: "SomeServer".to_string(),
+		});
+
+		let embed = remove_idle_policy_embed(result, "SomeServer");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: No idle policy")
+			.colour(Colour(0xdd2e44))
+			.description("Servitor server SomeServer has no idle policy");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_successful_keep_alive_then_reply_with_deadline() {
+		let embed = keep_alive_embed(Ok(Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap()), "SomeServer", "2h");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Keeping server alive")
+			.colour(Colour(0x77b255))
+			.description("Servitor server SomeServer will not be stopped for being idle until <t:1741291185:f>");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_unauthorized_keep_alive_then_reply_with_unauthorized() {
+		let result = Err(KeepAliveError::Unauthorized {
+			user: UserId::new(12345678901234567),
+			server_name: "SomeServer".to_string(),
+		});
+
+		let embed = keep_alive_embed(result, "SomeServer", "2h");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Unauthorized")
+			.colour(Colour(0xdd2e44))
+			.description("You are not authorized to keep the Servitor server SomeServer alive");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_too_long_keep_alive_then_reply_with_invalid_duration() {
+		let result = Err(KeepAliveError::TooLong {
+			server_name: "SomeServer".to_string(),
+			duration: TimeDelta::days(100_000_000),
+		});
+
+		let embed = keep_alive_embed(result, "SomeServer", "100000000d");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Invalid duration")
+			.colour(Colour(0xdd2e44))
+			.description("`100000000d` is not a duration, use something like `90m`, `2h` or `1d12h`");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_idle_shutdown_then_notice_says_server_was_stopped() {
+		let shutdown = IdleShutdown {
+			server_name: "SomeServer".to_string(),
+			channel: ChannelId::new(1),
+			idle_since: Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap(),
+			result: Ok(()),
+		};
+
+		let embed = idle_shutdown_embed(&shutdown);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Idle server stopped")
+			.colour(Colour(0x55acee))
+			.description("Servitor server SomeServer was idle since <t:1741291185:f> and has been stopped");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_failed_idle_shutdown_then_notice_reports_error() {
+		let shutdown = IdleShutdown {
+			server_name: "SomeServer".to_string(),
+			channel: ChannelId::new(1),
+			idle_since: Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap(),
+			result: Err(ServitorError::InternalServerError),
+		};
+
+		let embed = idle_shutdown_embed(&shutdown);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Failed to stop idle server")
+			.colour(Colour(0xdd2e44))
+			.description(
+				"Servitor server SomeServer was idle since <t:1741291185:f> but stopping it failed: \
+				The Servitor instance encountered an unexpected issue",
+			);
+
+		assert_eq!(embed, expected_embed);
+	}
+}
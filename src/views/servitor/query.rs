@@ -0,0 +1,73 @@
+use super::describe_query;
+use crate::controllers::servitor::query::QueryConfigError;
+use crate::controllers::servitor::ServerError;
+use crate::data::servitor::QueryConfig;
+use crate::embeds;
+use serenity::all::CreateEmbed;
+
+pub fn set_query_embed(result: Result<(), ServerError>, server_name: &str, query: &QueryConfig) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success(
+			"Game query set",
+			format!(
+				"Status of Servitor server {server_name} will include the game queried via {}",
+				describe_query(Some(query))
+			),
+		),
+		Err(_) => embeds::invalid_servitor_server(server_name),
+	}
+}
+
+pub fn remove_query_embed(result: Result<(), QueryConfigError>, server_name: &str) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success(
+			"Game query removed",
+			format!("Status of Servitor server {server_name} will no longer query the game"),
+		),
+		Err(e) => match e {
+			QueryConfigError::Server(_) => embeds::invalid_servitor_server(server_name),
+			QueryConfigError::NotConfigured { .. } => embeds::error(
+				"No game query",
+				format!("Servitor server {server_name} has no game query"),
+			),
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serenity::all::Colour;
+
+	#[test]
+	fn given_successful_set_query_then_reply_with_protocol_and_address() {
+		let query = QueryConfig::A2s {
+			address: "localhost:27015".to_string(),
+		};
+
+		let embed = set_query_embed(Ok(()), "SomeServer", &query);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Game query set")
+			.colour(Colour(0x77b255))
+			.description("Status of Servitor server SomeServer will include the game queried via A2S at `localhost:27015`");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_remove_without_query_then_reply_with_no_game_query() {
+		let result = Err(QueryConfigError::NotConfigured {
+			server_name: "SomeServer".to_string(),
+		});
+
+		let embed = remove_query_embed(result, "SomeServer");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: No game query")
+			.colour(Colour(0xdd2e44))
+			.description("Servitor server SomeServer has no game query");
+
+		assert_eq!(embed, expected_embed);
+	}
+}
@@ -0,0 +1,274 @@
+use super::super::pagination::numbered_page;
+use super::action;
+use crate::controllers::servitor::action::ExecuteServitorActionError;
+use crate::controllers::servitor::schedule::{CancelScheduleError, ScheduleError};
+use crate::data::servitor::{SchedulableAction, Schedule, ScheduledAction, ServitorAction, ServitorSchedules};
+use crate::embeds;
+use serenity::all::{CreateEmbed, CreateEmbedFooter};
+
+const SCHEDULES_PER_PAGE: usize = 10;
+
+pub fn schedule_embed(result: Result<(u64, ScheduledAction), ScheduleError>, server_name: &str) -> CreateEmbed {
+	match result {
+		Ok((id, scheduled)) => embeds::success(
+			"Scheduled action",
+			format!(
+				"Scheduled `{}` on Servitor server {server_name} as #{id}, {}",
+				ServitorAction::from(scheduled.action),
+				describe_schedule(&scheduled)
+			),
+		),
+		Err(e) => match e {
+			ScheduleError::Server(_) => embeds::invalid_servitor_server(server_name),
+			ScheduleError::Unauthorized { action, .. } => embeds::error(
+				"Unauthorized",
+				format!("You are not authorized to run `{action}` on the Servitor server {server_name}"),
+			),
+			ScheduleError::InvalidTimezone { timezone } => embeds::error(
+				"Invalid timezone",
+				format!("`{timezone}` is not a known timezone, use names like `UTC` or `Europe/Berlin`"),
+			),
+			ScheduleError::InvalidWhen { when } => embeds::error(
+				"Invalid schedule",
+				format!(
+					"`{when}` is neither a date like `2025-03-07 04:00` nor a cron expression like `0 4 * * *`"
+				),
+			),
+			ScheduleError::InPast { at } => embeds::error(
+				"Invalid schedule",
+				format!("<t:{}:f> is in the past", at.timestamp()),
+			),
+			ScheduleError::NeverFires { expression } => embeds::error(
+				"Invalid schedule",
+				format!("The cron expression `{expression}` never fires"),
+			),
+		},
+	}
+}
+
+/// The scheduled actions split into pages, which is a single page saying so when there are none.
+pub fn list_schedules_pages(schedules: &ServitorSchedules) -> Vec<CreateEmbed> {
+	if schedules.is_empty() {
+		return vec![embeds::info("Scheduled actions", "There are no scheduled actions")];
+	}
+
+	let lines = schedules
+		.iter()
+		.map(|(id, scheduled)| {
+			format!(
+				"- #{id}: `{}` on {} by <@{}>, {}",
+				ServitorAction::from(scheduled.action),
+				scheduled.server_name,
+				scheduled.user,
+				describe_schedule(scheduled)
+			)
+		})
+		.collect::<Vec<_>>();
+
+	let pages = lines.len().div_ceil(SCHEDULES_PER_PAGE);
+	lines
+		.chunks(SCHEDULES_PER_PAGE)
+		.enumerate()
+		.map(|(page, chunk)| numbered_page(embeds::info("Scheduled actions", chunk.join("\n")), page, pages))
+		.collect()
+}
+
+pub fn cancel_schedule_embed(result: Result<ScheduledAction, CancelScheduleError>, id: u64) -> CreateEmbed {
+	match result {
+		Ok(scheduled) => embeds::success(
+			"Cancelled scheduled action",
+			format!(
+				"Scheduled `{}` #{id} on Servitor server {} will no longer run",
+				ServitorAction::from(scheduled.action),
+				scheduled.server_name
+			),
+		),
+		Err(e) => match e {
+			CancelScheduleError::DoesNotExist { .. } => embeds::error(
+				"Invalid scheduled action",
+				format!("No scheduled action #{id} exists"),
+			),
+			CancelScheduleError::Unauthorized { .. } => embeds::error(
+				"Unauthorized",
+				format!("You are not allowed to cancel scheduled action #{id}"),
+			),
+		},
+	}
+}
+
+pub fn scheduled_run_embed(
+	result: Result<(), ExecuteServitorActionError>,
+	id: u64,
+	scheduled: &ScheduledAction,
+) -> CreateEmbed {
+	let server_name = &scheduled.server_name;
+	let embed = match scheduled.action {
+		SchedulableAction::Start => action::start_embed(result, server_name),
+		SchedulableAction::Stop => action::stop_embed(result, server_name),
+		SchedulableAction::Restart => action::restart_embed(result, server_name),
+		SchedulableAction::Reload => action::reload_embed(result, server_name),
+	};
+
+	embed.footer(CreateEmbedFooter::new(format!("Scheduled action #{id}")))
+}
+
+fn describe_schedule(scheduled: &ScheduledAction) -> String {
+	let next_run = scheduled.next_run.timestamp();
+	match &scheduled.schedule {
+		Schedule::Once => format!("running once <t:{next_run}:f>"),
+		Schedule::Cron { expression, timezone } => {
+			format!("repeating on `{expression}` ({timezone}), next <t:{next_run}:f>")
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::{TimeZone, Utc};
+	use serenity::all::{ChannelId, Colour, UserId};
+	use std::collections::BTreeMap;
+
+	fn scheduled(schedule: Schedule) -> ScheduledAction {
+		ScheduledAction {
+			server_name: "SomeServer".to_string(),
+			action: SchedulableAction::Restart,
+			user: UserId::new(12345678901234567),
+			guild: None,
+			channel: ChannelId::new(1),
+			schedule,
+			next_run: Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap(),
+		}
+	}
+
+	fn cron() -> Schedule {
+		Schedule::Cron {
+			expression: "0 4 * * *".to_string(),
+			timezone: "Europe/Berlin".to_string(),
+		}
+	}
+
+	#[test]
+	fn given_successful_recurring_schedule_then_reply_with_next_run() {
+		let embed = schedule_embed(Ok((3, scheduled(cron()))), "SomeServer");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Scheduled action")
+			.colour(Colour(0x77b255))
+			.description(
+				"Scheduled `restart` on Servitor server SomeServer as #3, \
+				repeating on `0 4 * * *` (Europe/Berlin), next <t:1741291185:f>",
+			);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_schedule_in_past_then_reply_with_invalid_schedule() {
+		let result = Err(ScheduleError::InPast {
+			at: Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap(),
+		});
+
+		let embed = schedule_embed(result, "SomeServer");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Invalid schedule")
+			.colour(Colour(0xdd2e44))
+			.description("<t:1741291185:f> is in the past");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_schedule_unauthorized_then_reply_with_unauthorized() {
+		let result = Err(ScheduleError::Unauthorized {
+			user: UserId::new(12345678901234567),
+			server_name: "SomeServer".to_string(),
+			action: ServitorAction::Stop,
+		});
+
+		let embed = schedule_embed(result, "SomeServer");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Unauthorized")
+			.colour(Colour(0xdd2e44))
+			.description("You are not authorized to run `stop` on the Servitor server SomeServer");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_schedules_then_list_describes_each() {
+		let schedules = BTreeMap::from([(1, scheduled(Schedule::Once)), (2, scheduled(cron()))]);
+
+		let pages = list_schedules_pages(&schedules);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Scheduled actions")
+			.colour(Colour(0x55acee))
+			.description(
+				"- #1: `restart` on SomeServer by <@12345678901234567>, running once <t:1741291185:f>\n\
+				- #2: `restart` on SomeServer by <@12345678901234567>, \
+				repeating on `0 4 * * *` (Europe/Berlin), next <t:1741291185:f>",
+			);
+
+		assert_eq!(pages, vec![expected_embed]);
+	}
+
+	#[test]
+	fn given_many_schedules_then_list_splits_them_in_numbered_pages() {
+		let schedules = (1..=25).map(|id| (id, scheduled(Schedule::Once))).collect();
+
+		let pages = list_schedules_pages(&schedules);
+
+		assert_eq!(pages.len(), 3);
+		let last_page = CreateEmbed::default()
+			.title(":information_source: Scheduled actions")
+			.colour(Colour(0x55acee))
+			.description(
+				(21..=25)
+					.map(|id| format!("- #{id}: `restart` on SomeServer by <@12345678901234567>, running once <t:1741291185:f>"))
+					.collect::<Vec<_>>()
+					.join("\n"),
+			)
+			.footer(CreateEmbedFooter::new("Page 3/3"));
+		assert_eq!(pages[2], last_page);
+	}
+
+	#[test]
+	fn given_no_schedules_then_list_says_so() {
+		let pages = list_schedules_pages(&BTreeMap::new());
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Scheduled actions")
+			.colour(Colour(0x55acee))
+			.description("There are no scheduled actions");
+
+		assert_eq!(pages, vec![expected_embed]);
+	}
+
+	#[test]
+	fn given_cancel_of_missing_schedule_then_reply_with_invalid_scheduled_action() {
+		let embed = cancel_schedule_embed(Err(CancelScheduleError::DoesNotExist { id: 7 }), 7);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Invalid scheduled action")
+			.colour(Colour(0xdd2e44))
+			.description("No scheduled action #7 exists");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_scheduled_run_then_reply_with_action_result_and_footer() {
+		let embed = scheduled_run_embed(Ok(()), 2, &scheduled(cron()));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Servitor server restart")
+			.colour(Colour(0x77b255))
+			.description("Ran action restart for Servitor server SomeServer")
+			.footer(CreateEmbedFooter::new("Scheduled action #2"));
+
+		assert_eq!(embed, expected_embed);
+	}
+}
@@ -1,9 +1,12 @@
 use crate::controllers::servitor::server::{AddServerError, RemoveServerError};
 use crate::controllers::servitor::ServerError;
-use crate::data::servitor::{ServerInfo, ServitorData};
+use super::super::{format_denials, format_expiring, format_list, format_scope};
+use super::{describe_idle_policy, describe_query, format_actions};
+use crate::data::servitor::{ActionExpiries, ActionGrants, ServerInfo};
 use crate::embeds;
-use crate::views::format_list;
 use serenity::builder::CreateEmbed;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
 
 pub fn add_server_embed(
 	result: Result<(), AddServerError>,
@@ -18,7 +21,8 @@ pub fn add_server_embed(
 			.field("Unit Name", unit_name, true),
 		Err(e) => match e {
 			AddServerError::InvalidServitor { .. } => embeds::error("Invalid Servitor", format!("There is no such servitor instance with name {servitor}")),
-			AddServerError::Server(_) => embeds::error("Duplicate name", format!("A servitor server with name {server_name} already exists, try a different name"))
+			AddServerError::Server(_) => embeds::error("Duplicate name", format!("A servitor server with name {server_name} already exists, try a different name")),
+			AddServerError::NameTooLong { .. } => embeds::name_too_long(server_name),
 		}
 	}
 }
@@ -37,26 +41,35 @@ pub fn remove_server_embed(
 	}
 }
 
-pub fn list_servers_embed(servitor_data: &ServitorData) -> CreateEmbed {
-	let description = if servitor_data.is_empty() {
-		"There are no servitor servers configured".to_string()
-	} else {
-		let server_list = servitor_data
-			.iter()
-			.map(|(name, info)| format!("- {}: {} - `{}`", name, info.servitor, info.unit_name))
-			.collect::<Vec<String>>()
-			.join("\n");
-		format!("Configured servers:\n{server_list}")
-	};
+pub fn confirm_remove_server_embed(server_name: &str) -> CreateEmbed {
+	embeds::info(
+		"Confirm removal",
+		format!("Are you sure you want to remove the Servitor server {server_name}?"),
+	)
+}
 
-	embeds::info("Servitor server list", description)
+pub fn set_confirmation_embed(result: Result<(), ServerError>, server_name: &str, enabled: bool) -> CreateEmbed {
+	match result {
+		Ok(_) if enabled => embeds::success(
+			"Confirmation enabled",
+			format!("Destructive actions on Servitor server {server_name} will ask for confirmation"),
+		),
+		Ok(_) => embeds::success(
+			"Confirmation disabled",
+			format!("Destructive actions on Servitor server {server_name} will run without confirmation"),
+		),
+		Err(_) => embeds::invalid_servitor_server(server_name),
+	}
 }
 
 pub fn describe_server_embed(result: Result<&ServerInfo, ServerError>, name: &str) -> CreateEmbed {
 	match result {
 		Ok(server_info) => {
-			let users = format_list(&server_info.authorized_users, |id| format!("<@{id}>"));
-			let roles = format_list(&server_info.authorized_roles, |id| format!("<@&{id}>"));
+			let users = format_grants(&server_info.authorized_users, &server_info.user_expiries, |id| format!("<@{id}>"));
+			let roles = format_grants(&server_info.authorized_roles, &server_info.role_expiries, |id| format!("<@&{id}>"));
+			let groups = format_grants(&server_info.authorized_groups, &ActionExpiries::default(), |name| format!("`{name}`"));
+			let denied = format_denials(&server_info.denied);
+			let managers = format_list(&server_info.managers, |id| format!("<@{id}>"));
 
 			embeds::info(
 				format!("Servitor server {name}"),
@@ -64,8 +77,20 @@ pub fn describe_server_embed(result: Result<&ServerInfo, ServerError>, name: &st
 					"- Servitor: {}\n\
 - Unit Name: `{}`\n\
 - Authorized Users: {users}\n\
-- Authorized Roles: {roles}",
-					server_info.servitor, server_info.unit_name
+- Authorized Roles: {roles}\n\
+- Authorized Groups: {groups}\n\
+- Denied: {denied}\n\
+- Managers: {managers}\n\
+- Confirm destructive actions: {}\n\
+- Idle shutdown: {}\n\
+- Game query: {}\n\
+- Scope: {}",
+					server_info.servitor,
+					server_info.unit_name,
+					if server_info.skip_confirmation { "No" } else { "Yes" },
+					describe_idle_policy(server_info.idle.as_ref()),
+					describe_query(server_info.query.as_ref()),
+					format_scope(server_info.scope)
 				),
 			)
 		}
@@ -73,13 +98,44 @@ pub fn describe_server_embed(result: Result<&ServerInfo, ServerError>, name: &st
 	}
 }
 
+/// Lists the actions of every entity, those expiring at different times apart.
+fn format_grants<T: Display + Ord, F: Fn(&T) -> String>(
+	grants: &ActionGrants<T>,
+	expiries: &ActionExpiries<T>,
+	formatter: F,
+) -> String {
+	if grants.is_empty() {
+		"None".to_string()
+	} else {
+		grants
+			.iter()
+			.flat_map(|(id, actions)| {
+				let mut by_expiry = BTreeMap::<_, BTreeSet<_>>::new();
+				for action in actions {
+					let expiry = expiries.get(id).and_then(|expiries| expiries.get(action));
+					by_expiry.entry(expiry).or_default().insert(*action);
+				}
+				by_expiry
+					.into_iter()
+					.map(|(expiry, actions)| format_expiring(format!("{} ({})", formatter(id), format_actions(&actions)), expiry))
+					.collect::<Vec<_>>()
+			})
+			.collect::<Vec<_>>()
+			.join(", ")
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::controllers::servitor::ServerError;
-	use crate::data::servitor::ServerInfo;
-	use serenity::all::{Colour, RoleId, UserId};
-	use std::collections::BTreeSet;
+	use crate::data::authorization::Denials;
+	use crate::data::scope::Scope;
+	use crate::views::authorization::list_embed;
+	use crate::data::servitor::{IdlePolicy, IdleProbe, QueryConfig, ServerInfo, ServitorAction, ServitorData};
+	use chrono::{TimeZone, Utc};
+	use serenity::all::{ChannelId, Colour, GuildId, RoleId, UserId};
+	use std::collections::{BTreeMap, BTreeSet};
 
 	#[test]
 	fn given_add_server_error_with_invalid_servitor_then_reply_with_invalid_servitor() {
@@ -115,6 +171,22 @@ mod tests {
 		assert_eq!(embed, expected_embed);
 	}
 
+	#[test]
+	fn given_add_server_error_with_too_long_name_then_reply_with_error_name_too_long() {
+		let result = Err(AddServerError::NameTooLong {
+			name: "SomeServer".to_string(),
+		});
+
+		let embed = add_server_embed(result, "SomeServer", "foo", "bar");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Name too long")
+			.colour(Colour(0xdd2e44))
+			.description("SomeServer is longer than 24 characters, try a shorter name");
+
+		assert_eq!(embed, expected_embed);
+	}
+
 	#[test]
 	fn given_successful_add_server_then_reply_with_success_info() {
 		let embed = add_server_embed(Ok(()), "SomeServer", "foo", "bar");
@@ -160,19 +232,19 @@ mod tests {
 	}
 
 	#[test]
-	fn given_no_servers_then_list_servers_replies_with_empty_response() {
-		let embed = list_servers_embed(&ServitorData::new());
+	fn given_no_servers_then_list_embed_replies_with_empty_response() {
+		let embed = list_embed(&ServitorData::new());
 
 		let expected_embed = CreateEmbed::default()
 			.title(":information_source: Servitor server list")
 			.colour(Colour(0x55acee))
-			.description("There are no servitor servers configured");
+			.description("There are no Servitor servers configured");
 
 		assert_eq!(embed, expected_embed);
 	}
 
 	#[test]
-	fn given_some_servers_then_list_servers_replies_with_formatted_list() {
+	fn given_some_servers_then_list_embed_replies_with_formatted_list() {
 		let data = ServitorData::from([
 			(
 				"ServerOne".to_string(),
@@ -181,6 +253,16 @@ mod tests {
 					unit_name: "unit_one.service".to_string(),
 					authorized_users: Default::default(),
 					authorized_roles: Default::default(),
+					watch_channels: Default::default(),
+					skip_confirmation: false,
+					query: None,
+					idle: None,
+					scope: Default::default(),
+					managers: Default::default(),
+					user_expiries: Default::default(),
+					role_expiries: Default::default(),
+					authorized_groups: Default::default(),
+					denied: Default::default(),
 				},
 			),
 			(
@@ -190,6 +272,16 @@ mod tests {
 					unit_name: "unit_two.service".to_string(),
 					authorized_users: Default::default(),
 					authorized_roles: Default::default(),
+					watch_channels: Default::default(),
+					skip_confirmation: false,
+					query: None,
+					idle: None,
+					scope: Default::default(),
+					managers: Default::default(),
+					user_expiries: Default::default(),
+					role_expiries: Default::default(),
+					authorized_groups: Default::default(),
+					denied: Default::default(),
 				},
 			),
 			(
@@ -199,18 +291,28 @@ mod tests {
 					unit_name: "unit_three.service".to_string(),
 					authorized_users: Default::default(),
 					authorized_roles: Default::default(),
+					watch_channels: Default::default(),
+					skip_confirmation: false,
+					query: None,
+					idle: None,
+					scope: Default::default(),
+					managers: Default::default(),
+					user_expiries: Default::default(),
+					role_expiries: Default::default(),
+					authorized_groups: Default::default(),
+					denied: Default::default(),
 				},
 			),
 		]);
 
-		let embed = list_servers_embed(&data);
+		let embed = list_embed(&data);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":information_source: Servitor server list")
 			.colour(Colour(0x55acee))
 			// Order is not important here, so we adjust the test to match output order based on data
 			.description(
-				"Configured servers:\n\
+				"Configured Servitor servers:\n\
 - ServerOne: ServitorOne - `unit_one.service`\n\
 - ServerThree: ServitorThree - `unit_three.service`\n\
 - ServerTwo: ServitorTwo - `unit_two.service`",
@@ -242,6 +344,16 @@ mod tests {
 			unit_name: "bar".to_string(),
 			authorized_users: Default::default(),
 			authorized_roles: Default::default(),
+			watch_channels: Default::default(),
+			skip_confirmation: false,
+			query: None,
+			idle: None,
+			scope: Default::default(),
+			managers: Default::default(),
+			user_expiries: Default::default(),
+			role_expiries: Default::default(),
+			authorized_groups: Default::default(),
+			denied: Default::default(),
 		};
 
 		let embed = describe_server_embed(Ok(&server_info), "SomeServer");
@@ -254,7 +366,14 @@ mod tests {
 - Servitor: foo\n\
 - Unit Name: `bar`\n\
 - Authorized Users: None\n\
-- Authorized Roles: None",
+- Authorized Roles: None\n\
+- Authorized Groups: None\n\
+- Denied: None\n\
+- Managers: None\n\
+- Confirm destructive actions: Yes\n\
+- Idle shutdown: None\n\
+- Game query: None\n\
+- Scope: Global",
 			);
 
 		assert_eq!(embed, expected_embed);
@@ -265,14 +384,43 @@ mod tests {
 		let server_info = ServerInfo {
 			servitor: "foo".to_string(),
 			unit_name: "bar".to_string(),
-			authorized_users: BTreeSet::from([
-				UserId::new(12345678901234567),
-				UserId::new(12345678901234568),
+			authorized_users: BTreeMap::from([
+				(UserId::new(12345678901234567), ServitorAction::all()),
+				(
+					UserId::new(12345678901234568),
+					BTreeSet::from([ServitorAction::Status, ServitorAction::Logs]),
+				),
 			]),
-			authorized_roles: BTreeSet::from([
-				RoleId::new(98765432109876543),
-				RoleId::new(98765432109876544),
+			authorized_roles: BTreeMap::from([
+				(RoleId::new(98765432109876543), ServitorAction::all()),
+				(RoleId::new(98765432109876544), BTreeSet::from([ServitorAction::Start])),
 			]),
+			watch_channels: Default::default(),
+			skip_confirmation: true,
+			query: Some(QueryConfig::Minecraft {
+				address: "mc.example.com".to_string(),
+			}),
+			idle: Some(IdlePolicy {
+				probe: IdleProbe::Connections { port: 25565 },
+				minutes: 30,
+				channel: ChannelId::new(1),
+				keep_alive_until: Some(Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap()),
+			}),
+			scope: Scope::Guild(GuildId::new(1)),
+			managers: BTreeSet::from([UserId::new(11111111111111111)]),
+			user_expiries: ActionExpiries::from([(
+				UserId::new(12345678901234568),
+				BTreeMap::from([(ServitorAction::Logs, Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap())]),
+			)]),
+			role_expiries: ActionExpiries::from([(
+				RoleId::new(98765432109876544),
+				BTreeMap::from([(ServitorAction::Start, Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap())]),
+			)]),
+			authorized_groups: BTreeMap::from([("Friends".to_string(), BTreeSet::from([ServitorAction::Status]))]),
+			denied: Denials {
+				users: BTreeMap::from([(UserId::new(22222222222222222), Some("Griefing".to_string()))]),
+				roles: Default::default(),
+			},
 		};
 
 		let embed = describe_server_embed(Ok(&server_info), "SomeServer");
@@ -284,10 +432,45 @@ mod tests {
 				"\
 - Servitor: foo\n\
 - Unit Name: `bar`\n\
-- Authorized Users: <@12345678901234567>, <@12345678901234568>\n\
-- Authorized Roles: <@&98765432109876543>, <@&98765432109876544>",
+- Authorized Users: <@12345678901234567> (all actions), <@12345678901234568> (`status`), <@12345678901234568> (`logs`) until <t:1741291185:f>\n\
+- Authorized Roles: <@&98765432109876543> (all actions), <@&98765432109876544> (`start`) until <t:1741291185:f>\n\
+- Authorized Groups: `Friends` (`status`)\n\
+- Denied: <@22222222222222222> (Griefing)\n\
+- Managers: <@11111111111111111>\n\
+- Confirm destructive actions: No\n\
+- Idle shutdown: After 30 minutes without connections on port 25565, kept alive until <t:1741291185:f>\n\
+- Game query: Minecraft at `mc.example.com`\n\
+- Scope: Guild `1`",
 			);
 
 		assert_eq!(embed, expected_embed);
 	}
+
+	#[test]
+	fn given_set_confirmation_with_non_existing_server_then_reply_with_invalid_server() {
+		let result = Err(ServerError::DoesNotExist {
+			server_name: "NonExistingServer".to_string(),
+		});
+
+		let embed = set_confirmation_embed(result, "NonExistingServer", true);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Invalid Servitor server")
+			.colour(Colour(0xdd2e44))
+			.description("No Servitor server with name NonExistingServer exists");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_disabled_confirmation_then_reply_with_success_info() {
+		let embed = set_confirmation_embed(Ok(()), "SomeServer", false);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Confirmation disabled")
+			.colour(Colour(0x77b255))
+			.description("Destructive actions on Servitor server SomeServer will run without confirmation");
+
+		assert_eq!(embed, expected_embed);
+	}
 }
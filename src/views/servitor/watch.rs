@@ -0,0 +1,217 @@
+use crate::controllers::servitor::watch::{UnitStateChange, UnwatchError, WatchError};
+use crate::embeds;
+use serenity::all::{ChannelId, CreateEmbed, CreateEmbedFooter};
+
+pub fn watch_embed(result: Result<(), WatchError>, server_name: &str, channel: ChannelId) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success(
+			"Watching server",
+			format!("State changes of Servitor server {server_name} will be posted to <#{channel}>"),
+		),
+		Err(e) => match e {
+			WatchError::Server(_) => embeds::invalid_servitor_server(server_name),
+			WatchError::Unauthorized { .. } => embeds::error(
+				"Unauthorized",
+				format!("You are not authorized to check the status of the Servitor server {server_name}"),
+			),
+			WatchError::AlreadyWatching { .. } => embeds::error(
+				"Already watching",
+				format!("Channel <#{channel}> is already watching Servitor server {server_name}"),
+			),
+		},
+	}
+}
+
+pub fn unwatch_embed(result: Result<(), UnwatchError>, server_name: &str, channel: ChannelId) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success(
+			"Stopped watching server",
+			format!("State changes of Servitor server {server_name} will no longer be posted to <#{channel}>"),
+		),
+		Err(e) => match e {
+			UnwatchError::Server(_) => embeds::invalid_servitor_server(server_name),
+			UnwatchError::Unauthorized { .. } => embeds::error(
+				"Unauthorized",
+				format!("You are not authorized to check the status of the Servitor server {server_name}"),
+			),
+			UnwatchError::NotWatching { .. } => embeds::error(
+				"Not watching",
+				format!("Channel <#{channel}> is not watching Servitor server {server_name}"),
+			),
+		},
+	}
+}
+
+pub fn state_change_embed(change: &UnitStateChange, server_name: &str) -> CreateEmbed {
+	let previous = &change.previous;
+	let current = &change.current;
+	let title = "Servitor server state changed";
+	let description = format!(
+		"Servitor server {server_name} went from `{}` (`{}`) to `{}` (`{}`)",
+		previous.state, previous.sub_state, current.state, current.sub_state
+	);
+
+	if current.state == "failed" {
+		embeds::error(title, description)
+	} else {
+		embeds::info(title, description)
+	}
+	.field("State", &current.state, true)
+	.field("SubState", &current.sub_state, true)
+	.footer(CreateEmbedFooter::new("Since:"))
+	.timestamp(current.since)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::controllers::servitor::ServerError;
+	use crate::services::servitor::UnitStatus;
+	use chrono::{TimeZone, Utc};
+	use serenity::all::{Colour, UserId};
+	use std::collections::BTreeMap;
+
+	fn unit_status(state: &str, sub_state: &str) -> UnitStatus {
+		UnitStatus {
+			service: "bar.service".to_string(),
+			state: state.to_string(),
+			sub_state: sub_state.to_string(),
+			since: Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap(),
+		}
+	}
+
+	#[test]
+	fn given_watch_with_non_existing_server_then_reply_with_invalid_server() {
+		let result = Err(WatchError::Server(ServerError::DoesNotExist {
+			server_name: "NonExistingServer".to_string(),
+		}));
+
+		let embed = watch_embed(result, "NonExistingServer", ChannelId::new(1));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Invalid Servitor server")
+			.colour(Colour(0xdd2e44))
+			.description("No Servitor server with name NonExistingServer exists");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_watch_with_unauthorized_then_reply_with_unauthorized() {
+		let result = Err(WatchError::Unauthorized {
+			user: UserId::new(12345678901234567),
+			server_name: "SomeServer".to_string(),
+		});
+
+		let embed = watch_embed(result, "SomeServer", ChannelId::new(1));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Unauthorized")
+			.colour(Colour(0xdd2e44))
+			.description("You are not authorized to check the status of the Servitor server SomeServer");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_watch_with_already_watching_then_reply_with_already_watching() {
+		let result = Err(WatchError::AlreadyWatching {
+			server_name: "SomeServer".to_string(),
+			channel: ChannelId::new(1),
+		});
+
+		let embed = watch_embed(result, "SomeServer", ChannelId::new(1));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Already watching")
+			.colour(Colour(0xdd2e44))
+			.description("Channel <#1> is already watching Servitor server SomeServer");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_successful_watch_then_reply_with_success_info() {
+		let embed = watch_embed(Ok(()), "SomeServer", ChannelId::new(1));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Watching server")
+			.colour(Colour(0x77b255))
+			.description("State changes of Servitor server SomeServer will be posted to <#1>");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_unwatch_with_not_watching_then_reply_with_not_watching() {
+		let result = Err(UnwatchError::NotWatching {
+			server_name: "SomeServer".to_string(),
+			channel: ChannelId::new(1),
+		});
+
+		let embed = unwatch_embed(result, "SomeServer", ChannelId::new(1));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Not watching")
+			.colour(Colour(0xdd2e44))
+			.description("Channel <#1> is not watching Servitor server SomeServer");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_successful_unwatch_then_reply_with_success_info() {
+		let embed = unwatch_embed(Ok(()), "SomeServer", ChannelId::new(1));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Stopped watching server")
+			.colour(Colour(0x77b255))
+			.description("State changes of Servitor server SomeServer will no longer be posted to <#1>");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_state_change_to_failed_then_reply_with_error_notification() {
+		let change = UnitStateChange {
+			previous: unit_status("active", "running"),
+			current: unit_status("failed", "failed"),
+			watchers: BTreeMap::new(),
+		};
+
+		let embed = state_change_embed(&change, "SomeServer");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Servitor server state changed")
+			.colour(Colour(0xdd2e44))
+			.description("Servitor server SomeServer went from `active` (`running`) to `failed` (`failed`)")
+			.field("State", "failed", true)
+			.field("SubState", "failed", true)
+			.footer(CreateEmbedFooter::new("Since:"))
+			.timestamp(Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap());
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_state_change_to_active_then_reply_with_info_notification() {
+		let change = UnitStateChange {
+			previous: unit_status("activating", "start"),
+			current: unit_status("active", "running"),
+			watchers: BTreeMap::new(),
+		};
+
+		let embed = state_change_embed(&change, "SomeServer");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Servitor server state changed")
+			.colour(Colour(0x55acee))
+			.description("Servitor server SomeServer went from `activating` (`start`) to `active` (`running`)")
+			.field("State", "active", true)
+			.field("SubState", "running", true)
+			.footer(CreateEmbedFooter::new("Since:"))
+			.timestamp(Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap());
+
+		assert_eq!(embed, expected_embed);
+	}
+}
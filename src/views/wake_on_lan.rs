@@ -1,3 +1,4 @@
 pub mod authorization;
 pub mod machine;
 pub mod wake;
+pub mod manager;
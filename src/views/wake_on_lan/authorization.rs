@@ -1,112 +1,52 @@
-use crate::controllers::wake_on_lan::authorization::{AddPermissionError, RemovePermissionError};
+use super::super::authorization::AuthorizationView;
+use crate::data::wake_on_lan::WakeOnLanMachineInfo;
 use crate::embeds;
-use serenity::all::{CreateEmbed, RoleId, UserId};
-
-pub fn permit_user_embed(
-	result: Result<(), AddPermissionError>,
-	machine_name: &str,
-	user_id: UserId,
-) -> CreateEmbed {
-	match result {
-		Ok(_) => embeds::success(
-			"User permitted",
-			"Successfully permitted user to wake the machine!",
-		)
-		.field("Machine", machine_name, true)
-		.field("User", format!("<@{user_id}>"), true),
-		Err(e) => match e {
-			AddPermissionError::Machine(_) => embeds::invalid_machine(machine_name),
-			AddPermissionError::AlreadyAuthorized { .. } => embeds::error(
-				"User already permitted",
-				format!("User <@{user_id}> is already permitted to wake machine {machine_name}"),
-			),
-		},
-	}
-}
+use serenity::all::CreateEmbed;
+
+impl AuthorizationView for WakeOnLanMachineInfo {
+	const FIELD: &'static str = "Machine";
 
-pub fn revoke_user_embed(
-	result: Result<(), RemovePermissionError>,
-	machine_name: &str,
-	user_id: UserId,
-) -> CreateEmbed {
-	match result {
-		Ok(_) => embeds::success(
-			"User permission revoked",
-			"Successfully revoked user's permission to wake the machine!",
-		)
-		.field("Machine", machine_name, true)
-		.field("User", format!("<@{user_id}>"), true),
-		Err(e) => match e {
-			RemovePermissionError::Machine(_) => embeds::invalid_machine(machine_name),
-			RemovePermissionError::AlreadyNotAuthorized { .. } => embeds::error(
-				"User not permitted",
-				format!(
-					"User <@{user_id}> is already not permitted to wake machine {machine_name}"
-				),
-			),
-		},
+	fn describe_grant(_: &()) -> String {
+		"wake".to_string()
 	}
-}
 
-pub fn permit_role_embed(
-	result: Result<(), AddPermissionError>,
-	machine_name: &str,
-	role_id: RoleId,
-) -> CreateEmbed {
-	match result {
-		Ok(_) => embeds::success(
-			"Role permitted",
-			"Successfully permitted role to wake the machine!",
-		)
-		.field("Machine", machine_name, true)
-		.field("Role", format!("<@&{role_id}>"), true),
-		Err(e) => match e {
-			AddPermissionError::Machine(_) => embeds::invalid_machine(machine_name),
-			AddPermissionError::AlreadyAuthorized { .. } => embeds::error(
-				"Role already permitted",
-				format!("Role <@&{role_id}> is already permitted to wake machine {machine_name}"),
-			),
-		},
+	fn invalid_resource_embed(name: &str) -> CreateEmbed {
+		embeds::invalid_machine(name)
 	}
-}
 
-pub fn revoke_role_embed(
-	result: Result<(), RemovePermissionError>,
-	machine_name: &str,
-	role_id: RoleId,
-) -> CreateEmbed {
-	match result {
-		Ok(_) => embeds::success(
-			"Role permission revoked",
-			"Successfully revoked role's permission to wake the machine!",
-		)
-		.field("Machine", machine_name, true)
-		.field("Role", format!("<@&{role_id}>"), true),
-		Err(e) => match e {
-			RemovePermissionError::Machine(_) => embeds::invalid_machine(machine_name),
-			RemovePermissionError::AlreadyNotAuthorized { .. } => embeds::error(
-				"Role not permitted",
-				format!(
-					"Role <@&{role_id}> is already not permitted to wake machine {machine_name}"
-				),
-			),
-		},
+	fn list_entry(&self) -> String {
+		format!("`{}`", self.mac)
 	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::controllers::wake_on_lan::MachineError;
-	use serenity::all::Colour;
+	use crate::controllers::authorization::{
+		AddDenialError, AddGroupPermissionError, AddPermissionError, RemoveDenialError, RemoveGroupPermissionError,
+		RemovePermissionError,
+	};
 	use crate::controllers::DiscordEntity;
+	use crate::views::authorization::{
+		deny_embed, lift_denial_embed, permit_embed, permit_group_embed, revoke_embed, revoke_group_embed,
+	};
+	use crate::controllers::wake_on_lan::MachineError;
+	use chrono::{TimeZone, Utc};
+	use serenity::all::{Colour, RoleId, UserId};
+	use crate::controllers::group::GroupError;
 
 	#[test]
 	fn given_permit_user_error_with_nonexistent_machine_then_reply_with_error_no_machine() {
-		let result = Err(AddPermissionError::Machine(MachineError::DoesNotExist {
+		let result = Err(AddPermissionError::Resource(MachineError::DoesNotExist {
 			machine_name: "NonExistentMachine".to_string(),
 		}));
-		let embed = permit_user_embed(result, "NonExistentMachine", UserId::new(12345678901234567));
+		let embed = permit_embed::<WakeOnLanMachineInfo>(
+			result,
+			"NonExistentMachine",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			&(),
+			None,
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: Invalid Machine")
@@ -119,10 +59,17 @@ mod tests {
 	#[test]
 	fn given_permit_user_error_with_already_authorized_then_reply_with_error_already_authorized() {
 		let result = Err(AddPermissionError::AlreadyAuthorized {
-			machine_name: "SomeMachine".to_string(),
+			name: "SomeMachine".to_string(),
 			entity: DiscordEntity::User(UserId::new(12345678901234567)),
+			grant: ()
 		});
-		let embed = permit_user_embed(result, "SomeMachine", UserId::new(12345678901234567));
+		let embed = permit_embed::<WakeOnLanMachineInfo>(
+			result,
+			"SomeMachine",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			&(),
+			None,
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: User already permitted")
@@ -136,7 +83,13 @@ mod tests {
 
 	#[test]
 	fn given_successful_permit_user_then_should_reply_with_success_info() {
-		let embed = permit_user_embed(Ok(()), "SomeMachine", UserId::new(12345678901234567));
+		let embed = permit_embed::<WakeOnLanMachineInfo>(
+			Ok(()),
+			"SomeMachine",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			&(),
+			None,
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":white_check_mark: User permitted")
@@ -148,12 +101,40 @@ mod tests {
 		assert_eq!(embed, expected_embed);
 	}
 
+	#[test]
+	fn given_successful_temporary_permit_user_then_reply_with_expiry() {
+		let expires = Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap();
+
+		let embed = permit_embed::<WakeOnLanMachineInfo>(
+			Ok(()),
+			"SomeMachine",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			&(),
+			Some(expires),
+		);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: User permitted")
+			.colour(Colour(0x77b255))
+			.description("Successfully permitted user to wake the machine!")
+			.field("Machine", "SomeMachine", true)
+			.field("User", "<@12345678901234567>", true)
+			.field("Expires", "<t:1741291185:R>", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
 	#[test]
 	fn given_revoke_user_error_with_nonexistent_machine_then_reply_with_error_no_machine() {
-		let result = Err(RemovePermissionError::Machine(MachineError::DoesNotExist {
+		let result = Err(RemovePermissionError::Resource(MachineError::DoesNotExist {
 			machine_name: "NonExistentMachine".to_string(),
 		}));
-		let embed = revoke_user_embed(result, "NonExistentMachine", UserId::new(12345678901234567));
+		let embed = revoke_embed::<WakeOnLanMachineInfo>(
+			result,
+			"NonExistentMachine",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			&(),
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: Invalid Machine")
@@ -166,10 +147,16 @@ mod tests {
 	#[test]
 	fn given_revoke_user_error_with_already_unauthorized_then_reply_with_already_unauthorized() {
 		let result = Err(RemovePermissionError::AlreadyNotAuthorized {
-			machine_name: "SomeMachine".to_string(),
+			name: "SomeMachine".to_string(),
 			entity: DiscordEntity::User(UserId::new(76543210987654321)),
+			grant: ()
 		});
-		let embed = revoke_user_embed(result, "SomeMachine", UserId::new(76543210987654321));
+		let embed = revoke_embed::<WakeOnLanMachineInfo>(
+			result,
+			"SomeMachine",
+			DiscordEntity::User(UserId::new(76543210987654321)),
+			&(),
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: User not permitted")
@@ -183,7 +170,12 @@ mod tests {
 
 	#[test]
 	fn given_successful_revoke_user_then_should_reply_with_success_info() {
-		let embed = revoke_user_embed(Ok(()), "SomeMachine", UserId::new(12345678901234567));
+		let embed = revoke_embed::<WakeOnLanMachineInfo>(
+			Ok(()),
+			"SomeMachine",
+			DiscordEntity::User(UserId::new(12345678901234567)),
+			&(),
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":white_check_mark: User permission revoked")
@@ -197,10 +189,16 @@ mod tests {
 
 	#[test]
 	fn given_permit_role_error_with_nonexistent_machine_then_reply_with_error_no_machine() {
-		let result = Err(AddPermissionError::Machine(MachineError::DoesNotExist {
+		let result = Err(AddPermissionError::Resource(MachineError::DoesNotExist {
 			machine_name: "NonExistentMachine".to_string(),
 		}));
-		let embed = permit_role_embed(result, "NonExistentMachine", RoleId::new(12345678901234567));
+		let embed = permit_embed::<WakeOnLanMachineInfo>(
+			result,
+			"NonExistentMachine",
+			DiscordEntity::Role(RoleId::new(12345678901234567)),
+			&(),
+			None,
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: Invalid Machine")
@@ -213,10 +211,17 @@ mod tests {
 	#[test]
 	fn given_permit_role_error_with_already_authorized_then_reply_with_error_already_authorized() {
 		let result = Err(AddPermissionError::AlreadyAuthorized {
-			machine_name: "SomeMachine".to_string(),
+			name: "SomeMachine".to_string(),
 			entity: DiscordEntity::Role(RoleId::new(12345678901234567)),
+			grant: ()
 		});
-		let embed = permit_role_embed(result, "SomeMachine", RoleId::new(12345678901234567));
+		let embed = permit_embed::<WakeOnLanMachineInfo>(
+			result,
+			"SomeMachine",
+			DiscordEntity::Role(RoleId::new(12345678901234567)),
+			&(),
+			None,
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: Role already permitted")
@@ -230,7 +235,13 @@ mod tests {
 
 	#[test]
 	fn given_successful_permit_role_then_should_reply_with_success_info() {
-		let embed = permit_role_embed(Ok(()), "SomeMachine", RoleId::new(12345678901234567));
+		let embed = permit_embed::<WakeOnLanMachineInfo>(
+			Ok(()),
+			"SomeMachine",
+			DiscordEntity::Role(RoleId::new(12345678901234567)),
+			&(),
+			None,
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":white_check_mark: Role permitted")
@@ -244,10 +255,15 @@ mod tests {
 
 	#[test]
 	fn given_revoke_role_error_with_nonexistent_machine_then_reply_with_error_no_machine() {
-		let result = Err(RemovePermissionError::Machine(MachineError::DoesNotExist {
+		let result = Err(RemovePermissionError::Resource(MachineError::DoesNotExist {
 			machine_name: "NonExistentMachine".to_string(),
 		}));
-		let embed = revoke_role_embed(result, "NonExistentMachine", RoleId::new(12345678901234567));
+		let embed = revoke_embed::<WakeOnLanMachineInfo>(
+			result,
+			"NonExistentMachine",
+			DiscordEntity::Role(RoleId::new(12345678901234567)),
+			&(),
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: Invalid Machine")
@@ -260,10 +276,16 @@ mod tests {
 	#[test]
 	fn given_revoke_role_error_with_already_unauthorized_then_reply_with_already_unauthorized() {
 		let result = Err(RemovePermissionError::AlreadyNotAuthorized {
-			machine_name: "SomeMachine".to_string(),
+			name: "SomeMachine".to_string(),
 			entity: DiscordEntity::Role(RoleId::new(76543210987654321)),
+			grant: ()
 		});
-		let embed = revoke_role_embed(result, "SomeMachine", RoleId::new(76543210987654321));
+		let embed = revoke_embed::<WakeOnLanMachineInfo>(
+			result,
+			"SomeMachine",
+			DiscordEntity::Role(RoleId::new(76543210987654321)),
+			&(),
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":x: Role not permitted")
@@ -277,7 +299,12 @@ mod tests {
 
 	#[test]
 	fn given_successful_revoke_role_then_should_reply_with_success_info() {
-		let embed = revoke_role_embed(Ok(()), "SomeMachine", RoleId::new(12345678901234567));
+		let embed = revoke_embed::<WakeOnLanMachineInfo>(
+			Ok(()),
+			"SomeMachine",
+			DiscordEntity::Role(RoleId::new(12345678901234567)),
+			&(),
+		);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":white_check_mark: Role permission revoked")
@@ -288,4 +315,120 @@ mod tests {
 
 		assert_eq!(embed, expected_embed);
 	}
+
+	#[test]
+	fn given_permit_group_error_with_nonexistent_group_then_reply_with_error_no_group() {
+		let result = Err(AddGroupPermissionError::Group(GroupError::DoesNotExist {
+			group: "Family".to_string(),
+		}));
+		let embed = permit_group_embed::<WakeOnLanMachineInfo>(
+			result,
+			"SomeMachine",
+			"Family",
+			&(),
+		);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Invalid Group")
+			.colour(Colour(0xdd2e44))
+			.description("No access group with name Family exists");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_successful_permit_group_then_should_reply_with_success_info() {
+		let embed = permit_group_embed::<WakeOnLanMachineInfo>(
+			Ok(()),
+			"SomeMachine",
+			"Friends",
+			&(),
+		);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Group permitted")
+			.colour(Colour(0x77b255))
+			.description("Successfully permitted access group to wake the machine!")
+			.field("Machine", "SomeMachine", true)
+			.field("Group", "Friends", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_revoke_group_error_with_already_unauthorized_then_reply_with_already_unauthorized() {
+		let result = Err(RemoveGroupPermissionError::AlreadyNotAuthorized {
+			name: "SomeMachine".to_string(),
+			group: "Friends".to_string(),
+			grant: ()
+		});
+		let embed = revoke_group_embed::<WakeOnLanMachineInfo>(
+			result,
+			"SomeMachine",
+			"Friends",
+			&(),
+		);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Group not permitted")
+			.colour(Colour(0xdd2e44))
+			.description("Access group Friends is already not permitted to wake machine SomeMachine");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_successful_deny_then_reply_with_reason() {
+		let entity = DiscordEntity::User(UserId::new(12345678901234567));
+		let embed = deny_embed::<WakeOnLanMachineInfo>(
+			Ok(()),
+			"SomeMachine",
+			entity,
+			Some("Spamming wakes"),
+		);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Denied")
+			.colour(Colour(0x77b255))
+			.description("Successfully denied from the machine, whatever they are permitted!")
+			.field("Machine", "SomeMachine", true)
+			.field("Denied", "<@12345678901234567>", true)
+			.field("Reason", "Spamming wakes", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_deny_error_with_already_denied_then_reply_with_already_denied() {
+		let entity = DiscordEntity::Role(RoleId::new(98765432109876543));
+		let result = Err(AddDenialError::AlreadyDenied {
+			name: "SomeMachine".to_string(),
+			entity,
+		});
+		let embed = deny_embed::<WakeOnLanMachineInfo>(result, "SomeMachine", entity, None);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Already denied")
+			.colour(Colour(0xdd2e44))
+			.description("<@&98765432109876543> is already denied from machine SomeMachine");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_lift_denial_error_with_not_denied_then_reply_with_not_denied() {
+		let entity = DiscordEntity::User(UserId::new(12345678901234567));
+		let result = Err(RemoveDenialError::NotDenied {
+			name: "SomeMachine".to_string(),
+			entity,
+		});
+		let embed = lift_denial_embed::<WakeOnLanMachineInfo>(result, "SomeMachine", entity);
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Not denied")
+			.colour(Colour(0xdd2e44))
+			.description("<@12345678901234567> is not denied from machine SomeMachine");
+
+		assert_eq!(embed, expected_embed);
+	}
 }
@@ -1,8 +1,10 @@
-use super::super::format_list;
+use super::super::{format_denials, format_expiring, format_list, format_scope};
+use crate::components::ComponentId;
 use crate::controllers::wake_on_lan::machine::{AddMachineError, RemoveMachineError};
 use crate::controllers::wake_on_lan::MachineError;
-use crate::data::wake_on_lan::{WakeOnLanData, WakeOnLanMachineInfo};
+use crate::data::wake_on_lan::WakeOnLanMachineInfo;
 use crate::embeds;
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton};
 use serenity::builder::CreateEmbed;
 
 pub fn add_machine_embed(
@@ -23,6 +25,7 @@ pub fn add_machine_embed(
 				"Invalid MAC Address",
 				format!("Mac address {mac_address} is invalid: {m}"),
 			),
+			AddMachineError::NameTooLong { .. } => embeds::name_too_long(machine_name),
 		},
 	}
 }
@@ -41,19 +44,25 @@ pub fn remove_machine_embed(
 	}
 }
 
-pub fn list_machines_embed(wake_on_lan_data: &WakeOnLanData) -> CreateEmbed {
-	let description = if wake_on_lan_data.is_empty() {
-		"There are no machines configured".to_string()
-	} else {
-		let machine_list = wake_on_lan_data
-			.iter()
-			.map(|m| format!("- {}: `{}`", m.0, m.1.mac))
-			.collect::<Vec<String>>()
-			.join("\n");
-		format!("Configured machines:\n{machine_list}")
-	};
+pub fn confirm_remove_machine_embed(machine_name: &str) -> CreateEmbed {
+	embeds::info(
+		"Confirm removal",
+		format!("Are you sure you want to remove the machine {machine_name}?"),
+	)
+}
 
-	embeds::info("Machine list", description)
+pub fn set_address_embed(result: Result<(), MachineError>, machine_name: &str, address: Option<&str>) -> CreateEmbed {
+	match (result, address) {
+		(Ok(_), Some(address)) => embeds::success(
+			"Address set",
+			format!("Dashboards will show machine {machine_name} as online while `{address}` answers"),
+		),
+		(Ok(_), None) => embeds::success(
+			"Address removed",
+			format!("Dashboards will no longer check whether machine {machine_name} is online"),
+		),
+		(Err(_), _) => embeds::invalid_machine(machine_name),
+	}
 }
 
 pub fn describe_machine_embed(
@@ -62,16 +71,29 @@ pub fn describe_machine_embed(
 ) -> CreateEmbed {
 	match result {
 		Ok(machine_info) => {
-			let users = format_list(&machine_info.authorized_users, |id| format!("<@{id}>"));
-			let roles = format_list(&machine_info.authorized_roles, |id| format!("<@&{id}>"));
+			let users = format_list(&machine_info.authorized_users, |id| {
+				format_expiring(format!("<@{id}>"), machine_info.user_expiries.get(id))
+			});
+			let roles = format_list(&machine_info.authorized_roles, |id| {
+				format_expiring(format!("<@&{id}>"), machine_info.role_expiries.get(id))
+			});
+			let groups = format_list(&machine_info.authorized_groups, |name| format!("`{name}`"));
+			let denied = format_denials(&machine_info.denied);
+			let managers = format_list(&machine_info.managers, |id| format!("<@{id}>"));
+			let address = machine_info.address.as_ref().map_or("None".to_string(), |address| format!("`{address}`"));
 
 			embeds::info(
 				format!("Machine {machine_name}"),
 				format!(
 					"- MAC Address: `{}`\n\
                      - Authorized Users: {}\n\
-                     - Authorized Roles: {}",
-					machine_info.mac, users, roles
+                     - Authorized Roles: {}\n\
+                     - Authorized Groups: {}\n\
+                     - Denied: {}\n\
+                     - Managers: {}\n\
+                     - Address: {}\n\
+                     - Scope: {}",
+					machine_info.mac, users, roles, groups, denied, managers, address, format_scope(machine_info.scope)
 				),
 			)
 		},
@@ -79,15 +101,30 @@ pub fn describe_machine_embed(
 	}
 }
 
+pub fn describe_machine_buttons(machine_name: &str) -> Vec<CreateActionRow> {
+	let id = ComponentId::Wake {
+		machine_name: machine_name.to_string(),
+	};
+
+	vec![CreateActionRow::Buttons(vec![
+		CreateButton::new(id.to_string()).label("Wake").style(ButtonStyle::Primary),
+	])]
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::controllers::wake_on_lan::MachineError;
-	use crate::data::wake_on_lan::WakeOnLanMachineInfo;
+	use crate::data::authorization::{Denials, GrantExpiries};
+	use crate::data::scope::Scope;
+	use crate::views::authorization::list_embed;
+	use chrono::{TimeZone, Utc};
+	use crate::data::wake_on_lan::{WakeOnLanData, WakeOnLanMachineInfo};
 	use crate::errors::InvalidMacError;
 	use crate::services::wake_on_lan::MacAddress;
-	use serenity::all::{Colour, RoleId, UserId};
-	use std::collections::BTreeSet;
+	use rstest::rstest;
+	use serenity::all::{Colour, GuildId, RoleId, UserId};
+	use std::collections::{BTreeMap, BTreeSet};
 
 	#[test]
 	fn given_add_machine_error_with_existing_machine_then_reply_with_error_existing_machine() {
@@ -140,6 +177,35 @@ mod tests {
 		assert_eq!(embed, expected_embed);
 	}
 
+	#[test]
+	fn given_add_machine_error_with_too_long_name_then_reply_with_error_name_too_long() {
+		let result = Err(AddMachineError::NameTooLong {
+			name: "SomeMachine".into(),
+		});
+
+		let embed = add_machine_embed(result, "SomeMachine", "01:02:03:04:05:06");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: Name too long")
+			.colour(Colour(0xdd2e44))
+			.description("SomeMachine is longer than 24 characters, try a shorter name");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[rstest]
+	#[case(Some("some-machine:22"), "Address set", "Dashboards will show machine SomeMachine as online while `some-machine:22` answers")]
+	#[case(None, "Address removed", "Dashboards will no longer check whether machine SomeMachine is online")]
+	fn given_successful_set_address_then_reply_with_success(
+		#[case] address: Option<&str>,
+		#[case] title: &str,
+		#[case] description: &str,
+	) {
+		let embed = set_address_embed(Ok(()), "SomeMachine", address);
+
+		assert_eq!(embed, embeds::success(title, description));
+	}
+
 	#[test]
 	fn given_successful_add_machine_then_reply_with_success_info() {
 		let embed = add_machine_embed(Ok(()), "SomeMachine", "01:02:03:04:05:06");
@@ -184,8 +250,20 @@ mod tests {
 	}
 
 	#[test]
-	fn given_no_added_machines_then_list_machines_replies_with_empty_response() {
-		let embed = list_machines_embed(&WakeOnLanData::new());
+	fn given_machine_then_confirm_remove_machine_asks_for_confirmation() {
+		let embed = confirm_remove_machine_embed("SomeMachine");
+
+		let expected_embed = CreateEmbed::default()
+			.title(":information_source: Confirm removal")
+			.colour(Colour(0x55acee))
+			.description("Are you sure you want to remove the machine SomeMachine?");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_no_added_machines_then_list_embed_replies_with_empty_response() {
+		let embed = list_embed(&WakeOnLanData::new());
 
 		let expected_embed = CreateEmbed::default()
 			.title(":information_source: Machine list")
@@ -196,7 +274,7 @@ mod tests {
 	}
 
 	#[test]
-	fn given_some_machines_then_list_machines_replies_formatted_list() {
+	fn given_some_machines_then_list_embed_replies_formatted_list() {
 		let data = WakeOnLanData::from([
 			(
 				"MachineOne".to_string(),
@@ -204,6 +282,13 @@ mod tests {
 					mac: MacAddress([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
 					authorized_users: Default::default(),
 					authorized_roles: Default::default(),
+					scope: Default::default(),
+					managers: Default::default(),
+					user_expiries: Default::default(),
+					role_expiries: Default::default(),
+					authorized_groups: Default::default(),
+					denied: Default::default(),
+					address: None,
 				},
 			),
 			(
@@ -212,6 +297,13 @@ mod tests {
 					mac: MacAddress([0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C]),
 					authorized_users: Default::default(),
 					authorized_roles: Default::default(),
+					scope: Default::default(),
+					managers: Default::default(),
+					user_expiries: Default::default(),
+					role_expiries: Default::default(),
+					authorized_groups: Default::default(),
+					denied: Default::default(),
+					address: None,
 				},
 			),
 			(
@@ -220,11 +312,18 @@ mod tests {
 					mac: MacAddress([0x0D, 0x0E, 0x0F, 0x10, 0x11, 0x12]),
 					authorized_users: Default::default(),
 					authorized_roles: Default::default(),
+					scope: Default::default(),
+					managers: Default::default(),
+					user_expiries: Default::default(),
+					role_expiries: Default::default(),
+					authorized_groups: Default::default(),
+					denied: Default::default(),
+					address: None,
 				},
 			),
 		]);
 
-		let embed = list_machines_embed(&data);
+		let embed = list_embed(&data);
 
 		let expected_embed = CreateEmbed::default()
 			.title(":information_source: Machine list")
@@ -261,6 +360,13 @@ mod tests {
 			mac: MacAddress([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
 			authorized_users: Default::default(),
 			authorized_roles: Default::default(),
+			scope: Default::default(),
+			managers: Default::default(),
+			user_expiries: Default::default(),
+			role_expiries: Default::default(),
+			authorized_groups: Default::default(),
+			denied: Default::default(),
+			address: None,
 		};
 
 		let embed = describe_machine_embed(Ok(&machine_info), "SomeMachine");
@@ -269,7 +375,12 @@ mod tests {
 			"Machine SomeMachine",
 			"- MAC Address: `01:02:03:04:05:06`\n\
          - Authorized Users: None\n\
-         - Authorized Roles: None",
+         - Authorized Roles: None\n\
+         - Authorized Groups: None\n\
+         - Denied: None\n\
+         - Managers: None\n\
+         - Address: None\n\
+         - Scope: Global",
 		);
 
 		assert_eq!(embed, expected_embed);
@@ -287,6 +398,19 @@ mod tests {
 				RoleId::new(98765432109876543),
 				RoleId::new(98765432109876544),
 			]),
+			scope: Scope::Guild(GuildId::new(1)),
+			managers: BTreeSet::from([UserId::new(11111111111111111)]),
+			user_expiries: GrantExpiries::from([(
+				UserId::new(12345678901234568),
+				Utc.with_ymd_and_hms(2025, 3, 6, 19, 59, 45).unwrap(),
+			)]),
+			role_expiries: Default::default(),
+			authorized_groups: BTreeSet::from(["Friends".to_string()]),
+			denied: Denials {
+				users: BTreeMap::from([(UserId::new(22222222222222222), Some("Spamming wakes".to_string()))]),
+				roles: BTreeMap::from([(RoleId::new(33333333333333333), None)]),
+			},
+			address: Some("some-machine:22".to_string()),
 		};
 
 		let embed = describe_machine_embed(Ok(&machine_info), "SomeMachine");
@@ -294,10 +418,28 @@ mod tests {
 		let expected_embed = embeds::info(
 			"Machine SomeMachine",
 			"- MAC Address: `01:02:03:04:05:06`\n\
-         - Authorized Users: <@12345678901234567>, <@12345678901234568>\n\
-         - Authorized Roles: <@&98765432109876543>, <@&98765432109876544>",
+         - Authorized Users: <@12345678901234567>, <@12345678901234568> until <t:1741291185:f>\n\
+         - Authorized Roles: <@&98765432109876543>, <@&98765432109876544>\n\
+         - Authorized Groups: `Friends`\n\
+         - Denied: <@22222222222222222> (Spamming wakes), <@&33333333333333333>\n\
+         - Managers: <@11111111111111111>\n\
+         - Address: `some-machine:22`\n\
+         - Scope: Guild `1`",
 		);
 
 		assert_eq!(embed, expected_embed);
 	}
+
+	#[test]
+	fn given_machine_then_describe_machine_buttons_has_wake_button() {
+		let buttons = describe_machine_buttons("SomeMachine");
+
+		let expected_buttons = vec![CreateActionRow::Buttons(vec![CreateButton::new(
+			"wol:wake:SomeMachine",
+		)
+		.label("Wake")
+		.style(ButtonStyle::Primary)])];
+
+		assert_eq!(buttons, expected_buttons);
+	}
 }
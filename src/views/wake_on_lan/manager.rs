@@ -0,0 +1,99 @@
+use crate::controllers::wake_on_lan::manager::{AddManagerError, RemoveManagerError};
+use crate::embeds;
+use serenity::all::{CreateEmbed, UserId};
+
+pub fn add_manager_embed(result: Result<(), AddManagerError>, machine_name: &str, user_id: UserId) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success("Manager added", "Successfully made user a manager of the machine!")
+			.field("Machine", machine_name, true)
+			.field("User", format!("<@{user_id}>"), true),
+		Err(e) => match e {
+			AddManagerError::Machine(_) => embeds::invalid_machine(machine_name),
+			AddManagerError::AlreadyManager { .. } => embeds::error(
+				"User already manager",
+				format!("User <@{user_id}> already manages machine {machine_name}"),
+			),
+		},
+	}
+}
+
+pub fn remove_manager_embed(result: Result<(), RemoveManagerError>, machine_name: &str, user_id: UserId) -> CreateEmbed {
+	match result {
+		Ok(_) => embeds::success("Manager removed", "Successfully removed user from the managers of the machine!")
+			.field("Machine", machine_name, true)
+			.field("User", format!("<@{user_id}>"), true),
+		Err(e) => match e {
+			RemoveManagerError::Machine(_) => embeds::invalid_machine(machine_name),
+			RemoveManagerError::NotManager { .. } => embeds::error(
+				"User not manager",
+				format!("User <@{user_id}> does not manage machine {machine_name}"),
+			),
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::controllers::wake_on_lan::MachineError;
+	use serenity::all::Colour;
+
+	#[test]
+	fn given_successful_add_manager_then_reply_with_success_info() {
+		let embed = add_manager_embed(Ok(()), "SomeMachine", UserId::new(12345678901234567));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":white_check_mark: Manager added")
+			.colour(Colour(0x77b255))
+			.description("Successfully made user a manager of the machine!")
+			.field("Machine", "SomeMachine", true)
+			.field("User", "<@12345678901234567>", true);
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_add_manager_error_with_existing_manager_then_reply_with_error() {
+		let result = Err(AddManagerError::AlreadyManager {
+			machine_name: "SomeMachine".into(),
+			user: UserId::new(12345678901234567),
+		});
+
+		let embed = add_manager_embed(result, "SomeMachine", UserId::new(12345678901234567));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: User already manager")
+			.colour(Colour(0xdd2e44))
+			.description("User <@12345678901234567> already manages machine SomeMachine");
+
+		assert_eq!(embed, expected_embed);
+	}
+
+	#[test]
+	fn given_remove_manager_error_with_nonexistent_machine_then_reply_with_invalid_machine() {
+		let result = Err(RemoveManagerError::Machine(MachineError::DoesNotExist {
+			machine_name: "SomeMachine".into(),
+		}));
+
+		let embed = remove_manager_embed(result, "SomeMachine", UserId::new(12345678901234567));
+
+		assert_eq!(embed, embeds::invalid_machine("SomeMachine"));
+	}
+
+	#[test]
+	fn given_remove_manager_error_with_non_manager_then_reply_with_error() {
+		let result = Err(RemoveManagerError::NotManager {
+			machine_name: "SomeMachine".into(),
+			user: UserId::new(12345678901234567),
+		});
+
+		let embed = remove_manager_embed(result, "SomeMachine", UserId::new(12345678901234567));
+
+		let expected_embed = CreateEmbed::default()
+			.title(":x: User not manager")
+			.colour(Colour(0xdd2e44))
+			.description("User <@12345678901234567> does not manage machine SomeMachine");
+
+		assert_eq!(embed, expected_embed);
+	}
+}
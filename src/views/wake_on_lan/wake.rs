@@ -1,6 +1,8 @@
 use crate::controllers::wake_on_lan::wake::WakeError;
+use crate::data::access::AccessResource;
 use crate::embeds;
-use serenity::builder::CreateEmbed;
+use crate::views::access::request_access_buttons;
+use serenity::builder::{CreateActionRow, CreateEmbed};
 
 pub fn wake_embed(result: Result<(), WakeError>, machine_name: &str) -> CreateEmbed {
 	match result {
@@ -13,6 +15,16 @@ pub fn wake_embed(result: Result<(), WakeError>, machine_name: &str) -> CreateEm
 	}
 }
 
+/// Offers unauthorized users to ask for access to the machine.
+pub fn wake_buttons(result: &Result<(), WakeError>) -> Vec<CreateActionRow> {
+	match result {
+		Err(WakeError::Unauthorized { machine_name, .. }) => request_access_buttons(AccessResource::Machine {
+			name: machine_name.clone(),
+		}),
+		_ => vec![],
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -79,4 +91,22 @@ mod tests {
 
 		assert_eq!(embed, expected_embed);
 	}
+
+	#[test]
+	fn given_unauthorized_wake_then_wake_buttons_offer_access_request() {
+		let result = Err(WakeError::Unauthorized {
+			user: UserId::new(12345678901234567),
+			machine_name: "SomeMachine".to_string(),
+		});
+
+		let buttons = wake_buttons(&result);
+
+		assert_eq!(
+			buttons,
+			request_access_buttons(AccessResource::Machine {
+				name: "SomeMachine".to_string()
+			})
+		);
+		assert!(wake_buttons(&Ok(())).is_empty());
+	}
 }